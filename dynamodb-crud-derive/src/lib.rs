@@ -0,0 +1,781 @@
+#![deny(missing_docs)]
+#![deny(warnings)]
+
+//! # DynamoDB CRUD derive
+//!
+//! Derive macros for [`dynamodb-crud`](https://docs.rs/dynamodb-crud), re-exported by its
+//! `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, GenericArgument, PathArguments, Type, parse_macro_input};
+
+/// Operator applied by a filter field to its attribute.
+#[derive(Clone, Copy)]
+enum Operator {
+    BeginsWith,
+    Contains,
+    Equals,
+    Exists,
+    GreaterThan,
+    GreaterThanOrEqual,
+    In,
+    LessThan,
+    LessThanOrEqual,
+    NotContains,
+    NotEqual,
+}
+
+impl Operator {
+    fn parse(operator: &syn::LitStr) -> syn::Result<Self> {
+        let operator = match operator.value().as_str() {
+            "begins_with" => Self::BeginsWith,
+            "contains" => Self::Contains,
+            "equals" => Self::Equals,
+            "exists" => Self::Exists,
+            "greater_than" => Self::GreaterThan,
+            "greater_than_or_equal" => Self::GreaterThanOrEqual,
+            "in" => Self::In,
+            "less_than" => Self::LessThan,
+            "less_than_or_equal" => Self::LessThanOrEqual,
+            "not_contains" => Self::NotContains,
+            "not_equal" => Self::NotEqual,
+            other => {
+                return Err(syn::Error::new(
+                    operator.span(),
+                    format!("unknown filter operator `{other}`"),
+                ));
+            }
+        };
+        Ok(operator)
+    }
+}
+
+/// Filter field, with the attribute it applies to.
+struct FilterField {
+    ident: syn::Ident,
+    name: String,
+    operator: Operator,
+    value_type: Type,
+}
+
+impl FilterField {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let ident = field
+            .ident
+            .clone()
+            .ok_or_else(|| syn::Error::new_spanned(field, "filter fields must be named"))?;
+        let value_type = get_option_type(&field.ty).ok_or_else(|| {
+            syn::Error::new_spanned(&field.ty, "filter fields must be of type `Option<_>`")
+        })?;
+        let mut name = ident.to_string();
+        let mut operator = Operator::Equals;
+        for attribute in &field.attrs {
+            if !attribute.path().is_ident("dynamo_filter") {
+                continue;
+            }
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("operator") {
+                    operator = Operator::parse(&meta.value()?.parse()?)?;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name` or `operator`"))
+                }
+            })?;
+        }
+        let field = Self {
+            ident,
+            name,
+            operator,
+            value_type,
+        };
+        Ok(field)
+    }
+
+    /// The bounds on the value type needed to build the condition.
+    fn get_bounds(&self, value: &syn::Ident) -> Vec<syn::WherePredicate> {
+        let value_type = &self.value_type;
+        match self.operator {
+            Operator::BeginsWith => {
+                vec![syn::parse_quote!(#value_type: ::std::convert::Into<::std::string::String>)]
+            }
+            Operator::Exists => Vec::new(),
+            Operator::In => vec![
+                syn::parse_quote!(#value_type: ::std::iter::IntoIterator),
+                syn::parse_quote!(
+                    <#value_type as ::std::iter::IntoIterator>::Item: ::std::convert::Into<#value>
+                ),
+            ],
+            _ => vec![syn::parse_quote!(#value_type: ::std::convert::Into<#value>)],
+        }
+    }
+
+    /// The statement pushing the condition of the field, if set.
+    fn get_push(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let name = &self.name;
+        let condition = quote!(::dynamodb_crud::common::condition::Condition);
+        let condition = match self.operator {
+            Operator::BeginsWith => quote!(#condition::BeginsWith(value.into())),
+            Operator::Contains => quote!(#condition::Contains(value.into())),
+            Operator::Equals => quote!(#condition::Equals(value.into())),
+            Operator::Exists => quote! {
+                if value { #condition::NotNull } else { #condition::Null }
+            },
+            Operator::GreaterThan => quote!(#condition::GreaterThan(value.into())),
+            Operator::GreaterThanOrEqual => quote!(#condition::GreaterThanOrEqual(value.into())),
+            Operator::In => quote! {
+                #condition::In(value.into_iter().map(::std::convert::Into::into).collect())
+            },
+            Operator::LessThan => quote!(#condition::LessThan(value.into())),
+            Operator::LessThanOrEqual => quote!(#condition::LessThanOrEqual(value.into())),
+            Operator::NotContains => quote!(#condition::NotContains(value.into())),
+            Operator::NotEqual => quote!(#condition::NotEqual(value.into())),
+        };
+        quote! {
+            if let ::std::option::Option::Some(value) = self.#ident {
+                conditions.push(::dynamodb_crud::common::condition::KeyCondition {
+                    condition: #condition,
+                    name: ::std::string::String::from(#name),
+                });
+            }
+        }
+    }
+}
+
+/// Get the inner type of an `Option`.
+fn get_option_type(ty: &Type) -> Option<Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+fn expand_dynamo_filter(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`DynamoFilter` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`DynamoFilter` can only be derived for structs with named fields",
+        ));
+    };
+    let fields = fields
+        .named
+        .iter()
+        .map(FilterField::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let value = format_ident!("__DynamoValue");
+    let mut generics = input.generics.clone();
+    generics.params.push(syn::parse_quote!(#value));
+    let where_clause = generics.make_where_clause();
+    for field in &fields {
+        where_clause.predicates.extend(field.get_bounds(&value));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+    let ident = &input.ident;
+    let capacity = fields.len();
+    let pushes = fields.iter().map(FilterField::get_push);
+    let expanded = quote! {
+        impl #impl_generics ::dynamodb_crud::common::condition::DynamoFilter<#value>
+            for #ident #type_generics #where_clause
+        {
+            fn into_condition(
+                self,
+            ) -> ::std::option::Option<::dynamodb_crud::common::condition::ConditionMap<#value>> {
+                let mut conditions = ::std::vec::Vec::with_capacity(#capacity);
+                #(#pushes)*
+                if conditions.is_empty() {
+                    ::std::option::Option::None
+                } else {
+                    ::std::option::Option::Some(::dynamodb_crud::common::condition::ConditionMap::Leaves(
+                        ::dynamodb_crud::common::condition::LogicalOperator::And,
+                        conditions,
+                    ))
+                }
+            }
+        }
+    };
+    Ok(expanded)
+}
+
+/// Scalar type of a key attribute.
+#[derive(Clone, Copy)]
+enum AttributeType {
+    Binary,
+    Number,
+    String,
+}
+
+impl AttributeType {
+    fn parse(attribute_type: &syn::LitStr) -> syn::Result<Self> {
+        let attribute_type = match attribute_type.value().as_str() {
+            "B" => Self::Binary,
+            "N" => Self::Number,
+            "S" => Self::String,
+            other => {
+                return Err(syn::Error::new(
+                    attribute_type.span(),
+                    format!("unknown attribute type `{other}`, expected `B`, `N` or `S`"),
+                ));
+            }
+        };
+        Ok(attribute_type)
+    }
+
+    /// Infer the attribute type of strings, numbers and bytes.
+    fn infer(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::Reference(reference) => Self::infer(&reference.elem),
+            Type::Slice(slice) if is_u8(&slice.elem) => Some(Self::Binary),
+            Type::Path(type_path) => {
+                let segment = type_path.path.segments.last()?;
+                let attribute_type = match segment.ident.to_string().as_str() {
+                    "String" | "str" | "char" => Self::String,
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32"
+                    | "u64" | "u128" | "usize" | "f32" | "f64" => Self::Number,
+                    "Vec" => {
+                        let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                            return None;
+                        };
+                        match arguments.args.first()? {
+                            GenericArgument::Type(ty) if is_u8(ty) => Self::Binary,
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                };
+                Some(attribute_type)
+            }
+            _ => None,
+        }
+    }
+
+    fn get_scalar_attribute_type(self) -> TokenStream2 {
+        let variant = match self {
+            Self::Binary => format_ident!("B"),
+            Self::Number => format_ident!("N"),
+            Self::String => format_ident!("S"),
+        };
+        quote!(::aws_sdk_dynamodb::types::ScalarAttributeType::#variant)
+    }
+}
+
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.is_ident("u8"))
+}
+
+/// Key field of an item, with its attribute.
+struct KeyField {
+    attribute_type: Option<AttributeType>,
+    ident: syn::Ident,
+    name: String,
+    ty: Type,
+}
+
+impl KeyField {
+    fn find(fields: &syn::FieldsNamed, key: &syn::LitStr) -> syn::Result<Self> {
+        let field = fields
+            .named
+            .iter()
+            .find(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| *ident == key.value())
+            })
+            .ok_or_else(|| {
+                syn::Error::new(key.span(), format!("no field named `{}`", key.value()))
+            })?;
+        let ident = field.ident.clone().expect("named field");
+        let mut name = ident.to_string();
+        let mut attribute_type = AttributeType::infer(&field.ty);
+        for attribute in &field.attrs {
+            if !attribute.path().is_ident("dynamo") {
+                continue;
+            }
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("attribute_type") {
+                    attribute_type = Some(AttributeType::parse(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name` or `attribute_type`"))
+                }
+            })?;
+        }
+        let field = Self {
+            attribute_type,
+            ident,
+            name,
+            ty: field.ty.clone(),
+        };
+        Ok(field)
+    }
+
+    /// The expression building the key from a value of the field type.
+    fn get_key(&self, value: TokenStream2) -> TokenStream2 {
+        let name = &self.name;
+        quote! {
+            ::dynamodb_crud::common::key::Key {
+                name: ::std::string::String::from(#name),
+                value: ::std::convert::Into::into(#value),
+            }
+        }
+    }
+
+    /// The expression building the key attribute of the table definition.
+    fn get_key_attribute(&self) -> syn::Result<TokenStream2> {
+        let attribute_type = self.attribute_type.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &self.ty,
+                "cannot infer the attribute type of the key, \
+                 set `#[dynamo(attribute_type = \"B\" | \"N\" | \"S\")]`",
+            )
+        })?;
+        let attribute_type = attribute_type.get_scalar_attribute_type();
+        let name = &self.name;
+        let key_attribute = quote! {
+            ::dynamodb_crud::table::common::KeyAttribute {
+                attribute_type: #attribute_type,
+                name: ::std::string::String::from(#name),
+            }
+        };
+        Ok(key_attribute)
+    }
+}
+
+/// The expression building the key schema of a table or an index.
+fn get_key_schema(
+    partition_key: &KeyField,
+    sort_key: Option<&KeyField>,
+) -> syn::Result<TokenStream2> {
+    let partition_key = partition_key.get_key_attribute()?;
+    let sort_key = match sort_key {
+        Some(sort_key) => {
+            let sort_key = sort_key.get_key_attribute()?;
+            quote!(::std::option::Option::Some(#sort_key))
+        }
+        None => quote!(::std::option::Option::None),
+    };
+    let key_schema = quote! {
+        ::dynamodb_crud::table::common::KeySchema {
+            partition_key: #partition_key,
+            sort_key: #sort_key,
+        }
+    };
+    Ok(key_schema)
+}
+
+/// Global secondary index declared on an item.
+struct GlobalIndex {
+    keys_only: bool,
+    name: String,
+    partition_key: KeyField,
+    sort_key: Option<KeyField>,
+}
+
+impl GlobalIndex {
+    fn parse(fields: &syn::FieldsNamed, meta: &syn::meta::ParseNestedMeta) -> syn::Result<Self> {
+        let mut keys_only = false;
+        let mut name = None;
+        let mut partition_key = None;
+        let mut sort_key = None;
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("partition_key") {
+                partition_key = Some(KeyField::find(fields, &meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("sort_key") {
+                sort_key = Some(KeyField::find(fields, &meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("keys_only") {
+                keys_only = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name`, `partition_key`, `sort_key` or `keys_only`"))
+            }
+        })?;
+        let (Some(name), Some(partition_key)) = (name, partition_key) else {
+            return Err(meta.error("`global_index` requires `name` and `partition_key`"));
+        };
+        let index = Self {
+            keys_only,
+            name,
+            partition_key,
+            sort_key,
+        };
+        Ok(index)
+    }
+
+    /// The expression building the index of the table definition.
+    fn get_global_secondary_index(&self, throughput: &TokenStream2) -> syn::Result<TokenStream2> {
+        let key_schema = get_key_schema(&self.partition_key, self.sort_key.as_ref())?;
+        let name = &self.name;
+        let projection = match self.keys_only {
+            true => quote!(::dynamodb_crud::table::common::Projection::KeysOnly),
+            false => quote!(::dynamodb_crud::table::common::Projection::All),
+        };
+        let index = quote! {
+            ::dynamodb_crud::table::common::GlobalSecondaryIndex {
+                index_name: ::std::string::String::from(#name),
+                key_schema: #key_schema,
+                projection: #projection,
+                throughput: #throughput,
+            }
+        };
+        Ok(index)
+    }
+}
+
+/// The `#[dynamo(...)]` attributes of an item, shared by its derives.
+struct ItemAttributes {
+    global_indexes: Vec<GlobalIndex>,
+    partition_key: KeyField,
+    provisioned: Option<(i64, i64)>,
+    sort_key: Option<KeyField>,
+    stream: Option<syn::Ident>,
+    table: String,
+    time_to_live: Option<KeyField>,
+}
+
+impl ItemAttributes {
+    fn parse(input: &DeriveInput, derive: &str) -> syn::Result<Self> {
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("`{derive}` can only be derived for structs"),
+            ));
+        };
+        let Fields::Named(fields) = &data.fields else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("`{derive}` can only be derived for structs with named fields"),
+            ));
+        };
+        let mut global_indexes = Vec::new();
+        let mut partition_key = None;
+        let mut provisioned = None;
+        let mut sort_key = None;
+        let mut stream = None;
+        let mut table = None;
+        let mut time_to_live = None;
+        for attribute in &input.attrs {
+            if !attribute.path().is_ident("dynamo") {
+                continue;
+            }
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("table") {
+                    table = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("partition_key") {
+                    partition_key = Some(KeyField::find(fields, &meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("sort_key") {
+                    sort_key = Some(KeyField::find(fields, &meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("global_index") {
+                    global_indexes.push(GlobalIndex::parse(fields, &meta)?);
+                    Ok(())
+                } else if meta.path.is_ident("provisioned") {
+                    let mut read = None;
+                    let mut write = None;
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("read") {
+                            read = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                            Ok(())
+                        } else if meta.path.is_ident("write") {
+                            write = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                            Ok(())
+                        } else {
+                            Err(meta.error("expected `read` or `write`"))
+                        }
+                    })?;
+                    let (Some(read), Some(write)) = (read, write) else {
+                        return Err(meta.error("`provisioned` requires `read` and `write`"));
+                    };
+                    provisioned = Some((read, write));
+                    Ok(())
+                } else if meta.path.is_ident("stream") {
+                    let view_type = meta.value()?.parse::<syn::LitStr>()?;
+                    let variant = match view_type.value().as_str() {
+                        "keys_only" => "KeysOnly",
+                        "new_image" => "NewImage",
+                        "old_image" => "OldImage",
+                        "new_and_old_images" => "NewAndOldImages",
+                        other => {
+                            return Err(syn::Error::new(
+                                view_type.span(),
+                                format!(
+                                    "unknown stream view type `{other}`, expected `keys_only`, \
+                                     `new_image`, `old_image` or `new_and_old_images`"
+                                ),
+                            ));
+                        }
+                    };
+                    stream = Some(format_ident!("{variant}"));
+                    Ok(())
+                } else if meta.path.is_ident("time_to_live") {
+                    time_to_live = Some(KeyField::find(fields, &meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "expected `table`, `partition_key`, `sort_key`, `global_index`, \
+                         `provisioned`, `stream` or `time_to_live`",
+                    ))
+                }
+            })?;
+        }
+        let (Some(table), Some(partition_key)) = (table, partition_key) else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!(
+                    "`{derive}` requires `#[dynamo(table = \"...\", partition_key = \"...\")]`"
+                ),
+            ));
+        };
+        let attributes = Self {
+            global_indexes,
+            partition_key,
+            provisioned,
+            sort_key,
+            stream,
+            table,
+            time_to_live,
+        };
+        Ok(attributes)
+    }
+}
+
+fn expand_dynamo_item(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ItemAttributes {
+        partition_key,
+        sort_key,
+        table,
+        ..
+    } = ItemAttributes::parse(&input, "DynamoItem")?;
+    let value = format_ident!("__DynamoValue");
+    let key_types: Vec<_> = std::iter::once(&partition_key)
+        .chain(&sort_key)
+        .map(|field| &field.ty)
+        .collect();
+    let mut generics = input.generics.clone();
+    generics.params.push(syn::parse_quote!(#value));
+    let where_clause = generics.make_where_clause();
+    for ty in &key_types {
+        where_clause.predicates.push(syn::parse_quote!(
+            #ty: ::std::clone::Clone + ::std::convert::Into<#value>
+        ));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (item_impl_generics, type_generics, item_where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+    let partition_key_name = &partition_key.name;
+    let partition_key_ident = &partition_key.ident;
+    let partition_key_type = &partition_key.ty;
+    let keys_partition_key = partition_key.get_key(quote!(self.#partition_key_ident.clone()));
+    let key_partition_key = partition_key.get_key(quote!(partition_key));
+    let (sort_key_name, keys_sort_key, key_sort_key, key_argument) = match &sort_key {
+        Some(sort_key) => {
+            let name = &sort_key.name;
+            let sort_key_ident = &sort_key.ident;
+            let sort_key_type = &sort_key.ty;
+            let keys_sort_key = sort_key.get_key(quote!(self.#sort_key_ident.clone()));
+            let key_sort_key = sort_key.get_key(quote!(sort_key));
+            (
+                quote!(::std::option::Option::Some(#name)),
+                quote!(::std::option::Option::Some(#keys_sort_key)),
+                quote!(::std::option::Option::Some(#key_sort_key)),
+                quote!(, sort_key: #sort_key_type),
+            )
+        }
+        None => (
+            quote!(::std::option::Option::None),
+            quote!(::std::option::Option::None),
+            quote!(::std::option::Option::None),
+            quote!(),
+        ),
+    };
+    let key_bounds = key_types
+        .iter()
+        .map(|ty| quote!(#ty: ::std::convert::Into<#value>));
+    let expanded = quote! {
+        impl #impl_generics ::dynamodb_crud::common::item::DynamoItem<#value>
+            for #ident #type_generics #where_clause
+        {
+            const TABLE_NAME: &'static str = #table;
+            const PARTITION_KEY: &'static str = #partition_key_name;
+            const SORT_KEY: ::std::option::Option<&'static str> = #sort_key_name;
+
+            fn keys(&self) -> ::dynamodb_crud::common::key::Keys<#value> {
+                ::dynamodb_crud::common::key::Keys {
+                    partition_key: #keys_partition_key,
+                    sort_key: #keys_sort_key,
+                }
+            }
+        }
+
+        impl #item_impl_generics #ident #type_generics #item_where_clause {
+            /// Build the primary key of the item from its key values.
+            pub fn key<#value>(
+                partition_key: #partition_key_type #key_argument
+            ) -> ::dynamodb_crud::common::key::Keys<#value>
+            where
+                #(#key_bounds,)*
+            {
+                ::dynamodb_crud::common::key::Keys {
+                    partition_key: #key_partition_key,
+                    sort_key: #key_sort_key,
+                }
+            }
+        }
+    };
+    Ok(expanded)
+}
+
+fn expand_dynamo_table(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attributes = ItemAttributes::parse(&input, "DynamoTable")?;
+    let key_schema = get_key_schema(&attributes.partition_key, attributes.sort_key.as_ref())?;
+    let (billing_mode, throughput) = match attributes.provisioned {
+        Some((read, write)) => {
+            let throughput = quote! {
+                ::dynamodb_crud::table::common::Throughput {
+                    read_capacity_units: #read,
+                    write_capacity_units: #write,
+                }
+            };
+            (
+                quote!(::dynamodb_crud::table::common::BillingMode::Provisioned(#throughput)),
+                quote!(::std::option::Option::Some(#throughput)),
+            )
+        }
+        None => (
+            quote!(::dynamodb_crud::table::common::BillingMode::PayPerRequest),
+            quote!(::std::option::Option::None),
+        ),
+    };
+    let global_secondary_indexes = attributes
+        .global_indexes
+        .iter()
+        .map(|index| index.get_global_secondary_index(&throughput))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let stream_view_type = match &attributes.stream {
+        Some(variant) => quote! {
+            ::std::option::Option::Some(::aws_sdk_dynamodb::types::StreamViewType::#variant)
+        },
+        None => quote!(::std::option::Option::None),
+    };
+    let time_to_live = match &attributes.time_to_live {
+        Some(field) => {
+            let name = &field.name;
+            quote!(::std::option::Option::Some(#name))
+        }
+        None => quote!(::std::option::Option::None),
+    };
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+    let table = &attributes.table;
+    let expanded = quote! {
+        impl #impl_generics ::dynamodb_crud::common::item::DynamoTable
+            for #ident #type_generics #where_clause
+        {
+            const TIME_TO_LIVE_ATTRIBUTE: ::std::option::Option<&'static str> = #time_to_live;
+
+            fn table_definition() -> ::dynamodb_crud::table::create_table::CreateTable {
+                ::dynamodb_crud::table::create_table::CreateTable {
+                    billing_mode: #billing_mode,
+                    global_secondary_indexes: ::std::vec![#(#global_secondary_indexes),*],
+                    key_schema: #key_schema,
+                    stream_view_type: #stream_view_type,
+                    table_name: ::std::string::String::from(#table),
+                    ..::std::default::Default::default()
+                }
+            }
+        }
+    };
+    Ok(expanded)
+}
+
+/// Derive `DynamoFilter` for a struct of optional filter fields.
+///
+/// Every `Option` field that is set becomes a condition on its attribute, and the conditions
+/// are combined with `AND`. Fields accept a `#[dynamo_filter(...)]` attribute with:
+/// - `name = "..."`: the attribute name, defaulting to the field name
+/// - `operator = "..."`: one of `begins_with`, `contains`, `equals` (default), `exists`,
+///   `greater_than`, `greater_than_or_equal`, `in`, `less_than`, `less_than_or_equal`,
+///   `not_contains` or `not_equal`
+///
+/// `exists` fields are `Option<bool>`, and `in` fields wrap a collection of values.
+#[proc_macro_derive(DynamoFilter, attributes(dynamo_filter))]
+pub fn derive_dynamo_filter(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_dynamo_filter(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `DynamoItem` for a struct stored in a table.
+///
+/// The struct takes a `#[dynamo(...)]` attribute with:
+/// - `table = "..."`: the table name
+/// - `partition_key = "..."`: the field of the partition key
+/// - `sort_key = "..."`: the field of the sort key, if the table has one
+///
+/// Key fields accept a `#[dynamo(name = "...")]` attribute setting the attribute name,
+/// defaulting to the field name, and their types must convert into the key values. Also
+/// generates a `key` function building the primary key from the key values.
+///
+/// The struct attributes of `DynamoTable` are accepted and ignored.
+#[proc_macro_derive(DynamoItem, attributes(dynamo))]
+pub fn derive_dynamo_item(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_dynamo_item(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `DynamoTable` for a struct stored in a table.
+///
+/// Shares the `#[dynamo(...)]` attributes of `DynamoItem`, along with:
+/// - `global_index(name = "...", partition_key = "...", sort_key = "...", keys_only)`: a
+///   global secondary index on the given fields, projecting every attribute unless
+///   `keys_only` is set, repeated for every index
+/// - `provisioned(read = ..., write = ...)`: provisioned capacity units for the table and its
+///   indexes, defaulting to pay per request
+/// - `stream = "..."`: the view type of the stream of the table, one of `keys_only`,
+///   `new_image`, `old_image` or `new_and_old_images`, disabled by default
+/// - `time_to_live = "..."`: the field of the time to live attribute
+///
+/// The attribute types of strings, numbers and `Vec<u8>` keys are inferred, other key fields
+/// take a `#[dynamo(attribute_type = "B" | "N" | "S")]` attribute. The generated code refers
+/// to `aws_sdk_dynamodb`, which must be a dependency.
+#[proc_macro_derive(DynamoTable, attributes(dynamo))]
+pub fn derive_dynamo_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_dynamo_table(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,146 @@
+//! Analysis utilities for inspecting the data stored in DynamoDB tables.
+//!
+//! This module provides scan-based reports for:
+//! - Finding hot partitions by item count and size per partition key
+//! - Profiling attribute presence, attribute types and item sizes
+//! - Inferring a Rust entity struct from existing data
+
+/// Schema inference suggesting a Rust entity struct from existing data.
+pub mod infer_schema;
+/// Partition key cardinality and heat analysis.
+pub mod partition_heat;
+/// Attribute, type and item size statistics of a table.
+pub mod profile_table;
+
+use aws_sdk_dynamodb::types;
+use std::collections;
+
+/// Approximate the size of a number as DynamoDB accounts for it.
+fn get_number_size(number: &str) -> usize {
+    let mantissa = number.split(['e', 'E']).next().unwrap_or_default();
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let significant_digits = digits.trim_start_matches('0').trim_end_matches('0').len();
+    significant_digits.div_ceil(2) + 1
+}
+
+/// Approximate the size of an attribute value as DynamoDB accounts for it.
+pub(crate) fn get_attribute_value_size(value: &types::AttributeValue) -> usize {
+    match value {
+        types::AttributeValue::B(blob) => blob.as_ref().len(),
+        types::AttributeValue::Bool(_) | types::AttributeValue::Null(_) => 1,
+        types::AttributeValue::Bs(blobs) => blobs.iter().map(|blob| blob.as_ref().len()).sum(),
+        types::AttributeValue::L(values) => {
+            3 + values
+                .iter()
+                .map(|value| 1 + get_attribute_value_size(value))
+                .sum::<usize>()
+        }
+        types::AttributeValue::M(map) => {
+            3 + map
+                .iter()
+                .map(|(name, value)| 1 + name.len() + get_attribute_value_size(value))
+                .sum::<usize>()
+        }
+        types::AttributeValue::N(number) => get_number_size(number),
+        types::AttributeValue::Ns(numbers) => {
+            numbers.iter().map(|number| get_number_size(number)).sum()
+        }
+        types::AttributeValue::S(string) => string.len(),
+        types::AttributeValue::Ss(strings) => strings.iter().map(String::len).sum(),
+        _ => 0,
+    }
+}
+
+/// Get the DynamoDB data type descriptor of an attribute value.
+pub(crate) fn get_attribute_value_type(value: &types::AttributeValue) -> &'static str {
+    match value {
+        types::AttributeValue::B(_) => "B",
+        types::AttributeValue::Bool(_) => "BOOL",
+        types::AttributeValue::Bs(_) => "BS",
+        types::AttributeValue::L(_) => "L",
+        types::AttributeValue::M(_) => "M",
+        types::AttributeValue::N(_) => "N",
+        types::AttributeValue::Ns(_) => "NS",
+        types::AttributeValue::Null(_) => "NULL",
+        types::AttributeValue::S(_) => "S",
+        types::AttributeValue::Ss(_) => "SS",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Approximate the size of an item as DynamoDB accounts for it.
+pub(crate) fn get_item_size(item: &collections::HashMap<String, types::AttributeValue>) -> usize {
+    item.iter()
+        .map(|(name, value)| name.len() + get_attribute_value_size(value))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aws_sdk_dynamodb::primitives::Blob;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::string(
+        types::AttributeValue::S(
+            "abc".to_string()
+        ),
+        3
+    )]
+    #[case::number(
+        types::AttributeValue::N(
+            "-12.3400".to_string()
+        ),
+        3
+    )]
+    #[case::binary(
+        types::AttributeValue::B(
+            Blob::new(
+                vec![1, 2]
+            )
+        ),
+        2
+    )]
+    #[case::list(
+        types::AttributeValue::L(
+            vec![
+                types::AttributeValue::Bool(true),
+                types::AttributeValue::S(
+                    "ab".to_string()
+                ),
+            ]
+        ),
+        8
+    )]
+    #[case::map(
+        types::AttributeValue::M(
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::Null(true)
+                    ),
+                ]
+            )
+        ),
+        6
+    )]
+    #[case::string_set(
+        types::AttributeValue::Ss(
+            vec![
+                "a".to_string(),
+                "bc".to_string(),
+            ]
+        ),
+        3
+    )]
+    fn test_get_attribute_value_size(
+        #[case] value: types::AttributeValue,
+        #[case] expected: usize,
+    ) {
+        let actual = get_attribute_value_size(&value);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,327 @@
+use crate::{analysis, common};
+
+use aws_sdk_dynamodb::Client;
+use serde::Serialize;
+use std::collections;
+
+/// Rust keywords that cannot be used as field names.
+const KEYWORDS: [&str; 52] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield", "Self",
+];
+
+/// An attribute inferred from the sampled items.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InferredAttribute {
+    /// The attribute name.
+    pub name: String,
+    /// Whether the attribute is missing (or null) in some sampled items.
+    pub optional: bool,
+    /// The suggested Rust type, without the `Option` wrapper.
+    pub rust_type: String,
+    /// The number of occurrences per observed DynamoDB data type (`S`, `N`, `M`, etc.).
+    pub types: collections::BTreeMap<String, usize>,
+}
+
+/// Output of the schema inference.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InferSchemaOutput {
+    /// The inferred attributes, key attributes first and then by name.
+    pub attributes: Vec<InferredAttribute>,
+    /// The source code of the suggested Rust entity struct.
+    pub entity: String,
+    /// The partition key attribute name.
+    pub partition_key_name: String,
+    /// The number of sampled items.
+    pub scanned_count: usize,
+    /// The sort key attribute name, if the table has one.
+    pub sort_key_name: Option<String>,
+}
+
+/// Get the suggested Rust type and optional `with` serde module of the observed types.
+fn get_rust_type(types: &collections::BTreeMap<String, usize>) -> (&'static str, Option<&str>) {
+    let mut types = types.keys().filter(|type_| *type_ != "NULL");
+    match (types.next().map(String::as_str), types.next()) {
+        (Some("B"), None) => ("serde_bytes::ByteBuf", None),
+        (Some("BOOL"), None) => ("bool", None),
+        (Some("BS"), None) => (
+            "Vec<serde_bytes::ByteBuf>",
+            Some("serde_dynamo::binary_set"),
+        ),
+        (Some("L"), None) => ("Vec<serde_json::Value>", None),
+        (Some("N"), None) => ("f64", None),
+        (Some("NS"), None) => ("Vec<f64>", Some("serde_dynamo::number_set")),
+        (Some("S"), None) => ("String", None),
+        (Some("SS"), None) => ("Vec<String>", Some("serde_dynamo::string_set")),
+        _ => ("serde_json::Value", None),
+    }
+}
+
+/// Convert an attribute name into a snake case Rust field name.
+pub(crate) fn get_field_name(name: &str) -> String {
+    let mut field_name = String::with_capacity(name.len());
+    let mut previous: Option<char> = None;
+    for character in name.chars() {
+        if character.is_ascii_uppercase()
+            && previous
+                .is_some_and(|previous| previous.is_ascii_lowercase() || previous.is_ascii_digit())
+        {
+            field_name.push('_');
+        }
+        match character.is_ascii_alphanumeric() {
+            true => field_name.push(character.to_ascii_lowercase()),
+            false => field_name.push('_'),
+        }
+        previous = Some(character);
+    }
+    if field_name.is_empty() || field_name.starts_with(|character: char| character.is_ascii_digit())
+    {
+        field_name.insert(0, '_');
+    }
+    if KEYWORDS.contains(&field_name.as_str()) {
+        field_name.push('_');
+    }
+    field_name
+}
+
+fn get_field(
+    attribute: &InferredAttribute,
+    partition_key_name: &str,
+    sort_key_name: Option<&str>,
+) -> String {
+    let mut lines = Vec::new();
+    if attribute.name == partition_key_name {
+        lines.push("/// Partition key.".to_string());
+    } else if Some(attribute.name.as_str()) == sort_key_name {
+        lines.push("/// Sort key.".to_string());
+    }
+    let field_name = get_field_name(&attribute.name);
+    if field_name != attribute.name {
+        lines.push(format!("#[serde(rename = {:?})]", attribute.name));
+    }
+    let (_, with) = get_rust_type(&attribute.types);
+    let rust_type = match (with, attribute.optional) {
+        (Some(with), optional) => {
+            lines.push(format!("#[serde(with = {with:?})]"));
+            if optional {
+                lines
+                    .push("#[serde(default, skip_serializing_if = \"Vec::is_empty\")]".to_string());
+            }
+            attribute.rust_type.clone()
+        }
+        (None, true) => {
+            lines.push("#[serde(default, skip_serializing_if = \"Option::is_none\")]".to_string());
+            format!("Option<{}>", attribute.rust_type)
+        }
+        (None, false) => attribute.rust_type.clone(),
+    };
+    lines.push(format!("pub {field_name}: {rust_type},"));
+    lines
+        .into_iter()
+        .map(|line| format!("    {line}\n"))
+        .collect()
+}
+
+/// Render the source code of the suggested Rust entity struct.
+fn get_entity(
+    entity_name: &str,
+    attributes: &[InferredAttribute],
+    partition_key_name: &str,
+    sort_key_name: Option<&str>,
+) -> String {
+    let fields: Vec<_> = attributes
+        .iter()
+        .map(|attribute| get_field(attribute, partition_key_name, sort_key_name))
+        .collect();
+    format!(
+        "use serde::{{Deserialize, Serialize}};\n\n#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]\npub struct {entity_name} {{\n{}}}\n",
+        fields.join("\n")
+    )
+}
+
+/// Get the inferred attributes from a table profile, key attributes first and then by name.
+fn get_inferred_attributes(
+    profile: analysis::profile_table::ProfileTableOutput,
+    partition_key_name: &str,
+    sort_key_name: Option<&str>,
+) -> Vec<InferredAttribute> {
+    let mut attributes: Vec<_> = profile
+        .attributes
+        .into_iter()
+        .map(|attribute| {
+            let (rust_type, _) = get_rust_type(&attribute.types);
+            InferredAttribute {
+                optional: attribute.count < profile.scanned_count
+                    || attribute.types.contains_key("NULL"),
+                rust_type: rust_type.to_string(),
+                name: attribute.name,
+                types: attribute.types,
+            }
+        })
+        .collect();
+    attributes.sort_by_key(|attribute| {
+        let order = if attribute.name == partition_key_name {
+            0
+        } else if Some(attribute.name.as_str()) == sort_key_name {
+            1
+        } else {
+            2
+        };
+        (order, attribute.name.clone())
+    });
+    attributes
+}
+
+/// Schema inference.
+///
+/// Samples a table with a table profile, describes its key schema and suggests a Rust entity
+/// struct (serde attributes included) matching the observed attributes, to accelerate
+/// onboarding this crate onto existing tables. Numbers are always inferred as `f64`, and
+/// attributes observed with several types fall back to `serde_json::Value`.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{analysis, read};
+/// use serde_json::Value;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let infer_schema: analysis::infer_schema::InferSchema<Value> =
+///     analysis::infer_schema::InferSchema {
+///         entity_name: "User".to_string(),
+///         profile_table: analysis::profile_table::ProfileTable {
+///             max_items: Some(1000),
+///             scan: read::scan::Scan {
+///                 multiple_read_args: read::common::MultipleReadArgs {
+///                     table_name: "users".to_string(),
+///                     ..Default::default()
+///                 },
+///                 ..Default::default()
+///             },
+///             ..Default::default()
+///         },
+///     };
+/// let output = infer_schema.send(client).await?;
+/// println!("{}", output.entity);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InferSchema<T> {
+    /// The name of the suggested Rust entity struct.
+    pub entity_name: String,
+    /// The table profile sampling the items.
+    pub profile_table: analysis::profile_table::ProfileTable<T>,
+}
+
+impl<T: Clone + Serialize> InferSchema<T> {
+    /// Execute the schema inference.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.infer_schema", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<InferSchemaOutput, crate::Error> {
+        let output = client
+            .describe_table()
+            .table_name(&self.profile_table.scan.multiple_read_args.table_name)
+            .send()
+            .await?;
+        let (partition_key_name, sort_key_name) = common::get_key_schema_names(
+            output
+                .table
+                .as_ref()
+                .map(|table| table.key_schema())
+                .unwrap_or_default(),
+        );
+        let profile = self.profile_table.send(client).await?;
+        let scanned_count = profile.scanned_count;
+        let attributes =
+            get_inferred_attributes(profile, &partition_key_name, sort_key_name.as_deref());
+        let entity = get_entity(
+            &self.entity_name,
+            &attributes,
+            &partition_key_name,
+            sort_key_name.as_deref(),
+        );
+        let output = InferSchemaOutput {
+            attributes,
+            entity,
+            partition_key_name,
+            scanned_count,
+            sort_key_name,
+        };
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::snake_case("user_id", "user_id")]
+    #[case::camel_case("userId", "user_id")]
+    #[case::pascal_case("CreatedAt2", "created_at2")]
+    #[case::symbols("order-total.usd", "order_total_usd")]
+    #[case::digit("1st", "_1st")]
+    #[case::keyword("type", "type_")]
+    fn test_get_field_name(#[case] name: &str, #[case] expected: &str) {
+        let actual = get_field_name(name);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_infer_schema_entity() {
+        let profile = analysis::profile_table::ProfileTableOutput {
+            attributes: vec![
+                analysis::profile_table::AttributeProfile {
+                    count: 2,
+                    name: "name".to_string(),
+                    types: collections::BTreeMap::from([("S".to_string(), 2)]),
+                },
+                analysis::profile_table::AttributeProfile {
+                    count: 2,
+                    name: "userId".to_string(),
+                    types: collections::BTreeMap::from([("S".to_string(), 2)]),
+                },
+                analysis::profile_table::AttributeProfile {
+                    count: 1,
+                    name: "age".to_string(),
+                    types: collections::BTreeMap::from([("N".to_string(), 1)]),
+                },
+                analysis::profile_table::AttributeProfile {
+                    count: 1,
+                    name: "tags".to_string(),
+                    types: collections::BTreeMap::from([("SS".to_string(), 1)]),
+                },
+            ],
+            scanned_count: 2,
+            ..Default::default()
+        };
+        let attributes = get_inferred_attributes(profile, "userId", None);
+        let actual = get_entity("User", &attributes, "userId", None);
+        let expected = r#"use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct User {
+    /// Partition key.
+    #[serde(rename = "userId")]
+    pub user_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age: Option<f64>,
+
+    pub name: String,
+
+    #[serde(with = "serde_dynamo::string_set")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+"#;
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{analysis, read};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::Serialize;
+use std::collections;
+
+/// Statistics of a single partition key (or partition key prefix).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PartitionStatistics {
+    /// The number of scanned items with this partition key.
+    pub count: usize,
+    /// The partition key value or prefix, hex encoded for binary keys.
+    pub key: String,
+    /// The approximate aggregate size in bytes of the scanned items.
+    pub size: usize,
+}
+
+/// Output of the partition heat analysis.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PartitionHeatOutput {
+    /// Statistics per partition key, from the most to the least frequent.
+    pub partitions: Vec<PartitionStatistics>,
+    /// The number of scanned items.
+    pub scanned_count: usize,
+}
+
+/// partition heat accumulated over a scan segment
+#[derive(Clone, Debug, Default, PartialEq)]
+struct PartitionHeatAccumulator {
+    partitions: collections::HashMap<String, PartitionStatistics>,
+    scanned_count: usize,
+}
+
+impl PartitionHeatAccumulator {
+    fn add_item(
+        &mut self,
+        item: &collections::HashMap<String, types::AttributeValue>,
+        partition_key_name: &str,
+        prefix_length: Option<usize>,
+    ) {
+        self.scanned_count += 1;
+        let Some(mut key) = item.get(partition_key_name).and_then(get_key_string) else {
+            return;
+        };
+        if let Some(prefix_length) = prefix_length {
+            key = key.chars().take(prefix_length).collect();
+        }
+        let statistics = self
+            .partitions
+            .entry(key)
+            .or_insert_with_key(|key| PartitionStatistics {
+                key: key.clone(),
+                ..Default::default()
+            });
+        statistics.count += 1;
+        statistics.size += analysis::get_item_size(item);
+    }
+
+    fn merge(accumulators: Vec<Self>) -> PartitionHeatOutput {
+        let mut partitions = collections::HashMap::<String, PartitionStatistics>::new();
+        let mut scanned_count = 0;
+        for accumulator in accumulators {
+            scanned_count += accumulator.scanned_count;
+            for (key, other) in accumulator.partitions {
+                let statistics =
+                    partitions
+                        .entry(key)
+                        .or_insert_with_key(|key| PartitionStatistics {
+                            key: key.clone(),
+                            ..Default::default()
+                        });
+                statistics.count += other.count;
+                statistics.size += other.size;
+            }
+        }
+        let mut partitions: Vec<_> = partitions.into_values().collect();
+        partitions.sort_by(|left, right| {
+            right
+                .count
+                .cmp(&left.count)
+                .then_with(|| left.key.cmp(&right.key))
+        });
+        PartitionHeatOutput {
+            partitions,
+            scanned_count,
+        }
+    }
+}
+
+fn get_key_string(value: &types::AttributeValue) -> Option<String> {
+    match value {
+        types::AttributeValue::B(blob) => {
+            let key = blob
+                .as_ref()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            Some(key)
+        }
+        types::AttributeValue::N(number) => Some(number.clone()),
+        types::AttributeValue::S(string) => Some(string.clone()),
+        _ => None,
+    }
+}
+
+/// Partition heat analysis.
+///
+/// Scans (or samples) a table and reports the item count and approximate aggregate size
+/// per partition key or partition key prefix, to help diagnose hot partitions.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{analysis, read};
+/// use serde_json::Value;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let partition_heat: analysis::partition_heat::PartitionHeat<Value> =
+///     analysis::partition_heat::PartitionHeat {
+///         adaptive_concurrency: Some(read::scan::AdaptiveConcurrency {
+///             max_read_capacity_units: Some(500.0),
+///             ..Default::default()
+///         }),
+///         max_items: Some(10_000),
+///         partition_key_name: "id".to_string(),
+///         scan: read::scan::Scan {
+///             multiple_read_args: read::common::MultipleReadArgs {
+///                 table_name: "users".to_string(),
+///                 ..Default::default()
+///             },
+///             ..Default::default()
+///         },
+///         segments: Some(16),
+///         ..Default::default()
+///     };
+/// let output = partition_heat.send(client).await?;
+/// for partition in output.partitions.iter().take(10) {
+///     println!("{}: {} items, {} bytes", partition.key, partition.count, partition.size);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartitionHeat<T> {
+    /// Adaptive concurrency of the segments, tuned on the throttling and the consumed
+    /// capacity of the scan.
+    ///
+    /// If `None`, every segment is scanned at once.
+    pub adaptive_concurrency: Option<read::scan::AdaptiveConcurrency>,
+    /// The maximum number of items to sample (the whole table is scanned if `None`).
+    pub max_items: Option<usize>,
+    /// The partition key attribute name.
+    pub partition_key_name: String,
+    /// Group partition keys by their first characters instead of their whole value.
+    pub prefix_length: Option<usize>,
+    /// The scan providing the items (table name, index, filter, etc.).
+    ///
+    /// Items must include the partition key attribute; the segment settings are
+    /// overridden by `segments`.
+    pub scan: read::scan::Scan<T>,
+    /// The number of segments scanned in parallel (defaults to 1).
+    pub segments: Option<i32>,
+}
+
+impl<T: Clone + Serialize> PartitionHeat<T> {
+    /// Execute the partition heat analysis.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.partition_heat", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<PartitionHeatOutput, crate::Error> {
+        let partition_key_name = self.partition_key_name;
+        let prefix_length = self.prefix_length;
+        let accumulators = self
+            .scan
+            .fold_segments(
+                client,
+                self.segments.unwrap_or(1),
+                self.adaptive_concurrency.as_ref(),
+                self.max_items,
+                |accumulator: &mut PartitionHeatAccumulator, item| {
+                    accumulator.add_item(&item, &partition_key_name, prefix_length);
+                },
+            )
+            .await?;
+        Ok(PartitionHeatAccumulator::merge(accumulators))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::whole_key(
+        None,
+        PartitionHeatOutput {
+            partitions: vec![
+                PartitionStatistics {
+                    count: 2,
+                    key: "ab".to_string(),
+                    size: 6,
+                },
+                PartitionStatistics {
+                    count: 1,
+                    key: "ac".to_string(),
+                    size: 3,
+                },
+            ],
+            scanned_count: 4,
+        }
+    )]
+    #[case::prefix(
+        Some(1),
+        PartitionHeatOutput {
+            partitions: vec![
+                PartitionStatistics {
+                    count: 3,
+                    key: "a".to_string(),
+                    size: 9,
+                },
+            ],
+            scanned_count: 4,
+        }
+    )]
+    fn test_partition_heat(
+        #[case] prefix_length: Option<usize>,
+        #[case] expected: PartitionHeatOutput,
+    ) {
+        let items = [Some("ab"), Some("ac"), None, Some("ab")].map(|key| match key {
+            Some(key) => collections::HashMap::from([(
+                "k".to_string(),
+                types::AttributeValue::S(key.to_string()),
+            )]),
+            None => collections::HashMap::new(),
+        });
+        let mut accumulator = PartitionHeatAccumulator::default();
+        let mut other_accumulator = PartitionHeatAccumulator::default();
+        for (index, item) in items.iter().enumerate() {
+            let accumulator = match index % 2 {
+                0 => &mut accumulator,
+                _ => &mut other_accumulator,
+            };
+            accumulator.add_item(item, "k", prefix_length);
+        }
+        let actual = PartitionHeatAccumulator::merge(vec![accumulator, other_accumulator]);
+        assert_eq!(actual, expected);
+    }
+}
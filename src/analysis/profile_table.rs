@@ -0,0 +1,287 @@
+use crate::{analysis, read};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::Serialize;
+use std::collections;
+
+/// Statistics of a single top level attribute.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AttributeProfile {
+    /// The number of scanned items containing the attribute.
+    pub count: usize,
+    /// The attribute name.
+    pub name: String,
+    /// The number of occurrences per observed DynamoDB data type (`S`, `N`, `M`, etc.).
+    pub types: collections::BTreeMap<String, usize>,
+}
+
+/// Distribution of the approximate item sizes in bytes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ItemSizeDistribution {
+    /// The largest item size.
+    pub max: usize,
+    /// The mean item size, rounded down.
+    pub mean: usize,
+    /// The smallest item size.
+    pub min: usize,
+    /// The median item size.
+    pub p50: usize,
+    /// The 90th percentile item size.
+    pub p90: usize,
+    /// The 99th percentile item size.
+    pub p99: usize,
+    /// The aggregate size of the scanned items.
+    pub total: usize,
+}
+
+/// Output of the table profile.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProfileTableOutput {
+    /// Statistics per attribute, from the most to the least frequent.
+    pub attributes: Vec<AttributeProfile>,
+    /// The distribution of the scanned item sizes.
+    pub item_sizes: ItemSizeDistribution,
+    /// The number of scanned items.
+    pub scanned_count: usize,
+}
+
+/// table profile accumulated over a scan segment
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ProfileTableAccumulator {
+    attributes: collections::HashMap<String, AttributeProfile>,
+    item_sizes: Vec<usize>,
+}
+
+impl ProfileTableAccumulator {
+    fn add_item(&mut self, item: &collections::HashMap<String, types::AttributeValue>) {
+        for (name, value) in item {
+            let attribute = self
+                .attributes
+                .entry(name.clone())
+                .or_insert_with_key(|name| AttributeProfile {
+                    name: name.clone(),
+                    ..Default::default()
+                });
+            attribute.count += 1;
+            *attribute
+                .types
+                .entry(analysis::get_attribute_value_type(value).to_string())
+                .or_default() += 1;
+        }
+        self.item_sizes.push(analysis::get_item_size(item));
+    }
+
+    fn merge(accumulators: Vec<Self>) -> ProfileTableOutput {
+        let mut attributes = collections::HashMap::<String, AttributeProfile>::new();
+        let mut item_sizes = Vec::new();
+        for accumulator in accumulators {
+            item_sizes.extend(accumulator.item_sizes);
+            for (name, other) in accumulator.attributes {
+                let attribute =
+                    attributes
+                        .entry(name)
+                        .or_insert_with_key(|name| AttributeProfile {
+                            name: name.clone(),
+                            ..Default::default()
+                        });
+                attribute.count += other.count;
+                for (type_, count) in other.types {
+                    *attribute.types.entry(type_).or_default() += count;
+                }
+            }
+        }
+        let mut attributes: Vec<_> = attributes.into_values().collect();
+        attributes.sort_by(|left, right| {
+            right
+                .count
+                .cmp(&left.count)
+                .then_with(|| left.name.cmp(&right.name))
+        });
+        let scanned_count = item_sizes.len();
+        ProfileTableOutput {
+            attributes,
+            item_sizes: get_item_size_distribution(item_sizes),
+            scanned_count,
+        }
+    }
+}
+
+fn get_item_size_distribution(mut item_sizes: Vec<usize>) -> ItemSizeDistribution {
+    if item_sizes.is_empty() {
+        return ItemSizeDistribution::default();
+    }
+    item_sizes.sort_unstable();
+    let count = item_sizes.len();
+    let get_percentile = |percentile: usize| item_sizes[(percentile * count).div_ceil(100) - 1];
+    let total = item_sizes.iter().sum();
+    ItemSizeDistribution {
+        max: item_sizes[count - 1],
+        mean: total / count,
+        min: item_sizes[0],
+        p50: get_percentile(50),
+        p90: get_percentile(90),
+        p99: get_percentile(99),
+        total,
+    }
+}
+
+/// Table profile.
+///
+/// Scans (or samples) a table and reports how often each top level attribute is present,
+/// which data types it was observed with, and the distribution of the item sizes. Useful
+/// before writing migrations or designing projections.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{analysis, read};
+/// use serde_json::Value;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let profile_table: analysis::profile_table::ProfileTable<Value> =
+///     analysis::profile_table::ProfileTable {
+///         max_items: Some(1000),
+///         scan: read::scan::Scan {
+///             multiple_read_args: read::common::MultipleReadArgs {
+///                 table_name: "users".to_string(),
+///                 ..Default::default()
+///             },
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     };
+/// let output = profile_table.send(client).await?;
+/// for attribute in output.attributes {
+///     println!("{}: {}/{} {:?}", attribute.name, attribute.count, output.scanned_count, attribute.types);
+/// }
+/// println!("p99 item size: {} bytes", output.item_sizes.p99);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfileTable<T> {
+    /// Adaptive concurrency of the segments, tuned on the throttling and the consumed
+    /// capacity of the scan.
+    ///
+    /// If `None`, every segment is scanned at once.
+    pub adaptive_concurrency: Option<read::scan::AdaptiveConcurrency>,
+    /// The maximum number of items to sample (the whole table is scanned if `None`).
+    pub max_items: Option<usize>,
+    /// The scan providing the items (table name, index, filter, etc.).
+    ///
+    /// The segment settings are overridden by `segments`.
+    pub scan: read::scan::Scan<T>,
+    /// The number of segments scanned in parallel (defaults to 1).
+    pub segments: Option<i32>,
+}
+
+impl<T: Clone + Serialize> ProfileTable<T> {
+    /// Execute the table profile.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.profile_table", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<ProfileTableOutput, crate::Error> {
+        let accumulators = self
+            .scan
+            .fold_segments(
+                client,
+                self.segments.unwrap_or(1),
+                self.adaptive_concurrency.as_ref(),
+                self.max_items,
+                |accumulator: &mut ProfileTableAccumulator, item| accumulator.add_item(&item),
+            )
+            .await?;
+        Ok(ProfileTableAccumulator::merge(accumulators))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::empty(
+        vec![],
+        ItemSizeDistribution::default()
+    )]
+    #[case::single(
+        vec![7],
+        ItemSizeDistribution {
+            max: 7,
+            mean: 7,
+            min: 7,
+            p50: 7,
+            p90: 7,
+            p99: 7,
+            total: 7,
+        }
+    )]
+    #[case::many(
+        (1..=100).rev().collect(),
+        ItemSizeDistribution {
+            max: 100,
+            mean: 50,
+            min: 1,
+            p50: 50,
+            p90: 90,
+            p99: 99,
+            total: 5050,
+        }
+    )]
+    fn test_get_item_size_distribution(
+        #[case] item_sizes: Vec<usize>,
+        #[case] expected: ItemSizeDistribution,
+    ) {
+        let actual = get_item_size_distribution(item_sizes);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_profile_table() {
+        let items = [
+            collections::HashMap::from([
+                ("a".to_string(), types::AttributeValue::S("bc".to_string())),
+                ("d".to_string(), types::AttributeValue::Bool(true)),
+            ]),
+            collections::HashMap::from([(
+                "a".to_string(),
+                types::AttributeValue::N("1".to_string()),
+            )]),
+        ];
+        let mut accumulator = ProfileTableAccumulator::default();
+        let mut other_accumulator = ProfileTableAccumulator::default();
+        accumulator.add_item(&items[0]);
+        other_accumulator.add_item(&items[1]);
+        let actual = ProfileTableAccumulator::merge(vec![accumulator, other_accumulator]);
+        let expected = ProfileTableOutput {
+            attributes: vec![
+                AttributeProfile {
+                    count: 2,
+                    name: "a".to_string(),
+                    types: collections::BTreeMap::from([
+                        ("N".to_string(), 1),
+                        ("S".to_string(), 1),
+                    ]),
+                },
+                AttributeProfile {
+                    count: 1,
+                    name: "d".to_string(),
+                    types: collections::BTreeMap::from([("BOOL".to_string(), 1)]),
+                },
+            ],
+            item_sizes: ItemSizeDistribution {
+                max: 5,
+                mean: 4,
+                min: 3,
+                p50: 3,
+                p90: 5,
+                p99: 5,
+                total: 8,
+            },
+            scanned_count: 2,
+        };
+        assert_eq!(actual, expected);
+    }
+}
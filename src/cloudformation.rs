@@ -0,0 +1,267 @@
+//! CloudFormation export of the tables storing items.
+//!
+//! Tables are exported from the [`DynamoTable`](crate::common::item::DynamoTable) definition
+//! of their items, so infrastructure templates never drift from the definitions used by the
+//! application.
+
+use crate::common;
+
+use aws_sdk_dynamodb::{operation, types};
+use serde_json::{Map, Value, json};
+
+fn get_key_schema_json(key_schema: &[types::KeySchemaElement]) -> Value {
+    key_schema
+        .iter()
+        .map(|element| {
+            json!({
+                "AttributeName": element.attribute_name(),
+                "KeyType": element.key_type().as_str(),
+            })
+        })
+        .collect()
+}
+
+fn get_projection_json(projection: Option<&types::Projection>) -> Value {
+    let mut json = Map::new();
+    if let Some(projection_type) = projection.and_then(|projection| projection.projection_type()) {
+        json.insert(
+            "ProjectionType".to_string(),
+            json!(projection_type.as_str()),
+        );
+    }
+    if let Some(non_key_attributes) =
+        projection.and_then(|projection| projection.non_key_attributes.as_ref())
+    {
+        json.insert("NonKeyAttributes".to_string(), json!(non_key_attributes));
+    }
+    Value::Object(json)
+}
+
+fn get_throughput_json(throughput: &types::ProvisionedThroughput) -> Value {
+    json!({
+        "ReadCapacityUnits": throughput.read_capacity_units(),
+        "WriteCapacityUnits": throughput.write_capacity_units(),
+    })
+}
+
+/// Generate the `AWS::DynamoDB::Table` CloudFormation resource of the table storing an item.
+///
+/// The resource carries the keys, secondary indexes, billing mode, stream, encryption and
+/// deletion protection of the table definition, along with the time to live attribute, if
+/// any. CDK stacks can embed it with `CfnInclude`, or map its properties onto `CfnTable`.
+///
+/// ```rust
+/// use dynamodb_crud::{cloudformation, common::item::DynamoTable, table};
+///
+/// struct Session;
+///
+/// impl DynamoTable for Session {
+///     const TIME_TO_LIVE_ATTRIBUTE: Option<&'static str> = Some("expiresAt");
+///
+///     fn table_definition() -> table::create_table::CreateTable {
+///         table::create_table::CreateTable {
+///             key_schema: table::common::KeySchema {
+///                 partition_key: table::common::KeyAttribute {
+///                     name: "id".to_string(),
+///                     ..Default::default()
+///                 },
+///                 sort_key: None,
+///             },
+///             table_name: "sessions".to_string(),
+///             ..Default::default()
+///         }
+///     }
+/// }
+///
+/// let resource = cloudformation::get_table_resource::<Session>();
+/// assert_eq!(resource["Type"], "AWS::DynamoDB::Table");
+/// assert_eq!(
+///     resource["Properties"]["TimeToLiveSpecification"]["AttributeName"],
+///     "expiresAt"
+/// );
+/// ```
+pub fn get_table_resource<T: common::item::DynamoTable>() -> Value {
+    let input = operation::create_table::CreateTableInput::from(T::table_definition());
+    let mut properties = Map::new();
+    properties.insert(
+        "AttributeDefinitions".to_string(),
+        input
+            .attribute_definitions()
+            .iter()
+            .map(|definition| {
+                json!({
+                    "AttributeName": definition.attribute_name(),
+                    "AttributeType": definition.attribute_type().as_str(),
+                })
+            })
+            .collect(),
+    );
+    if let Some(billing_mode) = input.billing_mode() {
+        properties.insert("BillingMode".to_string(), json!(billing_mode.as_str()));
+    }
+    if let Some(deletion_protection) = input.deletion_protection_enabled() {
+        properties.insert(
+            "DeletionProtectionEnabled".to_string(),
+            json!(deletion_protection),
+        );
+    }
+    if !input.global_secondary_indexes().is_empty() {
+        let indexes = input
+            .global_secondary_indexes()
+            .iter()
+            .map(|index| {
+                let mut json = Map::new();
+                json.insert("IndexName".to_string(), json!(index.index_name()));
+                json.insert(
+                    "KeySchema".to_string(),
+                    get_key_schema_json(index.key_schema()),
+                );
+                json.insert(
+                    "Projection".to_string(),
+                    get_projection_json(index.projection()),
+                );
+                if let Some(throughput) = index.provisioned_throughput() {
+                    json.insert(
+                        "ProvisionedThroughput".to_string(),
+                        get_throughput_json(throughput),
+                    );
+                }
+                Value::Object(json)
+            })
+            .collect();
+        properties.insert("GlobalSecondaryIndexes".to_string(), indexes);
+    }
+    properties.insert(
+        "KeySchema".to_string(),
+        get_key_schema_json(input.key_schema()),
+    );
+    if !input.local_secondary_indexes().is_empty() {
+        let indexes = input
+            .local_secondary_indexes()
+            .iter()
+            .map(|index| {
+                json!({
+                    "IndexName": index.index_name(),
+                    "KeySchema": get_key_schema_json(index.key_schema()),
+                    "Projection": get_projection_json(index.projection()),
+                })
+            })
+            .collect();
+        properties.insert("LocalSecondaryIndexes".to_string(), indexes);
+    }
+    if let Some(throughput) = input.provisioned_throughput() {
+        properties.insert(
+            "ProvisionedThroughput".to_string(),
+            get_throughput_json(throughput),
+        );
+    }
+    if let Some(sse_specification) = input.sse_specification() {
+        let mut json = Map::new();
+        json.insert(
+            "SSEEnabled".to_string(),
+            json!(sse_specification.enabled().unwrap_or(true)),
+        );
+        if let Some(sse_type) = sse_specification.sse_type() {
+            json.insert("SSEType".to_string(), json!(sse_type.as_str()));
+        }
+        if let Some(kms_master_key_id) = sse_specification.kms_master_key_id() {
+            json.insert("KMSMasterKeyId".to_string(), json!(kms_master_key_id));
+        }
+        properties.insert("SSESpecification".to_string(), Value::Object(json));
+    }
+    if let Some(stream_view_type) = input
+        .stream_specification()
+        .and_then(|stream_specification| stream_specification.stream_view_type())
+    {
+        properties.insert(
+            "StreamSpecification".to_string(),
+            json!({ "StreamViewType": stream_view_type.as_str() }),
+        );
+    }
+    properties.insert("TableName".to_string(), json!(input.table_name()));
+    if let Some(time_to_live_attribute) = T::TIME_TO_LIVE_ATTRIBUTE {
+        properties.insert(
+            "TimeToLiveSpecification".to_string(),
+            json!({ "AttributeName": time_to_live_attribute, "Enabled": true }),
+        );
+    }
+    json!({
+        "Type": "AWS::DynamoDB::Table",
+        "Properties": properties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::table;
+
+    struct Order;
+
+    impl common::item::DynamoTable for Order {
+        const TIME_TO_LIVE_ATTRIBUTE: Option<&'static str> = Some("expiresAt");
+
+        fn table_definition() -> table::create_table::CreateTable {
+            table::create_table::CreateTable {
+                global_secondary_indexes: vec![table::common::GlobalSecondaryIndex {
+                    index_name: "byStatus".to_string(),
+                    key_schema: table::common::KeySchema {
+                        partition_key: table::common::KeyAttribute {
+                            attribute_type: types::ScalarAttributeType::S,
+                            name: "status".to_string(),
+                        },
+                        sort_key: None,
+                    },
+                    projection: table::common::Projection::Include(vec!["total".to_string()]),
+                    ..Default::default()
+                }],
+                key_schema: table::common::KeySchema {
+                    partition_key: table::common::KeyAttribute {
+                        attribute_type: types::ScalarAttributeType::S,
+                        name: "userId".to_string(),
+                    },
+                    sort_key: Some(table::common::KeyAttribute {
+                        attribute_type: types::ScalarAttributeType::N,
+                        name: "createdAt".to_string(),
+                    }),
+                },
+                stream_view_type: Some(types::StreamViewType::NewImage),
+                table_name: "orders".to_string(),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_table_resource() {
+        let actual = get_table_resource::<Order>();
+        let expected = json!({
+            "Type": "AWS::DynamoDB::Table",
+            "Properties": {
+                "AttributeDefinitions": [
+                    { "AttributeName": "userId", "AttributeType": "S" },
+                    { "AttributeName": "createdAt", "AttributeType": "N" },
+                    { "AttributeName": "status", "AttributeType": "S" },
+                ],
+                "BillingMode": "PAY_PER_REQUEST",
+                "GlobalSecondaryIndexes": [{
+                    "IndexName": "byStatus",
+                    "KeySchema": [{ "AttributeName": "status", "KeyType": "HASH" }],
+                    "Projection": {
+                        "NonKeyAttributes": ["total"],
+                        "ProjectionType": "INCLUDE",
+                    },
+                }],
+                "KeySchema": [
+                    { "AttributeName": "userId", "KeyType": "HASH" },
+                    { "AttributeName": "createdAt", "KeyType": "RANGE" },
+                ],
+                "StreamSpecification": { "StreamViewType": "NEW_IMAGE" },
+                "TableName": "orders",
+                "TimeToLiveSpecification": { "AttributeName": "expiresAt", "Enabled": true },
+            },
+        });
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,341 @@
+//! Code generation of typed table modules from DynamoDB table descriptions.
+//!
+//! The generated module contains:
+//! - The table, key and index names as constants
+//! - A typed primary key struct convertible into [`Keys`](crate::common::key::Keys)
+//! - Query helper functions for the table and each of its indexes
+//!
+//! Key values are converted into `serde_json::Value`, so the crate using the generated code
+//! must depend on `serde_json`.
+
+use crate::{analysis, common};
+
+use aws_sdk_dynamodb::{Client, types};
+
+/// key attribute of a table or index
+struct KeyAttribute {
+    field_name: String,
+    name: String,
+    rust_type: &'static str,
+    value: String,
+}
+
+impl KeyAttribute {
+    fn new(table: &types::TableDescription, name: &str) -> Self {
+        let field_name = analysis::infer_schema::get_field_name(name);
+        let attribute_type = table
+            .attribute_definitions()
+            .iter()
+            .find(|definition| definition.attribute_name() == name)
+            .map(|definition| definition.attribute_type());
+        let (rust_type, value) = match attribute_type {
+            Some(types::ScalarAttributeType::N) => ("f64", format!("Value::from({field_name})")),
+            Some(types::ScalarAttributeType::S) => {
+                ("String", format!("Value::String({field_name})"))
+            }
+            _ => ("Value", field_name.clone()),
+        };
+        Self {
+            field_name,
+            name: name.to_string(),
+            rust_type,
+            value,
+        }
+    }
+
+    fn get_key(&self, indentation: &str) -> String {
+        format!(
+            "common::key::Key {{\n{indentation}    name: {:?}.to_string(),\n{indentation}    value: {},\n{indentation}}}",
+            self.name, self.value
+        )
+    }
+}
+
+/// key schema of a table or index
+struct KeySchema {
+    partition_key: KeyAttribute,
+    sort_key: Option<KeyAttribute>,
+}
+
+impl KeySchema {
+    fn new(table: &types::TableDescription, key_schema: &[types::KeySchemaElement]) -> Self {
+        let (partition_key_name, sort_key_name) = common::get_key_schema_names(key_schema);
+        Self {
+            partition_key: KeyAttribute::new(table, &partition_key_name),
+            sort_key: sort_key_name.map(|sort_key_name| KeyAttribute::new(table, &sort_key_name)),
+        }
+    }
+
+    fn get_constants(&self, indentation: &str) -> String {
+        let mut constants = format!(
+            "{indentation}/// The partition key attribute name.\n{indentation}pub const PARTITION_KEY_NAME: &str = {:?};\n",
+            self.partition_key.name
+        );
+        if let Some(sort_key) = &self.sort_key {
+            constants += &format!(
+                "{indentation}/// The sort key attribute name.\n{indentation}pub const SORT_KEY_NAME: &str = {:?};\n",
+                sort_key.name
+            );
+        }
+        constants
+    }
+
+    fn get_query(
+        &self,
+        function_name: &str,
+        description: &str,
+        module_name: Option<&str>,
+    ) -> String {
+        let partition_key = &self.partition_key;
+        let index_name = match module_name {
+            Some(module_name) => {
+                format!("            index_name: Some({module_name}::NAME.to_string()),\n")
+            }
+            None => String::new(),
+        };
+        format!(
+            "/// Query {description} by partition key.\npub fn {function_name}({}: {}) -> read::query::Query<Value> {{\n    read::query::Query {{\n        multiple_read_args: read::common::MultipleReadArgs {{\n{index_name}            table_name: TABLE_NAME.to_string(),\n            ..Default::default()\n        }},\n        partition_key: {},\n        ..Default::default()\n    }}\n}}\n",
+            partition_key.field_name,
+            partition_key.rust_type,
+            partition_key.get_key("        "),
+        )
+    }
+}
+
+fn get_key_struct(table_name: &str, key_schema: &KeySchema) -> String {
+    let mut fields = format!(
+        "    /// The `{}` partition key.\n    pub {}: {},\n",
+        key_schema.partition_key.name,
+        key_schema.partition_key.field_name,
+        key_schema.partition_key.rust_type
+    );
+    let mut sort_key = "None".to_string();
+    if let Some(sort_key_attribute) = &key_schema.sort_key {
+        fields += &format!(
+            "    /// The `{}` sort key.\n    pub {}: {},\n",
+            sort_key_attribute.name, sort_key_attribute.field_name, sort_key_attribute.rust_type
+        );
+        sort_key = format!("Some({})", sort_key_attribute.get_key("            "));
+    }
+    let bindings: Vec<_> = [
+        Some(&key_schema.partition_key),
+        key_schema.sort_key.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|key| key.field_name.as_str())
+    .collect();
+    format!(
+        "/// Primary key of the `{table_name}` table.\n#[derive(Clone, Debug, PartialEq)]\npub struct Key {{\n{fields}}}\n\nimpl From<Key> for common::key::Keys<Value> {{\n    fn from(key: Key) -> Self {{\n        let Key {{ {} }} = key;\n        Self {{\n            partition_key: {},\n            sort_key: {sort_key},\n        }}\n    }}\n}}\n",
+        bindings.join(", "),
+        key_schema.partition_key.get_key("            "),
+    )
+}
+
+/// Generate the source code of the typed module of a table description.
+///
+/// Useful in build scripts working from a stored table description, see
+/// [`generate_table_module`] to describe the table first.
+pub fn get_table_module(table: &types::TableDescription) -> String {
+    let table_name = table.table_name().unwrap_or_default();
+    let key_schema = KeySchema::new(table, table.key_schema());
+    let mut sections = vec![
+        format!(
+            "// Typed keys, indexes and queries of the `{table_name}` table.\n// Generated by `dynamodb_crud::codegen` from the table description.\n\nuse dynamodb_crud::{{common, read}};\nuse serde_json::Value;\n"
+        ),
+        format!(
+            "/// The table name.\npub const TABLE_NAME: &str = {table_name:?};\n{}",
+            key_schema.get_constants("")
+        ),
+        get_key_struct(table_name, &key_schema),
+        key_schema.get_query("query", &format!("the `{table_name}` table"), None),
+    ];
+    let indexes = table
+        .global_secondary_indexes()
+        .iter()
+        .map(|index| (index.index_name(), index.key_schema()))
+        .chain(
+            table
+                .local_secondary_indexes()
+                .iter()
+                .map(|index| (index.index_name(), index.key_schema())),
+        );
+    for (index_name, index_key_schema) in indexes {
+        let index_name = index_name.unwrap_or_default();
+        let module_name = analysis::infer_schema::get_field_name(index_name);
+        let index_key_schema = KeySchema::new(table, index_key_schema);
+        sections.push(format!(
+            "/// The `{index_name}` index.\npub mod {module_name} {{\n    /// The index name.\n    pub const NAME: &str = {index_name:?};\n{}}}\n",
+            index_key_schema.get_constants("    ")
+        ));
+        sections.push(index_key_schema.get_query(
+            &format!("query_{module_name}"),
+            &format!("the `{index_name}` index"),
+            Some(&module_name),
+        ));
+    }
+    sections.join("\n")
+}
+
+/// Describe a table and generate the source code of its typed module.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::codegen;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let source = codegen::generate_table_module(client, "orders").await?;
+/// std::fs::write("src/tables/orders.rs", source)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "dynamodb_crud.generate_table_module", skip_all, err)
+)]
+pub async fn generate_table_module(
+    client: &Client,
+    table_name: &str,
+) -> Result<String, crate::Error> {
+    let output = client
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await?;
+    let table = output.table.unwrap_or_else(|| {
+        types::TableDescription::builder()
+            .table_name(table_name)
+            .build()
+    });
+    Ok(get_table_module(&table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_key_schema_element(name: &str, key_type: types::KeyType) -> types::KeySchemaElement {
+        types::KeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+            .unwrap()
+    }
+
+    fn get_attribute_definition(
+        name: &str,
+        attribute_type: types::ScalarAttributeType,
+    ) -> types::AttributeDefinition {
+        types::AttributeDefinition::builder()
+            .attribute_name(name)
+            .attribute_type(attribute_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_table_module() {
+        let table = types::TableDescription::builder()
+            .table_name("orders")
+            .attribute_definitions(get_attribute_definition(
+                "userId",
+                types::ScalarAttributeType::S,
+            ))
+            .attribute_definitions(get_attribute_definition(
+                "createdAt",
+                types::ScalarAttributeType::N,
+            ))
+            .attribute_definitions(get_attribute_definition(
+                "status",
+                types::ScalarAttributeType::S,
+            ))
+            .key_schema(get_key_schema_element("userId", types::KeyType::Hash))
+            .key_schema(get_key_schema_element("createdAt", types::KeyType::Range))
+            .global_secondary_indexes(
+                types::GlobalSecondaryIndexDescription::builder()
+                    .index_name("status-index")
+                    .key_schema(get_key_schema_element("status", types::KeyType::Hash))
+                    .build(),
+            )
+            .build();
+        let actual = get_table_module(&table);
+        let expected = r#"// Typed keys, indexes and queries of the `orders` table.
+// Generated by `dynamodb_crud::codegen` from the table description.
+
+use dynamodb_crud::{common, read};
+use serde_json::Value;
+
+/// The table name.
+pub const TABLE_NAME: &str = "orders";
+/// The partition key attribute name.
+pub const PARTITION_KEY_NAME: &str = "userId";
+/// The sort key attribute name.
+pub const SORT_KEY_NAME: &str = "createdAt";
+
+/// Primary key of the `orders` table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Key {
+    /// The `userId` partition key.
+    pub user_id: String,
+    /// The `createdAt` sort key.
+    pub created_at: f64,
+}
+
+impl From<Key> for common::key::Keys<Value> {
+    fn from(key: Key) -> Self {
+        let Key { user_id, created_at } = key;
+        Self {
+            partition_key: common::key::Key {
+                name: "userId".to_string(),
+                value: Value::String(user_id),
+            },
+            sort_key: Some(common::key::Key {
+                name: "createdAt".to_string(),
+                value: Value::from(created_at),
+            }),
+        }
+    }
+}
+
+/// Query the `orders` table by partition key.
+pub fn query(user_id: String) -> read::query::Query<Value> {
+    read::query::Query {
+        multiple_read_args: read::common::MultipleReadArgs {
+            table_name: TABLE_NAME.to_string(),
+            ..Default::default()
+        },
+        partition_key: common::key::Key {
+            name: "userId".to_string(),
+            value: Value::String(user_id),
+        },
+        ..Default::default()
+    }
+}
+
+/// The `status-index` index.
+pub mod status_index {
+    /// The index name.
+    pub const NAME: &str = "status-index";
+    /// The partition key attribute name.
+    pub const PARTITION_KEY_NAME: &str = "status";
+}
+
+/// Query the `status-index` index by partition key.
+pub fn query_status_index(status: String) -> read::query::Query<Value> {
+    read::query::Query {
+        multiple_read_args: read::common::MultipleReadArgs {
+            index_name: Some(status_index::NAME.to_string()),
+            table_name: TABLE_NAME.to_string(),
+            ..Default::default()
+        },
+        partition_key: common::key::Key {
+            name: "status".to_string(),
+            value: Value::String(status),
+        },
+        ..Default::default()
+    }
+}
+"#;
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,59 +1,284 @@
 //! Common utilities for DynamoDB operations.
 //!
 //! This module provides shared types and utilities used across read and write operations,
-//! including key handling, condition expressions, and attribute selection.
+//! including key handling, table-bound item types, condition expressions, attribute selection, retry policies, value
+//! serialization, attribute visibility, and consumed capacity breakdowns.
+
+/// Partial-failure results of bulk operations.
+pub mod bulk;
+
+/// Binary attribute values.
+pub mod bytes;
+
+/// Consumed capacity broken down by table and index.
+pub mod capacity;
 
 /// Condition expression building for filters and conditional writes.
 pub mod condition;
 
+/// Item types bound to a table, with their key attributes.
+pub mod item;
+
 /// Key types for identifying items in DynamoDB tables.
 pub mod key;
 
+/// Interceptor recording the metrics of every request.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// Per-call retry policies overriding the retry configuration of the client.
+pub mod retry;
+
 /// Attribute selection for projection expressions.
 pub mod selection;
 
+/// Pluggable conversion of values into DynamoDB attribute values.
+pub mod serializer;
+
+/// Field-level visibility policies stripping attributes from read items.
+pub mod visibility;
+
 use aws_sdk_dynamodb::types;
-use std::collections;
-
-pub(crate) fn add_placeholder(keys: &[String], identifier: &str) -> (String, Vec<String>) {
-    let placeholder = format!("#{identifier}");
-    let mut new_keys = Vec::with_capacity(keys.len() + 1);
-    new_keys.extend_from_slice(keys);
-    new_keys.push(placeholder.clone());
-    (placeholder, new_keys)
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::{collections, fmt::Write};
+
+/// Push the placeholder of an identifier onto an attribute path.
+///
+/// Returns the placeholder and the previous path length, to truncate the path back once the
+/// nested attributes are written.
+pub(crate) fn push_placeholder(
+    path: &mut String,
+    placeholders: &mut PlaceholderAllocator,
+    identifier: &str,
+) -> (String, usize) {
+    let path_length = path.len();
+    if path_length > 0 {
+        path.push('.');
+    }
+    let placeholder = placeholders.allocate_name(identifier);
+    path.push_str(&placeholder);
+    (placeholder, path_length)
 }
 
-fn get_expression(left: String, operator: &str, right: String) -> String {
-    if left.is_empty() {
-        right
-    } else if right.is_empty() {
-        left
-    } else {
-        format!("{left}{operator}{right}")
+/// Push a list index onto an attribute path.
+///
+/// Returns the previous path length, to truncate the path back once the element attributes are
+/// written.
+pub(crate) fn push_index(path: &mut String, index: usize) -> usize {
+    let path_length = path.len();
+    let _ = write!(path, "[{index}]");
+    path_length
+}
+
+/// Get the partition and sort key attribute names of a key schema.
+pub(crate) fn get_key_schema_names(
+    key_schema: &[types::KeySchemaElement],
+) -> (String, Option<String>) {
+    let get_name = |key_type: types::KeyType| {
+        key_schema
+            .iter()
+            .find(|key| *key.key_type() == key_type)
+            .map(|key| key.attribute_name().to_string())
+    };
+    (
+        get_name(types::KeyType::Hash).unwrap_or_default(),
+        get_name(types::KeyType::Range),
+    )
+}
+
+/// Keep only the characters allowed in placeholders: letters, digits and underscores.
+fn sanitize_placeholder(name: &str) -> String {
+    name.chars()
+        .filter(|character| character.is_ascii_alphanumeric() || *character == '_')
+        .collect()
+}
+
+/// Naming strategy of the placeholders of the expressions of an operation.
+///
+/// ```rust
+/// use dynamodb_crud::{common, write};
+/// use serde_json::Value;
+///
+/// let write_args: write::common::WriteArgs<Value> = write::common::WriteArgs {
+///     placeholder_naming: Some(common::PlaceholderNaming::Compact),
+///     table_name: "users".to_string(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum PlaceholderNaming {
+    /// Placeholders derived from the attribute names and the operators, such as `#status`
+    /// and `:status_eq0`.
+    #[default]
+    Readable,
+    /// Short numbered placeholders, such as `#n0` and `:v0`, keeping large expressions
+    /// within the size limits.
+    Compact,
+    /// Value placeholders made of the given prefix and a counter, such as `:p0`, along with
+    /// readable attribute name placeholders.
+    ///
+    /// Characters other than letters, digits and underscores are stripped from the prefix.
+    Prefix(String),
+}
+
+/// Allocator of the placeholders of an operation.
+///
+/// Shared by every expression merged into the same request, so value placeholders are
+/// numbered once across the condition, key condition and update expressions and never collide,
+/// and every attribute name gets the same placeholder in all of them.
+#[derive(Debug, Default)]
+pub(crate) struct PlaceholderAllocator {
+    index: usize,
+    names: collections::HashMap<String, String>,
+    naming: PlaceholderNaming,
+    used_names: collections::HashSet<String>,
+}
+
+impl PlaceholderAllocator {
+    /// Get an allocator following the naming strategy, readable by default.
+    pub(crate) fn new(naming: Option<PlaceholderNaming>) -> Self {
+        Self {
+            naming: naming.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Allocate a value placeholder made of the given name and the next index.
+    ///
+    /// The name is replaced by the prefix of the naming strategy, if any.
+    pub(crate) fn allocate(&mut self, name: &str) -> String {
+        let prefix = match &self.naming {
+            PlaceholderNaming::Readable => sanitize_placeholder(name),
+            PlaceholderNaming::Prefix(prefix) if !sanitize_placeholder(prefix).is_empty() => {
+                sanitize_placeholder(prefix)
+            }
+            PlaceholderNaming::Compact | PlaceholderNaming::Prefix(_) => "v".to_string(),
+        };
+        let placeholder = format!(":{prefix}{}", self.index);
+        self.index += 1;
+        placeholder
+    }
+
+    /// Allocate the placeholder of an attribute name, reused for every occurrence of the name.
+    ///
+    /// Names made of letters, digits and underscores are kept as is, others are stripped of
+    /// the illegal characters, and a placeholder already taken by another name is suffixed
+    /// with a counter.
+    pub(crate) fn allocate_name(&mut self, name: &str) -> String {
+        if let Some(placeholder) = self.names.get(name) {
+            return placeholder.clone();
+        }
+        if self.naming == PlaceholderNaming::Compact {
+            let placeholder = format!("#n{}", self.names.len());
+            self.names.insert(name.to_string(), placeholder.clone());
+            return placeholder;
+        }
+        let mut base = sanitize_placeholder(name);
+        if base.is_empty() {
+            base.push('n');
+        }
+        let mut placeholder = format!("#{base}");
+        let mut suffix = 0;
+        while self.used_names.contains(&placeholder) {
+            suffix += 1;
+            placeholder = format!("#{base}_{suffix}");
+        }
+        self.used_names.insert(placeholder.clone());
+        self.names.insert(name.to_string(), placeholder.clone());
+        placeholder
     }
 }
 
-/// expression operation
+/// Expression along with the attribute names and values of its placeholders.
+///
+/// Conditions, update expressions and selections convert into their expression, such as to
+/// log them or snapshot-test them. The placeholder maps keep their insertion order, so the
+/// same input always generates the same expression maps.
+///
+/// ```rust
+/// use dynamodb_crud::common;
+///
+/// let condition = common::condition::ConditionMap::equals("status", "active");
+/// let expression = common::ExpressionInput::try_from(&condition).unwrap();
+/// assert_eq!(expression.expression, "#status = :status_eq0");
+/// ```
 #[derive(Clone, Debug, Default, PartialEq)]
-pub(crate) struct ExpressionInput {
-    pub(crate) expression: String,
-    pub(crate) expression_attribute_names: collections::HashMap<String, String>,
-    pub(crate) expression_attribute_values: collections::HashMap<String, types::AttributeValue>,
+pub struct ExpressionInput {
+    /// The expression, referring to attribute names and values through placeholders.
+    pub expression: String,
+    /// The attribute names of the placeholders, by placeholder.
+    pub expression_attribute_names: IndexMap<String, String>,
+    /// The attribute values of the placeholders, by placeholder.
+    pub expression_attribute_values: IndexMap<String, types::AttributeValue>,
 }
 
 impl ExpressionInput {
-    pub(crate) fn merge(operator: &str, items: Vec<Self>) -> Self {
-        let mut operation = Self::default();
-        for item in items {
-            operation
-                .expression_attribute_names
-                .extend(item.expression_attribute_names);
-            operation
-                .expression_attribute_values
-                .extend(item.expression_attribute_values);
-            operation.expression = get_expression(operation.expression, operator, item.expression);
+    /// Append a sub-expression written by `write` to the expression buffer.
+    ///
+    /// Sub-expressions of the group starting at `group_start` are separated by `separator`,
+    /// and empty ones are dropped along with their separator.
+    pub(crate) fn push_expression<R>(
+        &mut self,
+        group_start: usize,
+        separator: &str,
+        write: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let start = self.expression.len();
+        if start > group_start {
+            self.expression.push_str(separator);
         }
-        operation
+        let expression_start = self.expression.len();
+        let output = write(self);
+        if self.expression.len() == expression_start {
+            self.expression.truncate(start);
+        }
+        output
+    }
+
+    /// Append a hand-written expression fragment, renaming its placeholders to placeholders
+    /// allocated for the operation.
+    ///
+    /// Name placeholders get the placeholder of their attribute name, shared with the
+    /// generated sub-expressions, and value placeholders get fresh ones, so the fragment never
+    /// collides with them. Placeholders missing from the maps are kept as is.
+    pub(crate) fn push_raw_expression<T: Serialize>(
+        &mut self,
+        expression: &str,
+        names: &IndexMap<String, String>,
+        values: &IndexMap<String, T>,
+        placeholders: &mut PlaceholderAllocator,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> serde_dynamo::Result<()> {
+        let mut renamed = collections::HashMap::with_capacity(names.len() + values.len());
+        for (placeholder, name) in names {
+            let allocated = placeholders.allocate_name(name);
+            self.expression_attribute_names
+                .insert(allocated.clone(), name.clone());
+            renamed.insert(placeholder.as_str(), allocated);
+        }
+        for (placeholder, value) in values {
+            let value = serializer.to_attribute_value(value)?;
+            let allocated = placeholders.allocate("raw");
+            self.expression_attribute_values
+                .insert(allocated.clone(), value);
+            renamed.insert(placeholder.as_str(), allocated);
+        }
+        let mut rest = expression;
+        while let Some(start) = rest.find(['#', ':']) {
+            self.expression.push_str(&rest[..start]);
+            let end = rest[start + 1..]
+                .find(|character: char| !character.is_ascii_alphanumeric() && character != '_')
+                .map_or(rest.len(), |end| start + 1 + end);
+            let placeholder = &rest[start..end];
+            match renamed.get(placeholder) {
+                Some(allocated) => self.expression.push_str(allocated),
+                None => self.expression.push_str(placeholder),
+            }
+            rest = &rest[end..];
+        }
+        self.expression.push_str(rest);
+        Ok(())
     }
 
     pub(crate) fn merge_into(
@@ -63,12 +288,83 @@ impl ExpressionInput {
     ) -> String {
         match names {
             Some(existing) => existing.extend(self.expression_attribute_names),
-            None => *names = Some(self.expression_attribute_names),
+            None => *names = Some(self.expression_attribute_names.into_iter().collect()),
         }
         match values {
             Some(existing) => existing.extend(self.expression_attribute_values),
-            None => *values = Some(self.expression_attribute_values),
+            None => *values = Some(self.expression_attribute_values.into_iter().collect()),
         }
         self.expression
     }
 }
+
+/// Sleep implementation of the test clients.
+#[cfg(test)]
+#[derive(Debug)]
+struct TokioSleep;
+
+#[cfg(test)]
+impl aws_sdk_dynamodb::config::AsyncSleep for TokioSleep {
+    fn sleep(&self, duration: std::time::Duration) -> aws_sdk_dynamodb::config::Sleep {
+        aws_sdk_dynamodb::config::Sleep::new(tokio::time::sleep(duration))
+    }
+}
+
+/// Build a client for the tests, which must intercept its requests before they are sent.
+#[cfg(test)]
+pub(crate) fn get_test_client(
+    builder: aws_sdk_dynamodb::config::Builder,
+) -> aws_sdk_dynamodb::Client {
+    let config = builder
+        .behavior_version(aws_sdk_dynamodb::config::BehaviorVersion::latest())
+        .sleep_impl(TokioSleep)
+        .build();
+    aws_sdk_dynamodb::Client::from_conf(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::valid(vec!["a", "b_c", "a"], vec!["#a", "#b_c", "#a"])]
+    #[case::illegal(vec!["a-b", "a.b", "ab"], vec!["#ab", "#ab_1", "#ab_2"])]
+    #[case::empty(vec!["-", "é"], vec!["#n", "#n_1"])]
+    fn test_allocate_name(#[case] names: Vec<&str>, #[case] expected: Vec<&str>) {
+        let mut placeholders = PlaceholderAllocator::default();
+        let actual: Vec<_> = names
+            .into_iter()
+            .map(|name| placeholders.allocate_name(name))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_allocate() {
+        let mut placeholders = PlaceholderAllocator::default();
+        assert_eq!(placeholders.allocate("a-b_eq"), ":ab_eq0");
+        assert_eq!(placeholders.allocate("a_eq"), ":a_eq1");
+    }
+
+    #[rstest]
+    #[case::readable(PlaceholderNaming::Readable, [":a_eq0", ":b_gt1"], ["#a", "#b", "#a"])]
+    #[case::compact(PlaceholderNaming::Compact, [":v0", ":v1"], ["#n0", "#n1", "#n0"])]
+    #[case::prefix(
+        PlaceholderNaming::Prefix("p-".to_string()),
+        [":p0", ":p1"],
+        ["#a", "#b", "#a"]
+    )]
+    fn test_placeholder_naming(
+        #[case] naming: PlaceholderNaming,
+        #[case] expected_values: [&str; 2],
+        #[case] expected_names: [&str; 3],
+    ) {
+        let mut placeholders = PlaceholderAllocator::new(Some(naming));
+        let actual_values = [placeholders.allocate("a_eq"), placeholders.allocate("b_gt")];
+        let actual_names = ["a", "b", "a"].map(|name| placeholders.allocate_name(name));
+        assert_eq!(actual_values, expected_values);
+        assert_eq!(actual_names, expected_names);
+    }
+}
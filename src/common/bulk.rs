@@ -0,0 +1,112 @@
+use crate::common::capacity;
+
+/// Result of a bulk operation, reporting the failed inputs instead of failing fast.
+///
+/// Callers can retry or report only the inputs that failed.
+///
+/// ```rust
+/// use dynamodb_crud::common::bulk;
+///
+/// let result: bulk::BulkResult<u32, String> = bulk::BulkResult {
+///     failed: vec![(2, "throttled".to_string())],
+///     succeeded: vec![1, 3],
+/// };
+/// let retry: Vec<u32> = result.failed.into_iter().map(|(input, _)| input).collect();
+/// assert_eq!(retry, vec![2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkResult<I, E> {
+    /// The inputs that failed, along with the error.
+    pub failed: Vec<(I, E)>,
+    /// The inputs that were processed successfully.
+    pub succeeded: Vec<I>,
+}
+
+impl<I, E> Default for BulkResult<I, E> {
+    fn default() -> Self {
+        Self {
+            failed: Vec::new(),
+            succeeded: Vec::new(),
+        }
+    }
+}
+
+impl<I, E> BulkResult<I, E> {
+    /// Convert the inputs, keeping their outcome.
+    pub(crate) fn map_inputs<J>(self, mut map: impl FnMut(I) -> J) -> BulkResult<J, E> {
+        BulkResult {
+            failed: self
+                .failed
+                .into_iter()
+                .map(|(input, error)| (map(input), error))
+                .collect(),
+            succeeded: self.succeeded.into_iter().map(map).collect(),
+        }
+    }
+
+    /// Whether every input was processed successfully.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Estimate of the requests a bulk operation would send and the capacity they would consume,
+/// computed without sending them.
+///
+/// The capacity is an estimate: retries of unprocessed requests, index writes and throttling
+/// are not accounted for.
+///
+/// ```rust
+/// use dynamodb_crud::write;
+/// use indexmap::IndexMap;
+/// use serde_json::json;
+///
+/// let batch_write = write::batch_write_item::BatchWriteItem {
+///     request_items: IndexMap::from([(
+///         "users".to_string(),
+///         (0..30)
+///             .map(|id| {
+///                 write::batch_write_item::BatchWriteItemRequest::PutItem(
+///                     write::batch_write_item::BatchWriteItemRequestPutItem {
+///                         item: json!({"id": id.to_string()}),
+///                     },
+///                 )
+///             })
+///             .collect(),
+///     )]),
+///     ..Default::default()
+/// };
+/// let estimate = batch_write.estimate_cost().unwrap();
+/// assert_eq!(estimate.requests, 30);
+/// assert_eq!(estimate.chunks, 2);
+/// assert_eq!(estimate.estimated_capacity.write_capacity_units, 30.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CostEstimate {
+    /// The number of chunks the requests would be sent in, one call each before any retry.
+    pub chunks: usize,
+    /// The estimated capacity units the requests would consume.
+    pub estimated_capacity: capacity::CapacityUnits,
+    /// The number of requests: the items to write or delete, or the keys to read.
+    pub requests: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_inputs() {
+        let result: BulkResult<u32, &str> = BulkResult {
+            failed: vec![(1, "a")],
+            succeeded: vec![2, 3],
+        };
+        let actual = result.map_inputs(|input| input * 10);
+        let expected = BulkResult {
+            failed: vec![(10, "a")],
+            succeeded: vec![20, 30],
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_success());
+    }
+}
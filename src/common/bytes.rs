@@ -0,0 +1,139 @@
+use aws_sdk_dynamodb::types;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::{fmt, ops};
+
+/// Binary value, stored as a DynamoDB binary (`B`) attribute.
+///
+/// A plain `Vec<u8>` is serialized as a list of numbers, so binary keys, condition values and
+/// set inputs are wrapped in `Bytes` instead.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_crud::common::{bytes::Bytes, key};
+/// use std::collections::HashMap;
+///
+/// let keys = key::Keys {
+///     partition_key: key::Key {
+///         name: "hash".to_string(),
+///         value: Bytes::from(vec![0, 1]),
+///     },
+///     sort_key: None,
+/// };
+/// let keys: HashMap<String, AttributeValue> = keys.try_into().unwrap();
+/// assert_eq!(keys["hash"], AttributeValue::B(vec![0, 1].into()));
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Bytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
+
+impl From<Bytes> for types::AttributeValue {
+    fn from(bytes: Bytes) -> Self {
+        Self::B(bytes.0.into())
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl ops::Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+/// visitor accepting binary values, along with sequences of bytes for formats without them
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = Bytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a binary value")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Ok(Bytes::from(bytes))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Bytes(bytes))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(Bytes(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common::{self, condition};
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let bytes = Bytes::from(vec![0, 1, 2]);
+        let value: types::AttributeValue = serde_dynamo::to_attribute_value(&bytes).unwrap();
+        assert_eq!(value, types::AttributeValue::B(vec![0, 1, 2].into()));
+        let actual: Bytes = serde_dynamo::from_attribute_value(value).unwrap();
+        assert_eq!(actual, bytes);
+    }
+
+    #[test]
+    fn test_bytes_condition() {
+        let condition =
+            condition::ConditionMap::attribute("a", condition::Condition::Equals(Bytes(vec![1])));
+        let actual = condition
+            .get_expression_operation(
+                &mut common::PlaceholderAllocator::default(),
+                &common::serializer::SerdeDynamo,
+            )
+            .unwrap();
+        let expected = types::AttributeValue::B(vec![1].into());
+        assert_eq!(
+            actual
+                .expression_attribute_values
+                .values()
+                .collect::<Vec<_>>(),
+            vec![&expected]
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use aws_sdk_dynamodb::types;
+use indexmap::IndexMap;
+use std::ops;
+
+/// Capacity units consumed by a table, an index or a whole operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CapacityUnits {
+    /// The total capacity units consumed.
+    pub capacity_units: f64,
+    /// The read capacity units consumed, if detailed by DynamoDB.
+    pub read_capacity_units: f64,
+    /// The write capacity units consumed, if detailed by DynamoDB.
+    pub write_capacity_units: f64,
+}
+
+/// Size of the read capacity unit, in bytes.
+const READ_UNIT_SIZE: usize = 4096;
+
+/// Size of the write capacity unit, in bytes.
+const WRITE_UNIT_SIZE: usize = 1024;
+
+impl CapacityUnits {
+    /// Estimate the units consumed by reading an item of the given size, rounded up to 4 KB,
+    /// with eventually consistent reads consuming half of them.
+    pub(crate) fn estimate_read(item_size: usize, consistent_read: bool) -> Self {
+        let units = item_size.div_ceil(READ_UNIT_SIZE).max(1) as f64;
+        let read_capacity_units = if consistent_read { units } else { units / 2.0 };
+        Self {
+            capacity_units: read_capacity_units,
+            read_capacity_units,
+            write_capacity_units: 0.0,
+        }
+    }
+
+    /// Estimate the units consumed by writing an item of the given size, rounded up to 1 KB.
+    pub(crate) fn estimate_write(item_size: usize) -> Self {
+        let write_capacity_units = item_size.div_ceil(WRITE_UNIT_SIZE).max(1) as f64;
+        Self {
+            capacity_units: write_capacity_units,
+            read_capacity_units: 0.0,
+            write_capacity_units,
+        }
+    }
+}
+
+impl ops::AddAssign for CapacityUnits {
+    fn add_assign(&mut self, other: Self) {
+        self.capacity_units += other.capacity_units;
+        self.read_capacity_units += other.read_capacity_units;
+        self.write_capacity_units += other.write_capacity_units;
+    }
+}
+
+impl From<&types::Capacity> for CapacityUnits {
+    fn from(capacity: &types::Capacity) -> Self {
+        Self {
+            capacity_units: capacity.capacity_units.unwrap_or_default(),
+            read_capacity_units: capacity.read_capacity_units.unwrap_or_default(),
+            write_capacity_units: capacity.write_capacity_units.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&types::ConsumedCapacity> for CapacityUnits {
+    fn from(consumed_capacity: &types::ConsumedCapacity) -> Self {
+        Self {
+            capacity_units: consumed_capacity.capacity_units.unwrap_or_default(),
+            read_capacity_units: consumed_capacity.read_capacity_units.unwrap_or_default(),
+            write_capacity_units: consumed_capacity.write_capacity_units.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<CapacityUnits> for types::Capacity {
+    fn from(capacity_units: CapacityUnits) -> Self {
+        Self::builder()
+            .capacity_units(capacity_units.capacity_units)
+            .read_capacity_units(capacity_units.read_capacity_units)
+            .write_capacity_units(capacity_units.write_capacity_units)
+            .build()
+    }
+}
+
+/// Add the capacity of every index to the merged ones.
+fn merge_indexes<'a>(
+    merged_indexes: &mut IndexMap<String, CapacityUnits>,
+    indexes: impl IntoIterator<Item = (&'a String, CapacityUnits)>,
+) {
+    for (index_name, capacity_units) in indexes {
+        *merged_indexes.entry(index_name.clone()).or_default() += capacity_units;
+    }
+}
+
+/// Capacity consumed on a table and on each of its indexes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableCapacity {
+    /// The capacity consumed on every global secondary index, by index name.
+    pub global_secondary_indexes: IndexMap<String, CapacityUnits>,
+    /// The capacity consumed on every local secondary index, by index name.
+    pub local_secondary_indexes: IndexMap<String, CapacityUnits>,
+    /// The capacity consumed on the base table, if detailed by DynamoDB.
+    pub table: CapacityUnits,
+    /// The capacity consumed on the table and its indexes.
+    pub total: CapacityUnits,
+}
+
+impl TableCapacity {
+    /// Add the capacity consumed by an operation on the table.
+    pub fn add(&mut self, consumed_capacity: &types::ConsumedCapacity) {
+        self.total += CapacityUnits::from(consumed_capacity);
+        if let Some(table) = &consumed_capacity.table {
+            self.table += CapacityUnits::from(table);
+        }
+        merge_indexes(
+            &mut self.global_secondary_indexes,
+            consumed_capacity
+                .global_secondary_indexes
+                .iter()
+                .flatten()
+                .map(|(index_name, capacity)| (index_name, CapacityUnits::from(capacity))),
+        );
+        merge_indexes(
+            &mut self.local_secondary_indexes,
+            consumed_capacity
+                .local_secondary_indexes
+                .iter()
+                .flatten()
+                .map(|(index_name, capacity)| (index_name, CapacityUnits::from(capacity))),
+        );
+    }
+
+    /// Merge the capacity consumed by other operations on the table.
+    pub fn merge(&mut self, other: Self) {
+        self.total += other.total;
+        self.table += other.table;
+        merge_indexes(
+            &mut self.global_secondary_indexes,
+            other
+                .global_secondary_indexes
+                .iter()
+                .map(|(index_name, capacity_units)| (index_name, *capacity_units)),
+        );
+        merge_indexes(
+            &mut self.local_secondary_indexes,
+            other
+                .local_secondary_indexes
+                .iter()
+                .map(|(index_name, capacity_units)| (index_name, *capacity_units)),
+        );
+    }
+
+    /// Convert the capacity back into the consumed capacity of the table with the given name.
+    fn to_consumed_capacity(&self, table_name: &str) -> types::ConsumedCapacity {
+        let get_indexes = |indexes: &IndexMap<String, CapacityUnits>| {
+            (!indexes.is_empty()).then(|| {
+                indexes
+                    .iter()
+                    .map(|(index_name, capacity_units)| {
+                        (index_name.clone(), types::Capacity::from(*capacity_units))
+                    })
+                    .collect()
+            })
+        };
+        types::ConsumedCapacity::builder()
+            .table_name(table_name)
+            .capacity_units(self.total.capacity_units)
+            .read_capacity_units(self.total.read_capacity_units)
+            .write_capacity_units(self.total.write_capacity_units)
+            .table(self.table.into())
+            .set_global_secondary_indexes(get_indexes(&self.global_secondary_indexes))
+            .set_local_secondary_indexes(get_indexes(&self.local_secondary_indexes))
+            .build()
+    }
+}
+
+/// Capacity consumed by one or more operations, broken down by table and index.
+///
+/// Unlike summing the consumed capacities into a single one, the breakdown keeps the capacity
+/// of every table and of every index, along with the totals.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types;
+/// use dynamodb_crud::common::capacity::CapacityBreakdown;
+///
+/// let breakdown: CapacityBreakdown = [
+///     types::ConsumedCapacity::builder()
+///         .table_name("users")
+///         .capacity_units(1.0)
+///         .build(),
+///     types::ConsumedCapacity::builder()
+///         .table_name("orders")
+///         .capacity_units(2.5)
+///         .build(),
+/// ]
+/// .into_iter()
+/// .collect();
+/// assert_eq!(breakdown.tables["orders"].total.capacity_units, 2.5);
+/// assert_eq!(breakdown.total.capacity_units, 3.5);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CapacityBreakdown {
+    /// The capacity consumed on every table, by table name, in the order they were returned.
+    pub tables: IndexMap<String, TableCapacity>,
+    /// The capacity consumed on every table.
+    pub total: CapacityUnits,
+}
+
+impl CapacityBreakdown {
+    /// Add the capacity consumed by an operation.
+    pub fn add(&mut self, consumed_capacity: &types::ConsumedCapacity) {
+        self.total += CapacityUnits::from(consumed_capacity);
+        let table_name = consumed_capacity.table_name.clone().unwrap_or_default();
+        self.tables
+            .entry(table_name)
+            .or_default()
+            .add(consumed_capacity);
+    }
+
+    /// Merge the capacity consumed by other operations.
+    pub fn merge(&mut self, other: Self) {
+        self.total += other.total;
+        for (table_name, table_capacity) in other.tables {
+            self.tables
+                .entry(table_name)
+                .or_default()
+                .merge(table_capacity);
+        }
+    }
+
+    /// Whether no capacity was added, such as when it wasn't requested.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Convert the breakdown back into one consumed capacity per table.
+    pub fn to_consumed_capacities(&self) -> Vec<types::ConsumedCapacity> {
+        self.tables
+            .iter()
+            .map(|(table_name, table_capacity)| table_capacity.to_consumed_capacity(table_name))
+            .collect()
+    }
+}
+
+impl Extend<types::ConsumedCapacity> for CapacityBreakdown {
+    fn extend<I: IntoIterator<Item = types::ConsumedCapacity>>(&mut self, capacities: I) {
+        for consumed_capacity in capacities {
+            self.add(&consumed_capacity);
+        }
+    }
+}
+
+impl FromIterator<types::ConsumedCapacity> for CapacityBreakdown {
+    fn from_iter<I: IntoIterator<Item = types::ConsumedCapacity>>(capacities: I) -> Self {
+        let mut breakdown = Self::default();
+        breakdown.extend(capacities);
+        breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use std::collections;
+
+    fn get_consumed_capacity(
+        table_name: &str,
+        capacity_units: f64,
+        index_name: &str,
+    ) -> types::ConsumedCapacity {
+        types::ConsumedCapacity::builder()
+            .table_name(table_name)
+            .capacity_units(capacity_units)
+            .table(types::Capacity::builder().capacity_units(1.0).build())
+            .global_secondary_indexes(
+                index_name,
+                types::Capacity::builder()
+                    .capacity_units(capacity_units - 1.0)
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_capacity_breakdown() {
+        let breakdown: CapacityBreakdown = [
+            get_consumed_capacity("a", 2.0, "b"),
+            get_consumed_capacity("c", 3.0, "d"),
+            get_consumed_capacity("a", 4.0, "e"),
+        ]
+        .into_iter()
+        .collect();
+        let expected = CapacityBreakdown {
+            tables: IndexMap::from([
+                (
+                    "a".to_string(),
+                    TableCapacity {
+                        global_secondary_indexes: IndexMap::from([
+                            (
+                                "b".to_string(),
+                                CapacityUnits {
+                                    capacity_units: 1.0,
+                                    ..Default::default()
+                                },
+                            ),
+                            (
+                                "e".to_string(),
+                                CapacityUnits {
+                                    capacity_units: 3.0,
+                                    ..Default::default()
+                                },
+                            ),
+                        ]),
+                        local_secondary_indexes: IndexMap::new(),
+                        table: CapacityUnits {
+                            capacity_units: 2.0,
+                            ..Default::default()
+                        },
+                        total: CapacityUnits {
+                            capacity_units: 6.0,
+                            ..Default::default()
+                        },
+                    },
+                ),
+                (
+                    "c".to_string(),
+                    TableCapacity {
+                        global_secondary_indexes: IndexMap::from([(
+                            "d".to_string(),
+                            CapacityUnits {
+                                capacity_units: 2.0,
+                                ..Default::default()
+                            },
+                        )]),
+                        local_secondary_indexes: IndexMap::new(),
+                        table: CapacityUnits {
+                            capacity_units: 1.0,
+                            ..Default::default()
+                        },
+                        total: CapacityUnits {
+                            capacity_units: 3.0,
+                            ..Default::default()
+                        },
+                    },
+                ),
+            ]),
+            total: CapacityUnits {
+                capacity_units: 9.0,
+                ..Default::default()
+            },
+        };
+        assert_eq!(breakdown, expected);
+    }
+
+    #[test]
+    fn test_to_consumed_capacities() {
+        let breakdown: CapacityBreakdown = [
+            get_consumed_capacity("a", 2.0, "b"),
+            get_consumed_capacity("a", 3.0, "b"),
+        ]
+        .into_iter()
+        .collect();
+        let consumed_capacities = breakdown.to_consumed_capacities();
+        assert_eq!(consumed_capacities.len(), 1);
+        assert_eq!(consumed_capacities[0].capacity_units, Some(5.0));
+        assert_eq!(
+            consumed_capacities[0].global_secondary_indexes,
+            Some(collections::HashMap::from([(
+                "b".to_string(),
+                types::Capacity::from(CapacityUnits {
+                    capacity_units: 3.0,
+                    ..Default::default()
+                })
+            )]))
+        );
+    }
+
+    #[rstest]
+    #[case::empty(0, true, 1.0)]
+    #[case::consistent(4097, true, 2.0)]
+    #[case::eventually_consistent(4097, false, 1.0)]
+    fn test_estimate_read(
+        #[case] item_size: usize,
+        #[case] consistent_read: bool,
+        #[case] expected: f64,
+    ) {
+        let actual = CapacityUnits::estimate_read(item_size, consistent_read);
+        assert_eq!(actual.read_capacity_units, expected);
+        assert_eq!(actual.capacity_units, expected);
+    }
+
+    #[rstest]
+    #[case::empty(0, 1.0)]
+    #[case::one_unit(1024, 1.0)]
+    #[case::rounded_up(1025, 2.0)]
+    fn test_estimate_write(#[case] item_size: usize, #[case] expected: f64) {
+        let actual = CapacityUnits::estimate_write(item_size);
+        assert_eq!(actual.write_capacity_units, expected);
+        assert_eq!(actual.capacity_units, expected);
+    }
+}
@@ -1,10 +1,10 @@
-use crate::common;
+use crate::common::{self, serializer};
 
 use aws_sdk_dynamodb::types;
 use indexmap::IndexMap;
 use serde::Serialize;
-use serde_dynamo::{Error, Result, to_attribute_value};
-use std::{collections, ops};
+use serde_dynamo::{Error, Result};
+use std::{fmt::Write, ops};
 
 /// Logical operator for combining conditions.
 #[derive(Clone, Debug, PartialEq)]
@@ -26,6 +26,82 @@ impl ops::Deref for LogicalOperator {
     }
 }
 
+/// Comparison operator between an attribute size and a value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Comparator {
+    /// Equal to the value.
+    Equals,
+    /// Greater than the value.
+    GreaterThan,
+    /// Greater than or equal to the value.
+    GreaterThanOrEqual,
+    /// Less than the value.
+    LessThan,
+    /// Less than or equal to the value.
+    LessThanOrEqual,
+    /// Not equal to the value.
+    NotEqual,
+}
+
+impl ops::Deref for Comparator {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Equals => "=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanOrEqual => ">=",
+            Self::LessThan => "<",
+            Self::LessThanOrEqual => "<=",
+            Self::NotEqual => "<>",
+        }
+    }
+}
+
+/// DynamoDB data type of an attribute.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AttributeType {
+    /// Binary.
+    Binary,
+    /// Binary set.
+    BinarySet,
+    /// Boolean.
+    Boolean,
+    /// List.
+    List,
+    /// Map.
+    Map,
+    /// Null.
+    Null,
+    /// Number.
+    Number,
+    /// Number set.
+    NumberSet,
+    /// String.
+    String,
+    /// String set.
+    StringSet,
+}
+
+impl ops::Deref for AttributeType {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Binary => "B",
+            Self::BinarySet => "BS",
+            Self::Boolean => "BOOL",
+            Self::List => "L",
+            Self::Map => "M",
+            Self::Null => "NULL",
+            Self::Number => "N",
+            Self::NumberSet => "NS",
+            Self::String => "S",
+            Self::StringSet => "SS",
+        }
+    }
+}
+
 /// Condition types for DynamoDB expressions.
 ///
 /// ```rust
@@ -34,9 +110,14 @@ impl ops::Deref for LogicalOperator {
 /// let eq = condition::Condition::Equals("value".to_string());
 /// let gt = condition::Condition::GreaterThan(100);
 /// let null: condition::Condition<String> = condition::Condition::Null;
+/// let short = condition::Condition::SizeCompare(condition::Comparator::LessThan, 10);
+/// let list: condition::Condition<String> =
+///     condition::Condition::AttributeType(condition::AttributeType::List);
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum Condition<T> {
+    /// Checks if an attribute is of a specified data type.
+    AttributeType(AttributeType),
     /// Checks if an attribute begins with a specified prefix (string types only).
     BeginsWith(String),
     /// Checks if an attribute value is between two values (inclusive).
@@ -63,123 +144,141 @@ pub enum Condition<T> {
     NotNull,
     /// Checks if an attribute does not exist (is null).
     Null,
+    /// Compares the size of an attribute with a specified value: the length of a string or
+    /// binary, or the number of elements of a set, list or map.
+    SizeCompare(Comparator, T),
 }
 
 impl<T: Serialize> Condition<T> {
-    fn get_expression(
-        self,
+    fn write_expression(
+        &self,
         key: &str,
         key_placeholder: &str,
-        index: &mut usize,
-    ) -> Result<(String, collections::HashMap<String, types::AttributeValue>)> {
-        let mut expression_attribute_values = collections::HashMap::new();
-        let expression = match self {
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> Result<()> {
+        let expression = &mut operation.expression;
+        let expression_attribute_values = &mut operation.expression_attribute_values;
+        match self {
+            Self::AttributeType(attribute_type) => {
+                let value_placeholder = placeholders.allocate(&format!("{key}_type"));
+                let _ = write!(
+                    expression,
+                    "attribute_type({key_placeholder}, {value_placeholder})"
+                );
+                expression_attribute_values.insert(
+                    value_placeholder,
+                    types::AttributeValue::S(attribute_type.to_string()),
+                );
+            }
             Self::BeginsWith(prefix) => {
-                let value_placeholder = format!(":{key}_begins_with{index}");
-                *index += 1;
-                let expression = format!("begins_with({key_placeholder}, {value_placeholder})");
+                let value_placeholder = placeholders.allocate(&format!("{key}_begins_with"));
+                let _ = write!(
+                    expression,
+                    "begins_with({key_placeholder}, {value_placeholder})"
+                );
                 expression_attribute_values
-                    .insert(value_placeholder, types::AttributeValue::S(prefix));
-                expression
+                    .insert(value_placeholder, types::AttributeValue::S(prefix.clone()));
             }
             Self::Between(value1, value2) => {
-                let value1 = to_attribute_value(value1)?;
-                let value2 = to_attribute_value(value2)?;
-                let value_placeholder_1 = format!(":{key}_between{index}");
-                *index += 1;
-                let value_placeholder_2 = format!(":{key}_between{index}");
-                *index += 1;
-                let expression = format!(
+                let value1 = serializer.to_attribute_value(value1)?;
+                let value2 = serializer.to_attribute_value(value2)?;
+                let value_placeholder_1 = placeholders.allocate(&format!("{key}_between"));
+                let value_placeholder_2 = placeholders.allocate(&format!("{key}_between"));
+                let _ = write!(
+                    expression,
                     "{key_placeholder} BETWEEN {value_placeholder_1} AND {value_placeholder_2}"
                 );
                 expression_attribute_values.insert(value_placeholder_1, value1);
                 expression_attribute_values.insert(value_placeholder_2, value2);
-                expression
             }
             Self::Contains(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_contains{index}");
-                *index += 1;
-                let expression = format!("contains({key_placeholder}, {value_placeholder})");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_contains"));
+                let _ = write!(
+                    expression,
+                    "contains({key_placeholder}, {value_placeholder})"
+                );
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::Equals(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_eq{index}");
-                *index += 1;
-                let expression = format!("{key_placeholder} = {value_placeholder}");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_eq"));
+                let _ = write!(expression, "{key_placeholder} = {value_placeholder}");
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::GreaterThan(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_gt{index}");
-                *index += 1;
-                let expression = format!("{key_placeholder} > {value_placeholder}");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_gt"));
+                let _ = write!(expression, "{key_placeholder} > {value_placeholder}");
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::GreaterThanOrEqual(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_gte{index}");
-                *index += 1;
-                let expression = format!("{key_placeholder} >= {value_placeholder}");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_gte"));
+                let _ = write!(expression, "{key_placeholder} >= {value_placeholder}");
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::In(values) => {
-                let mut placeholders = Vec::with_capacity(values.len());
-                for (in_index, value) in values.into_iter().enumerate() {
-                    let value = to_attribute_value(value)?;
-                    let placeholder = format!(":{key}_in{index}_{in_index}");
-                    *index += 1;
-                    expression_attribute_values.insert(placeholder.clone(), value);
-                    placeholders.push(placeholder);
+                expression_attribute_values.reserve(values.len());
+                let _ = write!(expression, "{key_placeholder} IN (");
+                for (in_index, value) in values.iter().enumerate() {
+                    let value = serializer.to_attribute_value(value)?;
+                    let placeholder = placeholders.allocate(&format!("{key}_in"));
+                    if in_index > 0 {
+                        expression.push_str(", ");
+                    }
+                    expression.push_str(&placeholder);
+                    expression_attribute_values.insert(placeholder, value);
                 }
-                let placeholders = placeholders.join(", ");
-                format!("{key_placeholder} IN ({placeholders})")
+                expression.push(')');
             }
             Self::LessThan(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_lt{index}");
-                *index += 1;
-                let expression = format!("{key_placeholder} < {value_placeholder}");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_lt"));
+                let _ = write!(expression, "{key_placeholder} < {value_placeholder}");
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::LessThanOrEqual(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_lte{index}");
-                *index += 1;
-                let expression = format!("{key_placeholder} <= {value_placeholder}");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_lte"));
+                let _ = write!(expression, "{key_placeholder} <= {value_placeholder}");
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::NotContains(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_not_contains{index}");
-                *index += 1;
-                let expression = format!("NOT contains({key_placeholder}, {value_placeholder})");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_not_contains"));
+                let _ = write!(
+                    expression,
+                    "NOT contains({key_placeholder}, {value_placeholder})"
+                );
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::NotEqual(value) => {
-                let value = to_attribute_value(value)?;
-                let value_placeholder = format!(":{key}_ne{index}");
-                *index += 1;
-                let expression = format!("{key_placeholder} <> {value_placeholder}");
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_ne"));
+                let _ = write!(expression, "{key_placeholder} <> {value_placeholder}");
                 expression_attribute_values.insert(value_placeholder, value);
-                expression
             }
             Self::NotNull => {
-                format!("attribute_exists({key_placeholder})")
+                let _ = write!(expression, "attribute_exists({key_placeholder})");
             }
             Self::Null => {
-                format!("attribute_not_exists({key_placeholder})")
+                let _ = write!(expression, "attribute_not_exists({key_placeholder})");
             }
-        };
-        Ok((expression, expression_attribute_values))
+            Self::SizeCompare(comparator, value) => {
+                let value = serializer.to_attribute_value(value)?;
+                let value_placeholder = placeholders.allocate(&format!("{key}_size"));
+                let comparator: &str = comparator;
+                let _ = write!(
+                    expression,
+                    "size({key_placeholder}) {comparator} {value_placeholder}"
+                );
+                expression_attribute_values.insert(value_placeholder, value);
+            }
+        }
+        Ok(())
     }
 }
 
@@ -193,30 +292,96 @@ pub struct KeyCondition<T> {
 }
 
 impl<T: Serialize> KeyCondition<T> {
-    pub(crate) fn get_expression_operation(keys: Vec<Self>) -> Result<common::ExpressionInput> {
-        let mut expressions = Vec::with_capacity(keys.len());
-        let mut expression_attribute_names = collections::HashMap::with_capacity(keys.len());
-        let mut expression_attribute_values = collections::HashMap::new();
-        let mut index = 0;
+    pub(crate) fn get_expression_operation(
+        keys: Vec<Self>,
+        placeholders: &mut common::PlaceholderAllocator,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> Result<common::ExpressionInput> {
+        let mut operation = common::ExpressionInput {
+            expression_attribute_names: IndexMap::with_capacity(keys.len()),
+            expression_attribute_values: IndexMap::with_capacity(keys.len()),
+            ..Default::default()
+        };
         for key in keys {
-            let placeholder = format!("#{}", key.name);
-            let (expression, condition_expression_attribute_values) = key
-                .condition
-                .get_expression(&key.name, &placeholder, &mut index)?;
-            expressions.push(expression);
-            expression_attribute_names.insert(placeholder, key.name);
-            expression_attribute_values.extend(condition_expression_attribute_values);
+            let placeholder = placeholders.allocate_name(&key.name);
+            operation.push_expression(0, &LogicalOperator::And, |operation| {
+                key.condition.write_expression(
+                    &key.name,
+                    &placeholder,
+                    placeholders,
+                    operation,
+                    serializer,
+                )
+            })?;
+            operation
+                .expression_attribute_names
+                .insert(placeholder, key.name);
         }
-        let expression = expressions.join(LogicalOperator::And.as_ref());
-        let operation = common::ExpressionInput {
-            expression,
-            expression_attribute_names,
-            expression_attribute_values,
-        };
         Ok(operation)
     }
 }
 
+/// Condition applied to a sort key, limited to the comparisons supported by key conditions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SortKeyCondition<T> {
+    /// Checks if the sort key begins with a specified prefix (string types only).
+    BeginsWith(String),
+    /// Checks if the sort key is between two values (inclusive).
+    Between(T, T),
+    /// Checks if the sort key equals a specified value.
+    Equals(T),
+    /// Checks if the sort key is greater than a specified value.
+    GreaterThan(T),
+    /// Checks if the sort key is greater than or equal to a specified value.
+    GreaterThanOrEqual(T),
+    /// Checks if the sort key is less than a specified value.
+    LessThan(T),
+    /// Checks if the sort key is less than or equal to a specified value.
+    LessThanOrEqual(T),
+}
+
+impl<T> From<SortKeyCondition<T>> for Condition<T> {
+    fn from(condition: SortKeyCondition<T>) -> Self {
+        match condition {
+            SortKeyCondition::BeginsWith(prefix) => Self::BeginsWith(prefix),
+            SortKeyCondition::Between(low, high) => Self::Between(low, high),
+            SortKeyCondition::Equals(value) => Self::Equals(value),
+            SortKeyCondition::GreaterThan(value) => Self::GreaterThan(value),
+            SortKeyCondition::GreaterThanOrEqual(value) => Self::GreaterThanOrEqual(value),
+            SortKeyCondition::LessThan(value) => Self::LessThan(value),
+            SortKeyCondition::LessThanOrEqual(value) => Self::LessThanOrEqual(value),
+        }
+    }
+}
+
+/// Condition applied to the sort key of a query.
+///
+/// ```rust
+/// use dynamodb_crud::common::condition;
+///
+/// let sort_key = condition::SortKey {
+///     condition: condition::SortKeyCondition::BeginsWith("2024-".to_string()),
+///     name: "createdAt".to_string(),
+/// };
+/// # let _: condition::SortKey<String> = sort_key;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortKey<T> {
+    /// The condition to apply to the sort key.
+    pub condition: SortKeyCondition<T>,
+    /// The name of the sort key.
+    pub name: String,
+}
+
+impl<T> From<SortKey<T>> for KeyCondition<T> {
+    fn from(sort_key: SortKey<T>) -> Self {
+        Self {
+            condition: sort_key.condition.into(),
+            name: sort_key.name,
+        }
+    }
+}
+
 /// Map of conditions with logical operators.
 ///
 /// ```rust
@@ -231,99 +396,461 @@ impl<T: Serialize> KeyCondition<T> {
 ///         },
 ///     ],
 /// );
+/// let negated = !map;
+/// ```
+///
+/// Conditions can also be combined fluently, with `.not()` coming from [`std::ops::Not`]:
+///
+/// ```rust
+/// use dynamodb_crud::common::condition::ConditionMap;
+/// use std::ops::Not;
+///
+/// // (#status = :status_eq0 OR #status = :status_eq1) AND NOT (attribute_exists(#deletedAt))
+/// let map = ConditionMap::equals("status", "active")
+///     .or(ConditionMap::equals("status", "pending"))
+///     .and(ConditionMap::attribute_exists("deletedAt").not());
+/// ```
+///
+/// Fragments the other variants cannot express are written by hand, and their placeholders
+/// merged with the generated ones:
+///
+/// ```rust
+/// use dynamodb_crud::common::{self, condition::ConditionMap};
+/// use indexmap::IndexMap;
+///
+/// let map = ConditionMap::equals("status", 1).and(ConditionMap::Raw {
+///     expression: "size(#tags) BETWEEN :low AND :high".to_string(),
+///     names: IndexMap::from([("#tags".to_string(), "tags".to_string())]),
+///     values: IndexMap::from([(":low".to_string(), 1), (":high".to_string(), 5)]),
+/// });
+/// let expression = common::ExpressionInput::try_from(&map).unwrap();
+/// assert_eq!(
+///     expression.expression,
+///     "#status = :status_eq0 AND (size(#tags) BETWEEN :raw1 AND :raw2)"
+/// );
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum ConditionMap<T> {
+    /// Group conditions - sub-conditions on the same path combined with the logical operator,
+    /// each parenthesized when needed.
+    Group(LogicalOperator, Vec<ConditionMap<T>>),
+    /// Index leaf conditions - flat list of conditions on list elements, by position, combined
+    /// with the logical operator.
+    IndexLeaves(LogicalOperator, Vec<(usize, Condition<T>)>),
+    /// Index node conditions - nested conditions within list elements, by position.
+    IndexNode(LogicalOperator, IndexMap<usize, ConditionMap<T>>),
     /// Leaf conditions - flat list of conditions combined with the logical operator.
     Leaves(LogicalOperator, Vec<KeyCondition<T>>),
     /// Node conditions - nested conditions for hierarchical attribute paths.
     Node(LogicalOperator, IndexMap<String, ConditionMap<T>>),
+    /// Negated conditions - the whole sub-expression is negated, within parentheses.
+    Not(Box<ConditionMap<T>>),
+    /// Raw condition - a hand-written expression fragment, for conditions the other variants
+    /// cannot express.
+    ///
+    /// Its placeholders are renamed to placeholders allocated for the operation, so they are
+    /// merged with the generated ones without colliding. Placeholders missing from the maps
+    /// are kept as is.
+    Raw {
+        /// The expression fragment, referring to attribute names and values through
+        /// placeholders.
+        expression: String,
+        /// The attribute names of the name placeholders, such as `#tags`, by placeholder.
+        names: IndexMap<String, String>,
+        /// The values of the value placeholders, such as `:min`, by placeholder.
+        values: IndexMap<String, T>,
+    },
+}
+
+impl<T> ops::Not for ConditionMap<T> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self::Not(Box::new(self))
+    }
 }
 
-impl<T: Serialize> TryFrom<ConditionMap<T>> for common::ExpressionInput {
+impl<T> ConditionMap<T> {
+    /// Condition on a single attribute.
+    ///
+    /// ```rust
+    /// use dynamodb_crud::common::condition;
+    ///
+    /// let map = condition::ConditionMap::attribute(
+    ///     "status",
+    ///     condition::Condition::Equals("active".to_string()),
+    /// );
+    /// ```
+    pub fn attribute(name: impl Into<String>, condition: Condition<T>) -> Self {
+        Self::Leaves(
+            LogicalOperator::And,
+            vec![KeyCondition {
+                condition,
+                name: name.into(),
+            }],
+        )
+    }
+
+    /// Condition matching the items where the attribute exists.
+    pub fn attribute_exists(name: impl Into<String>) -> Self {
+        Self::attribute(name, Condition::NotNull)
+    }
+
+    /// Condition matching the items where the attribute is missing.
+    pub fn attribute_missing(name: impl Into<String>) -> Self {
+        Self::attribute(name, Condition::Null)
+    }
+
+    /// Condition matching the items where the attribute begins with the prefix.
+    pub fn begins_with(name: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self::attribute(name, Condition::BeginsWith(prefix.into()))
+    }
+
+    /// Condition matching the items where the attribute is between two values, inclusive.
+    pub fn between(name: impl Into<String>, low: T, high: T) -> Self {
+        Self::attribute(name, Condition::Between(low, high))
+    }
+
+    /// Condition matching the items where the attribute equals the value.
+    pub fn equals(name: impl Into<String>, value: T) -> Self {
+        Self::attribute(name, Condition::Equals(value))
+    }
+
+    /// Condition matching the items where the attribute is one of the values.
+    pub fn one_of(name: impl Into<String>, values: impl IntoIterator<Item = T>) -> Self {
+        Self::attribute(name, Condition::In(values.into_iter().collect()))
+    }
+
+    /// Condition matching the items matching both conditions.
+    pub fn and(self, other: Self) -> Self {
+        self.combine(LogicalOperator::And, other)
+    }
+
+    /// Condition matching the items matching either condition.
+    pub fn or(self, other: Self) -> Self {
+        self.combine(LogicalOperator::Or, other)
+    }
+
+    /// Combine with another condition, extending the group if it has the same operator.
+    fn combine(self, operator: LogicalOperator, other: Self) -> Self {
+        match self {
+            Self::Group(group_operator, mut conditions) if group_operator == operator => {
+                conditions.push(other);
+                Self::Group(operator, conditions)
+            }
+            condition => Self::Group(operator, vec![condition, other]),
+        }
+    }
+
+    /// Whether the condition applies to the given top-level attribute.
+    pub(crate) fn has_attribute(&self, name: &str) -> bool {
+        match self {
+            Self::Group(_, conditions) => conditions
+                .iter()
+                .any(|condition| condition.has_attribute(name)),
+            Self::IndexLeaves(..) | Self::IndexNode(..) => false,
+            Self::Leaves(_, leaves) => leaves.iter().any(|leaf| leaf.name == name),
+            Self::Node(_, map) => map.contains_key(name),
+            Self::Not(condition) => condition.has_attribute(name),
+            Self::Raw { names, .. } => names.values().any(|raw_name| raw_name == name),
+        }
+    }
+
+    /// Whether the condition is written as a single term, never needing parentheses.
+    fn is_single_term(&self) -> bool {
+        match self {
+            Self::Group(_, conditions) => match conditions.as_slice() {
+                [condition] => condition.is_single_term(),
+                _ => conditions.is_empty(),
+            },
+            Self::IndexLeaves(_, leaves) => leaves.len() <= 1,
+            Self::IndexNode(_, map) => match map.values().next() {
+                Some(value) if map.len() == 1 => value.is_single_term(),
+                _ => map.is_empty(),
+            },
+            Self::Leaves(_, leaves) => leaves.len() <= 1,
+            Self::Node(_, map) => match map.values().next() {
+                Some(value) if map.len() == 1 => value.is_single_term(),
+                _ => map.is_empty(),
+            },
+            Self::Not(_) => true,
+            Self::Raw { .. } => false,
+        }
+    }
+}
+
+impl<T: Serialize> TryFrom<&ConditionMap<T>> for common::ExpressionInput {
     type Error = Error;
 
-    fn try_from(condition_map: ConditionMap<T>) -> Result<Self> {
-        condition_map.get_expression_operation_recursive(&[], &mut 0, false)
+    fn try_from(condition_map: &ConditionMap<T>) -> Result<Self> {
+        condition_map.get_expression_operation(
+            &mut common::PlaceholderAllocator::default(),
+            &serializer::SerdeDynamo,
+        )
     }
 }
 
 impl<T: Serialize> ConditionMap<T> {
+    /// Build the condition expression, converting the values with the given serializer.
+    pub(crate) fn get_expression_operation(
+        &self,
+        placeholders: &mut common::PlaceholderAllocator,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> Result<common::ExpressionInput> {
+        let mut operation = common::ExpressionInput::default();
+        self.write_expression_recursive(
+            &mut String::new(),
+            placeholders,
+            false,
+            &mut operation,
+            serializer,
+        )?;
+        Ok(operation)
+    }
+
     fn is_composite(&self, is_nested: bool) -> bool {
         match self {
+            Self::Group(..) => is_nested && !self.is_single_term(),
+            Self::IndexLeaves(_, leaves) => is_nested && leaves.len() > 1,
+            Self::IndexNode(_, map) => Self::is_node_composite(map.values(), is_nested),
             Self::Leaves(_, leaves) => is_nested && leaves.len() > 1,
-            Self::Node(_, map) => {
-                let has_multiple_keys = map.len() > 1;
-                let child_is_nested = is_nested || has_multiple_keys;
-                for value in map.values() {
-                    if value.is_composite(child_is_nested) {
-                        // if any child is composite, we don't need to wrap this node:
-                        // the child will be wrapped individually
-                        return false;
-                    }
-                }
-                if is_nested {
-                    // nested level: composite only if has multiple keys
-                    has_multiple_keys
-                } else {
-                    // root level: never composite
-                    false
-                }
+            Self::Node(_, map) => Self::is_node_composite(map.values(), is_nested),
+            // the negation wraps its own sub-expression
+            Self::Not(_) => false,
+            Self::Raw { .. } => is_nested,
+        }
+    }
+
+    fn is_node_composite<'a>(
+        values: impl ExactSizeIterator<Item = &'a Self>,
+        is_nested: bool,
+    ) -> bool
+    where
+        T: 'a,
+    {
+        let has_multiple_keys = values.len() > 1;
+        let child_is_nested = is_nested || has_multiple_keys;
+        for value in values {
+            if value.is_composite(child_is_nested) {
+                // if any child is composite, we don't need to wrap this node:
+                // the child will be wrapped individually
+                return false;
             }
         }
+        if is_nested {
+            // nested level: composite only if has multiple keys
+            has_multiple_keys
+        } else {
+            // root level: never composite
+            false
+        }
     }
 
-    fn get_expression_operation_recursive(
-        self,
-        keys: &[String],
-        index: &mut usize,
+    fn write_expression_recursive(
+        &self,
+        path: &mut String,
+        placeholders: &mut common::PlaceholderAllocator,
         mut is_nested: bool,
-    ) -> Result<common::ExpressionInput> {
-        let mut operations = Vec::new();
+        operation: &mut common::ExpressionInput,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> Result<()> {
         let is_composite = self.is_composite(is_nested);
-        let operator = match self {
+        if is_composite {
+            operation.expression.push('(');
+        }
+        let group_start = operation.expression.len();
+        match self {
+            Self::Group(operator, conditions) => {
+                let is_grouped = conditions.len() > 1;
+                for condition in conditions {
+                    let is_wrapped = is_grouped && !condition.is_single_term();
+                    operation.push_expression(group_start, operator, |operation| {
+                        let start = operation.expression.len();
+                        if is_wrapped {
+                            operation.expression.push('(');
+                        }
+                        let condition_start = operation.expression.len();
+                        condition.write_expression_recursive(
+                            path,
+                            placeholders,
+                            false,
+                            operation,
+                            serializer,
+                        )?;
+                        if operation.expression.len() == condition_start {
+                            operation.expression.truncate(start);
+                        } else if is_wrapped {
+                            operation.expression.push(')');
+                        }
+                        Ok(())
+                    })?;
+                }
+            }
+            Self::IndexLeaves(operator, conditions) => {
+                for (index, condition) in conditions {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, operator, |operation| {
+                        condition.write_expression(
+                            &format!("index{index}"),
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
+                }
+            }
+            Self::IndexNode(operator, map) => {
+                is_nested = is_nested || map.len() > 1;
+                for (index, value) in map {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, operator, |operation| {
+                        value.write_expression_recursive(
+                            path,
+                            placeholders,
+                            is_nested,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
+                }
+            }
             Self::Leaves(operator, key_conditions) => {
                 for key_condition in key_conditions {
-                    let (placeholder, new_keys) =
-                        common::add_placeholder(keys, &key_condition.name);
-                    let key_placeholder = new_keys.join(".");
-                    let (expression, expression_attribute_values) = key_condition
-                        .condition
-                        .get_expression(&key_condition.name, &key_placeholder, index)?;
-                    let expression_attribute_names =
-                        collections::HashMap::from([(placeholder, key_condition.name)]);
-                    let operation = common::ExpressionInput {
-                        expression,
-                        expression_attribute_names,
-                        expression_attribute_values,
-                    };
-                    operations.push(operation);
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, &key_condition.name);
+                    operation.push_expression(group_start, operator, |operation| {
+                        key_condition.condition.write_expression(
+                            &key_condition.name,
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    operation
+                        .expression_attribute_names
+                        .insert(placeholder, key_condition.name.clone());
+                    path.truncate(path_length);
                 }
-                operator
             }
             Self::Node(operator, map) => {
-                operations.reserve(map.len());
                 is_nested = is_nested || map.len() > 1;
                 for (key, value) in map {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &key);
-                    let mut condition_operation =
-                        value.get_expression_operation_recursive(&new_keys, index, is_nested)?;
-                    condition_operation
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
+                    operation
                         .expression_attribute_names
-                        .insert(placeholder, key);
-                    operations.push(condition_operation);
+                        .insert(placeholder, key.clone());
+                    operation.push_expression(group_start, operator, |operation| {
+                        value.write_expression_recursive(
+                            path,
+                            placeholders,
+                            is_nested,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
                 }
-                operator
             }
-        };
-        let mut operation = common::ExpressionInput::merge(&operator, operations);
+            Self::Not(condition_map) => {
+                operation.expression.push_str("NOT (");
+                let negated_start = operation.expression.len();
+                condition_map.write_expression_recursive(
+                    path,
+                    placeholders,
+                    false,
+                    operation,
+                    serializer,
+                )?;
+                if operation.expression.len() == negated_start {
+                    operation.expression.truncate(group_start);
+                } else {
+                    operation.expression.push(')');
+                }
+            }
+            Self::Raw {
+                expression,
+                names,
+                values,
+            } => {
+                operation.push_raw_expression(
+                    expression,
+                    names,
+                    values,
+                    placeholders,
+                    serializer,
+                )?;
+            }
+        }
         if is_composite {
-            operation.expression = format!("({})", operation.expression);
+            operation.expression.push(')');
         }
-        Ok(operation)
+        Ok(())
     }
 }
 
+/// Struct of optional filters, such as the query parameters of an HTTP endpoint, converted into
+/// the filter of a query or scan.
+///
+/// Usually derived with the `derive` feature, see [`DynamoFilter`](derive@DynamoFilter).
+pub trait DynamoFilter<T> {
+    /// Convert the filters that are set into a condition matching all of them, or `None` if no
+    /// filter is set.
+    fn into_condition(self) -> Option<ConditionMap<T>>;
+}
+
+/// Derive [`DynamoFilter`](trait@DynamoFilter) for a struct of `Option` fields.
+///
+/// ```rust
+/// use dynamodb_crud::common::condition::{self, DynamoFilter};
+/// use serde_json::Value;
+///
+/// #[derive(DynamoFilter)]
+/// struct UserFilter {
+///     status: Option<String>,
+///     #[dynamo_filter(name = "age", operator = "greater_than_or_equal")]
+///     min_age: Option<u32>,
+///     #[dynamo_filter(name = "deletedAt", operator = "exists")]
+///     deleted: Option<bool>,
+///     #[dynamo_filter(name = "role", operator = "in")]
+///     roles: Option<Vec<String>>,
+/// }
+///
+/// let filter = UserFilter {
+///     status: Some("active".to_string()),
+///     min_age: Some(18),
+///     deleted: None,
+///     roles: Some(vec!["admin".to_string()]),
+/// };
+/// let condition: Option<condition::ConditionMap<Value>> = filter.into_condition();
+/// assert_eq!(
+///     condition,
+///     Some(condition::ConditionMap::Leaves(
+///         condition::LogicalOperator::And,
+///         vec![
+///             condition::KeyCondition {
+///                 condition: condition::Condition::Equals(Value::from("active")),
+///                 name: "status".to_string(),
+///             },
+///             condition::KeyCondition {
+///                 condition: condition::Condition::GreaterThanOrEqual(Value::from(18)),
+///                 name: "age".to_string(),
+///             },
+///             condition::KeyCondition {
+///                 condition: condition::Condition::In(vec![Value::from("admin")]),
+///                 name: "role".to_string(),
+///             },
+///         ],
+///     )),
+/// );
+/// ```
+#[cfg(feature = "derive")]
+pub use dynamodb_crud_derive::DynamoFilter;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -348,13 +875,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a = :a_eq0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [(
                     "#a".to_string(),
                     "a".to_string(),
                 )]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [(
                     ":a_eq0".to_string(),
                     types::AttributeValue::N(
@@ -388,13 +915,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a = :a_eq0 AND #c = :c_eq1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#c".to_string(), "c".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":a_eq0".to_string(),
@@ -437,13 +964,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a BETWEEN :a_between0 AND :a_between1 OR begins_with(#b, :b_begins_with2)".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":a_between0".to_string(),
@@ -509,15 +1036,14 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a.#b = :b_eq0 AND #b.#d = :d_eq1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
-                    ("#b".to_string(), "b".to_string()),
                     ("#d".to_string(), "d".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":b_eq0".to_string(),
@@ -603,7 +1129,7 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "(#a.#b.#c = :c_eq0 AND #a.#b.#e = :e_eq1) AND (#b.#g = :g_eq2 OR #b.#i = :i_eq3)".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
@@ -613,7 +1139,7 @@ mod tests {
                     ("#i".to_string(), "i".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":c_eq0".to_string(),
@@ -667,13 +1193,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a = :a_eq0 OR #a = :a_eq1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [(
                     "#a".to_string(),
                     "a".to_string(),
                 )]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":a_eq0".to_string(),
@@ -715,13 +1241,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a > :a_gt0 OR #a < :a_lt1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [(
                     "#a".to_string(),
                     "a".to_string(),
                 )]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":a_gt0".to_string(),
@@ -773,13 +1299,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a.#b = :b_eq0 OR #a.#b = :b_eq1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":b_eq0".to_string(),
@@ -839,14 +1365,14 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#x.#a = :a_eq0 AND #y.#a = :a_eq1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#x".to_string(), "x".to_string()),
-                    ("#y".to_string(), "y".to_string()),
                     ("#a".to_string(), "a".to_string()),
+                    ("#y".to_string(), "y".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":a_eq0".to_string(),
@@ -864,11 +1390,392 @@ mod tests {
             ),
         }
     )]
+    #[case::leaves_in_and_equals(
+        ConditionMap::Leaves(
+            LogicalOperator::And,
+            vec![
+                KeyCondition {
+                    name: "a".to_string(),
+                    condition: Condition::In(
+                        vec![
+                            Value::Number(
+                                1.into()
+                            ),
+                            Value::Number(
+                                2.into()
+                            ),
+                        ]
+                    ),
+                },
+                KeyCondition {
+                    name: "b".to_string(),
+                    condition: Condition::Equals(
+                        Value::Number(
+                            3.into()
+                        )
+                    ),
+                },
+            ]
+        ),
+        common::ExpressionInput {
+            expression: "#a IN (:a_in0, :a_in1) AND #b = :b_eq2".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                    ("#b".to_string(), "b".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":a_in0".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                    (
+                        ":a_in1".to_string(),
+                        types::AttributeValue::N(
+                            "2".to_string()
+                        )
+                    ),
+                    (
+                        ":b_eq2".to_string(),
+                        types::AttributeValue::N(
+                            "3".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
+    #[case::leaves_size_and_attribute_type(
+        ConditionMap::Leaves(
+            LogicalOperator::And,
+            vec![
+                KeyCondition {
+                    name: "a".to_string(),
+                    condition: Condition::SizeCompare(
+                        Comparator::GreaterThan,
+                        Value::Number(
+                            1.into()
+                        )
+                    ),
+                },
+                KeyCondition {
+                    name: "b".to_string(),
+                    condition: Condition::AttributeType(
+                        AttributeType::List
+                    ),
+                },
+            ]
+        ),
+        common::ExpressionInput {
+            expression: "size(#a) > :a_size0 AND attribute_type(#b, :b_type1)".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                    ("#b".to_string(), "b".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":a_size0".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                    (
+                        ":b_type1".to_string(),
+                        types::AttributeValue::S(
+                            "L".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
+    #[case::node_not(
+        ConditionMap::Node(
+            LogicalOperator::And,
+            IndexMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        !ConditionMap::Leaves(
+                            LogicalOperator::Or,
+                            vec![
+                                KeyCondition {
+                                    name: "b".to_string(),
+                                    condition: Condition::Equals(
+                                        Value::Number(
+                                            1.into()
+                                        )
+                                    ),
+                                },
+                                KeyCondition {
+                                    name: "c".to_string(),
+                                    condition: Condition::Equals(
+                                        Value::Number(
+                                            2.into()
+                                        )
+                                    ),
+                                },
+                            ]
+                        )
+                    ),
+                    (
+                        "d".to_string(),
+                        ConditionMap::Leaves(
+                            LogicalOperator::And,
+                            vec![
+                                KeyCondition {
+                                    name: "e".to_string(),
+                                    condition: Condition::Equals(
+                                        Value::Number(
+                                            3.into()
+                                        )
+                                    ),
+                                },
+                            ]
+                        )
+                    ),
+                ]
+            )
+        ),
+        common::ExpressionInput {
+            expression: "NOT (#a.#b = :b_eq0 OR #a.#c = :c_eq1) AND #d.#e = :e_eq2".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                    ("#b".to_string(), "b".to_string()),
+                    ("#c".to_string(), "c".to_string()),
+                    ("#d".to_string(), "d".to_string()),
+                    ("#e".to_string(), "e".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":b_eq0".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                    (
+                        ":c_eq1".to_string(),
+                        types::AttributeValue::N(
+                            "2".to_string()
+                        )
+                    ),
+                    (
+                        ":e_eq2".to_string(),
+                        types::AttributeValue::N(
+                            "3".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
     fn test_condition_map_to_condition_operation(
         #[case] condition_map: ConditionMap<Value>,
         #[case] expected: common::ExpressionInput,
     ) {
-        let actual: common::ExpressionInput = condition_map.try_into().unwrap();
+        let actual: common::ExpressionInput = (&condition_map).try_into().unwrap();
+        assert!(
+            actual
+                .expression_attribute_names
+                .keys()
+                .eq(expected.expression_attribute_names.keys())
+        );
+        assert!(
+            actual
+                .expression_attribute_values
+                .keys()
+                .eq(expected.expression_attribute_values.keys())
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::attribute_exists(ConditionMap::attribute_exists("a"), Condition::NotNull)]
+    #[case::attribute_missing(ConditionMap::attribute_missing("a"), Condition::Null)]
+    #[case::begins_with(
+        ConditionMap::begins_with("a", "b"),
+        Condition::BeginsWith(
+            "b".to_string()
+        )
+    )]
+    #[case::between(
+        ConditionMap::between(
+            "a",
+            Value::Number(
+                1.into()
+            ),
+            Value::Number(
+                2.into()
+            )
+        ),
+        Condition::Between(
+            Value::Number(
+                1.into()
+            ),
+            Value::Number(
+                2.into()
+            )
+        )
+    )]
+    #[case::equals(
+        ConditionMap::equals("a", Value::Bool(true)),
+        Condition::Equals(Value::Bool(true))
+    )]
+    #[case::one_of(
+        ConditionMap::one_of(
+            "a",
+            [
+                Value::String(
+                    "b".to_string()
+                ),
+                Value::String(
+                    "c".to_string()
+                ),
+            ]
+        ),
+        Condition::In(
+            vec![
+                Value::String(
+                    "b".to_string()
+                ),
+                Value::String(
+                    "c".to_string()
+                ),
+            ]
+        )
+    )]
+    fn test_condition_map_constructors(
+        #[case] actual: ConditionMap<Value>,
+        #[case] condition: Condition<Value>,
+    ) {
+        let expected = ConditionMap::Leaves(
+            LogicalOperator::And,
+            vec![KeyCondition {
+                condition,
+                name: "a".to_string(),
+            }],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::and_or(
+        ConditionMap::equals("a", 1)
+            .and(ConditionMap::equals("b", 2))
+            .or(ConditionMap::equals("c", 3)),
+        "(#a = :a_eq0 AND #b = :b_eq1) OR #c = :c_eq2"
+    )]
+    #[case::and_not(
+        ConditionMap::equals("a", 1)
+            .and(!ConditionMap::equals("b", 2).or(ConditionMap::equals("c", 3))),
+        "#a = :a_eq0 AND NOT (#b = :b_eq1 OR #c = :c_eq2)"
+    )]
+    #[case::flattened(
+        ConditionMap::equals("a", 1)
+            .or(ConditionMap::equals("b", 2))
+            .or(ConditionMap::equals("c", 3)),
+        "#a = :a_eq0 OR #b = :b_eq1 OR #c = :c_eq2"
+    )]
+    #[case::nested_group(
+        ConditionMap::Node(
+            LogicalOperator::And,
+            IndexMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        ConditionMap::equals("b", 1).or(ConditionMap::equals("c", 2))
+                    ),
+                    ("d".to_string(), ConditionMap::equals("e", 3)),
+                ]
+            )
+        ),
+        "(#a.#b = :b_eq0 OR #a.#c = :c_eq1) AND #d.#e = :e_eq2"
+    )]
+    #[case::list_index(
+        ConditionMap::Node(
+            LogicalOperator::And,
+            IndexMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        ConditionMap::IndexNode(
+                            LogicalOperator::Or,
+                            IndexMap::from(
+                                [
+                                    (0, ConditionMap::equals("b", 1)),
+                                    (
+                                        1,
+                                        ConditionMap::IndexLeaves(
+                                            LogicalOperator::And,
+                                            vec![(2, Condition::NotNull)]
+                                        )
+                                    ),
+                                ]
+                            )
+                        )
+                    ),
+                ]
+            )
+        )
+        .and(
+            ConditionMap::Node(
+                LogicalOperator::And,
+                IndexMap::from(
+                    [
+                        (
+                            "c".to_string(),
+                            ConditionMap::IndexLeaves(
+                                LogicalOperator::And,
+                                vec![(0, Condition::Equals(2))]
+                            )
+                        ),
+                    ]
+                )
+            )
+        ),
+        "(#a[0].#b = :b_eq0 OR attribute_exists(#a[1][2])) AND #c[0] = :index0_eq1"
+    )]
+    fn test_condition_map_combinators(
+        #[case] condition_map: ConditionMap<i32>,
+        #[case] expected: &str,
+    ) {
+        let actual: common::ExpressionInput = (&condition_map).try_into().unwrap();
+        assert_eq!(actual.expression, expected);
+    }
+
+    #[test]
+    fn test_condition_map_raw() {
+        let condition_map = ConditionMap::equals("a", 1).and(ConditionMap::Raw {
+            expression: "size(#x) > :y OR attribute_not_exists(#x)".to_string(),
+            names: IndexMap::from([("#x".to_string(), "a".to_string())]),
+            values: IndexMap::from([(":y".to_string(), 2)]),
+        });
+        let actual: common::ExpressionInput = (&condition_map).try_into().unwrap();
+        let expected = common::ExpressionInput {
+            expression: "#a = :a_eq0 AND (size(#a) > :raw1 OR attribute_not_exists(#a))"
+                .to_string(),
+            expression_attribute_names: IndexMap::from([("#a".to_string(), "a".to_string())]),
+            expression_attribute_values: IndexMap::from([
+                (
+                    ":a_eq0".to_string(),
+                    types::AttributeValue::N("1".to_string()),
+                ),
+                (
+                    ":raw1".to_string(),
+                    types::AttributeValue::N("2".to_string()),
+                ),
+            ]),
+        };
         assert_eq!(actual, expected);
     }
 }
@@ -0,0 +1,177 @@
+use crate::{common, read, table, write};
+
+use aws_sdk_dynamodb::{Client, operation};
+
+/// Item type bound to a table, with its primary key attributes.
+///
+/// Generic over the value type of the keys, like the operations it builds. Usually derived
+/// with the `derive` feature, see [`DynamoItem`](derive@DynamoItem).
+pub trait DynamoItem<T>: Sized {
+    /// The name of the table storing the items.
+    const TABLE_NAME: &'static str;
+    /// The name of the partition key attribute.
+    const PARTITION_KEY: &'static str;
+    /// The name of the sort key attribute, if the table has one.
+    const SORT_KEY: Option<&'static str>;
+
+    /// The primary key of the item.
+    fn keys(&self) -> common::key::Keys<T>;
+
+    /// Put item operation writing the item into its table.
+    fn put(self) -> write::put_item::PutItem<Self> {
+        write::put_item::PutItem {
+            item: self,
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                table_name: Self::TABLE_NAME.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get item operation reading the item with the given primary key from its table.
+    fn get(keys: common::key::Keys<T>) -> read::get_item::GetItem<T> {
+        read::get_item::GetItem {
+            keys,
+            return_consumed_capacity: None,
+            single_read_args: read::common::SingleReadArgs {
+                table_name: Self::TABLE_NAME.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Delete item operation deleting the item with the given primary key from its table.
+    fn delete(keys: common::key::Keys<T>) -> write::delete_item::DeleteItem<T> {
+        write::delete_item::DeleteItem {
+            keys,
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                table_name: Self::TABLE_NAME.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Derive [`DynamoItem`](trait@DynamoItem) for a struct stored in a table.
+///
+/// Also generates a `key` function building the primary key from the key values.
+///
+/// ```rust
+/// use dynamodb_crud::common::{item::DynamoItem, key};
+/// use serde::Serialize;
+/// use serde_json::Value;
+///
+/// #[derive(DynamoItem, Serialize)]
+/// #[dynamo(table = "users", partition_key = "id", sort_key = "created_at")]
+/// struct User {
+///     id: String,
+///     created_at: u64,
+///     name: String,
+/// }
+///
+/// let keys: key::Keys<Value> = User::key("1".to_string(), 2);
+/// let get_item = User::get(keys);
+/// assert_eq!(get_item.single_read_args.table_name, "users");
+/// assert_eq!(get_item.keys.partition_key.value, Value::from("1"));
+/// assert_eq!(<User as DynamoItem<Value>>::SORT_KEY, Some("created_at"));
+/// ```
+#[cfg(feature = "derive")]
+pub use dynamodb_crud_derive::DynamoItem;
+
+/// Item type with the definition of the table storing it.
+///
+/// Usually derived with the `derive` feature, see [`DynamoTable`](derive@DynamoTable).
+pub trait DynamoTable {
+    /// The name of the time to live attribute of the table, if it has one.
+    ///
+    /// Time to live is enabled separately from the creation of the table.
+    const TIME_TO_LIVE_ATTRIBUTE: Option<&'static str> = None;
+
+    /// Create table operation creating the table storing the items.
+    fn table_definition() -> table::create_table::CreateTable;
+
+    /// Create the table storing the items.
+    fn create_table(
+        client: &Client,
+    ) -> impl Future<Output = Result<operation::create_table::CreateTableOutput, crate::Error>> + Send
+    {
+        Self::table_definition().send(client)
+    }
+}
+
+/// Derive [`DynamoTable`](trait@DynamoTable) for a struct stored in a table.
+///
+/// The keys and global secondary indexes come from the `#[dynamo(...)]` attributes shared with
+/// [`DynamoItem`](derive@DynamoItem), and the table is billed per request unless
+/// `provisioned` is set.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types;
+/// use dynamodb_crud::{common::item::{DynamoItem, DynamoTable}, table};
+///
+/// #[derive(DynamoItem, DynamoTable)]
+/// #[dynamo(table = "users", partition_key = "id", sort_key = "created_at")]
+/// #[dynamo(global_index(name = "byEmail", partition_key = "email", keys_only))]
+/// #[dynamo(stream = "new_and_old_images", time_to_live = "expires_at")]
+/// struct User {
+///     id: String,
+///     created_at: u64,
+///     email: String,
+///     #[dynamo(name = "expiresAt")]
+///     expires_at: u64,
+/// }
+///
+/// let create_table = User::table_definition();
+/// assert_eq!(create_table.table_name, "users");
+/// assert_eq!(create_table.billing_mode, table::common::BillingMode::PayPerRequest);
+/// let sort_key = create_table.key_schema.sort_key.unwrap();
+/// assert_eq!(sort_key.attribute_type, types::ScalarAttributeType::N);
+/// let index = &create_table.global_secondary_indexes[0];
+/// assert_eq!(index.key_schema.partition_key.name, "email");
+/// assert_eq!(index.projection, table::common::Projection::KeysOnly);
+/// assert_eq!(create_table.stream_view_type, Some(types::StreamViewType::NewAndOldImages));
+/// assert_eq!(User::TIME_TO_LIVE_ATTRIBUTE, Some("expiresAt"));
+/// ```
+#[cfg(feature = "derive")]
+pub use dynamodb_crud_derive::DynamoTable;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::Value;
+
+    #[derive(Debug, PartialEq)]
+    struct Item {
+        a: String,
+    }
+
+    impl DynamoItem<Value> for Item {
+        const TABLE_NAME: &'static str = "b";
+        const PARTITION_KEY: &'static str = "a";
+        const SORT_KEY: Option<&'static str> = None;
+
+        fn keys(&self) -> common::key::Keys<Value> {
+            common::key::Keys {
+                partition_key: common::key::Key {
+                    name: Self::PARTITION_KEY.to_string(),
+                    value: Value::from(self.a.clone()),
+                },
+                sort_key: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamo_item() {
+        let item = Item { a: "c".to_string() };
+        let delete_item = Item::delete(item.keys());
+        assert_eq!(delete_item.keys, item.keys());
+        assert_eq!(delete_item.write_args.table_name, "b");
+        let put_item = item.put();
+        assert_eq!(put_item.write_args.table_name, "b");
+        assert_eq!(put_item.item, Item { a: "c".to_string() });
+    }
+}
@@ -1,6 +1,8 @@
+use crate::{common::serializer, table};
+
 use aws_sdk_dynamodb::types;
-use serde::Serialize;
-use serde_dynamo::{Error, Result, to_attribute_value};
+use serde::{Serialize, de::DeserializeOwned, de::Error as _};
+use serde_dynamo::{Error, Result};
 use std::collections;
 
 /// Key component.
@@ -42,20 +44,150 @@ pub struct Keys<T> {
     pub sort_key: Option<Key<T>>,
 }
 
+impl<T: Serialize> Keys<T> {
+    /// Convert the keys into attribute values with the given serializer.
+    pub(crate) fn serialize(
+        self,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> Result<collections::HashMap<String, types::AttributeValue>> {
+        let partition_key_value = serializer.to_attribute_value(self.partition_key.value)?;
+        let mut keys = collections::HashMap::from([(self.partition_key.name, partition_key_value)]);
+        if let Some(sort_key) = self.sort_key {
+            let sort_key_value = serializer.to_attribute_value(sort_key.value)?;
+            keys.insert(sort_key.name, sort_key_value);
+        }
+        Ok(keys)
+    }
+}
+
 impl<T: Serialize> TryFrom<Keys<T>> for collections::HashMap<String, types::AttributeValue> {
     type Error = Error;
 
-    fn try_from(key: Keys<T>) -> Result<Self> {
-        let partition_key_value = to_attribute_value(key.partition_key.value)?;
-        let mut keys = Self::from([(key.partition_key.name, partition_key_value)]);
-        if let Some(sort_key) = key.sort_key {
-            let sort_key_value = to_attribute_value(sort_key.value)?;
-            keys.insert(sort_key.name, sort_key_value);
+    fn try_from(keys: Keys<T>) -> Result<Self> {
+        keys.serialize(&serializer::SerdeDynamo)
+    }
+}
+
+/// Pagination key of a query or scan: the primary key of the last evaluated item, along with
+/// its index keys when reading a secondary index.
+///
+/// Pass it as the `exclusive_start_key` of the next read to continue where it left off.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_crud::{common::key, table};
+/// use std::collections::HashMap;
+///
+/// let key_schema = table::common::KeySchema {
+///     partition_key: table::common::KeyAttribute {
+///         name: "id".to_string(),
+///         ..Default::default()
+///     },
+///     sort_key: None,
+/// };
+/// let item = HashMap::from([("id".to_string(), AttributeValue::S("1".to_string()))]);
+/// let last_evaluated_key: key::LastEvaluatedKey<String> =
+///     key::LastEvaluatedKey::new(item, &key_schema, None).unwrap();
+/// assert_eq!(last_evaluated_key.keys.partition_key.value, "1");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LastEvaluatedKey<T> {
+    /// The keys of the secondary index read, if any.
+    pub index_keys: Option<Keys<T>>,
+    /// The primary key of the table.
+    pub keys: Keys<T>,
+}
+
+/// Get a key attribute of an item, deserializing its value.
+fn get_key<T: DeserializeOwned>(
+    item: &collections::HashMap<String, types::AttributeValue>,
+    key_attribute: &table::common::KeyAttribute,
+) -> Result<Key<T>> {
+    let value = item
+        .get(&key_attribute.name)
+        .cloned()
+        .ok_or_else(|| Error::custom(format!("missing key attribute {}", key_attribute.name)))?;
+    let key = Key {
+        name: key_attribute.name.clone(),
+        value: serde_dynamo::from_attribute_value(value)?,
+    };
+    Ok(key)
+}
+
+/// Get the keys of a key schema from an item, deserializing their values.
+fn get_keys<T: DeserializeOwned>(
+    item: &collections::HashMap<String, types::AttributeValue>,
+    key_schema: &table::common::KeySchema,
+) -> Result<Keys<T>> {
+    let keys = Keys {
+        partition_key: get_key(item, &key_schema.partition_key)?,
+        sort_key: key_schema
+            .sort_key
+            .as_ref()
+            .map(|sort_key| get_key(item, sort_key))
+            .transpose()?,
+    };
+    Ok(keys)
+}
+
+impl<T: DeserializeOwned> LastEvaluatedKey<T> {
+    /// Deserialize a raw pagination key, splitting it by the key schemas of the table and of
+    /// the secondary index read, if any.
+    pub fn new(
+        item: collections::HashMap<String, types::AttributeValue>,
+        key_schema: &table::common::KeySchema,
+        index_key_schema: Option<&table::common::KeySchema>,
+    ) -> Result<Self> {
+        let last_evaluated_key = Self {
+            index_keys: index_key_schema
+                .map(|index_key_schema| get_keys(&item, index_key_schema))
+                .transpose()?,
+            keys: get_keys(&item, key_schema)?,
+        };
+        Ok(last_evaluated_key)
+    }
+}
+
+impl<T: Serialize> LastEvaluatedKey<T> {
+    /// Convert the table and index keys into a single raw pagination key with the given
+    /// serializer.
+    pub(crate) fn serialize(
+        self,
+        serializer: &impl serializer::ValueSerializer,
+    ) -> Result<collections::HashMap<String, types::AttributeValue>> {
+        let mut keys = self.keys.serialize(serializer)?;
+        if let Some(index_keys) = self.index_keys {
+            keys.extend(index_keys.serialize(serializer)?);
         }
         Ok(keys)
     }
 }
 
+/// Get the canonical encoding of the key attributes of an item, ordered by attribute name, so
+/// keys can be compared and hashed.
+///
+/// Key attributes are only strings, numbers or binaries, other attributes are skipped.
+pub(crate) fn get_key_bytes<'a>(
+    key: impl IntoIterator<Item = (&'a String, &'a types::AttributeValue)>,
+) -> Vec<u8> {
+    let key: collections::BTreeMap<_, _> = key.into_iter().collect();
+    let mut bytes = Vec::new();
+    for (name, value) in key {
+        let (tag, value) = match value {
+            types::AttributeValue::B(value) => (b'B', value.as_ref()),
+            types::AttributeValue::N(value) => (b'N', value.as_bytes()),
+            types::AttributeValue::S(value) => (b'S', value.as_bytes()),
+            _ => continue,
+        };
+        bytes.push(tag);
+        for chunk in [name.as_bytes(), value] {
+            bytes.extend((chunk.len() as u32).to_be_bytes());
+            bytes.extend(chunk);
+        }
+    }
+    bytes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +309,89 @@ mod tests {
         let actual: collections::HashMap<String, types::AttributeValue> = keys.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_last_evaluated_key() {
+        let key_schema = table::common::KeySchema {
+            partition_key: table::common::KeyAttribute {
+                name: "a".to_string(),
+                ..Default::default()
+            },
+            sort_key: Some(table::common::KeyAttribute {
+                name: "b".to_string(),
+                ..Default::default()
+            }),
+        };
+        let index_key_schema = table::common::KeySchema {
+            partition_key: table::common::KeyAttribute {
+                name: "c".to_string(),
+                ..Default::default()
+            },
+            sort_key: Some(table::common::KeyAttribute {
+                name: "a".to_string(),
+                ..Default::default()
+            }),
+        };
+        let item = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("d".to_string())),
+            ("b".to_string(), types::AttributeValue::N("1".to_string())),
+            ("c".to_string(), types::AttributeValue::S("e".to_string())),
+        ]);
+        let actual: LastEvaluatedKey<Value> =
+            LastEvaluatedKey::new(item.clone(), &key_schema, Some(&index_key_schema)).unwrap();
+        let expected = LastEvaluatedKey {
+            index_keys: Some(Keys {
+                partition_key: Key {
+                    name: "c".to_string(),
+                    value: Value::from("e"),
+                },
+                sort_key: Some(Key {
+                    name: "a".to_string(),
+                    value: Value::from("d"),
+                }),
+            }),
+            keys: Keys {
+                partition_key: Key {
+                    name: "a".to_string(),
+                    value: Value::from("d"),
+                },
+                sort_key: Some(Key {
+                    name: "b".to_string(),
+                    value: Value::from(1),
+                }),
+            },
+        };
+        assert_eq!(actual, expected);
+        let actual = expected.serialize(&serializer::SerdeDynamo).unwrap();
+        assert_eq!(actual, item);
+    }
+
+    #[test]
+    fn test_last_evaluated_key_missing_attribute() {
+        let key_schema = table::common::KeySchema {
+            partition_key: table::common::KeyAttribute {
+                name: "a".to_string(),
+                ..Default::default()
+            },
+            sort_key: None,
+        };
+        let actual = LastEvaluatedKey::<Value>::new(collections::HashMap::new(), &key_schema, None);
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_get_key_bytes() {
+        let key = collections::HashMap::from([
+            ("b".to_string(), types::AttributeValue::N("1".to_string())),
+            ("a".to_string(), types::AttributeValue::S("c".to_string())),
+            ("d".to_string(), types::AttributeValue::Bool(true)),
+        ]);
+        let actual = get_key_bytes(&key);
+        let expected = [
+            b"S\0\0\0\x01a\0\0\0\x01c".as_slice(),
+            b"N\0\0\0\x01b\0\0\0\x011".as_slice(),
+        ]
+        .concat();
+        assert_eq!(actual, expected);
+    }
 }
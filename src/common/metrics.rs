@@ -0,0 +1,412 @@
+use aws_sdk_dynamodb::{config, operation, types};
+use aws_smithy_runtime_api::{
+    box_error::BoxError,
+    client::{
+        interceptors::context::{Input, Output},
+        orchestrator::{Metadata, OrchestratorError},
+        retries::RequestAttempts,
+    },
+};
+use aws_smithy_types::config_bag;
+use std::{collections, time};
+
+/// Label of requests whose items belong to several tables.
+const MULTIPLE_TABLES: &str = "multiple";
+
+/// Label of requests not targeting a table.
+const NO_TABLE: &str = "none";
+
+/// Get the table label of the given table names, deduplicated.
+fn get_table_label<'a>(table_names: impl IntoIterator<Item = &'a str>) -> String {
+    let table_names: collections::BTreeSet<&str> = table_names.into_iter().collect();
+    let mut table_names = table_names.into_iter();
+    match (table_names.next(), table_names.next()) {
+        (None, _) => NO_TABLE.to_string(),
+        (Some(table_name), None) => table_name.to_string(),
+        (Some(_), Some(_)) => MULTIPLE_TABLES.to_string(),
+    }
+}
+
+/// Get the table names of an item of a write transaction.
+fn get_transact_write_table_name(transact_write_item: &types::TransactWriteItem) -> Option<&str> {
+    transact_write_item
+        .condition_check()
+        .map(types::ConditionCheck::table_name)
+        .or_else(|| transact_write_item.delete().map(types::Delete::table_name))
+        .or_else(|| transact_write_item.put().map(types::Put::table_name))
+        .or_else(|| transact_write_item.update().map(types::Update::table_name))
+}
+
+/// Return the table label of the input if it is one of the given single table operations.
+macro_rules! return_table_label {
+    ($input:expr, $($operation:ident::$input_type:ident),+ $(,)?) => {
+        $(
+            if let Some(input) = $input.downcast_ref::<operation::$operation::$input_type>() {
+                return get_table_label(input.table_name());
+            }
+        )+
+    };
+}
+
+/// Get the table label of the input of an operation.
+fn get_input_table_label(input: &Input) -> String {
+    return_table_label!(
+        input,
+        create_table::CreateTableInput,
+        delete_item::DeleteItemInput,
+        delete_table::DeleteTableInput,
+        describe_table::DescribeTableInput,
+        get_item::GetItemInput,
+        put_item::PutItemInput,
+        query::QueryInput,
+        scan::ScanInput,
+        update_item::UpdateItemInput,
+        update_table::UpdateTableInput,
+    );
+    if let Some(input) = input.downcast_ref::<operation::batch_get_item::BatchGetItemInput>() {
+        return get_table_label(
+            input
+                .request_items()
+                .into_iter()
+                .flatten()
+                .map(|(k, _)| k.as_str()),
+        );
+    }
+    if let Some(input) = input.downcast_ref::<operation::batch_write_item::BatchWriteItemInput>() {
+        return get_table_label(
+            input
+                .request_items()
+                .into_iter()
+                .flatten()
+                .map(|(k, _)| k.as_str()),
+        );
+    }
+    if let Some(input) =
+        input.downcast_ref::<operation::transact_get_items::TransactGetItemsInput>()
+    {
+        return get_table_label(
+            input
+                .transact_items()
+                .iter()
+                .filter_map(|transact_get_item| transact_get_item.get())
+                .map(types::Get::table_name),
+        );
+    }
+    if let Some(input) =
+        input.downcast_ref::<operation::transact_write_items::TransactWriteItemsInput>()
+    {
+        return get_table_label(
+            input
+                .transact_items()
+                .iter()
+                .filter_map(get_transact_write_table_name),
+        );
+    }
+    NO_TABLE.to_string()
+}
+
+/// Whether the input of an operation only reads items, so that its consumed capacity is read
+/// capacity.
+fn is_read_input(input: &Input) -> bool {
+    if let Some(input) = input.downcast_ref::<operation::execute_statement::ExecuteStatementInput>()
+    {
+        return input
+            .statement()
+            .and_then(|statement| statement.trim_start().get(..6))
+            .is_some_and(|keyword| keyword.eq_ignore_ascii_case("select"));
+    }
+    input
+        .downcast_ref::<operation::batch_get_item::BatchGetItemInput>()
+        .is_some()
+        || input
+            .downcast_ref::<operation::get_item::GetItemInput>()
+            .is_some()
+        || input
+            .downcast_ref::<operation::query::QueryInput>()
+            .is_some()
+        || input.downcast_ref::<operation::scan::ScanInput>().is_some()
+        || input
+            .downcast_ref::<operation::transact_get_items::TransactGetItemsInput>()
+            .is_some()
+}
+
+/// Get the number of items returned by the output of an operation, if it returns items.
+fn get_item_count(output: &Output) -> Option<usize> {
+    if let Some(output) = output.downcast_ref::<operation::batch_get_item::BatchGetItemOutput>() {
+        return Some(
+            output
+                .responses()
+                .into_iter()
+                .flatten()
+                .map(|(_, items)| items.len())
+                .sum(),
+        );
+    }
+    if let Some(output) =
+        output.downcast_ref::<operation::execute_statement::ExecuteStatementOutput>()
+    {
+        return Some(output.items().len());
+    }
+    if let Some(output) = output.downcast_ref::<operation::get_item::GetItemOutput>() {
+        return Some(usize::from(output.item().is_some()));
+    }
+    if let Some(output) = output.downcast_ref::<operation::query::QueryOutput>() {
+        return Some(output.count().max(0) as usize);
+    }
+    if let Some(output) = output.downcast_ref::<operation::scan::ScanOutput>() {
+        return Some(output.count().max(0) as usize);
+    }
+    if let Some(output) =
+        output.downcast_ref::<operation::transact_get_items::TransactGetItemsOutput>()
+    {
+        return Some(
+            output
+                .responses()
+                .iter()
+                .filter(|item_response| item_response.item().is_some())
+                .count(),
+        );
+    }
+    None
+}
+
+/// Return the capacity consumed by the output if it is one of the given operations.
+macro_rules! return_consumed_capacity {
+    ($output:expr, $($operation:ident::$output_type:ident),+ $(,)?) => {
+        $(
+            if let Some(output) = $output.downcast_ref::<operation::$operation::$output_type>() {
+                return output.consumed_capacity().into_iter().collect();
+            }
+        )+
+    };
+}
+
+/// Get the capacity consumed by the output of an operation, if returned.
+fn get_consumed_capacity(output: &Output) -> Vec<&types::ConsumedCapacity> {
+    return_consumed_capacity!(
+        output,
+        batch_execute_statement::BatchExecuteStatementOutput,
+        batch_get_item::BatchGetItemOutput,
+        batch_write_item::BatchWriteItemOutput,
+        delete_item::DeleteItemOutput,
+        execute_statement::ExecuteStatementOutput,
+        execute_transaction::ExecuteTransactionOutput,
+        get_item::GetItemOutput,
+        put_item::PutItemOutput,
+        query::QueryOutput,
+        scan::ScanOutput,
+        transact_get_items::TransactGetItemsOutput,
+        transact_write_items::TransactWriteItemsOutput,
+        update_item::UpdateItemOutput,
+    );
+    Vec::new()
+}
+
+/// Get the read and write capacity units of a consumed capacity.
+///
+/// Only the total is returned unless the capacity is detailed by index, so the total is
+/// attributed to reads or writes depending on the operation.
+fn get_capacity_units(consumed_capacity: &types::ConsumedCapacity, is_read: bool) -> (f64, f64) {
+    match (
+        consumed_capacity.read_capacity_units(),
+        consumed_capacity.write_capacity_units(),
+    ) {
+        (None, None) => {
+            let capacity_units = consumed_capacity.capacity_units().unwrap_or_default();
+            if is_read {
+                (capacity_units, 0.0)
+            } else {
+                (0.0, capacity_units)
+            }
+        }
+        (read_capacity_units, write_capacity_units) => (
+            read_capacity_units.unwrap_or_default(),
+            write_capacity_units.unwrap_or_default(),
+        ),
+    }
+}
+
+/// Get the label of the class of a failed request.
+///
+/// Errors the retry classifiers do not classify are client errors if DynamoDB returned them,
+/// and transient errors if the request timed out or could not be sent.
+fn get_error_class_label<E>(
+    retry_error_class: Option<super::retry::RetryErrorClass>,
+    error: &OrchestratorError<E>,
+) -> &'static str {
+    match retry_error_class {
+        Some(super::retry::RetryErrorClass::Client) => "client",
+        Some(super::retry::RetryErrorClass::Server) => "server",
+        Some(super::retry::RetryErrorClass::Throttling) => "throttling",
+        Some(super::retry::RetryErrorClass::Transient) => "transient",
+        None if error.is_timeout_error() || error.is_connector_error() => "transient",
+        None if error.is_operation_error() => "client",
+        None => "other",
+    }
+}
+
+/// request being measured
+#[derive(Clone, Debug)]
+struct PendingRequest {
+    is_read: bool,
+    started_at: time::Instant,
+    table: String,
+}
+
+impl config_bag::Storable for PendingRequest {
+    type Storer = config_bag::StoreReplace<Self>;
+}
+
+/// Interceptor recording the metrics of every request.
+///
+/// Register it once on the configuration of the client, so every operation sent with it is
+/// measured.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::{Client, config};
+/// use dynamodb_crud::common::metrics;
+///
+/// let config = config::Builder::new()
+///     .behavior_version(config::BehaviorVersion::latest())
+///     .interceptor(metrics::MetricsInterceptor)
+///     .build();
+/// let client = Client::from_conf(config);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsInterceptor;
+
+impl config::Intercept for MetricsInterceptor {
+    fn name(&self) -> &'static str {
+        "dynamodb_crud metrics interceptor"
+    }
+
+    fn read_before_execution(
+        &self,
+        context: &config::interceptors::BeforeSerializationInterceptorContextRef<'_>,
+        cfg: &mut config::ConfigBag,
+    ) -> Result<(), BoxError> {
+        cfg.interceptor_state().store_put(PendingRequest {
+            is_read: is_read_input(context.input()),
+            started_at: time::Instant::now(),
+            table: get_input_table_label(context.input()),
+        });
+        Ok(())
+    }
+
+    fn read_after_execution(
+        &self,
+        context: &config::interceptors::FinalizerInterceptorContextRef<'_>,
+        runtime_components: &config::RuntimeComponents,
+        cfg: &mut config::ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(pending_request) = cfg.load::<PendingRequest>() else {
+            return Ok(());
+        };
+        let operation = cfg
+            .load::<Metadata>()
+            .map_or("", Metadata::name)
+            .to_string();
+        let labels = vec![
+            metrics::Label::new("operation", operation),
+            metrics::Label::new("table", pending_request.table.clone()),
+        ];
+        metrics::histogram!("dynamodb_crud.request.duration", labels.clone())
+            .record(pending_request.started_at.elapsed());
+        let attempts = cfg
+            .load::<RequestAttempts>()
+            .map_or(1, RequestAttempts::attempts);
+        metrics::counter!("dynamodb_crud.request.retries", labels.clone())
+            .increment(attempts.saturating_sub(1) as u64);
+        match context.output_or_error() {
+            Some(Ok(output)) => {
+                if let Some(item_count) = get_item_count(output) {
+                    metrics::histogram!("dynamodb_crud.request.items", labels.clone())
+                        .record(item_count as f64);
+                }
+                for consumed_capacity in get_consumed_capacity(output) {
+                    let (read_capacity_units, write_capacity_units) =
+                        get_capacity_units(consumed_capacity, pending_request.is_read);
+                    let mut labels = labels.clone();
+                    if let Some(table_name) = consumed_capacity.table_name() {
+                        labels[1] = metrics::Label::new("table", table_name.to_string());
+                    }
+                    if read_capacity_units > 0.0 {
+                        metrics::histogram!(
+                            "dynamodb_crud.consumed_read_capacity_units",
+                            labels.clone()
+                        )
+                        .record(read_capacity_units);
+                    }
+                    if write_capacity_units > 0.0 {
+                        metrics::histogram!("dynamodb_crud.consumed_write_capacity_units", labels)
+                            .record(write_capacity_units);
+                    }
+                }
+            }
+            Some(Err(error)) => {
+                let retry_action = super::retry::get_retry_action(context, runtime_components);
+                let retry_error_class = super::retry::get_retry_error_class(&retry_action);
+                let mut labels = labels;
+                labels.push(metrics::Label::new(
+                    "error_class",
+                    get_error_class_label(retry_error_class, error),
+                ));
+                metrics::counter!("dynamodb_crud.request.errors", labels).increment(1);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::none(vec![], NO_TABLE)]
+    #[case::single(vec!["users", "users"], "users")]
+    #[case::multiple(vec!["users", "orders"], MULTIPLE_TABLES)]
+    fn test_get_table_label(#[case] table_names: Vec<&str>, #[case] expected: &str) {
+        assert_eq!(get_table_label(table_names), expected);
+    }
+
+    #[test]
+    fn test_get_input_table_label() {
+        let input = operation::transact_get_items::TransactGetItemsInput::builder()
+            .transact_items(
+                types::TransactGetItem::builder()
+                    .get(
+                        types::Get::builder()
+                            .table_name("users")
+                            .set_key(Some(collections::HashMap::new()))
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(get_input_table_label(&Input::erase(input)), "users");
+    }
+
+    #[rstest]
+    #[case::total_read(None, None, true, (2.0, 0.0))]
+    #[case::total_write(None, None, false, (0.0, 2.0))]
+    #[case::detailed(Some(1.0), Some(0.5), true, (1.0, 0.5))]
+    fn test_get_capacity_units(
+        #[case] read_capacity_units: Option<f64>,
+        #[case] write_capacity_units: Option<f64>,
+        #[case] is_read: bool,
+        #[case] expected: (f64, f64),
+    ) {
+        let consumed_capacity = types::ConsumedCapacity::builder()
+            .capacity_units(2.0)
+            .set_read_capacity_units(read_capacity_units)
+            .set_write_capacity_units(write_capacity_units)
+            .build();
+        assert_eq!(get_capacity_units(&consumed_capacity, is_read), expected);
+    }
+}
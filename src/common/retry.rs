@@ -0,0 +1,489 @@
+use aws_sdk_dynamodb::{Client, client::customize, config};
+use aws_smithy_runtime_api::client::retries::classifiers::{
+    ClassifyRetry, RetryAction, RetryClassifierPriority, RetryReason,
+};
+#[cfg(feature = "tracing")]
+use aws_smithy_runtime_api::{
+    box_error::BoxError,
+    client::{orchestrator::Metadata, retries::RequestAttempts},
+};
+#[cfg(feature = "tracing")]
+use aws_smithy_types::config_bag;
+use aws_smithy_types::retry::ErrorKind;
+use std::{collections, hash, time};
+
+/// Class of errors that may be retried.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RetryErrorClass {
+    /// Errors caused by the request, retried only if the SDK considers them retryable.
+    Client,
+    /// Server errors, such as a 500 HTTP error.
+    Server,
+    /// Errors telling the client to back off, such as throttled or over capacity requests.
+    Throttling,
+    /// Connection-level errors, such as socket timeouts.
+    Transient,
+}
+
+impl From<RetryErrorClass> for ErrorKind {
+    fn from(retry_error_class: RetryErrorClass) -> Self {
+        match retry_error_class {
+            RetryErrorClass::Client => Self::ClientError,
+            RetryErrorClass::Server => Self::ServerError,
+            RetryErrorClass::Throttling => Self::ThrottlingError,
+            RetryErrorClass::Transient => Self::TransientError,
+        }
+    }
+}
+
+/// Retry policy overriding the retry configuration of the client for a single call.
+///
+/// Unset fields keep the value configured on the client.
+///
+/// With the `tracing` feature and `RetryTracingInterceptor` registered on the client, every
+/// retry emits an event on the `dynamodb_crud::retry` target, with the operation, the attempt,
+/// the delay in milliseconds and the error class as fields, so that metrics can be derived
+/// from them.
+///
+/// ```rust
+/// use dynamodb_crud::common::retry;
+/// use std::time::Duration;
+///
+/// let retry_policy = retry::RetryPolicy {
+///     initial_backoff: Some(Duration::from_millis(100)),
+///     max_attempts: Some(10),
+///     retry_error_classes: Some(vec![retry::RetryErrorClass::Throttling]),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RetryPolicy {
+    /// The delay before the first retry, grown exponentially on the following ones.
+    pub initial_backoff: Option<time::Duration>,
+    /// The maximum number of attempts, including the initial one.
+    ///
+    /// `Some(1)` disables retries.
+    pub max_attempts: Option<u32>,
+    /// The maximum delay between two attempts.
+    pub max_backoff: Option<time::Duration>,
+    /// The classes of errors to retry.
+    ///
+    /// Errors the SDK classifies as retryable are not retried unless their class is listed.
+    /// If `None`, every error the SDK classifies as retryable is retried.
+    pub retry_error_classes: Option<Vec<RetryErrorClass>>,
+}
+
+impl RetryPolicy {
+    fn get_retry_config(
+        &self,
+        retry_config: config::retry::RetryConfig,
+    ) -> config::retry::RetryConfig {
+        let mut retry_config = retry_config;
+        if let Some(initial_backoff) = self.initial_backoff {
+            retry_config = retry_config.with_initial_backoff(initial_backoff);
+        }
+        if let Some(max_attempts) = self.max_attempts {
+            retry_config = retry_config.with_max_attempts(max_attempts);
+        }
+        if let Some(max_backoff) = self.max_backoff {
+            retry_config = retry_config.with_max_backoff(max_backoff);
+        }
+        retry_config
+    }
+
+    /// Get the configuration overriding the one of the given client for a call, applying the
+    /// policy.
+    pub(crate) fn get_config_override(&self, client: &Client) -> config::Builder {
+        let retry_config = client
+            .config()
+            .retry_config()
+            .cloned()
+            .unwrap_or_else(config::retry::RetryConfig::standard);
+        let mut builder = config::Builder::new().retry_config(self.get_retry_config(retry_config));
+        if let Some(retry_error_classes) = &self.retry_error_classes {
+            builder = builder.retry_classifier(ErrorClassClassifier {
+                retry_error_classes: retry_error_classes.clone(),
+            });
+        }
+        builder
+    }
+}
+
+/// Operation sent with the per-call retry policy of the crate operation building it.
+pub(crate) trait WithRetryPolicy: Sized {
+    /// Override the retry configuration of the client with the policy, if any.
+    fn with_retry_policy(self, client: &Client, retry_policy: Option<&RetryPolicy>) -> Self;
+}
+
+impl<T, E, B> WithRetryPolicy for customize::CustomizableOperation<T, E, B> {
+    fn with_retry_policy(self, client: &Client, retry_policy: Option<&RetryPolicy>) -> Self {
+        match retry_policy {
+            Some(retry_policy) => self.config_override(retry_policy.get_config_override(client)),
+            None => self,
+        }
+    }
+}
+
+/// Emit the structured event of a retry, with the operation, the attempt about to be made,
+/// the delay waited before it and the class of the error that caused it.
+#[cfg(feature = "tracing")]
+fn trace_retry(operation: &str, attempt: u32, delay: time::Duration, error_class: RetryErrorClass) {
+    tracing::info!(
+        target: "dynamodb_crud::retry",
+        operation,
+        attempt,
+        delay_ms = delay.as_millis() as u64,
+        error_class = ?error_class,
+        "retrying request"
+    );
+}
+
+#[cfg(not(feature = "tracing"))]
+fn trace_retry(_: &str, _: u32, _: time::Duration, _: RetryErrorClass) {}
+
+/// Get the retry action of the retry classifiers on the outcome of an attempt.
+///
+/// The classifiers are run again to know why the retry strategy retries.
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+pub(crate) fn get_retry_action(
+    context: &config::interceptors::FinalizerInterceptorContextRef<'_>,
+    runtime_components: &config::RuntimeComponents,
+) -> RetryAction {
+    let mut retry_action = RetryAction::NoActionIndicated;
+    for retry_classifier in runtime_components.retry_classifiers() {
+        match retry_classifier.classify_retry_v2(context.inner(), &retry_action) {
+            RetryAction::NoActionIndicated => {}
+            RetryAction::RetryForbidden => return RetryAction::RetryForbidden,
+            action => retry_action = action,
+        }
+    }
+    retry_action
+}
+
+/// Get the class of the error a retry action retries, if any.
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+pub(crate) fn get_retry_error_class(retry_action: &RetryAction) -> Option<RetryErrorClass> {
+    let RetryAction::RetryIndicated(RetryReason::RetryableError { kind, .. }) = retry_action else {
+        return None;
+    };
+    match kind {
+        ErrorKind::ClientError => Some(RetryErrorClass::Client),
+        ErrorKind::ServerError => Some(RetryErrorClass::Server),
+        ErrorKind::ThrottlingError => Some(RetryErrorClass::Throttling),
+        ErrorKind::TransientError => Some(RetryErrorClass::Transient),
+        _ => None,
+    }
+}
+
+/// failed attempt the SDK is about to retry
+#[cfg(feature = "tracing")]
+#[derive(Clone, Debug)]
+struct PendingRetry {
+    error_class: RetryErrorClass,
+    failed_at: time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl config_bag::Storable for PendingRetry {
+    type Storer = config_bag::StoreReplace<Self>;
+}
+
+/// Interceptor emitting the structured event of every retry of the SDK.
+///
+/// Register it once on the configuration of the client, so every operation sent with it is
+/// traced.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::{Client, config};
+/// use dynamodb_crud::common::retry;
+///
+/// let config = config::Builder::new()
+///     .behavior_version(config::BehaviorVersion::latest())
+///     .interceptor(retry::RetryTracingInterceptor)
+///     .build();
+/// let client = Client::from_conf(config);
+/// ```
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RetryTracingInterceptor;
+
+#[cfg(feature = "tracing")]
+impl config::Intercept for RetryTracingInterceptor {
+    fn name(&self) -> &'static str {
+        "dynamodb_crud retry tracing interceptor"
+    }
+
+    fn read_after_attempt(
+        &self,
+        context: &config::interceptors::FinalizerInterceptorContextRef<'_>,
+        runtime_components: &config::RuntimeComponents,
+        cfg: &mut config::ConfigBag,
+    ) -> Result<(), BoxError> {
+        let retry_action = get_retry_action(context, runtime_components);
+        if let Some(error_class) = get_retry_error_class(&retry_action) {
+            cfg.interceptor_state().store_put(PendingRetry {
+                error_class,
+                failed_at: time::Instant::now(),
+            });
+        }
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &self,
+        _: &config::interceptors::BeforeTransmitInterceptorContextRef<'_>,
+        _: &config::RuntimeComponents,
+        cfg: &mut config::ConfigBag,
+    ) -> Result<(), BoxError> {
+        let attempt = cfg
+            .load::<RequestAttempts>()
+            .map_or(1, RequestAttempts::attempts);
+        if attempt > 1 {
+            // attempts timing out are not classified, the timeout is a transient error
+            let (error_class, delay) = match cfg.load::<PendingRetry>() {
+                Some(pending_retry) => {
+                    (pending_retry.error_class, pending_retry.failed_at.elapsed())
+                }
+                None => (RetryErrorClass::Transient, time::Duration::ZERO),
+            };
+            let operation = cfg.load::<Metadata>().map_or("", Metadata::name);
+            trace_retry(operation, attempt, delay, error_class);
+            cfg.interceptor_state().unset::<PendingRetry>();
+        }
+        Ok(())
+    }
+}
+
+/// Exponential backoff with full jitter, for retries made by the crate itself.
+///
+/// Unset fields of the retry policy fall back to the given defaults.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Backoff {
+    pub(crate) initial_backoff: time::Duration,
+    pub(crate) max_attempts: u32,
+    pub(crate) max_backoff: time::Duration,
+}
+
+impl Backoff {
+    /// Get the backoff of the retry policy, if any, falling back to the defaults.
+    pub(crate) fn new(retry_policy: Option<&RetryPolicy>, defaults: Self) -> Self {
+        let Some(retry_policy) = retry_policy else {
+            return defaults;
+        };
+        Self {
+            initial_backoff: retry_policy
+                .initial_backoff
+                .unwrap_or(defaults.initial_backoff),
+            max_attempts: retry_policy
+                .max_attempts
+                .unwrap_or(defaults.max_attempts)
+                .max(1),
+            max_backoff: retry_policy.max_backoff.unwrap_or(defaults.max_backoff),
+        }
+    }
+
+    /// Get the delay before the retry following the given attempt, scaled by a jitter
+    /// between 0 and 1.
+    fn get_delay(&self, attempt: u32, jitter: f64) -> time::Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        delay.mul_f64(jitter.clamp(0.0, 1.0))
+    }
+
+    /// Sleep before the retry of the unprocessed requests following the given attempt of the
+    /// operation.
+    ///
+    /// Requests are left unprocessed when they exceed the capacity of the table, so the retry
+    /// is reported as a throttling one.
+    pub(crate) async fn sleep(&self, operation: &str, attempt: u32) {
+        let delay = self.get_delay(attempt, get_jitter());
+        trace_retry(operation, attempt + 1, delay, RetryErrorClass::Throttling);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Get a random number between 0 and 1, seeded by the randomly keyed standard hasher.
+fn get_jitter() -> f64 {
+    let random = hash::BuildHasher::hash_one(&collections::hash_map::RandomState::new(), ());
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// retry classifier forbidding the retries of unlisted error classes
+#[derive(Debug)]
+struct ErrorClassClassifier {
+    retry_error_classes: Vec<RetryErrorClass>,
+}
+
+impl ErrorClassClassifier {
+    fn get_retry_action(&self, previous: &RetryAction) -> RetryAction {
+        match previous {
+            RetryAction::RetryIndicated(RetryReason::RetryableError { kind, .. })
+                if !self
+                    .retry_error_classes
+                    .iter()
+                    .any(|retry_error_class| ErrorKind::from(*retry_error_class) == *kind) =>
+            {
+                RetryAction::RetryForbidden
+            }
+            _ => RetryAction::NoActionIndicated,
+        }
+    }
+}
+
+impl ClassifyRetry for ErrorClassClassifier {
+    fn classify_retry(&self, _: &config::interceptors::InterceptorContext) -> RetryAction {
+        RetryAction::NoActionIndicated
+    }
+
+    fn classify_retry_v2(
+        &self,
+        _: &config::interceptors::InterceptorContext,
+        previous: &RetryAction,
+    ) -> RetryAction {
+        self.get_retry_action(previous)
+    }
+
+    fn name(&self) -> &'static str {
+        "dynamodb_crud error class classifier"
+    }
+
+    fn priority(&self) -> RetryClassifierPriority {
+        RetryClassifierPriority::run_after(RetryClassifierPriority::transient_error_classifier())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[test]
+    fn test_get_config_override() {
+        let retry_config = config::retry::RetryConfig::standard().with_max_attempts(7);
+        let client =
+            super::super::get_test_client(config::Builder::new().retry_config(retry_config));
+        let retry_policy = RetryPolicy {
+            initial_backoff: Some(time::Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let actual = retry_policy.get_config_override(&client).build();
+        let expected = config::retry::RetryConfig::standard()
+            .with_max_attempts(7)
+            .with_initial_backoff(time::Duration::from_millis(100));
+        assert_eq!(actual.retry_config(), Some(&expected));
+    }
+
+    #[rstest]
+    #[case::empty(RetryPolicy::default(), config::retry::RetryConfig::standard())]
+    #[case::full(
+        RetryPolicy {
+            initial_backoff: Some(
+                time::Duration::from_millis(100)
+            ),
+            max_attempts: Some(10),
+            max_backoff: Some(
+                time::Duration::from_secs(5)
+            ),
+            retry_error_classes: None,
+        },
+        config::retry::RetryConfig::standard()
+            .with_initial_backoff(
+                time::Duration::from_millis(100)
+            )
+            .with_max_attempts(10)
+            .with_max_backoff(
+                time::Duration::from_secs(5)
+            )
+    )]
+    fn test_get_retry_config(
+        #[case] retry_policy: RetryPolicy,
+        #[case] expected: config::retry::RetryConfig,
+    ) {
+        let actual = retry_policy.get_retry_config(config::retry::RetryConfig::standard());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::default(
+        None,
+        Backoff {
+            initial_backoff: time::Duration::from_millis(50),
+            max_attempts: 5,
+            max_backoff: time::Duration::from_secs(1),
+        }
+    )]
+    #[case::override_(
+        Some(
+            RetryPolicy {
+                initial_backoff: Some(
+                    time::Duration::from_millis(10)
+                ),
+                max_attempts: Some(0),
+                ..Default::default()
+            }
+        ),
+        Backoff {
+            initial_backoff: time::Duration::from_millis(10),
+            max_attempts: 1,
+            max_backoff: time::Duration::from_secs(1),
+        }
+    )]
+    fn test_backoff(#[case] retry_policy: Option<RetryPolicy>, #[case] expected: Backoff) {
+        let defaults = Backoff {
+            initial_backoff: time::Duration::from_millis(50),
+            max_attempts: 5,
+            max_backoff: time::Duration::from_secs(1),
+        };
+        let actual = Backoff::new(retry_policy.as_ref(), defaults);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::first(1, 1.0, time::Duration::from_millis(50))]
+    #[case::third(3, 1.0, time::Duration::from_millis(200))]
+    #[case::capped(10, 1.0, time::Duration::from_secs(1))]
+    #[case::jittered(3, 0.5, time::Duration::from_millis(100))]
+    fn test_get_delay(#[case] attempt: u32, #[case] jitter: f64, #[case] expected: time::Duration) {
+        let backoff = Backoff {
+            initial_backoff: time::Duration::from_millis(50),
+            max_attempts: 5,
+            max_backoff: time::Duration::from_secs(1),
+        };
+        let actual = backoff.get_delay(attempt, jitter);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_jitter() {
+        let jitter = get_jitter();
+        assert!((0.0..1.0).contains(&jitter));
+    }
+
+    #[rstest]
+    #[case::listed(RetryAction::throttling_error(), RetryAction::NoActionIndicated)]
+    #[case::unlisted(RetryAction::server_error(), RetryAction::RetryForbidden)]
+    #[case::no_action(RetryAction::NoActionIndicated, RetryAction::NoActionIndicated)]
+    fn test_get_retry_action(#[case] previous: RetryAction, #[case] expected: RetryAction) {
+        let classifier = ErrorClassClassifier {
+            retry_error_classes: vec![RetryErrorClass::Throttling],
+        };
+        let actual = classifier.get_retry_action(&previous);
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[rstest]
+    #[case::server(RetryAction::server_error(), Some(RetryErrorClass::Server))]
+    #[case::throttling(RetryAction::throttling_error(), Some(RetryErrorClass::Throttling))]
+    #[case::forbidden(RetryAction::RetryForbidden, None)]
+    fn test_get_retry_error_class(
+        #[case] retry_action: RetryAction,
+        #[case] expected: Option<RetryErrorClass>,
+    ) {
+        assert_eq!(get_retry_error_class(&retry_action), expected);
+    }
+}
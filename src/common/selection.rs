@@ -1,5 +1,6 @@
 use crate::common;
 
+use aws_sdk_dynamodb::types;
 use indexmap::IndexMap;
 use std::{collections, hash};
 
@@ -13,8 +14,28 @@ use std::{collections, hash};
 ///     "name".to_string(),
 /// ]);
 /// ```
+///
+/// List elements are selected by position with the index variants:
+///
+/// ```rust
+/// use dynamodb_crud::common::selection::SelectionMap;
+/// use indexmap::IndexMap;
+///
+/// // #items[0], #matrix[1][2]
+/// let selection = SelectionMap::Node(IndexMap::from([
+///     ("items".to_string(), SelectionMap::IndexLeaves(vec![0])),
+///     (
+///         "matrix".to_string(),
+///         SelectionMap::IndexNode(IndexMap::from([(1, SelectionMap::IndexLeaves(vec![2]))])),
+///     ),
+/// ]));
+/// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SelectionMap {
+    /// Index leaf selection - a flat list of list element positions to select.
+    IndexLeaves(Vec<usize>),
+    /// Index node selection - nested selection within list elements, by position.
+    IndexNode(IndexMap<usize, SelectionMap>),
     /// Leaf selection - a flat list of attribute names to select.
     Leaves(Vec<String>),
     /// Node selection - nested selection for hierarchical attribute paths.
@@ -24,6 +45,11 @@ pub enum SelectionMap {
 impl hash::Hash for SelectionMap {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         match self {
+            Self::IndexLeaves(indexes) => indexes.hash(state),
+            Self::IndexNode(map) => map.iter().for_each(|(index, value)| {
+                index.hash(state);
+                value.hash(state);
+            }),
             Self::Leaves(leaves) => leaves.hash(state),
             Self::Node(map) => map.iter().for_each(|(key, value)| {
                 key.hash(state);
@@ -33,45 +59,168 @@ impl hash::Hash for SelectionMap {
     }
 }
 
-impl From<SelectionMap> for common::ExpressionInput {
-    fn from(selection_map: SelectionMap) -> Self {
-        selection_map.get_selection_operation_recursive(&[])
+/// Remove the maps left empty by a nested selection from an item.
+///
+/// Selecting a nested path whose attribute is missing returns its parent map empty, which
+/// breaks the strict deserialization of the parent. Removing the empty maps lets optional
+/// parents deserialize as `None` instead. Maps within lists are pruned too, but the list
+/// elements themselves are kept, so their positions are preserved.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_crud::common::selection;
+/// use std::collections::HashMap;
+///
+/// let mut item = HashMap::from([
+///     ("id".to_string(), AttributeValue::S("1".to_string())),
+///     ("address".to_string(), AttributeValue::M(HashMap::new())),
+/// ]);
+/// selection::prune_empty_maps(&mut item);
+/// assert!(!item.contains_key("address"));
+/// ```
+pub fn prune_empty_maps(item: &mut collections::HashMap<String, types::AttributeValue>) {
+    item.retain(|_, value| !prune_empty_value(value));
+}
+
+/// Prune the empty maps nested in a value, returning whether the value is an empty map.
+fn prune_empty_value(value: &mut types::AttributeValue) -> bool {
+    match value {
+        types::AttributeValue::L(list) => {
+            list.iter_mut().for_each(|element| {
+                prune_empty_value(element);
+            });
+            false
+        }
+        types::AttributeValue::M(nested) => {
+            prune_empty_maps(nested);
+            nested.is_empty()
+        }
+        _ => false,
+    }
+}
+
+impl From<&SelectionMap> for common::ExpressionInput {
+    fn from(selection_map: &SelectionMap) -> Self {
+        selection_map.get_expression_operation(&mut common::PlaceholderAllocator::default())
     }
 }
 
 impl SelectionMap {
-    pub(crate) fn get_selection_operation_recursive(
-        self,
-        keys: &[String],
-    ) -> common::ExpressionInput {
-        let operations: Vec<_> = match self {
+    /// Keep only the selected attributes of an item, the way a projection expression would.
+    pub(crate) fn project_item(
+        &self,
+        mut item: collections::HashMap<String, types::AttributeValue>,
+    ) -> collections::HashMap<String, types::AttributeValue> {
+        match self {
+            Self::IndexLeaves(_) | Self::IndexNode(_) => collections::HashMap::new(),
             Self::Leaves(leaves) => leaves
-                .into_iter()
-                .map(|leaf| {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &leaf);
-                    let expression_attribute_names =
-                        collections::HashMap::from([(placeholder, leaf)]);
-                    let expression = new_keys.join(".");
-                    common::ExpressionInput {
-                        expression,
-                        expression_attribute_names,
-                        ..Default::default()
-                    }
-                })
+                .iter()
+                .filter_map(|leaf| item.remove_entry(leaf))
                 .collect(),
             Self::Node(map) => map
+                .iter()
+                .filter_map(|(key, value)| {
+                    let (key, nested) = item.remove_entry(key)?;
+                    Some((key, value.project_value(nested)?))
+                })
+                .collect(),
+        }
+    }
+
+    /// Keep only the selected elements of a list, in their original order.
+    fn project_list(&self, list: Vec<types::AttributeValue>) -> Vec<types::AttributeValue> {
+        match self {
+            Self::IndexLeaves(indexes) => list
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, element)| indexes.contains(&index).then_some(element))
+                .collect(),
+            Self::IndexNode(map) => list
                 .into_iter()
-                .map(|(key, value)| {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &key);
-                    let mut operation = value.get_selection_operation_recursive(&new_keys);
+                .enumerate()
+                .filter_map(|(index, element)| map.get(&index)?.project_value(element))
+                .collect(),
+            Self::Leaves(_) | Self::Node(_) => Vec::new(),
+        }
+    }
+
+    /// Keep only the selected attributes of a map or list value.
+    fn project_value(&self, value: types::AttributeValue) -> Option<types::AttributeValue> {
+        match value {
+            types::AttributeValue::L(list) => {
+                Some(types::AttributeValue::L(self.project_list(list)))
+            }
+            types::AttributeValue::M(nested) => {
+                Some(types::AttributeValue::M(self.project_item(nested)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Build the projection expression, sharing the name placeholders of the operation.
+    pub(crate) fn get_expression_operation(
+        &self,
+        placeholders: &mut common::PlaceholderAllocator,
+    ) -> common::ExpressionInput {
+        let mut operation = common::ExpressionInput::default();
+        self.write_selection_recursive(&mut String::new(), placeholders, &mut operation);
+        operation
+    }
+
+    pub(crate) fn write_selection_recursive(
+        &self,
+        path: &mut String,
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+    ) {
+        let group_start = operation.expression.len();
+        match self {
+            Self::IndexLeaves(indexes) => {
+                for index in indexes {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, ", ", |operation| {
+                        operation.expression.push_str(path);
+                    });
+                    path.truncate(path_length);
+                }
+            }
+            Self::IndexNode(map) => {
+                for (index, value) in map {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, ", ", |operation| {
+                        value.write_selection_recursive(path, placeholders, operation);
+                    });
+                    path.truncate(path_length);
+                }
+            }
+            Self::Leaves(leaves) => {
+                operation.expression_attribute_names.reserve(leaves.len());
+                for leaf in leaves {
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, leaf);
+                    operation.push_expression(group_start, ", ", |operation| {
+                        operation.expression.push_str(path);
+                    });
                     operation
                         .expression_attribute_names
-                        .insert(placeholder, key);
+                        .insert(placeholder, leaf.clone());
+                    path.truncate(path_length);
+                }
+            }
+            Self::Node(map) => {
+                for (key, value) in map {
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
                     operation
-                })
-                .collect(),
-        };
-        common::ExpressionInput::merge(", ", operations)
+                        .expression_attribute_names
+                        .insert(placeholder, key.clone());
+                    operation.push_expression(group_start, ", ", |operation| {
+                        value.write_selection_recursive(path, placeholders, operation);
+                    });
+                    path.truncate(path_length);
+                }
+            }
+        }
     }
 }
 
@@ -90,7 +239,7 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                 ]
@@ -107,7 +256,7 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a, #b".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
@@ -143,7 +292,7 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a.#b, #a.#c, #d.#e, #d.#f".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
@@ -192,7 +341,7 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "#a.#b.#c, #a.#b.#d, #b.#e, #b.#f".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#a".to_string(), "a".to_string()),
                     ("#b".to_string(), "b".to_string()),
@@ -205,11 +354,290 @@ mod tests {
             ..Default::default()
         }
     )]
+    #[case::node_empty_child(
+        SelectionMap::Node(
+            IndexMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        SelectionMap::Leaves(
+                            vec![]
+                        )
+                    ),
+                    (
+                        "b".to_string(),
+                        SelectionMap::Leaves(
+                            vec![
+                                "c".to_string(),
+                            ]
+                        )
+                    ),
+                ]
+            )
+        ),
+        common::ExpressionInput {
+            expression: "#b.#c".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                    ("#b".to_string(), "b".to_string()),
+                    ("#c".to_string(), "c".to_string()),
+                ]
+            ),
+            ..Default::default()
+        }
+    )]
+    #[case::node_index(
+        SelectionMap::Node(
+            IndexMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        SelectionMap::IndexLeaves(
+                            vec![0, 2]
+                        )
+                    ),
+                    (
+                        "b".to_string(),
+                        SelectionMap::IndexNode(
+                            IndexMap::from(
+                                [
+                                    (
+                                        0,
+                                        SelectionMap::IndexLeaves(
+                                            vec![2]
+                                        )
+                                    ),
+                                    (
+                                        1,
+                                        SelectionMap::Leaves(
+                                            vec![
+                                                "c".to_string(),
+                                            ]
+                                        )
+                                    ),
+                                ]
+                            )
+                        )
+                    ),
+                ]
+            )
+        ),
+        common::ExpressionInput {
+            expression: "#a[0], #a[2], #b[0][2], #b[1].#c".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                    ("#b".to_string(), "b".to_string()),
+                    ("#c".to_string(), "c".to_string()),
+                ]
+            ),
+            ..Default::default()
+        }
+    )]
     fn test_selection_map_to_selection_operation(
         #[case] selection_map: SelectionMap,
         #[case] expected: common::ExpressionInput,
     ) {
-        let actual: common::ExpressionInput = selection_map.into();
+        let actual: common::ExpressionInput = (&selection_map).into();
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case::leaves(
+        SelectionMap::Leaves(
+            vec![
+                "a".to_string(),
+                "e".to_string(),
+            ]
+        ),
+        collections::HashMap::from(
+            [
+                (
+                    "a".to_string(),
+                    types::AttributeValue::S(
+                        "b".to_string()
+                    )
+                ),
+            ]
+        )
+    )]
+    #[case::node(
+        SelectionMap::Node(
+            IndexMap::from(
+                [
+                    (
+                        "c".to_string(),
+                        SelectionMap::Leaves(
+                            vec![
+                                "d".to_string(),
+                            ]
+                        )
+                    ),
+                    (
+                        "a".to_string(),
+                        SelectionMap::Leaves(
+                            vec![
+                                "d".to_string(),
+                            ]
+                        )
+                    ),
+                ]
+            )
+        ),
+        collections::HashMap::from(
+            [
+                (
+                    "c".to_string(),
+                    types::AttributeValue::M(
+                        collections::HashMap::from(
+                            [
+                                (
+                                    "d".to_string(),
+                                    types::AttributeValue::N(
+                                        "1".to_string()
+                                    )
+                                ),
+                            ]
+                        )
+                    )
+                ),
+            ]
+        )
+    )]
+    #[case::node_index(
+        SelectionMap::Node(
+            IndexMap::from(
+                [
+                    (
+                        "f".to_string(),
+                        SelectionMap::IndexNode(
+                            IndexMap::from(
+                                [
+                                    (
+                                        1,
+                                        SelectionMap::Leaves(
+                                            vec![
+                                                "d".to_string(),
+                                            ]
+                                        )
+                                    ),
+                                ]
+                            )
+                        )
+                    ),
+                    (
+                        "g".to_string(),
+                        SelectionMap::IndexLeaves(
+                            vec![0]
+                        )
+                    ),
+                ]
+            )
+        ),
+        collections::HashMap::from(
+            [
+                (
+                    "f".to_string(),
+                    types::AttributeValue::L(
+                        vec![
+                            types::AttributeValue::M(
+                                collections::HashMap::from(
+                                    [
+                                        (
+                                            "d".to_string(),
+                                            types::AttributeValue::N(
+                                                "1".to_string()
+                                            )
+                                        ),
+                                    ]
+                                )
+                            ),
+                        ]
+                    )
+                ),
+                (
+                    "g".to_string(),
+                    types::AttributeValue::L(
+                        vec![
+                            types::AttributeValue::S(
+                                "b".to_string()
+                            ),
+                        ]
+                    )
+                ),
+            ]
+        )
+    )]
+    fn test_project_item(
+        #[case] selection_map: SelectionMap,
+        #[case] expected: collections::HashMap<String, types::AttributeValue>,
+    ) {
+        let item = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("b".to_string())),
+            (
+                "c".to_string(),
+                types::AttributeValue::M(collections::HashMap::from([
+                    ("d".to_string(), types::AttributeValue::N("1".to_string())),
+                    ("e".to_string(), types::AttributeValue::N("2".to_string())),
+                ])),
+            ),
+            (
+                "f".to_string(),
+                types::AttributeValue::L(vec![
+                    types::AttributeValue::M(collections::HashMap::from([(
+                        "d".to_string(),
+                        types::AttributeValue::N("0".to_string()),
+                    )])),
+                    types::AttributeValue::M(collections::HashMap::from([
+                        ("d".to_string(), types::AttributeValue::N("1".to_string())),
+                        ("e".to_string(), types::AttributeValue::N("2".to_string())),
+                    ])),
+                ]),
+            ),
+            (
+                "g".to_string(),
+                types::AttributeValue::L(vec![
+                    types::AttributeValue::S("b".to_string()),
+                    types::AttributeValue::S("c".to_string()),
+                ]),
+            ),
+        ]);
+        let actual = selection_map.project_item(item);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_prune_empty_maps() {
+        let mut item = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("b".to_string())),
+            (
+                "c".to_string(),
+                types::AttributeValue::M(collections::HashMap::from([(
+                    "d".to_string(),
+                    types::AttributeValue::M(collections::HashMap::new()),
+                )])),
+            ),
+            (
+                "e".to_string(),
+                types::AttributeValue::L(vec![types::AttributeValue::M(
+                    collections::HashMap::from([(
+                        "f".to_string(),
+                        types::AttributeValue::M(collections::HashMap::new()),
+                    )]),
+                )]),
+            ),
+        ]);
+        prune_empty_maps(&mut item);
+        let expected = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("b".to_string())),
+            (
+                "e".to_string(),
+                types::AttributeValue::L(vec![types::AttributeValue::M(
+                    collections::HashMap::new(),
+                )]),
+            ),
+        ]);
+        assert_eq!(item, expected);
+    }
 }
@@ -0,0 +1,101 @@
+use aws_sdk_dynamodb::types;
+use serde::{Serialize, ser::Error as _};
+use serde_dynamo::{Error, Result};
+use std::collections;
+
+/// Conversion of values into DynamoDB attribute values.
+///
+/// Every key, item and expression value goes through the serializer, so an implementation
+/// can customize the conversion (special number handling, a serde_dynamo fork, etc.).
+/// Operations use [`SerdeDynamo`] unless a serializer is passed to their `send_with` method.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_crud::common::serializer;
+/// use serde::Serialize;
+///
+/// /// Serializer storing every number as a string.
+/// struct NumbersAsStrings;
+///
+/// impl serializer::ValueSerializer for NumbersAsStrings {
+///     fn to_attribute_value<T: Serialize>(&self, value: T) -> serde_dynamo::Result<AttributeValue> {
+///         match serde_dynamo::to_attribute_value(value)? {
+///             AttributeValue::N(number) => Ok(AttributeValue::S(number)),
+///             value => Ok(value),
+///         }
+///     }
+/// }
+/// ```
+pub trait ValueSerializer {
+    /// Convert a value into an attribute value.
+    fn to_attribute_value<T: Serialize>(&self, value: T) -> Result<types::AttributeValue>;
+
+    /// Convert a value into an item.
+    ///
+    /// Defaults to [`to_attribute_value`](Self::to_attribute_value), failing unless the value
+    /// is converted into a map.
+    fn to_item<T: Serialize>(
+        &self,
+        value: T,
+    ) -> Result<collections::HashMap<String, types::AttributeValue>> {
+        match self.to_attribute_value(value)? {
+            types::AttributeValue::M(item) => Ok(item),
+            _ => Err(Error::custom(
+                "expected the item to be converted into a map",
+            )),
+        }
+    }
+}
+
+/// Default serializer, backed by [`serde_dynamo`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SerdeDynamo;
+
+impl ValueSerializer for SerdeDynamo {
+    fn to_attribute_value<T: Serialize>(&self, value: T) -> Result<types::AttributeValue> {
+        serde_dynamo::to_attribute_value(value)
+    }
+
+    fn to_item<T: Serialize>(
+        &self,
+        value: T,
+    ) -> Result<collections::HashMap<String, types::AttributeValue>> {
+        serde_dynamo::to_item(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{Value, json};
+
+    struct NumbersAsStrings;
+
+    impl ValueSerializer for NumbersAsStrings {
+        fn to_attribute_value<T: Serialize>(&self, value: T) -> Result<types::AttributeValue> {
+            match serde_dynamo::to_attribute_value(value)? {
+                types::AttributeValue::N(number) => Ok(types::AttributeValue::S(number)),
+                value => Ok(value),
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_attribute_value() {
+        let actual = NumbersAsStrings.to_attribute_value(1).unwrap();
+        let expected = types::AttributeValue::S("1".to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_to_item_default() {
+        let actual = NumbersAsStrings.to_item(json!({"a": "b"})).unwrap();
+        let expected = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        assert_eq!(actual, expected);
+        assert!(NumbersAsStrings.to_item(Value::Bool(true)).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use aws_sdk_dynamodb::types;
+use std::collections;
+
+/// Policy deciding which top-level attributes of the read items are visible to the caller.
+///
+/// Typed reads taking a policy strip the hidden attributes before deserializing the items, so
+/// services exposing items over an API can enforce field-level permissions in one place.
+/// Closures taking the attribute name are policies too.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_crud::common::visibility::{AttributePolicy, AttributeVisibility};
+/// use std::collections::{HashMap, HashSet};
+///
+/// fn get_policy(role: &str) -> AttributeVisibility {
+///     match role {
+///         "admin" => AttributeVisibility::Deny(HashSet::new()),
+///         _ => AttributeVisibility::Deny(HashSet::from(["ssn".to_string()])),
+///     }
+/// }
+///
+/// let mut item = HashMap::from([
+///     ("id".to_string(), AttributeValue::S("1".to_string())),
+///     ("ssn".to_string(), AttributeValue::S("123-45-6789".to_string())),
+/// ]);
+/// get_policy("support").apply(&mut item);
+/// assert!(!item.contains_key("ssn"));
+/// ```
+pub trait AttributePolicy {
+    /// Whether the attribute is visible.
+    fn is_visible(&self, attribute_name: &str) -> bool;
+
+    /// Remove the hidden attributes of an item.
+    fn apply(&self, item: &mut collections::HashMap<String, types::AttributeValue>) {
+        item.retain(|attribute_name, _| self.is_visible(attribute_name));
+    }
+}
+
+impl<F: Fn(&str) -> bool> AttributePolicy for F {
+    fn is_visible(&self, attribute_name: &str) -> bool {
+        self(attribute_name)
+    }
+}
+
+/// Policy listing the visible or the hidden attributes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttributeVisibility {
+    /// Only the listed attributes are visible.
+    Allow(collections::HashSet<String>),
+    /// Every attribute but the listed ones is visible.
+    Deny(collections::HashSet<String>),
+}
+
+impl AttributePolicy for AttributeVisibility {
+    fn is_visible(&self, attribute_name: &str) -> bool {
+        match self {
+            Self::Allow(attribute_names) => attribute_names.contains(attribute_name),
+            Self::Deny(attribute_names) => !attribute_names.contains(attribute_name),
+        }
+    }
+}
+
+/// Remove the hidden attributes of the read items, if any.
+pub(crate) fn apply_policy(
+    items: Option<Vec<collections::HashMap<String, types::AttributeValue>>>,
+    policy: &impl AttributePolicy,
+) -> Option<Vec<collections::HashMap<String, types::AttributeValue>>> {
+    items.map(|mut items| {
+        items.iter_mut().for_each(|item| policy.apply(item));
+        items
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn get_item() -> collections::HashMap<String, types::AttributeValue> {
+        collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("b".to_string())),
+            ("c".to_string(), types::AttributeValue::S("d".to_string())),
+        ])
+    }
+
+    #[rstest]
+    #[case::allow(
+        AttributeVisibility::Allow(collections::HashSet::from(["a".to_string()])),
+        vec!["a"]
+    )]
+    #[case::deny(
+        AttributeVisibility::Deny(collections::HashSet::from(["a".to_string()])),
+        vec!["c"]
+    )]
+    #[case::deny_none(AttributeVisibility::Deny(collections::HashSet::new()), vec!["a", "c"])]
+    fn test_attribute_visibility(#[case] policy: AttributeVisibility, #[case] expected: Vec<&str>) {
+        let mut item = get_item();
+        policy.apply(&mut item);
+        let mut actual: Vec<&str> = item.keys().map(String::as_str).collect();
+        actual.sort_unstable();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_apply_policy() {
+        let actual = apply_policy(Some(vec![get_item()]), &|attribute_name: &str| {
+            attribute_name != "c"
+        });
+        let expected = Some(vec![collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )])]);
+        assert_eq!(actual, expected);
+    }
+}
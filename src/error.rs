@@ -0,0 +1,307 @@
+use aws_sdk_dynamodb::{error, types};
+use serde::de::DeserializeOwned;
+use std::{collections, fmt};
+
+/// Error returned by the operations of the crate.
+///
+/// DynamoDB errors are classified so the common failures can be matched without unwrapping the
+/// error of every operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{Error, common, write};
+/// use serde_json::{Value, json};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let put_item: write::put_item::PutItem<Value> = write::put_item::PutItem {
+///     item: json!({"id": "1"}),
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         condition: Some(common::condition::ConditionMap::attribute(
+///             "id",
+///             common::condition::Condition::Null,
+///         )),
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// match put_item.send(client).await {
+///     Ok(_) => println!("created"),
+///     Err(Error::ConditionalCheckFailed(_)) => println!("already exists"),
+///     Err(Error::Throttling(_)) => println!("throttled, try again later"),
+///     Err(error) => return Err(error.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// A condition of the request was not met, along with the item as it was before the
+    /// request, if requested with `ReturnValuesOnConditionCheckFailure::AllOld`.
+    ConditionalCheckFailed(Option<collections::HashMap<String, types::AttributeValue>>),
+    /// An item could not be deserialized.
+    Deserialization(serde_dynamo::Error),
+    /// The request failed with another DynamoDB or SDK error.
+    DynamoDb(Box<aws_sdk_dynamodb::Error>),
+    /// The key conditions of a query are not supported by DynamoDB, or do not match the key
+    /// schema of its table or index.
+    InvalidKeyCondition(String),
+    /// The attributes selected by a query or scan are not supported by the table or index
+    /// read.
+    InvalidSelect(String),
+    /// A query or scan needed more pages than its maximum number of pages.
+    MaxPagesExceeded(usize),
+    /// The table or index does not exist, or is not active.
+    ResourceNotFound(Box<aws_sdk_dynamodb::Error>),
+    /// A value could not be serialized.
+    Serialization(serde_dynamo::Error),
+    /// The request was throttled because it exceeded the capacity of the table or the quota of
+    /// the account.
+    Throttling(Box<aws_sdk_dynamodb::Error>),
+    /// The transaction was canceled, with the reason of every item of the transaction.
+    TransactionCanceled(Vec<types::CancellationReason>),
+    /// A group of writes that must be applied atomically exceeds the maximum number of writes
+    /// of a transaction, along with its number of writes.
+    TransactionTooLarge(usize),
+    /// A parameter of a query template was not bound.
+    UnboundParameter(String),
+    /// A parameter bound to a query template is not one of its parameters.
+    UnknownParameter(String),
+    /// The request was rejected as invalid.
+    Validation(Box<aws_sdk_dynamodb::Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConditionalCheckFailed(_) => write!(formatter, "conditional check failed"),
+            Self::Deserialization(error) => write!(formatter, "deserialization failed: {error}"),
+            Self::DynamoDb(error) => write!(formatter, "dynamodb request failed: {error}"),
+            Self::InvalidKeyCondition(message) => {
+                write!(formatter, "invalid key condition: {message}")
+            }
+            Self::InvalidSelect(message) => write!(formatter, "invalid select: {message}"),
+            Self::MaxPagesExceeded(max_pages) => {
+                write!(formatter, "read exceeded the maximum of {max_pages} pages")
+            }
+            Self::ResourceNotFound(error) => write!(formatter, "resource not found: {error}"),
+            Self::Serialization(error) => write!(formatter, "serialization failed: {error}"),
+            Self::Throttling(error) => write!(formatter, "request throttled: {error}"),
+            Self::TransactionCanceled(reasons) => {
+                let codes = reasons
+                    .iter()
+                    .map(|reason| reason.code().unwrap_or("None"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(formatter, "transaction canceled: [{codes}]")
+            }
+            Self::TransactionTooLarge(writes) => {
+                write!(
+                    formatter,
+                    "transaction of {writes} writes exceeds the maximum"
+                )
+            }
+            Self::UnboundParameter(name) => write!(formatter, "unbound parameter {name}"),
+            Self::UnknownParameter(name) => write!(formatter, "unknown parameter {name}"),
+            Self::Validation(error) => write!(formatter, "validation failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ConditionalCheckFailed(_)
+            | Self::InvalidKeyCondition(_)
+            | Self::InvalidSelect(_)
+            | Self::MaxPagesExceeded(_)
+            | Self::TransactionCanceled(_)
+            | Self::TransactionTooLarge(_)
+            | Self::UnboundParameter(_)
+            | Self::UnknownParameter(_) => None,
+            Self::Deserialization(error) | Self::Serialization(error) => Some(error),
+            Self::DynamoDb(error)
+            | Self::ResourceNotFound(error)
+            | Self::Throttling(error)
+            | Self::Validation(error) => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<aws_sdk_dynamodb::Error> for Error {
+    fn from(error: aws_sdk_dynamodb::Error) -> Self {
+        match error {
+            aws_sdk_dynamodb::Error::ConditionalCheckFailedException(exception) => {
+                Self::ConditionalCheckFailed(exception.item)
+            }
+            aws_sdk_dynamodb::Error::ProvisionedThroughputExceededException(_)
+            | aws_sdk_dynamodb::Error::RequestLimitExceeded(_)
+            | aws_sdk_dynamodb::Error::ThrottlingException(_) => Self::Throttling(Box::new(error)),
+            aws_sdk_dynamodb::Error::ResourceNotFoundException(_) => {
+                Self::ResourceNotFound(Box::new(error))
+            }
+            aws_sdk_dynamodb::Error::TransactionCanceledException(exception) => {
+                Self::TransactionCanceled(exception.cancellation_reasons.unwrap_or_default())
+            }
+            error if error::ProvideErrorMetadata::code(&error) == Some("ValidationException") => {
+                Self::Validation(Box::new(error))
+            }
+            error => Self::DynamoDb(Box::new(error)),
+        }
+    }
+}
+
+impl<E, R> From<error::SdkError<E, R>> for Error
+where
+    aws_sdk_dynamodb::Error: From<error::SdkError<E, R>>,
+{
+    fn from(error: error::SdkError<E, R>) -> Self {
+        aws_sdk_dynamodb::Error::from(error).into()
+    }
+}
+
+impl Error {
+    /// Build the validation error of a request rejected before being sent.
+    pub(crate) fn validation(message: impl Into<String>) -> Self {
+        let error = error::BuildError::other(message.into());
+        Self::Validation(Box::new(aws_sdk_dynamodb::Error::from(error)))
+    }
+
+    /// Whether the request was throttled and may succeed if retried later.
+    pub fn is_throttling(&self) -> bool {
+        matches!(self, Self::Throttling(_))
+    }
+
+    /// Deserialize the item that failed the condition of a write.
+    ///
+    /// Returns `None` unless the error is a conditional check failure carrying the item, which
+    /// requires the write to set `return_values_on_condition_check_failure` to `AllOld`.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::{Client, types};
+    /// use dynamodb_crud::{common, write};
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Account {
+    ///     version: u64,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let put_item = write::put_item::PutItem {
+    ///     item: json!({"id": "1", "version": 2}),
+    ///     return_values: None,
+    ///     write_args: write::common::WriteArgs {
+    ///         condition: Some(common::condition::ConditionMap::equals(
+    ///             "version",
+    ///             json!(1),
+    ///         )),
+    ///         return_values_on_condition_check_failure: Some(
+    ///             types::ReturnValuesOnConditionCheckFailure::AllOld,
+    ///         ),
+    ///         table_name: "accounts".to_string(),
+    ///         ..Default::default()
+    ///     },
+    /// };
+    /// if let Err(error) = put_item.send(client).await {
+    ///     match error.conditional_check_item::<Account>() {
+    ///         Some(account) => println!("stale write, current version {}", account?.version),
+    ///         None => return Err(error.into()),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn conditional_check_item<O: DeserializeOwned>(&self) -> Option<serde_dynamo::Result<O>> {
+        match self {
+            Self::ConditionalCheckFailed(Some(item)) => Some(serde_dynamo::from_item(item.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aws_sdk_dynamodb::operation;
+
+    fn get_put_item_error(
+        error: operation::put_item::PutItemError,
+    ) -> error::SdkError<operation::put_item::PutItemError> {
+        error::SdkError::service_error(
+            error,
+            aws_sdk_dynamodb::config::http::HttpResponse::new(
+                400.try_into().unwrap(),
+                aws_smithy_types::body::SdkBody::empty(),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_conditional_check_failed() {
+        let item = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let exception = types::error::ConditionalCheckFailedException::builder()
+            .set_item(Some(item.clone()))
+            .build();
+        let error = get_put_item_error(
+            operation::put_item::PutItemError::ConditionalCheckFailedException(exception),
+        );
+        let actual = Error::from(error);
+        assert!(matches!(actual, Error::ConditionalCheckFailed(Some(actual)) if actual == item));
+    }
+
+    #[test]
+    fn test_conditional_check_item() {
+        let item = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let actual: serde_json::Value = Error::ConditionalCheckFailed(Some(item))
+            .conditional_check_item()
+            .unwrap()
+            .unwrap();
+        assert_eq!(actual, serde_json::json!({"a": "b"}));
+        assert!(
+            Error::ConditionalCheckFailed(None)
+                .conditional_check_item::<serde_json::Value>()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_throttling() {
+        let exception = types::error::ProvisionedThroughputExceededException::builder().build();
+        let error = get_put_item_error(
+            operation::put_item::PutItemError::ProvisionedThroughputExceededException(exception),
+        );
+        assert!(Error::from(error).is_throttling());
+    }
+
+    #[test]
+    fn test_resource_not_found() {
+        let exception = types::error::ResourceNotFoundException::builder().build();
+        let error = get_put_item_error(
+            operation::put_item::PutItemError::ResourceNotFoundException(exception),
+        );
+        assert!(matches!(Error::from(error), Error::ResourceNotFound(_)));
+    }
+
+    #[test]
+    fn test_transaction_canceled() {
+        let reason = types::CancellationReason::builder()
+            .code("ConditionalCheckFailed")
+            .build();
+        let exception = types::error::TransactionCanceledException::builder()
+            .cancellation_reasons(reason.clone())
+            .build();
+        let actual = Error::from(aws_sdk_dynamodb::Error::TransactionCanceledException(
+            exception,
+        ));
+        assert!(matches!(actual, Error::TransactionCanceled(reasons) if reasons == vec![reason]));
+    }
+}
@@ -0,0 +1,359 @@
+//! Append-only event streams stored in a DynamoDB table.
+//!
+//! This module provides:
+//! - Appending events to a stream with optimistic concurrency on the stream version
+//! - Reading the events of a stream in order, from a given version
+
+use crate::{common, read, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::{Serialize, de::DeserializeOwned, de::Error as _};
+use serde_dynamo::Error;
+use std::{collections, fmt};
+
+/// Error returned while appending or reading events.
+#[derive(Debug)]
+pub enum EventStoreError {
+    /// An event could not be deserialized.
+    Deserialization(Error),
+    /// The put item call appending the event failed.
+    PutItem(crate::Error),
+    /// The query reading the stream failed.
+    Query(crate::Error),
+    /// The event could not be serialized.
+    Serialization(Error),
+    /// The stream was appended to since the expected version was read.
+    VersionConflict {
+        /// The version the stream was expected to be at.
+        expected_version: u64,
+    },
+}
+
+impl fmt::Display for EventStoreError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deserialization(error) => write!(formatter, "deserialization failed: {error}"),
+            Self::PutItem(error) => write!(formatter, "put item failed: {error}"),
+            Self::Query(error) => write!(formatter, "query failed: {error}"),
+            Self::Serialization(error) => write!(formatter, "serialization failed: {error}"),
+            Self::VersionConflict { expected_version } => write!(
+                formatter,
+                "version conflict: the stream is no longer at version {expected_version}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EventStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialization(error) => Some(error),
+            Self::PutItem(error) => Some(error),
+            Self::Query(error) => Some(error),
+            Self::Serialization(error) => Some(error),
+            Self::VersionConflict { .. } => None,
+        }
+    }
+}
+
+/// An event read from a stream, along with its version.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StoredEvent<D> {
+    /// The deserialized event.
+    pub event: D,
+    /// The version of the stream the event produced, starting from 1.
+    pub version: u64,
+}
+
+/// key value of an event item, either the stream identifier or the version
+#[derive(Clone, Debug, PartialEq)]
+enum EventKeyValue {
+    StreamId(String),
+    Version(u64),
+}
+
+impl Serialize for EventKeyValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::StreamId(stream_id) => serializer.serialize_str(stream_id),
+            Self::Version(version) => serializer.serialize_u64(*version),
+        }
+    }
+}
+
+/// Store of append-only event streams.
+///
+/// Every event is an item whose partition key is the stream identifier and whose sort key is
+/// the version of the stream it produced. Appending the event of a version that already
+/// exists fails, so concurrent writers appending from the same version can't both succeed.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::events;
+/// use serde_json::{Value, json};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let store = events::EventStore {
+///     stream_id_name: "streamId".to_string(),
+///     table_name: "events".to_string(),
+///     version_name: "version".to_string(),
+/// };
+/// let history = store.read_stream::<Value>(client, "order-1", 1).await?;
+/// let version = history.last().map_or(0, |event| event.version);
+/// match store
+///     .append(client, "order-1", version, json!({"type": "shipped"}))
+///     .await
+/// {
+///     Ok(version) => println!("stream at version {version}"),
+///     Err(events::EventStoreError::VersionConflict { .. }) => println!("reload and retry"),
+///     Err(error) => return Err(error.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct EventStore {
+    /// The partition key name of the table, holding the stream identifier.
+    pub stream_id_name: String,
+    /// The name of the table.
+    pub table_name: String,
+    /// The sort key name of the table, holding the version as a number.
+    pub version_name: String,
+}
+
+impl EventStore {
+    fn get_append_input(
+        &self,
+        stream_id: &str,
+        version: u64,
+        item: collections::HashMap<String, types::AttributeValue>,
+    ) -> write::put_item::PutItemInput {
+        let mut item = item;
+        item.insert(
+            self.stream_id_name.clone(),
+            types::AttributeValue::S(stream_id.to_string()),
+        );
+        item.insert(
+            self.version_name.clone(),
+            types::AttributeValue::N(version.to_string()),
+        );
+        let write_operation = write::common::WriteInput {
+            condition_expression: Some("attribute_not_exists(#version)".to_string()),
+            expression_attribute_names: Some(collections::HashMap::from([(
+                "#version".to_string(),
+                self.version_name.clone(),
+            )])),
+            table_name: self.table_name.clone(),
+            ..Default::default()
+        };
+        write::put_item::PutItemInput {
+            item,
+            write_operation,
+        }
+    }
+
+    fn get_read_query(
+        &self,
+        stream_id: &str,
+        from_version: u64,
+    ) -> read::query::Query<EventKeyValue> {
+        read::query::Query {
+            key_schema: None,
+            multiple_read_args: read::common::MultipleReadArgs {
+                condition: None,
+                consistent_read: Some(true),
+                cursor: None,
+                deadline: None,
+                exclusive_start_key: None,
+                index_name: None,
+                limit: None,
+                max_items: None,
+                max_pages: None,
+                placeholder_naming: None,
+                prune_empty_maps: false,
+                retry_policy: None,
+                select: None,
+                selection: None,
+                table_name: self.table_name.clone(),
+            },
+            partition_key: common::key::Key {
+                name: self.stream_id_name.clone(),
+                value: EventKeyValue::StreamId(stream_id.to_string()),
+            },
+            return_consumed_capacity: None,
+            scan_index_forward: Some(true),
+            sort_key_condition: Some(common::condition::SortKey {
+                name: self.version_name.clone(),
+                condition: common::condition::SortKeyCondition::GreaterThanOrEqual(
+                    EventKeyValue::Version(from_version),
+                ),
+            }),
+        }
+    }
+
+    fn get_stored_event<D: DeserializeOwned>(
+        &self,
+        item: collections::HashMap<String, types::AttributeValue>,
+    ) -> Result<StoredEvent<D>, Error> {
+        let version = item
+            .get(&self.version_name)
+            .ok_or_else(|| Error::custom(format!("missing attribute {}", self.version_name)))?;
+        let version = serde_dynamo::from_attribute_value(version.clone())?;
+        let event = serde_dynamo::from_item(item)?;
+        Ok(StoredEvent { event, version })
+    }
+
+    /// Append an event to a stream expected to be at the given version, `0` for a new
+    /// stream, returning the new version of the stream.
+    ///
+    /// Fails with [`EventStoreError::VersionConflict`] if the stream was appended to since.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.events.append", skip(client, event), err)
+    )]
+    pub async fn append<T: Serialize>(
+        &self,
+        client: &Client,
+        stream_id: &str,
+        expected_version: u64,
+        event: T,
+    ) -> Result<u64, EventStoreError> {
+        let item =
+            common::serializer::ValueSerializer::to_item(&common::serializer::SerdeDynamo, event)
+                .map_err(EventStoreError::Serialization)?;
+        let version = expected_version + 1;
+        let put_item = self.get_append_input(stream_id, version, item);
+        let builder = client.put_item().set_item(Some(put_item.item));
+        match crate::apply_write_operation!(builder, put_item.write_operation)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(version),
+            Err(error) => match crate::Error::from(error) {
+                crate::Error::ConditionalCheckFailed(_) => {
+                    Err(EventStoreError::VersionConflict { expected_version })
+                }
+                error => Err(EventStoreError::PutItem(error)),
+            },
+        }
+    }
+
+    /// Read the events of a stream in order, starting from the given version.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.events.read_stream", skip(client), err)
+    )]
+    pub async fn read_stream<D: DeserializeOwned>(
+        &self,
+        client: &Client,
+        stream_id: &str,
+        from_version: u64,
+    ) -> Result<Vec<StoredEvent<D>>, EventStoreError> {
+        let output = self
+            .get_read_query(stream_id, from_version)
+            .send(client)
+            .await
+            .map_err(EventStoreError::Query)?;
+        output
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| self.get_stored_event(item))
+            .collect::<Result<_, _>>()
+            .map_err(EventStoreError::Deserialization)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{Value, json};
+
+    fn get_store() -> EventStore {
+        EventStore {
+            stream_id_name: "a".to_string(),
+            table_name: "b".to_string(),
+            version_name: "c".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_get_append_input() {
+        let item = collections::HashMap::from([(
+            "d".to_string(),
+            types::AttributeValue::S("e".to_string()),
+        )]);
+        let actual = get_store().get_append_input("f", 2, item);
+        let expected = write::put_item::PutItemInput {
+            item: collections::HashMap::from([
+                ("a".to_string(), types::AttributeValue::S("f".to_string())),
+                ("c".to_string(), types::AttributeValue::N("2".to_string())),
+                ("d".to_string(), types::AttributeValue::S("e".to_string())),
+            ]),
+            write_operation: write::common::WriteInput {
+                condition_expression: Some("attribute_not_exists(#version)".to_string()),
+                expression_attribute_names: Some(collections::HashMap::from([(
+                    "#version".to_string(),
+                    "c".to_string(),
+                )])),
+                table_name: "b".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_read_query() {
+        let actual = get_store().get_read_query("f", 3);
+        let expected = read::query::Query {
+            key_schema: None,
+            multiple_read_args: read::common::MultipleReadArgs {
+                condition: None,
+                consistent_read: Some(true),
+                cursor: None,
+                deadline: None,
+                exclusive_start_key: None,
+                index_name: None,
+                limit: None,
+                max_items: None,
+                max_pages: None,
+                placeholder_naming: None,
+                prune_empty_maps: false,
+                retry_policy: None,
+                select: None,
+                selection: None,
+                table_name: "b".to_string(),
+            },
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: EventKeyValue::StreamId("f".to_string()),
+            },
+            return_consumed_capacity: None,
+            scan_index_forward: Some(true),
+            sort_key_condition: Some(common::condition::SortKey {
+                name: "c".to_string(),
+                condition: common::condition::SortKeyCondition::GreaterThanOrEqual(
+                    EventKeyValue::Version(3),
+                ),
+            }),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_stored_event() {
+        let item = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("f".to_string())),
+            ("c".to_string(), types::AttributeValue::N("2".to_string())),
+            ("d".to_string(), types::AttributeValue::S("e".to_string())),
+        ]);
+        let actual: StoredEvent<Value> = get_store().get_stored_event(item).unwrap();
+        let expected = StoredEvent {
+            event: json!({"a": "f", "c": 2, "d": "e"}),
+            version: 2,
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,169 @@
+//! Health checks of the connection to DynamoDB and of the tables an application depends on.
+//!
+//! This module provides:
+//! - Probing connectivity and credentials with a minimal `ListTables` call
+//! - Describing tables concurrently to report whether each one exists and is active
+//! - A report suitable for readiness probes
+
+use aws_sdk_dynamodb::{Client, operation, types};
+use futures_util::future;
+use indexmap::IndexMap;
+
+/// Health of a table in a [`HealthReport`].
+#[derive(Debug)]
+pub enum TableHealth {
+    /// The table exists and is active.
+    Active,
+    /// The table exists but is not active, such as while it is created, updated or deleted.
+    NotActive(Option<types::TableStatus>),
+    /// The table does not exist.
+    NotFound,
+    /// The table could not be described.
+    Unavailable(crate::Error),
+}
+
+impl TableHealth {
+    /// Whether the table is active.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+impl From<Result<operation::describe_table::DescribeTableOutput, crate::Error>> for TableHealth {
+    fn from(output: Result<operation::describe_table::DescribeTableOutput, crate::Error>) -> Self {
+        match output {
+            Ok(output) => match output.table.and_then(|table| table.table_status) {
+                Some(types::TableStatus::Active) => Self::Active,
+                status => Self::NotActive(status),
+            },
+            Err(crate::Error::ResourceNotFound(_)) => Self::NotFound,
+            Err(error) => Self::Unavailable(error),
+        }
+    }
+}
+
+/// Report of a [`check`], suitable for readiness probes.
+#[derive(Debug)]
+pub struct HealthReport {
+    /// The error reaching DynamoDB, if the endpoint is unreachable or the credentials are
+    /// rejected.
+    pub connection_error: Option<crate::Error>,
+    /// The health of every checked table, in the order given.
+    pub tables: IndexMap<String, TableHealth>,
+}
+
+impl HealthReport {
+    /// Whether DynamoDB is reachable and every checked table is active.
+    pub fn is_healthy(&self) -> bool {
+        self.connection_error.is_none() && self.tables.values().all(TableHealth::is_active)
+    }
+}
+
+/// Check that DynamoDB is reachable with the credentials of the client, and that the given
+/// tables exist and are active.
+///
+/// The connectivity is probed with a `ListTables` call limited to one table, then the tables
+/// are described concurrently. The tables are not described if DynamoDB is unreachable.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::health;
+///
+/// # async fn example(client: &Client) {
+/// let report = health::check(client, ["users", "orders"]).await;
+/// if !report.is_healthy() {
+///     eprintln!("not ready: {report:?}");
+/// }
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "dynamodb_crud.health.check", skip_all)
+)]
+pub async fn check(
+    client: &Client,
+    tables: impl IntoIterator<Item = impl Into<String>>,
+) -> HealthReport {
+    let table_names: Vec<String> = tables.into_iter().map(Into::into).collect();
+    let connection = client
+        .list_tables()
+        .limit(1)
+        .send()
+        .await
+        .map_err(crate::Error::from);
+    if let Err(error) = connection {
+        let report = HealthReport {
+            connection_error: Some(error),
+            tables: IndexMap::new(),
+        };
+        return report;
+    }
+    let outputs = future::join_all(table_names.iter().map(|table_name| async move {
+        client
+            .describe_table()
+            .table_name(table_name)
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }))
+    .await;
+    HealthReport {
+        connection_error: None,
+        tables: table_names
+            .into_iter()
+            .zip(outputs.into_iter().map(TableHealth::from))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn get_describe_table_output(
+        status: Option<types::TableStatus>,
+    ) -> operation::describe_table::DescribeTableOutput {
+        operation::describe_table::DescribeTableOutput::builder()
+            .table(
+                types::TableDescription::builder()
+                    .set_table_status(status)
+                    .build(),
+            )
+            .build()
+    }
+
+    #[rstest]
+    #[case::active(Some(types::TableStatus::Active), true)]
+    #[case::creating(Some(types::TableStatus::Creating), false)]
+    #[case::missing_status(None, false)]
+    fn test_table_health(#[case] status: Option<types::TableStatus>, #[case] expected: bool) {
+        let actual = TableHealth::from(Ok(get_describe_table_output(status)));
+        assert_eq!(actual.is_active(), expected);
+    }
+
+    #[test]
+    fn test_table_health_not_found() {
+        let exception = types::error::ResourceNotFoundException::builder().build();
+        let error = crate::Error::from(aws_sdk_dynamodb::Error::ResourceNotFoundException(
+            exception,
+        ));
+        assert!(matches!(
+            TableHealth::from(Err(error)),
+            TableHealth::NotFound
+        ));
+    }
+
+    #[test]
+    fn test_health_report() {
+        let report = HealthReport {
+            connection_error: None,
+            tables: IndexMap::from([
+                ("a".to_string(), TableHealth::Active),
+                ("b".to_string(), TableHealth::NotFound),
+            ]),
+        };
+        assert!(!report.is_healthy());
+    }
+}
@@ -38,6 +38,7 @@
 //!         },
 //!         ..Default::default()
 //!     },
+//!     return_values: None,
 //!     update_expression: write::update_item::UpdateExpressionMap::Combined(vec![
 //!         // SET: Update name and increment age atomically
 //!         write::update_item::UpdateExpressionMap::Set(
@@ -67,24 +68,139 @@
 //! # }
 //! ```
 //!
+//! ## Errors
+//!
+//! Operations return the crate [`Error`], which classifies DynamoDB failures such as
+//! conditional check failures, throttling and canceled transactions so they can be matched
+//! directly.
+//!
+//! ## Metrics
+//!
+//! With the `metrics` feature and `common::metrics::MetricsInterceptor` registered on the
+//! configuration of the client, every request records its latency, retries, returned items,
+//! consumed capacity and errors with the [`metrics`](https://docs.rs/metrics) facade, labeled by
+//! operation and table. The metric names are listed in the README.
+//!
 //! ## Modules
 //!
+//! - [`mod@analysis`] - Scan-based reports on the data stored in tables
+//! - `cloudformation` - CloudFormation export of table definitions (requires the
+//!   `cloudformation` feature)
+//! - `codegen` - Typed table module generation (requires the `codegen` feature)
 //! - [`mod@common`] - Shared utilities for keys, conditions, and selections
-//! - [`mod@read`] - Read operations (GetItem, Query, Scan, BatchGetItem)
-//! - [`mod@write`] - Write operations (PutItem, UpdateItem, DeleteItem, BatchWriteItem)
+//! - [`mod@events`] - Append-only event streams with optimistic concurrency
+//! - [`mod@health`] - Connectivity and table status checks for readiness probes
+//! - [`mod@partiql`] - PartiQL operations (BatchExecuteStatement, ExecuteTransaction)
+//! - [`mod@raw`] - Non-generic operations on raw attribute values and pre-built expressions
+//! - [`mod@read`] - Read operations (GetItem, Query, Scan, BatchGetItem, TransactGetItems)
+//! - [`mod@repository`] - Typed repositories of the items of a table
+//! - `streams` - Typed change records of DynamoDB stream events delivered to Lambda (requires
+//!   the `lambda` feature)
+//! - [`mod@table`] - Table management operations (CreateTable, DeleteTable, DescribeTable, UpdateTable)
+//! - `testing` - Test fixture utilities (requires the `testing` feature)
+//! - [`mod@write`] - Write operations (PutItem, UpdateItem, DeleteItem, BatchWriteItem, TransactWriteItems)
+
+mod error;
+
+pub use error::Error;
+
+/// Analysis utilities for inspecting the data stored in DynamoDB tables.
+///
+/// This module provides scan-based reports for:
+/// - Finding hot partitions by item count and size per partition key
+/// - Profiling attribute presence, attribute types and item sizes
+/// - Inferring a Rust entity struct from existing data
+pub mod analysis;
 
-/// Common utilities for keys, conditions, and attribute selection.
+/// CloudFormation export of the tables storing items.
+///
+/// This module provides:
+/// - Generating the `AWS::DynamoDB::Table` resource of the table definition of an item
+#[cfg(feature = "cloudformation")]
+pub mod cloudformation;
+
+/// Code generation of typed table modules from DynamoDB table descriptions.
+///
+/// This module provides:
+/// - Generating table, key and index name constants, typed key structs and query helpers
+#[cfg(feature = "codegen")]
+pub mod codegen;
+
+/// Common utilities for keys, conditions, attribute selection, and value serialization.
 pub mod common;
 
+/// Append-only event streams stored in a DynamoDB table.
+///
+/// This module provides:
+/// - Appending events to a stream with optimistic concurrency on the stream version
+/// - Reading the events of a stream in order, from a given version
+pub mod events;
+
+/// Health checks of the connection to DynamoDB and of the tables used by an application.
+///
+/// This module provides:
+/// - Checking the endpoint and credentials of a client, and the status of tables, in one call
+pub mod health;
+
+/// PartiQL operations for reading and writing DynamoDB tables with SQL-compatible statements.
+///
+/// This module provides operations for:
+/// - Executing batches of statements, reporting the error of every failed statement
+/// - Executing statements atomically in a transaction, reporting why each statement failed
+pub mod partiql;
+
+/// Non-generic operations on raw attribute values and pre-built expressions.
+///
+/// This module provides operations for callers that already hold attribute values and
+/// expression strings, without the serde layer:
+/// - Getting, putting, updating and deleting items
+/// - Querying items, paginated like the typed query
+pub mod raw;
+
 /// Read operations for retrieving data from DynamoDB tables.
 ///
 /// This module provides operations for:
 /// - Getting individual items by key
 /// - Querying items with key conditions
 /// - Scanning entire tables
-/// - Batch retrieving multiple items
+/// - Batch retrieving multiple items, looked up by key in request order
+/// - Reading a consistent snapshot of multiple items in a transaction
 pub mod read;
 
+/// Typed repositories of the items bound to a table.
+///
+/// This module provides:
+/// - Getting, putting, patching and deleting items by their plain key values
+/// - Querying the items of a partition
+pub mod repository;
+
+/// Conversion of DynamoDB stream events into typed change records.
+///
+/// This module provides:
+/// - Deserializing the DynamoDB stream event payload of a Lambda trigger
+/// - Converting the DynamoDB JSON images of its records into typed entities
+#[cfg(feature = "lambda")]
+pub mod streams;
+
+/// Table management operations for provisioning DynamoDB tables.
+///
+/// This module provides operations for:
+/// - Creating tables with their key schema, secondary indexes, billing mode, encryption and
+///   streams
+/// - Deleting tables
+/// - Describing tables
+/// - Updating the throughput, billing mode, streams and global secondary indexes of tables
+/// - Waiting until tables are active or no longer exist
+pub mod table;
+
+/// Test fixture utilities for tables backed by DynamoDB or DynamoDB Local.
+///
+/// This module provides:
+/// - Seeding tables with typed or JSON items, returning a handle for cleanup
+/// - Seeding tables with random entities generated with the `fake` crate
+#[cfg(feature = "testing")]
+pub mod testing;
+
 /// Write operations for modifying data in DynamoDB tables.
 ///
 /// This module provides operations for:
@@ -92,4 +208,11 @@ pub mod read;
 /// - Updating items with various operations (set, add, remove)
 /// - Deleting items by key
 /// - Batch writing multiple items
+/// - Bulk putting items, routing conditional items through individual put item calls
+/// - Writing multiple items atomically in a transaction, guarded by condition checks
+/// - Enforcing unique attribute values with marker items written in the same transaction
+/// - Maintaining sparse indexes by setting and removing a flag attribute
+/// - Acquiring and releasing lease locks that expire after a time to live
+/// - Recording idempotency keys and caching the responses of processed requests
+/// - Using a table as a key-value store with expiring entries
 pub mod write;
@@ -0,0 +1,18 @@
+//! PartiQL operations for reading and writing DynamoDB tables with SQL-compatible statements.
+//!
+//! This module provides operations for:
+//! - Executing batches of statements, reporting the error of every failed statement
+//! - Executing statements atomically in a transaction, reporting why each statement failed
+//! - Building `SELECT` statements from typed fragments, binding every value to a parameter
+
+/// Batch execute statement operation for running many independent statements.
+pub mod batch_execute_statement;
+
+/// Common types for PartiQL operations.
+pub mod common;
+
+/// Execute transaction operation for running statements atomically.
+pub mod execute_transaction;
+
+/// Builder of statements assembled from typed fragments, safe from injection.
+pub mod safe_statement;
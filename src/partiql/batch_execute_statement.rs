@@ -0,0 +1,225 @@
+use crate::{common, common::retry::WithRetryPolicy as _, partiql};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::Serialize;
+use serde_dynamo::Result;
+use std::collections;
+
+/// Maximum number of statements DynamoDB accepts in a single BatchExecuteStatement call.
+const MAX_BATCH_STATEMENTS: usize = 25;
+
+/// Output of the batch execute statement operation, the outcome of every statement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchExecuteStatementOutput {
+    /// The capacity consumed by every batch execute statement call, if requested.
+    pub consumed_capacity: Vec<types::ConsumedCapacity>,
+    /// The outcome of every statement, in the order of the statements.
+    ///
+    /// Successful reads hold the item read, if any.
+    pub responses: Vec<
+        std::result::Result<
+            Option<collections::HashMap<String, types::AttributeValue>>,
+            types::BatchStatementError,
+        >,
+    >,
+}
+
+impl BatchExecuteStatementOutput {
+    /// Whether every statement was executed successfully.
+    pub fn is_success(&self) -> bool {
+        self.responses.iter().all(std::result::Result::is_ok)
+    }
+}
+
+/// Batch execute statement operation.
+///
+/// The statements are sent in chunks of at most 25, the limit of DynamoDB. Statements are
+/// independent: the failure of one is reported in the output without affecting the others.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::partiql;
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let batch_execute_statement = partiql::batch_execute_statement::BatchExecuteStatement {
+///     statements: vec![
+///         partiql::common::Statement {
+///             parameters: vec![json!("1"), json!("Jane")],
+///             statement: r#"INSERT INTO "users" VALUE {'id': ?, 'name': ?}"#.to_string(),
+///         },
+///     ],
+///     ..Default::default()
+/// };
+/// let output = batch_execute_statement.send(client).await?;
+/// for (index, response) in output.responses.iter().enumerate() {
+///     if let Err(error) = response {
+///         println!("statement {index} failed: {:?}", error.message());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchExecuteStatement<T> {
+    /// Whether to use a consistent read for the read statements.
+    pub consistent_read: Option<bool>,
+    /// Whether to return the consumed capacity information.
+    pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The statements to execute.
+    pub statements: Vec<partiql::common::Statement<T>>,
+}
+
+/// batch execute statement operation
+#[derive(Clone, Debug, Default, PartialEq)]
+struct BatchExecuteStatementInput {
+    return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    statements: Vec<types::BatchStatementRequest>,
+}
+
+impl BatchExecuteStatementInput {
+    fn new<T: Serialize>(
+        batch_execute_statement: BatchExecuteStatement<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let mut statements = Vec::with_capacity(batch_execute_statement.statements.len());
+        for statement in batch_execute_statement.statements {
+            let (statement, parameters) = statement.serialize(serializer)?;
+            let statement = types::BatchStatementRequest::builder()
+                .set_consistent_read(batch_execute_statement.consistent_read)
+                .set_parameters(parameters)
+                .statement(statement)
+                .build()
+                .unwrap();
+            statements.push(statement);
+        }
+        let operation = Self {
+            return_consumed_capacity: batch_execute_statement.return_consumed_capacity,
+            statements,
+        };
+        Ok(operation)
+    }
+}
+
+/// Get the outcome of a statement from its response.
+fn get_statement_result(
+    response: types::BatchStatementResponse,
+) -> std::result::Result<
+    Option<collections::HashMap<String, types::AttributeValue>>,
+    types::BatchStatementError,
+> {
+    match response.error {
+        Some(error) => Err(error),
+        None => Ok(response.item),
+    }
+}
+
+impl<T: Serialize> BatchExecuteStatement<T> {
+    /// Execute the batch execute statement operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> std::result::Result<BatchExecuteStatementOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the batch execute statement operation, converting the parameters with the
+    /// given serializer.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.batch_execute_statement", skip_all, err)
+    )]
+    pub async fn send_with(
+        self,
+        client: &Client,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> std::result::Result<BatchExecuteStatementOutput, crate::Error> {
+        let retry_policy = self.retry_policy.clone();
+        let batch_execute_statement = BatchExecuteStatementInput::new(self, serializer)
+            .map_err(crate::Error::Serialization)?;
+        let mut output = BatchExecuteStatementOutput::default();
+        for chunk in batch_execute_statement
+            .statements
+            .chunks(MAX_BATCH_STATEMENTS)
+        {
+            let chunk_output = client
+                .batch_execute_statement()
+                .set_return_consumed_capacity(
+                    batch_execute_statement.return_consumed_capacity.clone(),
+                )
+                .set_statements(Some(chunk.to_vec()))
+                .customize()
+                .with_retry_policy(client, retry_policy.as_ref())
+                .send()
+                .await?;
+            output
+                .consumed_capacity
+                .extend(chunk_output.consumed_capacity.unwrap_or_default());
+            output.responses.extend(
+                chunk_output
+                    .responses
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(get_statement_result),
+            );
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{Value, json};
+
+    #[test]
+    fn test_batch_execute_statement() {
+        let args: BatchExecuteStatement<Value> = BatchExecuteStatement {
+            consistent_read: Some(true),
+            return_consumed_capacity: Some(types::ReturnConsumedCapacity::Total),
+            retry_policy: None,
+            statements: vec![partiql::common::Statement {
+                parameters: vec![json!("a")],
+                statement: "b".to_string(),
+            }],
+        };
+        let actual =
+            BatchExecuteStatementInput::new(args, &common::serializer::SerdeDynamo).unwrap();
+        let expected = BatchExecuteStatementInput {
+            return_consumed_capacity: Some(types::ReturnConsumedCapacity::Total),
+            statements: vec![
+                types::BatchStatementRequest::builder()
+                    .consistent_read(true)
+                    .parameters(types::AttributeValue::S("a".to_string()))
+                    .statement("b")
+                    .build()
+                    .unwrap(),
+            ],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_statement_result() {
+        let error = types::BatchStatementError::builder()
+            .code(types::BatchStatementErrorCodeEnum::ConditionalCheckFailed)
+            .message("a")
+            .build();
+        let response = types::BatchStatementResponse::builder()
+            .error(error.clone())
+            .build();
+        assert_eq!(get_statement_result(response), Err(error));
+        let item = collections::HashMap::from([(
+            "b".to_string(),
+            types::AttributeValue::S("c".to_string()),
+        )]);
+        let response = types::BatchStatementResponse::builder()
+            .set_item(Some(item.clone()))
+            .build();
+        assert_eq!(get_statement_result(response), Ok(Some(item)));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::common;
+
+use aws_sdk_dynamodb::types;
+use serde::Serialize;
+use serde_dynamo::Result;
+
+/// A PartiQL statement along with its parameters.
+///
+/// ```rust
+/// use dynamodb_crud::partiql;
+/// use serde_json::json;
+///
+/// let statement = partiql::common::Statement {
+///     parameters: vec![json!("Jane"), json!("1")],
+///     statement: r#"UPDATE "users" SET name = ? WHERE id = ?"#.to_string(),
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Statement<T> {
+    /// The values bound to the `?` placeholders of the statement, in order.
+    pub parameters: Vec<T>,
+    /// The PartiQL statement.
+    pub statement: String,
+}
+
+impl<T: Serialize> Statement<T> {
+    /// Convert the parameters with the given serializer, returning the statement along with
+    /// them, or `None` if there are no parameters.
+    pub(crate) fn serialize(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<(String, Option<Vec<types::AttributeValue>>)> {
+        if self.parameters.is_empty() {
+            return Ok((self.statement, None));
+        }
+        let parameters = self
+            .parameters
+            .into_iter()
+            .map(|parameter| serializer.to_attribute_value(parameter))
+            .collect::<Result<_>>()?;
+        Ok((self.statement, Some(parameters)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    #[rstest]
+    #[case::without_parameters(
+        Statement {
+            parameters: Vec::new(),
+            statement: "a".to_string(),
+        },
+        ("a".to_string(), None)
+    )]
+    #[case::with_parameters(
+        Statement {
+            parameters: vec![
+                json!("b"),
+                json!(1),
+            ],
+            statement: "a".to_string(),
+        },
+        (
+            "a".to_string(),
+            Some(
+                vec![
+                    types::AttributeValue::S(
+                        "b".to_string()
+                    ),
+                    types::AttributeValue::N(
+                        "1".to_string()
+                    ),
+                ]
+            )
+        )
+    )]
+    fn test_statement(
+        #[case] statement: Statement<Value>,
+        #[case] expected: (String, Option<Vec<types::AttributeValue>>),
+    ) {
+        let actual = statement
+            .serialize(&common::serializer::SerdeDynamo)
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{common, common::retry::WithRetryPolicy as _, partiql};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+use serde::Serialize;
+use serde_dynamo::Result;
+
+/// Execute transaction operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{Error, partiql};
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let execute_transaction = partiql::execute_transaction::ExecuteTransaction {
+///     transact_statements: vec![
+///         partiql::common::Statement {
+///             parameters: vec![json!(10), json!("1")],
+///             statement: r#"UPDATE "accounts" SET balance = balance - ? WHERE id = ?"#
+///                 .to_string(),
+///         },
+///         partiql::common::Statement {
+///             parameters: vec![json!(10), json!("2")],
+///             statement: r#"UPDATE "accounts" SET balance = balance + ? WHERE id = ?"#
+///                 .to_string(),
+///         },
+///     ],
+///     ..Default::default()
+/// };
+/// match execute_transaction.send(client).await {
+///     Ok(_) => {}
+///     Err(Error::TransactionCanceled(reasons)) => {
+///         for (index, reason) in reasons.iter().enumerate() {
+///             if reason.code() != Some("None") {
+///                 println!("statement {index} failed: {:?}", reason.message());
+///             }
+///         }
+///     }
+///     Err(error) => return Err(error.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecuteTransaction<T> {
+    /// The idempotency token of the transaction.
+    pub client_request_token: Option<String>,
+    /// Whether to return the consumed capacity information.
+    pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The statements executed atomically (up to 100).
+    pub transact_statements: Vec<partiql::common::Statement<T>>,
+}
+
+fn get_execute_transaction_input<T: Serialize>(
+    execute_transaction: ExecuteTransaction<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> Result<operation::execute_transaction::ExecuteTransactionInput> {
+    let mut transact_statements = Vec::with_capacity(execute_transaction.transact_statements.len());
+    for statement in execute_transaction.transact_statements {
+        let (statement, parameters) = statement.serialize(serializer)?;
+        let statement = types::ParameterizedStatement::builder()
+            .set_parameters(parameters)
+            .statement(statement)
+            .build()
+            .unwrap();
+        transact_statements.push(statement);
+    }
+    let input = operation::execute_transaction::ExecuteTransactionInput::builder()
+        .set_client_request_token(execute_transaction.client_request_token)
+        .set_return_consumed_capacity(execute_transaction.return_consumed_capacity)
+        .set_transact_statements(Some(transact_statements))
+        .build()
+        .unwrap();
+    Ok(input)
+}
+
+impl<T: Serialize> TryFrom<ExecuteTransaction<T>>
+    for operation::execute_transaction::ExecuteTransactionInput
+{
+    type Error = serde_dynamo::Error;
+
+    fn try_from(execute_transaction: ExecuteTransaction<T>) -> Result<Self> {
+        get_execute_transaction_input(execute_transaction, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T: Serialize> ExecuteTransaction<T> {
+    /// Execute the execute transaction operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> std::result::Result<operation::execute_transaction::ExecuteTransactionOutput, crate::Error>
+    {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the execute transaction operation, converting the parameters with the given
+    /// serializer.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.execute_transaction", skip_all, err)
+    )]
+    pub async fn send_with(
+        self,
+        client: &Client,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> std::result::Result<operation::execute_transaction::ExecuteTransactionOutput, crate::Error>
+    {
+        let retry_policy = self.retry_policy.clone();
+        let execute_transaction =
+            get_execute_transaction_input(self, serializer).map_err(crate::Error::Serialization)?;
+        client
+            .execute_transaction()
+            .set_client_request_token(execute_transaction.client_request_token)
+            .set_return_consumed_capacity(execute_transaction.return_consumed_capacity)
+            .set_transact_statements(execute_transaction.transact_statements)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{Value, json};
+
+    #[test]
+    fn test_execute_transaction() {
+        let args: ExecuteTransaction<Value> = ExecuteTransaction {
+            client_request_token: Some("a".to_string()),
+            return_consumed_capacity: None,
+            retry_policy: None,
+            transact_statements: vec![
+                partiql::common::Statement {
+                    parameters: vec![json!("b")],
+                    statement: "c".to_string(),
+                },
+                partiql::common::Statement {
+                    parameters: Vec::new(),
+                    statement: "d".to_string(),
+                },
+            ],
+        };
+        let actual: operation::execute_transaction::ExecuteTransactionInput =
+            args.try_into().unwrap();
+        let expected = operation::execute_transaction::ExecuteTransactionInput::builder()
+            .client_request_token("a")
+            .transact_statements(
+                types::ParameterizedStatement::builder()
+                    .parameters(types::AttributeValue::S("b".to_string()))
+                    .statement("c")
+                    .build()
+                    .unwrap(),
+            )
+            .transact_statements(
+                types::ParameterizedStatement::builder()
+                    .statement("d")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{common, partiql};
+
+use std::fmt::Write;
+
+/// Builder of a PartiQL `SELECT` statement assembled from typed fragments.
+///
+/// Identifiers are always double-quoted and escaped, and every value is bound to a `?`
+/// parameter, so user input never becomes part of the statement text. The conditions are
+/// combined with `AND`.
+///
+/// ```rust
+/// use dynamodb_crud::{common::condition, partiql};
+/// use serde_json::Value;
+///
+/// let statement: partiql::common::Statement<Value> =
+///     partiql::safe_statement::SafeStatement::select("users")
+///         .index("byStatus")
+///         .project(["id", "name"])
+///         .equals("status", "active")
+///         .condition("age", condition::Condition::GreaterThanOrEqual(Value::from(18)))
+///         .build();
+/// assert_eq!(
+///     statement.statement,
+///     r#"SELECT "id", "name" FROM "users"."byStatus" WHERE "status" = ? AND "age" >= ?"#,
+/// );
+/// assert_eq!(statement.parameters, vec![Value::from("active"), Value::from(18)]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SafeStatement<T> {
+    conditions: Vec<common::condition::KeyCondition<T>>,
+    index_name: Option<String>,
+    projection: Vec<String>,
+    table_name: String,
+}
+
+impl<T> SafeStatement<T> {
+    /// Start building a statement selecting items of the given table.
+    pub fn select(table_name: impl Into<String>) -> Self {
+        Self {
+            conditions: Vec::new(),
+            index_name: None,
+            projection: Vec::new(),
+            table_name: table_name.into(),
+        }
+    }
+
+    /// Select from a secondary index instead of the table.
+    pub fn index(mut self, index_name: impl Into<String>) -> Self {
+        self.index_name = Some(index_name.into());
+        self
+    }
+
+    /// Select only the given attributes, along with any previously projected ones, instead of
+    /// whole items.
+    pub fn project(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.projection.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Match the items where the attribute satisfies the condition, combined with any previous
+    /// condition.
+    pub fn condition(
+        mut self,
+        name: impl Into<String>,
+        condition: common::condition::Condition<T>,
+    ) -> Self {
+        self.conditions.push(common::condition::KeyCondition {
+            condition,
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Match the items where the attribute equals the value.
+    pub fn equals(self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.condition(name, common::condition::Condition::Equals(value.into()))
+    }
+}
+
+impl<T: From<String>> SafeStatement<T> {
+    /// Build the statement, along with its parameters in order.
+    ///
+    /// Prefixes and attribute types are bound as string parameters too.
+    pub fn build(self) -> partiql::common::Statement<T> {
+        let mut statement = String::from("SELECT ");
+        if self.projection.is_empty() {
+            statement.push('*');
+        }
+        for (index, name) in self.projection.iter().enumerate() {
+            if index > 0 {
+                statement.push_str(", ");
+            }
+            push_identifier(&mut statement, name);
+        }
+        statement.push_str(" FROM ");
+        push_identifier(&mut statement, &self.table_name);
+        if let Some(index_name) = &self.index_name {
+            statement.push('.');
+            push_identifier(&mut statement, index_name);
+        }
+        let mut parameters = Vec::with_capacity(self.conditions.len());
+        for (index, key_condition) in self.conditions.into_iter().enumerate() {
+            statement.push_str(if index > 0 { " AND " } else { " WHERE " });
+            push_condition(&mut statement, &mut parameters, key_condition);
+        }
+        partiql::common::Statement {
+            parameters,
+            statement,
+        }
+    }
+}
+
+/// Push an identifier, double-quoted with its double quotes escaped.
+fn push_identifier(statement: &mut String, identifier: &str) {
+    statement.push('"');
+    statement.push_str(&identifier.replace('"', "\"\""));
+    statement.push('"');
+}
+
+/// Push the predicate of a condition, binding its values to parameters.
+fn push_condition<T: From<String>>(
+    statement: &mut String,
+    parameters: &mut Vec<T>,
+    key_condition: common::condition::KeyCondition<T>,
+) {
+    use common::condition::{Comparator, Condition};
+
+    let mut attribute = String::new();
+    push_identifier(&mut attribute, &key_condition.name);
+    let (comparator, value) = match key_condition.condition {
+        Condition::AttributeType(attribute_type) => {
+            let _ = write!(statement, "attribute_type({attribute}, ?)");
+            parameters.push(T::from(attribute_type.to_string()));
+            return;
+        }
+        Condition::BeginsWith(prefix) => {
+            let _ = write!(statement, "begins_with({attribute}, ?)");
+            parameters.push(T::from(prefix));
+            return;
+        }
+        Condition::Between(low, high) => {
+            let _ = write!(statement, "{attribute} BETWEEN ? AND ?");
+            parameters.extend([low, high]);
+            return;
+        }
+        Condition::Contains(value) => {
+            let _ = write!(statement, "contains({attribute}, ?)");
+            parameters.push(value);
+            return;
+        }
+        Condition::In(values) => {
+            let _ = write!(statement, "{attribute} IN [");
+            for index in 0..values.len() {
+                statement.push_str(if index > 0 { ", ?" } else { "?" });
+            }
+            statement.push(']');
+            parameters.extend(values);
+            return;
+        }
+        Condition::NotContains(value) => {
+            let _ = write!(statement, "NOT contains({attribute}, ?)");
+            parameters.push(value);
+            return;
+        }
+        Condition::NotNull => {
+            let _ = write!(statement, "{attribute} IS NOT MISSING");
+            return;
+        }
+        Condition::Null => {
+            let _ = write!(statement, "{attribute} IS MISSING");
+            return;
+        }
+        Condition::SizeCompare(comparator, value) => {
+            attribute = format!("size({attribute})");
+            (comparator, value)
+        }
+        Condition::Equals(value) => (Comparator::Equals, value),
+        Condition::GreaterThan(value) => (Comparator::GreaterThan, value),
+        Condition::GreaterThanOrEqual(value) => (Comparator::GreaterThanOrEqual, value),
+        Condition::LessThan(value) => (Comparator::LessThan, value),
+        Condition::LessThanOrEqual(value) => (Comparator::LessThanOrEqual, value),
+        Condition::NotEqual(value) => (Comparator::NotEqual, value),
+    };
+    let comparator: &str = &comparator;
+    let _ = write!(statement, "{attribute} {comparator} ?");
+    parameters.push(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::condition::{AttributeType, Comparator, Condition};
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    #[rstest]
+    #[case::attribute_type(
+        Condition::AttributeType(AttributeType::List),
+        r#"attribute_type("a", ?)"#,
+        vec![json!("L")]
+    )]
+    #[case::begins_with(
+        Condition::BeginsWith("b".to_string()),
+        r#"begins_with("a", ?)"#,
+        vec![json!("b")]
+    )]
+    #[case::between(
+        Condition::Between(json!(1), json!(2)),
+        r#""a" BETWEEN ? AND ?"#,
+        vec![json!(1), json!(2)]
+    )]
+    #[case::contains(Condition::Contains(json!("b")), r#"contains("a", ?)"#, vec![json!("b")])]
+    #[case::equals(Condition::Equals(json!(1)), r#""a" = ?"#, vec![json!(1)])]
+    #[case::greater_than(Condition::GreaterThan(json!(1)), r#""a" > ?"#, vec![json!(1)])]
+    #[case::in_values(
+        Condition::In(vec![json!(1), json!(2)]),
+        r#""a" IN [?, ?]"#,
+        vec![json!(1), json!(2)]
+    )]
+    #[case::not_contains(
+        Condition::NotContains(json!("b")),
+        r#"NOT contains("a", ?)"#,
+        vec![json!("b")]
+    )]
+    #[case::not_equal(Condition::NotEqual(json!(1)), r#""a" <> ?"#, vec![json!(1)])]
+    #[case::not_null(Condition::NotNull, r#""a" IS NOT MISSING"#, vec![])]
+    #[case::null(Condition::Null, r#""a" IS MISSING"#, vec![])]
+    #[case::size_compare(
+        Condition::SizeCompare(Comparator::LessThan, json!(3)),
+        r#"size("a") < ?"#,
+        vec![json!(3)]
+    )]
+    fn test_safe_statement_condition(
+        #[case] condition: Condition<Value>,
+        #[case] expected_predicate: &str,
+        #[case] expected_parameters: Vec<Value>,
+    ) {
+        let actual = SafeStatement::select("b").condition("a", condition).build();
+        let expected = partiql::common::Statement {
+            parameters: expected_parameters,
+            statement: format!(r#"SELECT * FROM "b" WHERE {expected_predicate}"#),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_safe_statement_escapes_identifiers() {
+        let actual: partiql::common::Statement<Value> = SafeStatement::select(r#"a" WHERE 1=1 --"#)
+            .project([r#"b""c"#])
+            .equals(r#"d" OR "e"#, "' OR 1=1")
+            .build();
+        let expected = partiql::common::Statement {
+            parameters: vec![json!("' OR 1=1")],
+            statement: r#"SELECT "b""""c" FROM "a"" WHERE 1=1 --" WHERE "d"" OR ""e" = ?"#
+                .to_string(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,380 @@
+//! Non-generic operations on raw attribute values and pre-built expressions.
+//!
+//! This module provides operations for:
+//! - Putting, updating, deleting and getting items given as attribute value maps
+//! - Querying with pre-built key condition, filter and projection expressions
+//!
+//! They skip the typed expression builders, for callers that already hold DynamoDB
+//! expressions, such as ones migrated from other clients.
+
+use crate::{common, common::retry::WithRetryPolicy as _, read, write};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+use indexmap::IndexMap;
+use std::{collections, hash, time};
+
+/// Keep a map only if it is not empty, as DynamoDB rejects empty expression maps.
+fn get_non_empty<K: Eq + hash::Hash, V>(map: IndexMap<K, V>) -> Option<collections::HashMap<K, V>> {
+    (!map.is_empty()).then(|| map.into_iter().collect())
+}
+
+/// Arguments of the raw write operations, with a pre-built condition expression.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawWriteArgs {
+    /// Condition expression that must be true for the operation to succeed.
+    pub condition_expression: Option<String>,
+    /// The placeholders of the attribute names used in the expressions.
+    pub expression_attribute_names: IndexMap<String, String>,
+    /// The placeholders of the values used in the expressions.
+    pub expression_attribute_values: IndexMap<String, types::AttributeValue>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// Which item attributes to return in the response.
+    pub return_values: Option<types::ReturnValue>,
+    /// The name of the table to write to.
+    pub table_name: String,
+}
+
+impl From<RawWriteArgs> for write::common::WriteInput {
+    fn from(write_args: RawWriteArgs) -> Self {
+        Self {
+            condition_expression: write_args.condition_expression,
+            expression_attribute_names: get_non_empty(write_args.expression_attribute_names),
+            expression_attribute_values: get_non_empty(write_args.expression_attribute_values),
+            return_values: write_args.return_values,
+            table_name: write_args.table_name,
+            ..Default::default()
+        }
+    }
+}
+
+/// Put item operation on an item of raw attribute values.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::{Client, types::AttributeValue};
+/// use dynamodb_crud::raw;
+/// use std::collections::HashMap;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let put_item = raw::RawPutItem {
+///     item: HashMap::from([("id".to_string(), AttributeValue::S("1".to_string()))]),
+///     write_args: raw::RawWriteArgs {
+///         condition_expression: Some("attribute_not_exists(id)".to_string()),
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// put_item.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawPutItem {
+    /// The item to put into the table.
+    pub item: collections::HashMap<String, types::AttributeValue>,
+    /// Additional write operation arguments (table name, condition, return values, etc.).
+    pub write_args: RawWriteArgs,
+}
+
+impl RawPutItem {
+    /// Execute the put item operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.raw.put_item", skip_all, err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::put_item::PutItemOutput, crate::Error> {
+        let retry_policy = self.write_args.retry_policy.clone();
+        let write_operation = write::common::WriteInput::from(self.write_args);
+        let builder = client.put_item().set_item(Some(self.item));
+        crate::apply_write_operation!(builder, write_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+/// Update item operation with a pre-built update expression.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawUpdateItem {
+    /// The primary key of the item to update.
+    pub keys: collections::HashMap<String, types::AttributeValue>,
+    /// The update expression specifying what changes to make.
+    pub update_expression: String,
+    /// Additional write operation arguments (table name, condition, return values, etc.).
+    pub write_args: RawWriteArgs,
+}
+
+impl RawUpdateItem {
+    /// Execute the update item operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.raw.update_item", skip_all, err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::update_item::UpdateItemOutput, crate::Error> {
+        let retry_policy = self.write_args.retry_policy.clone();
+        let write_operation = write::common::WriteInput::from(self.write_args);
+        let builder = client
+            .update_item()
+            .set_key(Some(self.keys))
+            .update_expression(self.update_expression);
+        crate::apply_write_operation!(builder, write_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+/// Delete item operation on a key of raw attribute values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawDeleteItem {
+    /// The primary key of the item to delete.
+    pub keys: collections::HashMap<String, types::AttributeValue>,
+    /// Additional write operation arguments (table name, condition, return values, etc.).
+    pub write_args: RawWriteArgs,
+}
+
+impl RawDeleteItem {
+    /// Execute the delete item operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.raw.delete_item", skip_all, err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::delete_item::DeleteItemOutput, crate::Error> {
+        let retry_policy = self.write_args.retry_policy.clone();
+        let write_operation = write::common::WriteInput::from(self.write_args);
+        let builder = client.delete_item().set_key(Some(self.keys));
+        crate::apply_write_operation!(builder, write_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+/// Get item operation on a key of raw attribute values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawGetItem {
+    /// Whether to use a consistent read.
+    pub consistent_read: Option<bool>,
+    /// The placeholders of the attribute names used in the projection expression.
+    pub expression_attribute_names: IndexMap<String, String>,
+    /// The primary key of the item to retrieve.
+    pub keys: collections::HashMap<String, types::AttributeValue>,
+    /// Which attributes to retrieve. If `None`, all attributes are retrieved.
+    pub projection_expression: Option<String>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The name of the table to read from.
+    pub table_name: String,
+}
+
+impl RawGetItem {
+    /// Execute the get item operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.raw.get_item", skip_all, err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::get_item::GetItemOutput, crate::Error> {
+        let single_read_operation = read::common::SingleReadInput {
+            consistent_read: self.consistent_read,
+            expression_attribute_names: get_non_empty(self.expression_attribute_names),
+            projection_expression: self.projection_expression,
+            table_name: self.table_name,
+        };
+        let builder = client.get_item().set_key(Some(self.keys));
+        crate::apply_single_read_operation!(builder, single_read_operation)
+            .customize()
+            .with_retry_policy(client, self.retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+/// Query operation with pre-built key condition, filter and projection expressions.
+///
+/// Every page is fetched, up to the maximum number of pages or the deadline, if any.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::{Client, types::AttributeValue};
+/// use dynamodb_crud::raw;
+/// use indexmap::IndexMap;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let query = raw::RawQuery {
+///     expression_attribute_values: IndexMap::from([(
+///         ":id".to_string(),
+///         AttributeValue::S("1".to_string()),
+///     )]),
+///     key_condition_expression: "userId = :id".to_string(),
+///     table_name: "orders".to_string(),
+///     ..Default::default()
+/// };
+/// let output = query.send(client).await?;
+/// println!("{} orders", output.count);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawQuery {
+    /// Whether to use a consistent read.
+    pub consistent_read: Option<bool>,
+    /// The instant after which no further page is fetched.
+    pub deadline: Option<time::Instant>,
+    /// The primary key of the item to continue a previous query from.
+    pub exclusive_start_key: Option<collections::HashMap<String, types::AttributeValue>>,
+    /// The placeholders of the attribute names used in the expressions.
+    pub expression_attribute_names: IndexMap<String, String>,
+    /// The placeholders of the values used in the expressions.
+    pub expression_attribute_values: IndexMap<String, types::AttributeValue>,
+    /// Filter expression applied to the matching items.
+    pub filter_expression: Option<String>,
+    /// The name of a secondary index to query instead of the table.
+    pub index_name: Option<String>,
+    /// The key condition expression.
+    pub key_condition_expression: String,
+    /// The maximum number of items to evaluate per page.
+    pub limit: Option<i32>,
+    /// The maximum number of matching items to collect across pages.
+    pub max_items: Option<usize>,
+    /// The maximum number of pages to fetch, and what to do once reached.
+    pub max_pages: Option<read::common::MaxPages>,
+    /// Which attributes to retrieve. If `None`, all attributes are retrieved.
+    pub projection_expression: Option<String>,
+    /// Retry policy overriding the retry configuration of the client for every page.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// Whether to scan the index forward (ascending) or backward (descending).
+    pub scan_index_forward: Option<bool>,
+    /// The name of the table to read from.
+    pub table_name: String,
+}
+
+impl From<RawQuery> for read::common::MultipleReadInput {
+    fn from(query: RawQuery) -> Self {
+        Self {
+            consistent_read: query.consistent_read,
+            deadline: query.deadline,
+            exclusive_start_key: query.exclusive_start_key,
+            expression_attribute_names: get_non_empty(query.expression_attribute_names),
+            expression_attribute_values: get_non_empty(query.expression_attribute_values),
+            filter_expression: query.filter_expression,
+            index_name: query.index_name,
+            limit: query.limit,
+            max_items: query.max_items,
+            max_pages: query.max_pages,
+            projection_expression: query.projection_expression,
+            prune_empty_maps: false,
+            retry_policy: query.retry_policy,
+            select: None,
+            table_name: query.table_name,
+        }
+    }
+}
+
+impl RawQuery {
+    /// Execute the query operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.raw.query", skip_all, err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::query::QueryOutput, crate::Error> {
+        let key_condition_expression = self.key_condition_expression.clone();
+        let scan_index_forward = self.scan_index_forward;
+        let multiple_read_operation = read::common::MultipleReadInput::from(self);
+        let deadline = multiple_read_operation.deadline;
+        let max_items = multiple_read_operation.max_items;
+        let max_pages = multiple_read_operation.max_pages;
+        let builder = client
+            .query()
+            .key_condition_expression(key_condition_expression)
+            .set_scan_index_forward(scan_index_forward);
+        let builder = crate::apply_multiple_read_operation!(builder, multiple_read_operation);
+        let mut paginator = read::common::Pages::new(
+            builder,
+            client,
+            max_items,
+            multiple_read_operation.retry_policy,
+        );
+        crate::get_paginated_output!(
+            paginator,
+            operation::query::QueryOutput,
+            max_pages,
+            deadline,
+            max_items
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_write_args() {
+        let write_args = RawWriteArgs {
+            condition_expression: Some("a = :b".to_string()),
+            expression_attribute_values: IndexMap::from([(
+                ":b".to_string(),
+                types::AttributeValue::S("c".to_string()),
+            )]),
+            table_name: "d".to_string(),
+            ..Default::default()
+        };
+        let actual = write::common::WriteInput::from(write_args);
+        let expected = write::common::WriteInput {
+            condition_expression: Some("a = :b".to_string()),
+            expression_attribute_names: None,
+            expression_attribute_values: Some(collections::HashMap::from([(
+                ":b".to_string(),
+                types::AttributeValue::S("c".to_string()),
+            )])),
+            table_name: "d".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_raw_query() {
+        let query = RawQuery {
+            expression_attribute_names: IndexMap::from([("#a".to_string(), "a".to_string())]),
+            index_name: Some("b".to_string()),
+            key_condition_expression: "#a = :c".to_string(),
+            limit: Some(1),
+            table_name: "d".to_string(),
+            ..Default::default()
+        };
+        let actual = read::common::MultipleReadInput::from(query);
+        let expected = read::common::MultipleReadInput {
+            expression_attribute_names: Some(collections::HashMap::from([(
+                "#a".to_string(),
+                "a".to_string(),
+            )])),
+            index_name: Some("b".to_string()),
+            limit: Some(1),
+            table_name: "d".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -4,7 +4,16 @@
 //! - Getting individual items by primary key
 //! - Querying items with key conditions
 //! - Scanning entire tables
-//! - Batch retrieving multiple items
+//! - Batch retrieving multiple items, looked up by key in request order
+//! - Reading a consistent snapshot of multiple items in a transaction
+//! - Coalescing concurrent single-item reads into batch reads
+//! - Sending query and scan results into bounded channels as pages arrive
+//! - Fetching only the keys of the items matching a query
+//! - Deserializing query and scan results into typed items
+//! - Streaming query and scan items lazily, page by page
+//! - Reading query and scan results page by page with a typed pagination key
+//! - Grouping query and scan items by the value of an attribute
+//! - Decoding heterogeneous query items into an enum of entity types
 
 /// Batch get item operation for retrieving multiple items efficiently.
 pub mod batch_get_item;
@@ -15,8 +24,14 @@ pub mod common;
 /// Get item operation for retrieving a single item by primary key.
 pub mod get_item;
 
+/// Loader coalescing concurrent get item requests into batch calls.
+pub mod loader;
+
 /// Query operation for retrieving items with key conditions.
 pub mod query;
 
 /// Scan operation for retrieving all items from a table.
 pub mod scan;
+
+/// Transact get items operation for reading multiple items atomically.
+pub mod transact_get_items;
@@ -1,13 +1,28 @@
-use crate::{common, read};
+use crate::{common, common::retry::WithRetryPolicy as _, read};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
+use aws_sdk_dynamodb::{Client, operation, types};
 use indexmap::IndexMap;
 use serde::Serialize;
 use serde_dynamo::{Error, Result};
-use std::collections;
+use std::{collections, time};
+
+/// Maximum number of keys DynamoDB accepts in a single BatchGetItem call.
+const MAX_BATCH_GET_KEYS: usize = 100;
+
+/// Backoff of the retries of unprocessed keys, unless overridden by a retry policy.
+pub(crate) const BATCH_GET_BACKOFF: common::retry::Backoff = common::retry::Backoff {
+    initial_backoff: time::Duration::from_millis(50),
+    max_attempts: 5,
+    max_backoff: time::Duration::from_secs(5),
+};
 
 /// Batch get item operation.
 ///
+/// The keys are requested in chunks of at most 100, the limit of DynamoDB, and the keys
+/// left unprocessed, for example once a response reaches 16 MB, are requested again with
+/// exponential backoff and jitter. The responses of every call are merged into a single
+/// output, whose unprocessed keys are the ones still unprocessed after the last attempt.
+///
 /// ```rust,no_run
 /// use aws_sdk_dynamodb::Client;
 /// use dynamodb_crud::{common, read};
@@ -42,59 +57,325 @@ pub struct BatchGetItem<T> {
     pub items: IndexMap<read::common::SingleReadArgs, Vec<common::key::Keys<T>>>,
     /// Whether to return the consumed capacity information.
     pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    ///
+    /// Its backoff and maximum number of attempts also apply to the retries of
+    /// unprocessed keys.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+}
+
+fn get_batch_get_item_input<T: Serialize>(
+    batch_get_item: BatchGetItem<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> Result<operation::batch_get_item::BatchGetItemInput> {
+    let mut request_items = collections::HashMap::with_capacity(batch_get_item.items.len());
+    for (args, keys) in batch_get_item.items {
+        let single_operation: read::common::SingleReadInput = args.into();
+        let mut serialized_keys = Vec::with_capacity(keys.len());
+        for key in keys {
+            let key = key.serialize(serializer)?;
+            serialized_keys.push(key);
+        }
+        let keys_and_attributes = types::KeysAndAttributes::builder()
+            .set_consistent_read(single_operation.consistent_read)
+            .set_expression_attribute_names(single_operation.expression_attribute_names)
+            .set_keys(Some(serialized_keys))
+            .set_projection_expression(single_operation.projection_expression)
+            .build()
+            .unwrap();
+        request_items.insert(single_operation.table_name, keys_and_attributes);
+    }
+    let input = operation::batch_get_item::BatchGetItemInput::builder()
+        .set_request_items(Some(request_items))
+        .set_return_consumed_capacity(batch_get_item.return_consumed_capacity)
+        .build()
+        .unwrap();
+    Ok(input)
 }
 
 impl<T: Serialize> TryFrom<BatchGetItem<T>> for operation::batch_get_item::BatchGetItemInput {
     type Error = Error;
 
     fn try_from(batch_get_item: BatchGetItem<T>) -> Result<Self> {
-        let mut request_items = collections::HashMap::with_capacity(batch_get_item.items.len());
-        for (args, keys) in batch_get_item.items {
-            let single_operation: read::common::SingleReadInput = args.into();
-            let mut serialized_keys = Vec::with_capacity(keys.len());
-            for key in keys {
-                let key = key.try_into()?;
-                serialized_keys.push(key);
+        get_batch_get_item_input(batch_get_item, &common::serializer::SerdeDynamo)
+    }
+}
+
+/// Split the request items into chunks of at most 100 keys, keeping the read arguments of
+/// every table.
+fn get_chunks(
+    request_items: collections::HashMap<String, types::KeysAndAttributes>,
+) -> Vec<collections::HashMap<String, types::KeysAndAttributes>> {
+    let mut chunks = Vec::new();
+    let mut chunk = collections::HashMap::new();
+    let mut chunk_len = 0;
+    for (table_name, keys_and_attributes) in request_items {
+        let mut keys = keys_and_attributes.keys.clone().into_iter().peekable();
+        while keys.peek().is_some() {
+            let mut table_keys_and_attributes = keys_and_attributes.clone();
+            table_keys_and_attributes.keys =
+                keys.by_ref().take(MAX_BATCH_GET_KEYS - chunk_len).collect();
+            chunk_len += table_keys_and_attributes.keys.len();
+            chunk.insert(table_name.clone(), table_keys_and_attributes);
+            if chunk_len == MAX_BATCH_GET_KEYS {
+                chunks.push(std::mem::take(&mut chunk));
+                chunk_len = 0;
+            }
+        }
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Add the keys of the request items to the merged ones.
+fn merge_request_items(
+    merged_request_items: &mut collections::HashMap<String, types::KeysAndAttributes>,
+    request_items: collections::HashMap<String, types::KeysAndAttributes>,
+) {
+    for (table_name, keys_and_attributes) in request_items {
+        match merged_request_items.entry(table_name) {
+            collections::hash_map::Entry::Occupied(mut entry) => {
+                entry.get_mut().keys.extend(keys_and_attributes.keys);
+            }
+            collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(keys_and_attributes);
             }
-            let keys_and_attributes = types::KeysAndAttributes::builder()
-                .set_consistent_read(single_operation.consistent_read)
-                .set_expression_attribute_names(single_operation.expression_attribute_names)
-                .set_keys(Some(serialized_keys))
-                .set_projection_expression(single_operation.projection_expression)
-                .build()
-                .unwrap();
-            request_items.insert(single_operation.table_name, keys_and_attributes);
         }
-        let input = Self::builder()
-            .set_request_items(Some(request_items))
-            .set_return_consumed_capacity(batch_get_item.return_consumed_capacity)
-            .build()
-            .unwrap();
-        Ok(input)
+    }
+}
+
+/// Get the items of the request in chunks, retrying unprocessed keys and merging the
+/// responses.
+///
+/// The capacity consumed by every call is merged into one consumed capacity per table.
+async fn get_items_in_chunks(
+    client: &Client,
+    batch_get_item: operation::batch_get_item::BatchGetItemInput,
+    retry_policy: Option<&common::retry::RetryPolicy>,
+    backoff: common::retry::Backoff,
+) -> Result<operation::batch_get_item::BatchGetItemOutput, crate::Error> {
+    let mut consumed_capacity = common::capacity::CapacityBreakdown::default();
+    let mut responses: collections::HashMap<String, Vec<_>> = collections::HashMap::new();
+    let mut unprocessed_keys = collections::HashMap::new();
+    for chunk in get_chunks(batch_get_item.request_items.unwrap_or_default()) {
+        let mut pending_keys = chunk;
+        let mut attempt = 0;
+        loop {
+            let output = client
+                .batch_get_item()
+                .set_request_items(Some(pending_keys))
+                .set_return_consumed_capacity(batch_get_item.return_consumed_capacity.clone())
+                .customize()
+                .with_retry_policy(client, retry_policy)
+                .send()
+                .await?;
+            consumed_capacity.extend(output.consumed_capacity.unwrap_or_default());
+            for (table_name, items) in output.responses.unwrap_or_default() {
+                responses.entry(table_name).or_default().extend(items);
+            }
+            pending_keys = output.unprocessed_keys.unwrap_or_default();
+            pending_keys.retain(|_, keys_and_attributes| !keys_and_attributes.keys.is_empty());
+            attempt += 1;
+            if pending_keys.is_empty() || attempt >= backoff.max_attempts {
+                break;
+            }
+            backoff.sleep("BatchGetItem", attempt).await;
+        }
+        merge_request_items(&mut unprocessed_keys, pending_keys);
+    }
+    let output = operation::batch_get_item::BatchGetItemOutput::builder()
+        .set_consumed_capacity(
+            (!consumed_capacity.is_empty()).then(|| consumed_capacity.to_consumed_capacities()),
+        )
+        .set_responses(Some(responses))
+        .set_unprocessed_keys(Some(unprocessed_keys))
+        .build();
+    Ok(output)
+}
+
+/// Get the requested keys of every table, in the order of the tables and of their keys.
+fn get_requested_keys(
+    table_names: impl IntoIterator<Item = String>,
+    mut request_items: collections::HashMap<String, types::KeysAndAttributes>,
+) -> Vec<(String, collections::HashMap<String, types::AttributeValue>)> {
+    let mut requested_keys = Vec::new();
+    for table_name in table_names {
+        if let Some(keys_and_attributes) = request_items.remove(&table_name) {
+            let keys = keys_and_attributes.keys.into_iter();
+            requested_keys.extend(keys.map(|keys| (table_name.clone(), keys)));
+        }
+    }
+    requested_keys
+}
+
+/// Output of the batch get item operation, along with the requested keys.
+///
+/// DynamoDB returns the items of every table in arbitrary order: look them up by key, or
+/// iterate over them in the order they were requested.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read};
+/// use indexmap::IndexMap;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let keys = ["1", "2"].map(|id| common::key::Keys {
+///     partition_key: common::key::Key {
+///         name: "id".to_string(),
+///         value: id.to_string(),
+///     },
+///     ..Default::default()
+/// });
+/// let batch_get = read::batch_get_item::BatchGetItem {
+///     items: IndexMap::from([(
+///         read::common::SingleReadArgs {
+///             table_name: "users".to_string(),
+///             ..Default::default()
+///         },
+///         keys.to_vec(),
+///     )]),
+///     ..Default::default()
+/// };
+/// let responses = batch_get.send_aligned(client).await?;
+/// for (table_name, keys, item) in responses.iter() {
+///     println!("{table_name} {keys:?}: {item:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchGetItemResponses {
+    /// The merged output of the batch get item calls.
+    pub output: operation::batch_get_item::BatchGetItemOutput,
+    /// The requested keys along with the name of their table, in request order.
+    requested_keys: Vec<(String, collections::HashMap<String, types::AttributeValue>)>,
+}
+
+impl BatchGetItemResponses {
+    /// Get the returned item of a table with the given keys, if any.
+    pub fn get(
+        &self,
+        table_name: &str,
+        keys: &collections::HashMap<String, types::AttributeValue>,
+    ) -> Option<&collections::HashMap<String, types::AttributeValue>> {
+        self.output
+            .responses
+            .as_ref()?
+            .get(table_name)?
+            .iter()
+            .find(|item| {
+                keys.iter()
+                    .all(|(name, value)| item.get(name) == Some(value))
+            })
+    }
+
+    /// Get the capacity consumed by the batch get item calls, by table and index.
+    pub fn consumed_capacity(&self) -> common::capacity::CapacityBreakdown {
+        self.output.consumed_capacity().iter().cloned().collect()
+    }
+
+    /// Iterate over the requested keys in request order, along with the name of their table
+    /// and their returned item.
+    ///
+    /// The item is `None` if it does not exist or if its keys are still unprocessed.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &str,
+            &collections::HashMap<String, types::AttributeValue>,
+            Option<&collections::HashMap<String, types::AttributeValue>>,
+        ),
+    > {
+        self.requested_keys
+            .iter()
+            .map(|(table_name, keys)| (table_name.as_str(), keys, self.get(table_name, keys)))
     }
 }
 
 impl<T: Serialize> BatchGetItem<T> {
     /// Execute the batch get item operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::batch_get_item::BatchGetItemOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the batch get item operation, converting the keys with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.batch_get_item", err)
+        tracing::instrument(name = "dynamodb_crud.batch_get_item", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<
-        operation::batch_get_item::BatchGetItemOutput,
-        error::SdkError<operation::batch_get_item::BatchGetItemError>,
-    > {
-        let batch_get_item: operation::batch_get_item::BatchGetItemInput =
-            self.try_into().map_err(error::BuildError::other)?;
-        client
-            .batch_get_item()
-            .set_request_items(batch_get_item.request_items)
-            .set_return_consumed_capacity(batch_get_item.return_consumed_capacity)
-            .send()
-            .await
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::batch_get_item::BatchGetItemOutput, crate::Error> {
+        let backoff = common::retry::Backoff::new(self.retry_policy.as_ref(), BATCH_GET_BACKOFF);
+        let retry_policy = self.retry_policy.clone();
+        let batch_get_item =
+            get_batch_get_item_input(self, serializer).map_err(crate::Error::Serialization)?;
+        get_items_in_chunks(client, batch_get_item, retry_policy.as_ref(), backoff).await
+    }
+
+    /// Estimate the chunks and the read capacity of the operation without sending it,
+    /// such as to schedule large jobs.
+    ///
+    /// The items are not read, so every item is assumed to be of the given size in bytes.
+    pub fn estimate_cost(
+        self,
+        item_size: usize,
+    ) -> Result<common::bulk::CostEstimate, crate::Error> {
+        let batch_get_item = get_batch_get_item_input(self, &common::serializer::SerdeDynamo)
+            .map_err(crate::Error::Serialization)?;
+        let request_items = batch_get_item.request_items.unwrap_or_default();
+        let mut report = common::bulk::CostEstimate::default();
+        for keys_and_attributes in request_items.values() {
+            let consistent_read = keys_and_attributes.consistent_read.unwrap_or_default();
+            for _ in &keys_and_attributes.keys {
+                report.estimated_capacity +=
+                    common::capacity::CapacityUnits::estimate_read(item_size, consistent_read);
+                report.requests += 1;
+            }
+        }
+        report.chunks = get_chunks(request_items).len();
+        Ok(report)
+    }
+
+    /// Execute the batch get item operation, keeping the requested keys to look up the
+    /// returned items in request order.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.batch_get_item.send_aligned", skip_all, err)
+    )]
+    pub async fn send_aligned(
+        self,
+        client: &Client,
+    ) -> Result<BatchGetItemResponses, crate::Error> {
+        let backoff = common::retry::Backoff::new(self.retry_policy.as_ref(), BATCH_GET_BACKOFF);
+        let retry_policy = self.retry_policy.clone();
+        let table_names: Vec<String> = self
+            .items
+            .keys()
+            .map(|args| args.table_name.clone())
+            .collect();
+        let batch_get_item = get_batch_get_item_input(self, &common::serializer::SerdeDynamo)
+            .map_err(crate::Error::Serialization)?;
+        let requested_keys = get_requested_keys(
+            table_names,
+            batch_get_item.request_items.clone().unwrap_or_default(),
+        );
+        let output =
+            get_items_in_chunks(client, batch_get_item, retry_policy.as_ref(), backoff).await?;
+        let responses = BatchGetItemResponses {
+            output,
+            requested_keys,
+        };
+        Ok(responses)
     }
 }
 
@@ -168,6 +449,7 @@ mod tests {
                     (
                         read::common::SingleReadArgs {
                             consistent_read: Some(false),
+                            retry_policy: None,
                             selection: Some(
                                 common::selection::SelectionMap::Leaves(
                                     vec![
@@ -200,6 +482,7 @@ mod tests {
                     (
                         read::common::SingleReadArgs {
                             consistent_read: Some(true),
+                            retry_policy: None,
                             selection: Some(
                                 common::selection::SelectionMap::Leaves(
                                     vec![
@@ -234,6 +517,7 @@ mod tests {
             return_consumed_capacity: Some(
                 types::ReturnConsumedCapacity::Total
             ),
+            retry_policy: None,
         },
         operation::batch_get_item::BatchGetItemInput::builder()
             .set_request_items(
@@ -351,4 +635,197 @@ mod tests {
         let actual: operation::batch_get_item::BatchGetItemInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    fn get_keys_and_attributes(ids: std::ops::Range<usize>) -> types::KeysAndAttributes {
+        let keys = ids
+            .map(|id| {
+                collections::HashMap::from([(
+                    "a".to_string(),
+                    types::AttributeValue::N(id.to_string()),
+                )])
+            })
+            .collect();
+        types::KeysAndAttributes::builder()
+            .set_keys(Some(keys))
+            .projection_expression("#b")
+            .build()
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case::single(
+        3,
+        vec![
+            collections::HashMap::from(
+                [
+                    ("c".to_string(), get_keys_and_attributes(0..3)),
+                ]
+            ),
+        ]
+    )]
+    #[case::exact(
+        100,
+        vec![
+            collections::HashMap::from(
+                [
+                    ("c".to_string(), get_keys_and_attributes(0..100)),
+                ]
+            ),
+        ]
+    )]
+    #[case::split(
+        201,
+        vec![
+            collections::HashMap::from(
+                [
+                    ("c".to_string(), get_keys_and_attributes(0..100)),
+                ]
+            ),
+            collections::HashMap::from(
+                [
+                    ("c".to_string(), get_keys_and_attributes(100..200)),
+                ]
+            ),
+            collections::HashMap::from(
+                [
+                    ("c".to_string(), get_keys_and_attributes(200..201)),
+                ]
+            ),
+        ]
+    )]
+    fn test_get_chunks(
+        #[case] count: usize,
+        #[case] expected: Vec<collections::HashMap<String, types::KeysAndAttributes>>,
+    ) {
+        let request_items =
+            collections::HashMap::from([("c".to_string(), get_keys_and_attributes(0..count))]);
+        let actual = get_chunks(request_items);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_chunks_tables() {
+        let request_items = collections::HashMap::from([
+            ("c".to_string(), get_keys_and_attributes(0..60)),
+            ("d".to_string(), get_keys_and_attributes(0..60)),
+        ]);
+        let actual: Vec<Vec<usize>> = get_chunks(request_items)
+            .iter()
+            .map(|chunk| {
+                let mut lens: Vec<_> = chunk
+                    .values()
+                    .map(|keys_and_attributes| keys_and_attributes.keys.len())
+                    .collect();
+                lens.sort();
+                lens
+            })
+            .collect();
+        assert_eq!(actual, vec![vec![40, 60], vec![20]]);
+    }
+
+    #[test]
+    fn test_merge_request_items() {
+        let mut merged_request_items =
+            collections::HashMap::from([("c".to_string(), get_keys_and_attributes(0..2))]);
+        merge_request_items(
+            &mut merged_request_items,
+            collections::HashMap::from([
+                ("c".to_string(), get_keys_and_attributes(2..3)),
+                ("d".to_string(), get_keys_and_attributes(0..1)),
+            ]),
+        );
+        let expected = collections::HashMap::from([
+            ("c".to_string(), get_keys_and_attributes(0..3)),
+            ("d".to_string(), get_keys_and_attributes(0..1)),
+        ]);
+        assert_eq!(merged_request_items, expected);
+    }
+
+    fn get_item(id: &str, name: &str) -> collections::HashMap<String, types::AttributeValue> {
+        collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S(id.to_string())),
+            ("b".to_string(), types::AttributeValue::S(name.to_string())),
+        ])
+    }
+
+    fn get_key(id: &str) -> collections::HashMap<String, types::AttributeValue> {
+        collections::HashMap::from([("a".to_string(), types::AttributeValue::S(id.to_string()))])
+    }
+
+    #[test]
+    fn test_get_requested_keys() {
+        let request_items = collections::HashMap::from([
+            ("c".to_string(), get_keys_and_attributes(0..2)),
+            ("d".to_string(), get_keys_and_attributes(2..3)),
+        ]);
+        let actual = get_requested_keys(
+            ["d".to_string(), "c".to_string(), "d".to_string()],
+            request_items,
+        );
+        let expected: Vec<_> = [("d", 2), ("c", 0), ("c", 1)]
+            .into_iter()
+            .map(|(table_name, index)| {
+                let keys = get_keys_and_attributes(index..index + 1).keys.remove(0);
+                (table_name.to_string(), keys)
+            })
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_batch_get_item_responses() {
+        let output = operation::batch_get_item::BatchGetItemOutput::builder()
+            .responses("c", vec![get_item("2", "e"), get_item("1", "d")])
+            .build();
+        let responses = BatchGetItemResponses {
+            output,
+            requested_keys: vec![
+                ("c".to_string(), get_key("1")),
+                ("c".to_string(), get_key("2")),
+                ("c".to_string(), get_key("3")),
+            ],
+        };
+        assert_eq!(responses.get("c", &get_key("2")), Some(&get_item("2", "e")));
+        assert_eq!(responses.get("f", &get_key("2")), None);
+        let actual: Vec<_> = responses.iter().map(|(_, _, item)| item.cloned()).collect();
+        let expected = vec![Some(get_item("1", "d")), Some(get_item("2", "e")), None];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_batch_get_item_estimate_cost() {
+        let keys: Vec<_> = (0..150)
+            .map(|id| common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::from(id),
+                },
+                ..Default::default()
+            })
+            .collect();
+        let args = BatchGetItem {
+            items: IndexMap::from([
+                (
+                    read::common::SingleReadArgs {
+                        table_name: "b".to_string(),
+                        ..Default::default()
+                    },
+                    keys[..100].to_vec(),
+                ),
+                (
+                    read::common::SingleReadArgs {
+                        consistent_read: Some(true),
+                        table_name: "c".to_string(),
+                        ..Default::default()
+                    },
+                    keys[100..].to_vec(),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let actual = args.estimate_cost(5000).unwrap();
+        assert_eq!(actual.requests, 150);
+        assert_eq!(actual.chunks, 2);
+        assert_eq!(actual.estimated_capacity.read_capacity_units, 200.0);
+    }
 }
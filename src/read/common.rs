@@ -1,9 +1,627 @@
-use crate::common;
+use crate::{common, common::retry::WithRetryPolicy as _, table};
 
-use aws_sdk_dynamodb::types;
-use serde::Serialize;
-use serde_dynamo::{Error, Result, to_attribute_value};
-use std::collections;
+use aws_sdk_dynamodb::{Client, error, operation, types};
+use futures_util::{Stream, StreamExt, stream};
+use indexmap::IndexMap;
+use serde::{
+    Serialize,
+    de::{DeserializeOwned, Error as _},
+};
+use serde_dynamo::{Error, Result};
+use std::{collections, fmt, hash, str, time};
+use tokio::sync::mpsc;
+
+/// Deserialized items of a query or scan, along with the counts and consumed capacity.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemsOutput<O> {
+    /// The capacity consumed by every page, if requested.
+    pub consumed_capacity: Option<types::ConsumedCapacity>,
+    /// The number of items returned.
+    pub count: i32,
+    /// The deserialized items.
+    pub items: Vec<O>,
+    /// The number of items evaluated before applying the filter.
+    pub scanned_count: i32,
+}
+
+impl<O: DeserializeOwned> ItemsOutput<O> {
+    /// Deserialize the items of a read output.
+    pub(crate) fn new(
+        items: Option<Vec<collections::HashMap<String, types::AttributeValue>>>,
+        count: i32,
+        scanned_count: i32,
+        consumed_capacity: Option<types::ConsumedCapacity>,
+    ) -> Result<Self> {
+        let output = Self {
+            consumed_capacity,
+            count,
+            items: serde_dynamo::from_items(items.unwrap_or_default())?,
+            scanned_count,
+        };
+        Ok(output)
+    }
+}
+
+/// Counts of the items of a query or scan, summed over every page.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ItemsCount {
+    /// The number of items matching the filter.
+    pub count: u64,
+    /// The number of items evaluated before applying the filter.
+    pub scanned_count: u64,
+}
+
+/// Deserialized items of a single query or scan page, along with the typed key to continue
+/// from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemsPage<O, K> {
+    /// The capacity consumed by the page, if requested.
+    pub consumed_capacity: Option<types::ConsumedCapacity>,
+    /// The number of items returned.
+    pub count: i32,
+    /// The opaque cursor to continue the read from, if more items remain.
+    pub cursor: Option<Cursor>,
+    /// The deserialized items.
+    pub items: Vec<O>,
+    /// The key to continue the read from, if more items remain.
+    pub last_evaluated_key: Option<common::key::LastEvaluatedKey<K>>,
+    /// The number of items evaluated before applying the filter.
+    pub scanned_count: i32,
+}
+
+impl<O: DeserializeOwned, K: DeserializeOwned> ItemsPage<O, K> {
+    /// Deserialize the items and the pagination key of a read page.
+    pub(crate) fn new(
+        output: ItemsOutput<O>,
+        last_evaluated_key: Option<collections::HashMap<String, types::AttributeValue>>,
+        key_schema: &table::common::KeySchema,
+        index_key_schema: Option<&table::common::KeySchema>,
+    ) -> Result<Self> {
+        let page = Self {
+            consumed_capacity: output.consumed_capacity,
+            count: output.count,
+            cursor: last_evaluated_key.clone().map(Cursor),
+            items: output.items,
+            last_evaluated_key: last_evaluated_key
+                .map(|last_evaluated_key| {
+                    common::key::LastEvaluatedKey::new(
+                        last_evaluated_key,
+                        key_schema,
+                        index_key_schema,
+                    )
+                })
+                .transpose()?,
+            scanned_count: output.scanned_count,
+        };
+        Ok(page)
+    }
+}
+
+/// Opaque cursor to continue a query or scan from, wrapping its raw pagination key.
+///
+/// Serialized as a string, so a web API can return it along with a page and take it back as
+/// the `cursor` of the next read, without exposing the keys of the table.
+///
+/// ```rust
+/// use dynamodb_crud::read;
+///
+/// # fn example(cursor: read::common::Cursor) -> Result<(), Box<dyn std::error::Error>> {
+/// let token = cursor.to_string();
+/// let cursor: read::common::Cursor = token.parse()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Cursor(collections::HashMap<String, types::AttributeValue>);
+
+/// Take a length-prefixed chunk of an encoded cursor.
+fn take_cursor_chunk<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let invalid = || Error::custom("truncated cursor");
+    let (length, rest) = bytes.split_first_chunk::<4>().ok_or_else(invalid)?;
+    let length = u32::from_be_bytes(*length) as usize;
+    let (chunk, rest) = rest.split_at_checked(length).ok_or_else(invalid)?;
+    *bytes = rest;
+    Ok(chunk)
+}
+
+/// HMAC-SHA256 signing of cursors.
+type CursorMac = hmac::Hmac<sha2::Sha256>;
+
+/// Length of the signature of a signed cursor.
+const CURSOR_SIGNATURE_LENGTH: usize = 32;
+
+impl Cursor {
+    /// Get the binary encoding of the raw pagination key, ordered by attribute name.
+    fn to_bytes(&self) -> Vec<u8> {
+        common::key::get_key_bytes(&self.0)
+    }
+
+    /// Parse the binary encoding of a raw pagination key.
+    fn from_bytes(mut bytes: &[u8]) -> Result<Self> {
+        let invalid = || Error::custom("invalid cursor");
+        let mut keys = collections::HashMap::new();
+        while let Some((&tag, rest)) = bytes.split_first() {
+            bytes = rest;
+            let name = take_cursor_chunk(&mut bytes)?;
+            let name = String::from_utf8(name.to_vec()).map_err(|_| invalid())?;
+            let value = take_cursor_chunk(&mut bytes)?.to_vec();
+            let value = match tag {
+                b'B' => types::AttributeValue::B(value.into()),
+                b'N' => types::AttributeValue::N(String::from_utf8(value).map_err(|_| invalid())?),
+                b'S' => types::AttributeValue::S(String::from_utf8(value).map_err(|_| invalid())?),
+                _ => return Err(invalid()),
+            };
+            keys.insert(name, value);
+        }
+        Ok(Self(keys))
+    }
+
+    /// Get the HMAC of an encoded cursor.
+    fn get_mac(bytes: &[u8], signing_key: &[u8]) -> CursorMac {
+        let mut mac = <CursorMac as hmac::KeyInit>::new_from_slice(signing_key)
+            .expect("HMAC accepts keys of any length");
+        hmac::Mac::update(&mut mac, bytes);
+        mac
+    }
+
+    /// Encode into a URL-safe base64 token, signed with HMAC-SHA256 if a signing key is given.
+    ///
+    /// A signed token cannot be tampered with by the clients of a web API, so it only ever
+    /// continues a read from a key returned by DynamoDB.
+    ///
+    /// ```rust
+    /// use dynamodb_crud::read;
+    ///
+    /// # fn example(cursor: read::common::Cursor) -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = cursor.encode(Some(b"secret"));
+    /// let cursor = read::common::Cursor::decode(&token, Some(b"secret"))?;
+    /// assert!(read::common::Cursor::decode(&token, Some(b"other")).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encode(&self, signing_key: Option<&[u8]>) -> String {
+        let mut bytes = self.to_bytes();
+        if let Some(signing_key) = signing_key {
+            let signature = hmac::Mac::finalize(Self::get_mac(&bytes, signing_key));
+            bytes.extend(signature.into_bytes());
+        }
+        base64::Engine::encode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, bytes)
+    }
+
+    /// Decode a token produced by [`encode`](Self::encode), verifying its signature if a
+    /// signing key is given.
+    ///
+    /// Fails if the token is malformed, or if its signature does not match the signing key.
+    pub fn decode(token: &str, signing_key: Option<&[u8]>) -> Result<Self> {
+        let bytes =
+            base64::Engine::decode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, token)
+                .map_err(|_| Error::custom("invalid cursor"))?;
+        let Some(signing_key) = signing_key else {
+            return Self::from_bytes(&bytes);
+        };
+        let (bytes, signature) = bytes
+            .split_at_checked(bytes.len().saturating_sub(CURSOR_SIGNATURE_LENGTH))
+            .filter(|(_, signature)| signature.len() == CURSOR_SIGNATURE_LENGTH)
+            .ok_or_else(|| Error::custom("unsigned cursor"))?;
+        hmac::Mac::verify_slice(Self::get_mac(bytes, signing_key), signature)
+            .map_err(|_| Error::custom("invalid cursor signature"))?;
+        Self::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.encode(None))
+    }
+}
+
+impl str::FromStr for Cursor {
+    type Err = Error;
+
+    fn from_str(cursor: &str) -> Result<Self> {
+        Self::decode(cursor, None)
+    }
+}
+
+impl Serialize for Cursor {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Cursor {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let cursor = String::deserialize(deserializer)?;
+        cursor.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// deserializer of the items of an entity type into the entity enum
+type EntityDecoder<E> =
+    Box<dyn Fn(collections::HashMap<String, types::AttributeValue>) -> Result<E> + Send + Sync>;
+
+/// Decoder of heterogeneous items into an enum of entities, chosen by a discriminator
+/// attribute.
+///
+/// Single-table designs store several entity types in one table, so a query returns items
+/// of different shapes: each entity type is registered with the variant wrapping it.
+///
+/// ```rust
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_crud::read;
+/// use serde::Deserialize;
+/// use std::collections::HashMap;
+///
+/// #[derive(Deserialize)]
+/// struct Customer {
+///     name: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Order {
+///     total: f64,
+/// }
+///
+/// enum Entity {
+///     Customer(Customer),
+///     Order(Order),
+/// }
+///
+/// let decoder = read::common::TypeDecoder::new("entityType")
+///     .register("customer", Entity::Customer)
+///     .register("order", Entity::Order);
+/// let item = HashMap::from([
+///     ("entityType".to_string(), AttributeValue::S("order".to_string())),
+///     ("total".to_string(), AttributeValue::N("9.5".to_string())),
+/// ]);
+/// assert!(matches!(decoder.decode(item), Ok(Entity::Order(_))));
+/// ```
+pub struct TypeDecoder<E> {
+    decoders: collections::HashMap<String, EntityDecoder<E>>,
+    discriminator: String,
+}
+
+impl<E> fmt::Debug for TypeDecoder<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entity_types: Vec<_> = self.decoders.keys().collect();
+        entity_types.sort();
+        formatter
+            .debug_struct("TypeDecoder")
+            .field("discriminator", &self.discriminator)
+            .field("entity_types", &entity_types)
+            .finish()
+    }
+}
+
+impl<E> TypeDecoder<E> {
+    /// Create a decoder reading the entity type from the given attribute.
+    pub fn new(discriminator: impl Into<String>) -> Self {
+        Self {
+            decoders: collections::HashMap::new(),
+            discriminator: discriminator.into(),
+        }
+    }
+
+    /// Register an entity type, deserializing its items and wrapping them in a variant.
+    pub fn register<D: DeserializeOwned + 'static>(
+        mut self,
+        entity_type: impl Into<String>,
+        variant: fn(D) -> E,
+    ) -> Self
+    where
+        E: 'static,
+    {
+        let decoder: EntityDecoder<E> =
+            Box::new(move |item| serde_dynamo::from_item(item).map(variant));
+        self.decoders.insert(entity_type.into(), decoder);
+        self
+    }
+
+    /// Decode an item into the entity of its type.
+    ///
+    /// Fails if the item lacks the discriminator or its type is not registered.
+    pub fn decode(&self, item: collections::HashMap<String, types::AttributeValue>) -> Result<E> {
+        let entity_type = item
+            .get(&self.discriminator)
+            .and_then(|entity_type| entity_type.as_s().ok())
+            .ok_or_else(|| {
+                Error::custom(format!(
+                    "missing string discriminator attribute {}",
+                    self.discriminator
+                ))
+            })?;
+        let decoder = self
+            .decoders
+            .get(entity_type)
+            .ok_or_else(|| Error::custom(format!("unregistered entity type {entity_type}")))?;
+        decoder(item)
+    }
+
+    /// Decode the items of a read output.
+    pub(crate) fn decode_items(
+        &self,
+        items: Option<Vec<collections::HashMap<String, types::AttributeValue>>>,
+    ) -> Result<Vec<E>> {
+        items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| self.decode(item))
+            .collect()
+    }
+}
+
+/// Split an item into the value of the grouping attribute and the deserialized item.
+///
+/// A missing attribute is deserialized from a null value, so `Option` keys group the
+/// items lacking it under `None`.
+fn get_group_entry<K: DeserializeOwned, O: DeserializeOwned>(
+    item: collections::HashMap<String, types::AttributeValue>,
+    attribute_name: &str,
+) -> Result<(K, O)> {
+    let key = item
+        .get(attribute_name)
+        .cloned()
+        .unwrap_or(types::AttributeValue::Null(true));
+    let key = serde_dynamo::from_attribute_value(key)?;
+    let item = serde_dynamo::from_item(item)?;
+    Ok((key, item))
+}
+
+/// Group the items of a read output by the value of an attribute, in order of first
+/// appearance.
+pub(crate) fn group_items<K, O>(
+    items: Option<Vec<collections::HashMap<String, types::AttributeValue>>>,
+    attribute_name: &str,
+) -> Result<IndexMap<K, Vec<O>>>
+where
+    K: DeserializeOwned + Eq + hash::Hash,
+    O: DeserializeOwned,
+{
+    let mut groups: IndexMap<K, Vec<O>> = IndexMap::new();
+    for item in items.unwrap_or_default() {
+        let (key, item) = get_group_entry(item, attribute_name)?;
+        groups.entry(key).or_default().push(item);
+    }
+    Ok(groups)
+}
+
+/// Group consecutive items of a stream sharing the value of an attribute.
+///
+/// Stops after the first error.
+pub(crate) fn group_consecutive_items<K, O>(
+    items: impl Stream<
+        Item = std::result::Result<
+            collections::HashMap<String, types::AttributeValue>,
+            crate::Error,
+        >,
+    >,
+    attribute_name: String,
+) -> impl Stream<Item = std::result::Result<(K, Vec<O>), crate::Error>>
+where
+    K: DeserializeOwned + PartialEq,
+    O: DeserializeOwned,
+{
+    let state = (Box::pin(items), None::<(K, Vec<O>)>, false);
+    stream::unfold(state, move |(mut items, mut group, done)| {
+        let attribute_name = attribute_name.clone();
+        async move {
+            if done {
+                return None;
+            }
+            loop {
+                let item = match items.next().await {
+                    Some(Ok(item)) => item,
+                    Some(Err(error)) => return Some((Err(error), (items, None, true))),
+                    None => return group.map(|group| (Ok(group), (items, None, true))),
+                };
+                let (key, item) = match get_group_entry(item, &attribute_name) {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        return Some((
+                            Err(crate::Error::Deserialization(error)),
+                            (items, None, true),
+                        ));
+                    }
+                };
+                match group.take() {
+                    Some((group_key, mut group_items)) if group_key == key => {
+                        group_items.push(item);
+                        group = Some((group_key, group_items));
+                    }
+                    Some(previous_group) => {
+                        return Some((Ok(previous_group), (items, Some((key, vec![item])), false)));
+                    }
+                    None => group = Some((key, vec![item])),
+                }
+            }
+        }
+    })
+}
+
+/// Cap on the number of pages a query or scan fetches, protecting against reading a whole
+/// table because of a bad filter.
+///
+/// At least one page is always fetched.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MaxPages {
+    /// Fail with [`crate::Error::MaxPagesExceeded`] if more pages remain after the given
+    /// number of pages.
+    Error(usize),
+    /// Stop after the given number of pages, returning the items read so far.
+    ///
+    /// The output of `send` carries the key to continue the read from.
+    Truncate(usize),
+}
+
+/// Counter of the pages fetched by a query or scan, enforcing its maximum number of pages
+/// and its deadline.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct PageCounter {
+    deadline: Option<time::Instant>,
+    items: usize,
+    max_items: Option<usize>,
+    max_pages: Option<MaxPages>,
+    pages: usize,
+}
+
+impl PageCounter {
+    pub(crate) fn new(
+        max_pages: Option<MaxPages>,
+        deadline: Option<time::Instant>,
+        max_items: Option<usize>,
+    ) -> Self {
+        Self {
+            deadline,
+            items: 0,
+            max_items,
+            max_pages,
+            pages: 0,
+        }
+    }
+
+    /// Count the items of a fetched page, kept whole.
+    pub(crate) fn count_items(&mut self, items: usize) {
+        self.items += items;
+    }
+
+    /// Count the items of a fetched page, truncated to the maximum number of items.
+    pub(crate) fn take_items<I>(&mut self, mut items: Vec<I>) -> Vec<I> {
+        if let Some(max_items) = self.max_items {
+            items.truncate(max_items.saturating_sub(self.items));
+        }
+        self.items += items.len();
+        items
+    }
+
+    /// Count a fetched page, returning whether to fetch the next one.
+    ///
+    /// No further page is fetched once the deadline has passed, or once the maximum number of
+    /// items is collected. Fails if more pages remain once the maximum number of pages is
+    /// reached with [`MaxPages::Error`].
+    pub(crate) fn count_page(
+        &mut self,
+        last_evaluated_key: Option<&collections::HashMap<String, types::AttributeValue>>,
+    ) -> std::result::Result<bool, crate::Error> {
+        self.pages += 1;
+        if last_evaluated_key.is_none() {
+            return Ok(false);
+        }
+        if self
+            .max_items
+            .is_some_and(|max_items| self.items >= max_items)
+        {
+            return Ok(false);
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| time::Instant::now() >= deadline)
+        {
+            return Ok(false);
+        }
+        match self.max_pages {
+            Some(MaxPages::Error(max_pages)) if self.pages >= max_pages => {
+                Err(crate::Error::MaxPagesExceeded(max_pages))
+            }
+            Some(MaxPages::Truncate(max_pages)) => Ok(self.pages < max_pages),
+            _ => Ok(true),
+        }
+    }
+}
+
+/// Pages of a query or scan, fetched one at a time with the retry policy of the read.
+///
+/// The paginators of the SDK can't override the configuration of the client for a call.
+///
+/// With a maximum number of items, the limit of every page is lowered to the items left, so
+/// the last page ends at the maximum and its last evaluated key continues right after it.
+pub(crate) struct Pages<B> {
+    builder: Option<B>,
+    client: Client,
+    remaining_items: Option<usize>,
+    retry_policy: Option<common::retry::RetryPolicy>,
+}
+
+impl<B> Pages<B> {
+    pub(crate) fn new(
+        builder: B,
+        client: &Client,
+        max_items: Option<usize>,
+        retry_policy: Option<common::retry::RetryPolicy>,
+    ) -> Self {
+        Self {
+            builder: Some(builder),
+            client: client.clone(),
+            remaining_items: max_items,
+            retry_policy,
+        }
+    }
+}
+
+/// implement fetching the next page of the pages of an operation
+macro_rules! impl_pages {
+    ($builder:ty, $output:ty, $error:ty) => {
+        impl Pages<$builder> {
+            /// Fetch the next page, if any.
+            ///
+            /// Like the paginators of the SDK, stops after an error or once the last evaluated
+            /// key is empty or repeated, and also once the maximum number of items is reached.
+            pub(crate) async fn next(
+                &mut self,
+            ) -> Option<std::result::Result<$output, error::SdkError<$error>>> {
+                let mut builder = self.builder.take()?;
+                if let Some(remaining_items) = self.remaining_items {
+                    if remaining_items == 0 {
+                        return None;
+                    }
+                    let remaining_items = i32::try_from(remaining_items).unwrap_or(i32::MAX);
+                    let limit = builder
+                        .get_limit()
+                        .map_or(remaining_items, |limit| limit.min(remaining_items));
+                    builder = builder.limit(limit);
+                }
+                let page = builder
+                    .clone()
+                    .customize()
+                    .with_retry_policy(&self.client, self.retry_policy.as_ref())
+                    .send()
+                    .await;
+                if let Ok(page) = &page {
+                    if let Some(remaining_items) = &mut self.remaining_items {
+                        *remaining_items =
+                            remaining_items.saturating_sub(page.count.max(0) as usize);
+                    }
+                    let last_evaluated_key = page
+                        .last_evaluated_key
+                        .as_ref()
+                        .filter(|last_evaluated_key| !last_evaluated_key.is_empty());
+                    if last_evaluated_key.is_some()
+                        && last_evaluated_key != builder.get_exclusive_start_key().as_ref()
+                    {
+                        self.builder =
+                            Some(builder.set_exclusive_start_key(last_evaluated_key.cloned()));
+                    }
+                }
+                Some(page)
+            }
+        }
+    };
+}
+
+impl_pages!(
+    operation::query::builders::QueryFluentBuilder,
+    operation::query::QueryOutput,
+    operation::query::QueryError
+);
+impl_pages!(
+    operation::scan::builders::ScanFluentBuilder,
+    operation::scan::ScanOutput,
+    operation::scan::ScanError
+);
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub(crate) struct SingleReadInput {
@@ -23,6 +641,10 @@ pub struct SingleReadArgs {
     /// `true` for strongly consistent reads, `false` or `None` for eventually consistent reads.
     /// Consistent reads consume more capacity units but guarantee you see the latest data.
     pub consistent_read: Option<bool>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    ///
+    /// Ignored by batch get item, which has its own retry policy.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
     /// Which attributes to retrieve (projection expression).
     ///
     /// If `None`, all attributes are retrieved. Use `SelectionMap` to specify
@@ -36,9 +658,14 @@ impl From<SingleReadArgs> for SingleReadInput {
     fn from(single_read_args: SingleReadArgs) -> Self {
         let (expression_attribute_names, projection_expression) = match single_read_args.selection {
             Some(selection) => {
-                let selection_operation: common::ExpressionInput = selection.into();
+                let selection_operation = common::ExpressionInput::from(&selection);
                 (
-                    Some(selection_operation.expression_attribute_names),
+                    Some(
+                        selection_operation
+                            .expression_attribute_names
+                            .into_iter()
+                            .collect(),
+                    ),
                     Some(selection_operation.expression),
                 )
             }
@@ -56,6 +683,7 @@ impl From<SingleReadArgs> for SingleReadInput {
 #[derive(Clone, Debug, Default, PartialEq)]
 pub(crate) struct MultipleReadInput {
     pub(crate) consistent_read: Option<bool>,
+    pub(crate) deadline: Option<time::Instant>,
     pub(crate) exclusive_start_key: Option<collections::HashMap<String, types::AttributeValue>>,
     pub(crate) expression_attribute_names: Option<collections::HashMap<String, String>>,
     pub(crate) expression_attribute_values:
@@ -63,7 +691,11 @@ pub(crate) struct MultipleReadInput {
     pub(crate) filter_expression: Option<String>,
     pub(crate) index_name: Option<String>,
     pub(crate) limit: Option<i32>,
+    pub(crate) max_items: Option<usize>,
+    pub(crate) max_pages: Option<MaxPages>,
     pub(crate) projection_expression: Option<String>,
+    pub(crate) prune_empty_maps: bool,
+    pub(crate) retry_policy: Option<common::retry::RetryPolicy>,
     pub(crate) select: Option<types::Select>,
     pub(crate) table_name: String,
 }
@@ -82,11 +714,23 @@ pub struct MultipleReadArgs<T> {
     ///
     /// `true` for strongly consistent reads, `false` or `None` for eventually consistent reads.
     pub consistent_read: Option<bool>,
+    /// Opaque cursor to continue a previous Query or Scan operation from, typically the
+    /// `cursor` of the previous page.
+    ///
+    /// Takes precedence over `exclusive_start_key`.
+    pub cursor: Option<Cursor>,
+    /// The instant after which no further page is fetched.
+    ///
+    /// Checked between pages, so a long read stops cleanly before an external timeout,
+    /// returning the items read so far. The output of `send` carries the key to continue the
+    /// read from. If `None`, the read has no deadline.
+    pub deadline: Option<time::Instant>,
     /// The exclusive start key for pagination.
     ///
-    /// Used to continue a previous Query or Scan operation from where it left off.
-    /// Typically obtained from the `last_evaluated_key` in the previous response.
-    pub exclusive_start_key: Option<collections::HashMap<String, T>>,
+    /// Used to continue a previous Query or Scan operation from where it left off: the
+    /// primary key of the last evaluated item, along with its index keys when reading a
+    /// secondary index. Typically the `last_evaluated_key` of the previous page.
+    pub exclusive_start_key: Option<common::key::LastEvaluatedKey<T>>,
     /// The name of a global secondary index or local secondary index to query.
     ///
     /// If specified, the operation will query the index instead of the base table.
@@ -96,6 +740,31 @@ pub struct MultipleReadArgs<T> {
     /// DynamoDB will return up to this many items. If more items match, you'll need
     /// to paginate using `exclusive_start_key`.
     pub limit: Option<i32>,
+    /// The maximum number of matching items to collect across pages, enforced client-side.
+    ///
+    /// Unlike `limit`, which applies to the items evaluated by every page, no further page is
+    /// fetched once this many items are collected, and the limit of every page is lowered to
+    /// the items left. Reads and counts stop at exactly this many items, and the last evaluated
+    /// key continues right after the returned items. If `None`, every matching item is
+    /// collected.
+    pub max_items: Option<usize>,
+    /// The maximum number of pages to fetch, and what to do once reached.
+    ///
+    /// If `None`, every page is fetched. Ignored when fetching a single page.
+    pub max_pages: Option<MaxPages>,
+    /// Naming strategy of the placeholders of the filter, key condition and projection
+    /// expressions.
+    ///
+    /// If `None`, placeholders are derived from the attribute names.
+    pub placeholder_naming: Option<common::PlaceholderNaming>,
+    /// Whether to remove the maps left empty by a nested selection from the returned items.
+    ///
+    /// Selecting a nested path whose parent exists but lacks the attribute returns the parent
+    /// as an empty map, which breaks strict deserialization. Pruning them lets optional
+    /// parents deserialize as `None` instead.
+    pub prune_empty_maps: bool,
+    /// Retry policy overriding the retry configuration of the client for every page.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
     /// Which attributes to return.
     ///
     /// Use `Select::AllAttributes` (default), `Select::AllProjectedAttributes`,
@@ -110,29 +779,27 @@ pub struct MultipleReadArgs<T> {
     pub table_name: String,
 }
 
-impl<T: Serialize> TryFrom<MultipleReadArgs<T>> for MultipleReadInput {
-    type Error = Error;
-
-    fn try_from(multiple_read_args: MultipleReadArgs<T>) -> Result<Self> {
-        let exclusive_start_key = match multiple_read_args.exclusive_start_key {
-            Some(exclusive_start_key) => {
-                let mut serialized_exclusive_start_key =
-                    collections::HashMap::with_capacity(exclusive_start_key.len());
-                for (key, value) in exclusive_start_key {
-                    let value = to_attribute_value(value)?;
-                    serialized_exclusive_start_key.insert(key, value);
-                }
-                Some(serialized_exclusive_start_key)
-            }
-            None => None,
+impl MultipleReadInput {
+    /// Convert the read arguments, converting the values with the given serializer.
+    pub(crate) fn new<T: Serialize>(
+        multiple_read_args: MultipleReadArgs<T>,
+        placeholders: &mut common::PlaceholderAllocator,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let exclusive_start_key = match multiple_read_args.cursor {
+            Some(cursor) => Some(cursor.0),
+            None => multiple_read_args
+                .exclusive_start_key
+                .map(|exclusive_start_key| exclusive_start_key.serialize(serializer))
+                .transpose()?,
         };
         let condition_operation: Option<common::ExpressionInput> = multiple_read_args
             .condition
-            .map(|condition| condition.try_into())
+            .map(|condition| condition.get_expression_operation(placeholders, serializer))
             .transpose()?;
         let selection_operation: Option<common::ExpressionInput> = multiple_read_args
             .selection
-            .map(|selection| selection.into());
+            .map(|selection| selection.get_expression_operation(placeholders));
         let (
             expression_attribute_names,
             expression_attribute_values,
@@ -166,13 +833,21 @@ impl<T: Serialize> TryFrom<MultipleReadArgs<T>> for MultipleReadInput {
         };
         let operation = Self {
             consistent_read: multiple_read_args.consistent_read,
+            deadline: multiple_read_args.deadline,
             exclusive_start_key,
-            expression_attribute_names,
-            expression_attribute_values,
+            expression_attribute_names: expression_attribute_names
+                .map(|expression_attribute_names| expression_attribute_names.into_iter().collect()),
+            expression_attribute_values: expression_attribute_values.map(
+                |expression_attribute_values| expression_attribute_values.into_iter().collect(),
+            ),
             filter_expression,
             index_name: multiple_read_args.index_name,
             limit: multiple_read_args.limit,
+            max_items: multiple_read_args.max_items,
+            max_pages: multiple_read_args.max_pages,
             projection_expression,
+            prune_empty_maps: multiple_read_args.prune_empty_maps,
+            retry_policy: multiple_read_args.retry_policy,
             select: multiple_read_args.select,
             table_name: multiple_read_args.table_name,
         };
@@ -180,14 +855,68 @@ impl<T: Serialize> TryFrom<MultipleReadArgs<T>> for MultipleReadInput {
     }
 }
 
+impl<T: Serialize> TryFrom<MultipleReadArgs<T>> for MultipleReadInput {
+    type Error = Error;
+
+    fn try_from(multiple_read_args: MultipleReadArgs<T>) -> Result<Self> {
+        let placeholder_naming = multiple_read_args.placeholder_naming.clone();
+        Self::new(
+            multiple_read_args,
+            &mut common::PlaceholderAllocator::new(placeholder_naming),
+            &common::serializer::SerdeDynamo,
+        )
+    }
+}
+
+/// Remove the maps left empty by a nested selection from the read items, if enabled.
+pub(crate) fn prune_items(
+    items: Option<&mut Vec<collections::HashMap<String, types::AttributeValue>>>,
+    prune_empty_maps: bool,
+) {
+    if let Some(items) = items.filter(|_| prune_empty_maps) {
+        items
+            .iter_mut()
+            .for_each(common::selection::prune_empty_maps);
+    }
+}
+
+/// Deserialize items and send them to a channel, waiting while the channel is full.
+///
+/// Returns `false` once the receiver is dropped, in which case the remaining items are discarded.
+pub(crate) async fn send_items_to_channel<D: DeserializeOwned>(
+    items: Vec<collections::HashMap<String, types::AttributeValue>>,
+    tx: &mpsc::Sender<D>,
+) -> std::result::Result<bool, crate::Error> {
+    for item in items {
+        let item = serde_dynamo::from_item(item).map_err(crate::Error::Deserialization)?;
+        if tx.send(item).await.is_err() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 /// get paginated output
+///
+/// Stops at the maximum number of pages or the deadline, if any, keeping the key to continue
+/// the read from.
 #[macro_export]
 macro_rules! get_paginated_output {
-    ($paginator:expr, $output_type:ty) => {{
+    ($paginator:expr, $output_type:ty, $max_pages:expr, $deadline:expr, $max_items:expr) => {{
+        let mut counter = $crate::read::common::PageCounter::new($max_pages, $deadline, $max_items);
         let mut outputs = Vec::new();
         while let Some(page) = $paginator.next().await {
-            outputs.push(page?);
+            let page = page?;
+            counter.count_items(page.items.as_ref().map_or(0, Vec::len));
+            let has_next_page = counter.count_page(page.last_evaluated_key.as_ref())?;
+            outputs.push(page);
+            if !has_next_page {
+                break;
+            }
         }
+        let last_evaluated_key = outputs
+            .last()
+            .and_then(|output| output.last_evaluated_key.clone());
         let (items, count, scanned, capacities) = outputs.into_iter().fold(
             (Vec::new(), 0, 0, Vec::new()),
             |(mut items, count, scanned, mut caps), output| {
@@ -210,32 +939,90 @@ macro_rules! get_paginated_output {
             .set_items(Some(items))
             .set_count(Some(count))
             .set_scanned_count(Some(scanned))
-            .set_consumed_capacity(Some(aggregated_capacity))
+            .set_consumed_capacity(aggregated_capacity)
+            .set_last_evaluated_key(last_evaluated_key)
             .build();
         Ok(output)
     }};
 }
 
+/// count the items of paginated pages
+///
+/// Stops at the maximum number of pages or items or the deadline, if any.
+#[macro_export]
+macro_rules! count_paginated_items {
+    ($paginator:expr, $max_pages:expr, $deadline:expr, $max_items:expr) => {{
+        let mut counter = $crate::read::common::PageCounter::new($max_pages, $deadline, $max_items);
+        let mut items_count = $crate::read::common::ItemsCount::default();
+        while let Some(page) = $paginator.next().await {
+            let page = page?;
+            let count = page.count.max(0) as u64;
+            items_count.count += count;
+            items_count.scanned_count += page.scanned_count.max(0) as u64;
+            counter.count_items(count as usize);
+            if !counter.count_page(page.last_evaluated_key.as_ref())? {
+                break;
+            }
+        }
+        Ok(items_count)
+    }};
+}
+
+/// stream the items of paginated pages
+///
+/// Stops at the maximum number of pages or items or the deadline, if any, and after the first
+/// error.
+#[macro_export]
+macro_rules! stream_paginated_items {
+    ($paginator:expr, $max_pages:expr, $deadline:expr, $max_items:expr) => {{
+        let counter = $crate::read::common::PageCounter::new($max_pages, $deadline, $max_items);
+        let state = (
+            Some($paginator),
+            counter,
+            ::std::collections::VecDeque::new(),
+        );
+        ::futures_util::stream::unfold(
+            state,
+            |(mut paginator, mut counter, mut items)| async move {
+                loop {
+                    if let Some(item) = items.pop_front() {
+                        return Some((Ok(item), (paginator, counter, items)));
+                    }
+                    let mut pages = match paginator.take()? {
+                        Ok(pages) => pages,
+                        Err(error) => return Some((Err(error), (None, counter, items))),
+                    };
+                    let page = match pages.next().await? {
+                        Ok(page) => page,
+                        Err(error) => {
+                            return Some((Err($crate::Error::from(error)), (None, counter, items)));
+                        }
+                    };
+                    let page_items = counter.take_items(page.items.unwrap_or_default());
+                    match counter.count_page(page.last_evaluated_key.as_ref()) {
+                        Ok(true) => paginator = Some(Ok(pages)),
+                        Ok(false) => {}
+                        Err(error) => return Some((Err(error), (None, counter, items))),
+                    }
+                    items.extend(page_items);
+                }
+            },
+        )
+    }};
+}
+
+/// Sum the capacities consumed by the pages of a read, keeping the capacity of every index.
+///
+/// The pages of a read all target the same table, so there is at most one consumed capacity.
 pub(crate) fn aggregate_capacity(
     capacities: Vec<types::ConsumedCapacity>,
-) -> types::ConsumedCapacity {
-    let (cap, read, write, table) = capacities.into_iter().fold(
-        (0.0, 0.0, 0.0, None),
-        |(cap, read, write, table), capacity| {
-            (
-                cap + capacity.capacity_units.unwrap_or(0.0),
-                read + capacity.read_capacity_units.unwrap_or(0.0),
-                write + capacity.write_capacity_units.unwrap_or(0.0),
-                table.or(capacity.table_name),
-            )
-        },
-    );
-    types::ConsumedCapacity::builder()
-        .set_table_name(table)
-        .set_capacity_units(Some(cap))
-        .set_read_capacity_units(Some(read))
-        .set_write_capacity_units(Some(write))
-        .build()
+) -> Option<types::ConsumedCapacity> {
+    capacities
+        .into_iter()
+        .collect::<common::capacity::CapacityBreakdown>()
+        .to_consumed_capacities()
+        .into_iter()
+        .next()
 }
 
 /// apply common single read operation settings to a builder
@@ -267,3 +1054,314 @@ macro_rules! apply_multiple_read_operation {
             .table_name($multiple_read_operation.table_name)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aws_sdk_dynamodb::config;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use serde_json::{Value, json};
+    use std::sync;
+
+    /// Interceptor capturing the limits of the queries instead of sending them.
+    #[derive(Debug, Default)]
+    struct LimitCapture(sync::Arc<sync::Mutex<Vec<Option<i32>>>>);
+
+    impl config::Intercept for LimitCapture {
+        fn name(&self) -> &'static str {
+            "limit capture"
+        }
+
+        fn read_before_execution(
+            &self,
+            context: &config::interceptors::BeforeSerializationInterceptorContextRef<'_>,
+            _: &mut config::ConfigBag,
+        ) -> std::result::Result<(), BoxError> {
+            if let Some(input) = context
+                .input()
+                .downcast_ref::<operation::query::QueryInput>()
+            {
+                self.0.lock().unwrap().push(input.limit);
+            }
+            Err("captured".into())
+        }
+    }
+
+    #[test]
+    fn test_pages_limit() {
+        let limits = sync::Arc::default();
+        let client = common::get_test_client(
+            config::Builder::new().interceptor(LimitCapture(sync::Arc::clone(&limits))),
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let mut pages = Pages::new(client.query().limit(10), &client, Some(3), None);
+            assert!(matches!(pages.next().await, Some(Err(_))));
+            let mut pages = Pages::new(client.query().limit(2), &client, Some(3), None);
+            assert!(matches!(pages.next().await, Some(Err(_))));
+            let mut pages = Pages::new(client.query(), &client, Some(0), None);
+            assert!(pages.next().await.is_none());
+        });
+        assert_eq!(*limits.lock().unwrap(), vec![Some(3), Some(2)]);
+    }
+
+    #[test]
+    fn test_send_items_to_channel() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let items = vec![
+            collections::HashMap::from([(
+                "a".to_string(),
+                types::AttributeValue::N("1".to_string()),
+            )]),
+            collections::HashMap::from([(
+                "a".to_string(),
+                types::AttributeValue::N("2".to_string()),
+            )]),
+        ];
+        let (tx, mut rx) = mpsc::channel::<Value>(2);
+        let is_open = runtime
+            .block_on(send_items_to_channel(items.clone(), &tx))
+            .unwrap();
+        assert!(is_open);
+        assert_eq!(rx.try_recv().unwrap(), json!({"a": 1}));
+        assert_eq!(rx.try_recv().unwrap(), json!({"a": 2}));
+        drop(rx);
+        let is_open = runtime.block_on(send_items_to_channel(items, &tx)).unwrap();
+        assert!(!is_open);
+    }
+
+    #[test]
+    fn test_items_output() {
+        let items = vec![
+            collections::HashMap::from([(
+                "a".to_string(),
+                types::AttributeValue::N("1".to_string()),
+            )]),
+            collections::HashMap::from([(
+                "a".to_string(),
+                types::AttributeValue::N("2".to_string()),
+            )]),
+        ];
+        let actual: ItemsOutput<Value> = ItemsOutput::new(Some(items), 2, 3, None).unwrap();
+        let expected = ItemsOutput {
+            consumed_capacity: None,
+            count: 2,
+            items: vec![json!({"a": 1}), json!({"a": 2})],
+            scanned_count: 3,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    fn get_grouped_items() -> Vec<collections::HashMap<String, types::AttributeValue>> {
+        [("b", "1"), ("b", "2"), ("c", "3"), ("b", "4")]
+            .into_iter()
+            .map(|(group, id)| {
+                collections::HashMap::from([
+                    ("a".to_string(), types::AttributeValue::S(group.to_string())),
+                    ("d".to_string(), types::AttributeValue::N(id.to_string())),
+                ])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_group_items() {
+        let actual: IndexMap<String, Vec<Value>> =
+            group_items(Some(get_grouped_items()), "a").unwrap();
+        let expected = IndexMap::from([
+            (
+                "b".to_string(),
+                vec![
+                    json!({"a": "b", "d": 1}),
+                    json!({"a": "b", "d": 2}),
+                    json!({"a": "b", "d": 4}),
+                ],
+            ),
+            ("c".to_string(), vec![json!({"a": "c", "d": 3})]),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_group_items_missing_attribute() {
+        let actual: IndexMap<Option<String>, Vec<Value>> =
+            group_items(Some(get_grouped_items()), "e").unwrap();
+        assert_eq!(actual.keys().collect::<Vec<_>>(), vec![&None]);
+        assert!(group_items::<String, Value>(Some(get_grouped_items()), "e").is_err());
+    }
+
+    #[test]
+    fn test_group_consecutive_items() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let items = stream::iter(get_grouped_items().into_iter().map(Ok));
+        let groups = group_consecutive_items(items, "a".to_string());
+        let actual: Vec<(String, Vec<Value>)> = runtime
+            .block_on(groups.collect::<Vec<_>>())
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let expected = vec![
+            (
+                "b".to_string(),
+                vec![json!({"a": "b", "d": 1}), json!({"a": "b", "d": 2})],
+            ),
+            ("c".to_string(), vec![json!({"a": "c", "d": 3})]),
+            ("b".to_string(), vec![json!({"a": "b", "d": 4})]),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Entity {
+        Number(Value),
+        Text(Value),
+    }
+
+    #[rstest::rstest]
+    #[case::number("b", Some(Entity::Number(json!({"a": "b", "c": 1}))))]
+    #[case::text("d", Some(Entity::Text(json!({"a": "d", "c": 1}))))]
+    #[case::unregistered("e", None)]
+    fn test_type_decoder(#[case] entity_type: &str, #[case] expected: Option<Entity>) {
+        let decoder = TypeDecoder::new("a")
+            .register("b", Entity::Number)
+            .register("d", Entity::Text);
+        let item = collections::HashMap::from([
+            (
+                "a".to_string(),
+                types::AttributeValue::S(entity_type.to_string()),
+            ),
+            ("c".to_string(), types::AttributeValue::N("1".to_string())),
+        ]);
+        let actual = decoder.decode(item).ok();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest::rstest]
+    #[case::unlimited(None, true, vec![true, true, true])]
+    #[case::last_page(Some(MaxPages::Error(1)), false, vec![false])]
+    #[case::truncate(Some(MaxPages::Truncate(2)), true, vec![true, false])]
+    fn test_page_counter(
+        #[case] max_pages: Option<MaxPages>,
+        #[case] has_more: bool,
+        #[case] expected: Vec<bool>,
+    ) {
+        let last_evaluated_key = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let mut counter = PageCounter::new(max_pages, None, None);
+        let actual: Vec<_> = expected
+            .iter()
+            .map(|_| {
+                counter
+                    .count_page(has_more.then_some(&last_evaluated_key))
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_page_counter_error() {
+        let last_evaluated_key = collections::HashMap::new();
+        let mut counter = PageCounter::new(Some(MaxPages::Error(2)), None, None);
+        assert!(counter.count_page(Some(&last_evaluated_key)).unwrap());
+        let actual = counter.count_page(Some(&last_evaluated_key));
+        assert!(matches!(actual, Err(crate::Error::MaxPagesExceeded(2))));
+    }
+
+    #[test]
+    fn test_page_counter_deadline() {
+        let last_evaluated_key = collections::HashMap::new();
+        let mut counter =
+            PageCounter::new(Some(MaxPages::Error(1)), Some(time::Instant::now()), None);
+        assert!(!counter.count_page(Some(&last_evaluated_key)).unwrap());
+    }
+
+    #[test]
+    fn test_page_counter_max_items() {
+        let last_evaluated_key = collections::HashMap::new();
+        let mut counter = PageCounter::new(None, None, Some(3));
+        assert_eq!(counter.take_items(vec![1, 2]), vec![1, 2]);
+        assert!(counter.count_page(Some(&last_evaluated_key)).unwrap());
+        assert_eq!(counter.take_items(vec![3, 4]), vec![3]);
+        assert!(!counter.count_page(Some(&last_evaluated_key)).unwrap());
+        let mut counter = PageCounter::new(None, None, Some(3));
+        counter.count_items(4);
+        assert!(!counter.count_page(Some(&last_evaluated_key)).unwrap());
+    }
+
+    #[test]
+    fn test_multiple_read_input_shared_name_placeholders() {
+        let args: MultipleReadArgs<Value> = MultipleReadArgs {
+            condition: Some(common::condition::ConditionMap::attribute_exists("a-b")),
+            selection: Some(common::selection::SelectionMap::Leaves(vec![
+                "a.b".to_string(),
+                "a-b".to_string(),
+            ])),
+            table_name: "c".to_string(),
+            ..Default::default()
+        };
+        let actual = MultipleReadInput::try_from(args).unwrap();
+        assert_eq!(
+            actual.filter_expression.as_deref(),
+            Some("attribute_exists(#ab)")
+        );
+        assert_eq!(actual.projection_expression.as_deref(), Some("#ab_1, #ab"));
+        let expected = collections::HashMap::from([
+            ("#ab".to_string(), "a-b".to_string()),
+            ("#ab_1".to_string(), "a.b".to_string()),
+        ]);
+        assert_eq!(actual.expression_attribute_names, Some(expected));
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor(collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("b".to_string())),
+            ("c".to_string(), types::AttributeValue::N("1".to_string())),
+            ("d".to_string(), types::AttributeValue::B(vec![0, 1].into())),
+        ]));
+        let token = serde_json::to_value(&cursor).unwrap();
+        assert!(token.is_string());
+        let actual: Cursor = serde_json::from_value(token).unwrap();
+        assert_eq!(actual, cursor);
+        let args: MultipleReadArgs<Value> = MultipleReadArgs {
+            cursor: Some(cursor.clone()),
+            table_name: "e".to_string(),
+            ..Default::default()
+        };
+        let actual = MultipleReadInput::try_from(args).unwrap();
+        assert_eq!(actual.exclusive_start_key, Some(cursor.0));
+    }
+
+    #[rstest::rstest]
+    #[case::not_base64("a+b")]
+    #[case::truncated("UwAAAA")]
+    #[case::unknown_type("WAAAAABhAAAAAA")]
+    fn test_cursor_invalid(#[case] token: &str) {
+        assert!(token.parse::<Cursor>().is_err());
+    }
+
+    #[rstest::rstest]
+    #[case::same_key(Some(b"a".as_slice()), true)]
+    #[case::other_key(Some(b"b".as_slice()), false)]
+    #[case::unverified(None, false)]
+    fn test_cursor_signed(#[case] signing_key: Option<&[u8]>, #[case] expected: bool) {
+        let cursor = Cursor(collections::HashMap::from([(
+            "c".to_string(),
+            types::AttributeValue::S("d".to_string()),
+        )]));
+        let token = cursor.encode(Some(b"a"));
+        let actual = Cursor::decode(&token, signing_key);
+        assert_eq!(actual.ok() == Some(cursor), expected);
+    }
+}
@@ -1,16 +1,17 @@
-use crate::{common, read};
+use crate::{common, common::retry::WithRetryPolicy as _, read};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
-use serde::Serialize;
+use aws_sdk_dynamodb::{Client, operation, types};
+use indexmap::IndexMap;
+use serde::{Serialize, de::DeserializeOwned};
 use serde_dynamo::{Error, Result};
 use std::collections;
 
 /// get item operation
 #[derive(Clone, Debug, Default, PartialEq)]
-struct GetItemInput {
-    keys: collections::HashMap<String, types::AttributeValue>,
-    return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
-    single_read_operation: read::common::SingleReadInput,
+pub(crate) struct GetItemInput {
+    pub(crate) keys: collections::HashMap<String, types::AttributeValue>,
+    pub(crate) return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    pub(crate) single_read_operation: read::common::SingleReadInput,
 }
 
 /// Get item operation.
@@ -48,12 +49,13 @@ pub struct GetItem<T> {
     pub single_read_args: read::common::SingleReadArgs,
 }
 
-impl<T: Serialize> TryFrom<GetItem<T>> for GetItemInput {
-    type Error = Error;
-
-    fn try_from(get_item: GetItem<T>) -> Result<Self> {
+impl GetItemInput {
+    pub(crate) fn new<T: Serialize>(
+        get_item: GetItem<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
         let single_operation: read::common::SingleReadInput = get_item.single_read_args.into();
-        let keys = get_item.keys.try_into()?;
+        let keys = get_item.keys.serialize(serializer)?;
         let operation = Self {
             keys,
             return_consumed_capacity: get_item.return_consumed_capacity,
@@ -63,30 +65,265 @@ impl<T: Serialize> TryFrom<GetItem<T>> for GetItemInput {
     }
 }
 
+impl<T: Serialize> TryFrom<GetItem<T>> for GetItemInput {
+    type Error = Error;
+
+    fn try_from(get_item: GetItem<T>) -> Result<Self> {
+        Self::new(get_item, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T> GetItem<T> {
+    fn into_exists_get_item(mut self) -> Self {
+        self.single_read_args.selection = Some(common::selection::SelectionMap::Leaves(vec![
+            self.keys.partition_key.name.clone(),
+        ]));
+        self
+    }
+}
+
 impl<T: Serialize> GetItem<T> {
     /// Execute the get item operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::get_item::GetItemOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the get item operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.get_item", err)
+        tracing::instrument(name = "dynamodb_crud.get_item", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<
-        operation::get_item::GetItemOutput,
-        error::SdkError<operation::get_item::GetItemError>,
-    > {
-        let get_item: GetItemInput = self.try_into().map_err(error::BuildError::other)?;
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::get_item::GetItemOutput, crate::Error> {
+        let retry_policy = self.single_read_args.retry_policy.clone();
+        let get_item = GetItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
         let builder = client
             .get_item()
             .set_key(Some(get_item.keys))
             .set_return_consumed_capacity(get_item.return_consumed_capacity);
         crate::apply_single_read_operation!(builder, get_item.single_read_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
             .send()
             .await
+            .map_err(crate::Error::from)
+    }
+
+    /// Execute the get item operation, deserializing the item if it exists.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let get_item = read::get_item::GetItem {
+    ///     keys: common::key::Keys {
+    ///         partition_key: common::key::Key {
+    ///             name: "id".to_string(),
+    ///             value: "1".to_string(),
+    ///         },
+    ///         ..Default::default()
+    ///     },
+    ///     single_read_args: read::common::SingleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// if let Some(user) = get_item.send_as::<User>(client).await? {
+    ///     println!("{}", user.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.get_item.send_as", skip_all, err)
+    )]
+    pub async fn send_as<O: DeserializeOwned>(
+        self,
+        client: &Client,
+    ) -> Result<Option<O>, crate::Error> {
+        let output = self.send(client).await?;
+        get_item_as(output)
+    }
+
+    /// Execute the get item operation, stripping the attributes hidden by the policy from the
+    /// item before deserializing it.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde_json::Value;
+    /// use std::collections::HashSet;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let get_item = read::get_item::GetItem {
+    ///     keys: common::key::Keys {
+    ///         partition_key: common::key::Key {
+    ///             name: "id".to_string(),
+    ///             value: "1".to_string(),
+    ///         },
+    ///         ..Default::default()
+    ///     },
+    ///     single_read_args: read::common::SingleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let policy = common::visibility::AttributeVisibility::Deny(HashSet::from([
+    ///     "passwordHash".to_string(),
+    /// ]));
+    /// let user = get_item.send_as_with_policy::<Value>(client, &policy).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.get_item.send_as_with_policy", skip_all, err)
+    )]
+    pub async fn send_as_with_policy<O: DeserializeOwned>(
+        self,
+        client: &Client,
+        policy: &impl common::visibility::AttributePolicy,
+    ) -> Result<Option<O>, crate::Error> {
+        let mut output = self.send(client).await?;
+        if let Some(item) = &mut output.item {
+            policy.apply(item);
+        }
+        get_item_as(output)
+    }
+
+    /// Execute the get item operation projecting only the given top-level attributes,
+    /// returning whether each of them exists on the item, in the given order.
+    ///
+    /// Returns `None` if the item does not exist. Overrides any selection.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let get_item = read::get_item::GetItem {
+    ///     keys: common::key::Keys {
+    ///         partition_key: common::key::Key {
+    ///             name: "id".to_string(),
+    ///             value: "1".to_string(),
+    ///         },
+    ///         ..Default::default()
+    ///     },
+    ///     single_read_args: read::common::SingleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let attributes = vec!["emailV2".to_string(), "legacyEmail".to_string()];
+    /// if let Some(exists) = get_item.attributes_exist(client, attributes).await? {
+    ///     println!("migrated: {}", exists["emailV2"] && !exists["legacyEmail"]);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.get_item.attributes_exist", skip_all, err)
+    )]
+    pub async fn attributes_exist(
+        mut self,
+        client: &Client,
+        attribute_names: Vec<String>,
+    ) -> Result<Option<IndexMap<String, bool>>, crate::Error> {
+        self.single_read_args.selection = Some(common::selection::SelectionMap::Leaves(
+            attribute_names.clone(),
+        ));
+        let output = self.send(client).await?;
+        let presence = output
+            .item
+            .map(|item| get_attributes_presence(&item, attribute_names));
+        Ok(presence)
+    }
+
+    /// Execute the get item operation projecting only the partition key, returning whether
+    /// the item exists.
+    ///
+    /// Overrides any selection.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let get_item = read::get_item::GetItem {
+    ///     keys: common::key::Keys {
+    ///         partition_key: common::key::Key {
+    ///             name: "id".to_string(),
+    ///             value: "1".to_string(),
+    ///         },
+    ///         ..Default::default()
+    ///     },
+    ///     single_read_args: read::common::SingleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// if !get_item.exists(client).await? {
+    ///     println!("user not found");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.get_item.exists", skip_all, err)
+    )]
+    pub async fn exists(self, client: &Client) -> Result<bool, crate::Error> {
+        let output = self.into_exists_get_item().send(client).await?;
+        Ok(output.item.is_some())
     }
 }
 
+/// Map each attribute name to whether the item has it.
+fn get_attributes_presence(
+    item: &collections::HashMap<String, types::AttributeValue>,
+    attribute_names: Vec<String>,
+) -> IndexMap<String, bool> {
+    attribute_names
+        .into_iter()
+        .map(|name| {
+            let exists = item.contains_key(&name);
+            (name, exists)
+        })
+        .collect()
+}
+
+/// Deserialize the item of a get item output, if any.
+fn get_item_as<O: DeserializeOwned>(
+    output: operation::get_item::GetItemOutput,
+) -> Result<Option<O>, crate::Error> {
+    output
+        .item
+        .map(serde_dynamo::from_item)
+        .transpose()
+        .map_err(crate::Error::Deserialization)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +390,7 @@ mod tests {
             ),
             single_read_args: read::common::SingleReadArgs {
                 consistent_read: Some(false),
+                retry_policy: None,
                 selection: Some(
                     common::selection::SelectionMap::Leaves(
                         vec![
@@ -205,4 +443,102 @@ mod tests {
         let actual: GetItemInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case::found(
+        operation::get_item::GetItemOutput::builder()
+            .item(
+                "a",
+                types::AttributeValue::S(
+                    "b".to_string()
+                ),
+            )
+            .build(),
+        Some(
+            serde_json::json!(
+                {
+                    "a": "b"
+                }
+            )
+        )
+    )]
+    #[case::missing(
+        operation::get_item::GetItemOutput::builder().build(),
+        None
+    )]
+    fn test_get_item_as(
+        #[case] output: operation::get_item::GetItemOutput,
+        #[case] expected: Option<Value>,
+    ) {
+        let actual = get_item_as(output).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::selection(
+        GetItem {
+            keys: common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::String(
+                        "b".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            single_read_args: read::common::SingleReadArgs {
+                selection: Some(
+                    common::selection::SelectionMap::Leaves(
+                        vec![
+                            "c".to_string(),
+                        ]
+                    )
+                ),
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        GetItem {
+            keys: common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::String(
+                        "b".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            single_read_args: read::common::SingleReadArgs {
+                selection: Some(
+                    common::selection::SelectionMap::Leaves(
+                        vec![
+                            "a".to_string(),
+                        ]
+                    )
+                ),
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    )]
+    fn test_get_item_exists(#[case] args: GetItem<Value>, #[case] expected: GetItem<Value>) {
+        let actual = args.into_exists_get_item();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_attributes_presence() {
+        let item = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let actual = get_attributes_presence(&item, vec!["c".to_string(), "a".to_string()]);
+        let expected = IndexMap::from([("c".to_string(), false), ("a".to_string(), true)]);
+        assert_eq!(
+            actual.into_iter().collect::<Vec<_>>(),
+            expected.into_iter().collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,446 @@
+use crate::{common, common::retry::WithRetryPolicy as _, read};
+
+use aws_sdk_dynamodb::{Client, error, types};
+use serde::Serialize;
+use std::{collections, sync, time};
+use tokio::sync::{mpsc, oneshot};
+
+/// Maximum number of keys DynamoDB accepts in a single BatchGetItem call.
+const MAX_BATCH_SIZE: usize = 100;
+
+type Item = collections::HashMap<String, types::AttributeValue>;
+
+/// Error shared by every load resolved by the same BatchGetItem call.
+pub type LoadError = sync::Arc<crate::Error>;
+
+type Responder = oneshot::Sender<Result<Option<Item>, LoadError>>;
+
+/// a pending load waiting to be dispatched
+#[derive(Debug)]
+struct LoadRequest {
+    keys: Item,
+    responder: Responder,
+}
+
+/// Arguments for a loader.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoaderArgs {
+    /// The maximum number of distinct keys merged into a single BatchGetItem call.
+    ///
+    /// Values above the DynamoDB limit of 100 keys are capped.
+    pub max_batch_size: usize,
+    /// Read arguments (table name, consistent read, selection) shared by every load.
+    ///
+    /// If a selection is given, it must include the key attributes so that returned
+    /// items can be matched back to their callers.
+    pub single_read_args: read::common::SingleReadArgs,
+    /// How long to wait for further loads after the first one before dispatching the batch.
+    pub window: time::Duration,
+}
+
+impl Default for LoaderArgs {
+    fn default() -> Self {
+        Self {
+            max_batch_size: MAX_BATCH_SIZE,
+            single_read_args: read::common::SingleReadArgs::default(),
+            window: time::Duration::from_millis(2),
+        }
+    }
+}
+
+/// Loader coalescing concurrent get item requests into batch get item calls.
+///
+/// Every load issued within the configured window is merged into a single BatchGetItem
+/// call, duplicated keys are requested once, and each caller is resolved with its own item.
+/// Unprocessed keys are retried with exponential backoff and jitter, following the retry
+/// policy of the read arguments if any, and the loads whose keys are still unprocessed after
+/// the last attempt fail with [`crate::Error::Throttling`].
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let loader = read::loader::Loader::new(
+///     client.clone(),
+///     read::loader::LoaderArgs {
+///         single_read_args: read::common::SingleReadArgs {
+///             table_name: "users".to_string(),
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     },
+/// );
+/// let keys = common::key::Keys {
+///     partition_key: common::key::Key {
+///         name: "id".to_string(),
+///         value: "1".to_string(),
+///     },
+///     ..Default::default()
+/// };
+/// let item = loader.load(keys).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Loader {
+    sender: mpsc::UnboundedSender<LoadRequest>,
+}
+
+impl Loader {
+    /// Create a loader and spawn its background dispatch task.
+    ///
+    /// Must be called from within a Tokio runtime. The task stops once every clone
+    /// of the loader has been dropped.
+    pub fn new(client: Client, loader_args: LoaderArgs) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(client, loader_args, receiver));
+        Self { sender }
+    }
+
+    /// Load a single item by primary key.
+    ///
+    /// Returns `None` if the item does not exist.
+    pub async fn load<T: Serialize>(
+        &self,
+        keys: common::key::Keys<T>,
+    ) -> Result<Option<Item>, LoadError> {
+        let keys = keys
+            .try_into()
+            .map_err(|error| sync::Arc::new(crate::Error::Serialization(error)))?;
+        let (responder, receiver) = oneshot::channel();
+        self.sender
+            .send(LoadRequest { keys, responder })
+            .map_err(|_| get_closed_error())?;
+        receiver.await.map_err(|_| get_closed_error())?
+    }
+}
+
+fn get_closed_error() -> LoadError {
+    let error = error::BuildError::other("loader is no longer running");
+    sync::Arc::new(aws_sdk_dynamodb::Error::from(error).into())
+}
+
+async fn run(
+    client: Client,
+    loader_args: LoaderArgs,
+    mut receiver: mpsc::UnboundedReceiver<LoadRequest>,
+) {
+    let retry_policy = loader_args.single_read_args.retry_policy.clone();
+    let backoff = common::retry::Backoff::new(
+        retry_policy.as_ref(),
+        read::batch_get_item::BATCH_GET_BACKOFF,
+    );
+    let max_batch_size = loader_args.max_batch_size.clamp(1, MAX_BATCH_SIZE);
+    let single_read_operation: read::common::SingleReadInput = loader_args.single_read_args.into();
+    while let Some(request) = receiver.recv().await {
+        let deadline = tokio::time::Instant::now() + loader_args.window;
+        let mut requests = vec![request];
+        while requests.len() < max_batch_size {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(request)) => requests.push(request),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        tokio::spawn(dispatch(
+            client.clone(),
+            single_read_operation.clone(),
+            retry_policy.clone(),
+            backoff,
+            requests,
+        ));
+    }
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "dynamodb_crud.loader.dispatch", skip_all)
+)]
+async fn dispatch(
+    client: Client,
+    single_read_operation: read::common::SingleReadInput,
+    retry_policy: Option<common::retry::RetryPolicy>,
+    backoff: common::retry::Backoff,
+    requests: Vec<LoadRequest>,
+) {
+    let mut pending = group_requests(requests);
+    let mut attempt = 0;
+    while !pending.is_empty() {
+        let keys = pending.iter().map(|(keys, _)| keys.clone()).collect();
+        let keys_and_attributes = types::KeysAndAttributes::builder()
+            .set_consistent_read(single_read_operation.consistent_read)
+            .set_expression_attribute_names(
+                single_read_operation.expression_attribute_names.clone(),
+            )
+            .set_keys(Some(keys))
+            .set_projection_expression(single_read_operation.projection_expression.clone())
+            .build()
+            .unwrap();
+        let result = client
+            .batch_get_item()
+            .request_items(&single_read_operation.table_name, keys_and_attributes)
+            .customize()
+            .with_retry_policy(&client, retry_policy.as_ref())
+            .send()
+            .await;
+        let output = match result {
+            Ok(output) => output,
+            Err(error) => {
+                reject(pending, sync::Arc::new(crate::Error::from(error)));
+                return;
+            }
+        };
+        let items = output
+            .responses
+            .and_then(|mut responses| responses.remove(&single_read_operation.table_name))
+            .unwrap_or_default();
+        let unprocessed_keys = output
+            .unprocessed_keys
+            .and_then(|mut unprocessed| unprocessed.remove(&single_read_operation.table_name))
+            .map(|keys_and_attributes| keys_and_attributes.keys)
+            .unwrap_or_default();
+        let mut unresolved = Vec::new();
+        for (keys, responders) in pending {
+            if unprocessed_keys.contains(&keys) {
+                unresolved.push((keys, responders));
+                continue;
+            }
+            let item = find_item(&keys, &items);
+            for responder in responders {
+                let _ = responder.send(Ok(item.cloned()));
+            }
+        }
+        pending = unresolved;
+        attempt += 1;
+        if pending.is_empty() {
+            return;
+        }
+        if attempt >= backoff.max_attempts {
+            reject(pending, get_unprocessed_error(attempt));
+            return;
+        }
+        backoff.sleep("BatchGetItem", attempt).await;
+    }
+}
+
+/// Resolve every pending load with the error.
+fn reject(pending: Vec<(Item, Vec<Responder>)>, error: LoadError) {
+    for (_, responders) in pending {
+        for responder in responders {
+            let _ = responder.send(Err(error.clone()));
+        }
+    }
+}
+
+/// Get the error of the loads whose keys are still unprocessed after the last attempt.
+///
+/// Keys are left unprocessed when the read exceeds the capacity of the table, so the loads
+/// fail as throttled.
+fn get_unprocessed_error(attempts: u32) -> LoadError {
+    let exception = types::error::ProvisionedThroughputExceededException::builder()
+        .message(format!("keys still unprocessed after {attempts} attempts"))
+        .build();
+    let error = aws_sdk_dynamodb::Error::ProvisionedThroughputExceededException(exception);
+    sync::Arc::new(error.into())
+}
+
+/// group requests by key so that each key is requested only once
+fn group_requests(requests: Vec<LoadRequest>) -> Vec<(Item, Vec<Responder>)> {
+    let mut groups: Vec<(Item, Vec<Responder>)> = Vec::with_capacity(requests.len());
+    for request in requests {
+        match groups.iter_mut().find(|(keys, _)| *keys == request.keys) {
+            Some((_, responders)) => responders.push(request.responder),
+            None => groups.push((request.keys, vec![request.responder])),
+        }
+    }
+    groups
+}
+
+fn find_item<'a>(keys: &Item, items: &'a [Item]) -> Option<&'a Item> {
+    items.iter().find(|item| {
+        keys.iter()
+            .all(|(name, value)| item.get(name) == Some(value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aws_smithy_runtime_api::{
+        client::{http, orchestrator::HttpRequest},
+        http::Response,
+    };
+    use aws_smithy_types::body::SdkBody;
+    use rstest::rstest;
+    use std::sync::atomic;
+
+    #[rstest]
+    #[case::found(
+        collections::HashMap::from(
+            [
+                (
+                    "a".to_string(),
+                    types::AttributeValue::S(
+                        "b".to_string()
+                    )
+                ),
+            ]
+        ),
+        vec![
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::S(
+                            "c".to_string()
+                        )
+                    ),
+                ]
+            ),
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        )
+                    ),
+                    (
+                        "d".to_string(),
+                        types::AttributeValue::S(
+                            "e".to_string()
+                        )
+                    ),
+                ]
+            ),
+        ],
+        Some(1)
+    )]
+    #[case::sort_key_mismatch(
+        collections::HashMap::from(
+            [
+                (
+                    "a".to_string(),
+                    types::AttributeValue::S(
+                        "b".to_string()
+                    )
+                ),
+                (
+                    "c".to_string(),
+                    types::AttributeValue::N(
+                        "1".to_string()
+                    )
+                ),
+            ]
+        ),
+        vec![
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        )
+                    ),
+                    (
+                        "c".to_string(),
+                        types::AttributeValue::N(
+                            "2".to_string()
+                        )
+                    ),
+                ]
+            ),
+        ],
+        None
+    )]
+    fn test_find_item(
+        #[case] keys: Item,
+        #[case] items: Vec<Item>,
+        #[case] expected: Option<usize>,
+    ) {
+        let actual = find_item(&keys, &items);
+        let expected = expected.map(|index| &items[index]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    fn test_group_requests() {
+        let keys = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let other_keys = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("c".to_string()),
+        )]);
+        let requests = [&keys, &other_keys, &keys]
+            .into_iter()
+            .map(|keys| LoadRequest {
+                keys: keys.clone(),
+                responder: oneshot::channel().0,
+            })
+            .collect();
+        let actual: Vec<_> = group_requests(requests)
+            .into_iter()
+            .map(|(keys, responders)| (keys, responders.len()))
+            .collect();
+        assert_eq!(actual, vec![(keys, 2), (other_keys, 1)]);
+    }
+
+    /// connector leaving every requested key unprocessed, counting its calls
+    #[derive(Clone, Debug, Default)]
+    struct UnprocessedConnector {
+        calls: sync::Arc<atomic::AtomicU32>,
+    }
+
+    impl http::HttpConnector for UnprocessedConnector {
+        fn call(&self, _request: HttpRequest) -> http::HttpConnectorFuture {
+            self.calls.fetch_add(1, atomic::Ordering::SeqCst);
+            let body =
+                r#"{"Responses":{"a":[]},"UnprocessedKeys":{"a":{"Keys":[{"b":{"S":"c"}}]}}}"#;
+            let response = Response::new(200.try_into().unwrap(), SdkBody::from(body));
+            http::HttpConnectorFuture::ready(Ok(response))
+        }
+    }
+
+    #[test]
+    fn test_loader_unprocessed() {
+        let connector = UnprocessedConnector::default();
+        let shared_connector = http::SharedHttpConnector::new(connector.clone());
+        let client = common::get_test_client(
+            aws_sdk_dynamodb::Config::builder()
+                .credentials_provider(aws_sdk_dynamodb::config::Credentials::new(
+                    "d", "e", None, None, "f",
+                ))
+                .http_client(http::http_client_fn(move |_, _| shared_connector.clone()))
+                .region(aws_sdk_dynamodb::config::Region::new("us-east-1")),
+        );
+        let loader_args = LoaderArgs {
+            single_read_args: read::common::SingleReadArgs {
+                retry_policy: Some(common::retry::RetryPolicy {
+                    initial_backoff: Some(time::Duration::from_millis(1)),
+                    max_attempts: Some(3),
+                    ..Default::default()
+                }),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let keys = common::key::Keys {
+            partition_key: common::key::Key {
+                name: "b".to_string(),
+                value: "c".to_string(),
+            },
+            ..Default::default()
+        };
+        let actual = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(async { Loader::new(client, loader_args).load(keys).await });
+        assert!(actual.unwrap_err().is_throttling());
+        assert_eq!(connector.calls.load(atomic::Ordering::SeqCst), 3);
+    }
+}
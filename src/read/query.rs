@@ -1,8 +1,12 @@
-use crate::{common, read};
+use crate::{common, common::retry::WithRetryPolicy as _, read, table};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
-use serde::Serialize;
+use aws_sdk_dynamodb::{Client, operation, types};
+use futures_util::{Stream, StreamExt};
+use indexmap::IndexMap;
+use serde::{Serialize, de::DeserializeOwned, de::Error as _};
 use serde_dynamo::{Error, Result};
+use std::{cell, collections, hash};
+use tokio::sync::mpsc;
 
 /// query operation
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -37,6 +41,11 @@ struct QueryInput {
 /// ```
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Query<T> {
+    /// The key schema of the queried table or index, validating the key conditions before
+    /// sending the request.
+    ///
+    /// If `None`, mismatched key conditions are only rejected by DynamoDB.
+    pub key_schema: Option<table::common::KeySchema>,
     /// Additional read operation arguments (table name, filter, selection, etc.).
     pub multiple_read_args: read::common::MultipleReadArgs<T>,
     /// The partition key value to query for.
@@ -46,13 +55,15 @@ pub struct Query<T> {
     /// Whether to scan the index forward (ascending) or backward (descending).
     pub scan_index_forward: Option<bool>,
     /// Optional condition to apply to the sort key.
-    pub sort_key_condition: Option<common::condition::KeyCondition<T>>,
+    pub sort_key_condition: Option<common::condition::SortKey<T>>,
 }
 
 impl<T: Serialize> Query<T> {
     fn get_key_condition_expression(
         partition_key: common::key::Key<T>,
-        sort_key: Option<common::condition::KeyCondition<T>>,
+        sort_key: Option<common::condition::SortKey<T>>,
+        placeholders: &mut common::PlaceholderAllocator,
+        serializer: &impl common::serializer::ValueSerializer,
     ) -> Result<common::ExpressionInput> {
         let condition = common::condition::Condition::Equals(partition_key.value);
         let partition_key = common::condition::KeyCondition {
@@ -61,20 +72,347 @@ impl<T: Serialize> Query<T> {
         };
         let mut keys = vec![partition_key];
         if let Some(sort_key) = sort_key {
-            keys.push(sort_key);
+            keys.push(sort_key.into());
         }
-        common::condition::KeyCondition::get_expression_operation(keys)
+        common::condition::KeyCondition::get_expression_operation(keys, placeholders, serializer)
     }
 }
 
-impl<T: Serialize> TryFrom<Query<T>> for QueryInput {
-    type Error = Error;
+impl<T> Query<T> {
+    /// Return only the attributes projected into the queried index, without fetching the
+    /// other attributes from the base table.
+    ///
+    /// Sets `Select::AllProjectedAttributes` and clears any selection. Fails if no index is
+    /// queried, since DynamoDB rejects this select on a base table.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::read;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let output = read::query::Query::<String>::builder("users", "email", "jane@example.com")
+    ///     .index("byEmail")
+    ///     .build()
+    ///     .projected_only()?
+    ///     .send(client)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn projected_only(mut self) -> std::result::Result<Self, crate::Error> {
+        if self.multiple_read_args.index_name.is_none() {
+            return Err(crate::Error::InvalidSelect(
+                "only the attributes projected into an index can be selected, but the query \
+                    reads the base table"
+                    .to_string(),
+            ));
+        }
+        self.multiple_read_args.select = Some(types::Select::AllProjectedAttributes);
+        self.multiple_read_args.selection = None;
+        Ok(self)
+    }
 
-    fn try_from(query: Query<T>) -> Result<Self> {
-        let mut multiple_read_operation: read::common::MultipleReadInput =
-            query.multiple_read_args.try_into()?;
-        let key_condition_operation =
-            Query::get_key_condition_expression(query.partition_key, query.sort_key_condition)?;
+    fn into_count_query(mut self) -> Self {
+        self.multiple_read_args.select = Some(types::Select::Count);
+        self.multiple_read_args.selection = None;
+        self
+    }
+
+    /// Check that the partition key is only matched by equality.
+    ///
+    /// DynamoDB rejects other conditions on the partition key, as well as filters on it.
+    fn validate_partition_key(&self) -> std::result::Result<(), crate::Error> {
+        let name = &self.partition_key.name;
+        let hint = "queries only match the partition key by equality, query a global secondary \
+            index keyed on another attribute or scan the table instead";
+        if self
+            .sort_key_condition
+            .as_ref()
+            .is_some_and(|sort_key_condition| &sort_key_condition.name == name)
+        {
+            return Err(crate::Error::InvalidKeyCondition(format!(
+                "sort key condition on the partition key {name}: {hint}"
+            )));
+        }
+        if self
+            .multiple_read_args
+            .condition
+            .as_ref()
+            .is_some_and(|condition| condition.has_attribute(name))
+        {
+            return Err(crate::Error::InvalidKeyCondition(format!(
+                "filter condition on the partition key {name}: {hint}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the key conditions against the key schema, if any.
+    fn validate_key_schema(&self) -> std::result::Result<(), crate::Error> {
+        let Some(key_schema) = &self.key_schema else {
+            return Ok(());
+        };
+        if self.partition_key.name != key_schema.partition_key.name {
+            return Err(crate::Error::InvalidKeyCondition(format!(
+                "partition key {} does not match the partition key {} of the key schema",
+                self.partition_key.name, key_schema.partition_key.name
+            )));
+        }
+        match (&self.sort_key_condition, &key_schema.sort_key) {
+            (Some(sort_key_condition), None) => Err(crate::Error::InvalidKeyCondition(format!(
+                "sort key condition on {} but the key schema has no sort key",
+                sort_key_condition.name
+            ))),
+            (Some(sort_key_condition), Some(sort_key))
+                if sort_key_condition.name != sort_key.name =>
+            {
+                Err(crate::Error::InvalidKeyCondition(format!(
+                    "sort key condition on {} does not match the sort key {} of the key schema",
+                    sort_key_condition.name, sort_key.name
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Project only the given key attributes.
+    ///
+    /// If no key names are given, the keys of the key schema are used, provided the base table
+    /// is queried: the primary key of the items can't be derived from the key schema of an
+    /// index.
+    pub(crate) fn into_keys_query(
+        mut self,
+        mut key_names: Vec<String>,
+    ) -> std::result::Result<(Self, Vec<String>), crate::Error> {
+        if key_names.is_empty() {
+            let key_schema = self
+                .key_schema
+                .as_ref()
+                .filter(|_| self.multiple_read_args.index_name.is_none())
+                .ok_or_else(|| {
+                    crate::Error::validation(
+                        "the primary key attribute names are required, unless the key schema \
+                            of the queried base table is given",
+                    )
+                })?;
+            key_names.push(key_schema.partition_key.name.clone());
+            if let Some(sort_key) = &key_schema.sort_key {
+                key_names.push(sort_key.name.clone());
+            }
+        }
+        self.multiple_read_args.select = Some(types::Select::SpecificAttributes);
+        self.multiple_read_args.selection =
+            Some(common::selection::SelectionMap::Leaves(key_names.clone()));
+        Ok((self, key_names))
+    }
+}
+
+impl<T> Query<T> {
+    /// Start building a query of the given table, for the items with the given partition key.
+    ///
+    /// ```rust
+    /// use dynamodb_crud::read;
+    ///
+    /// let query: read::query::Query<String> =
+    ///     read::query::Query::builder("orders", "userId", "1")
+    ///         .sort_key_begins_with("createdAt", "2024-")
+    ///         .descending()
+    ///         .limit(10)
+    ///         .build();
+    /// ```
+    pub fn builder(
+        table_name: impl Into<String>,
+        partition_key_name: impl Into<String>,
+        partition_key_value: impl Into<T>,
+    ) -> QueryBuilder<T> {
+        QueryBuilder {
+            condition: None,
+            consistent_read: None,
+            index_name: None,
+            limit: None,
+            max_items: None,
+            partition_key: common::key::Key {
+                name: partition_key_name.into(),
+                value: partition_key_value.into(),
+            },
+            scan_index_forward: None,
+            sort_key_condition: None,
+            table_name: table_name.into(),
+        }
+    }
+}
+
+/// Builder of a [`Query`], see [`Query::builder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryBuilder<T> {
+    condition: Option<common::condition::ConditionMap<T>>,
+    consistent_read: Option<bool>,
+    index_name: Option<String>,
+    limit: Option<i32>,
+    max_items: Option<usize>,
+    partition_key: common::key::Key<T>,
+    scan_index_forward: Option<bool>,
+    sort_key_condition: Option<common::condition::SortKey<T>>,
+    table_name: String,
+}
+
+impl<T> QueryBuilder<T> {
+    /// Apply any key condition to the sort key.
+    pub fn sort_key_condition(
+        mut self,
+        name: impl Into<String>,
+        condition: common::condition::SortKeyCondition<T>,
+    ) -> Self {
+        self.sort_key_condition = Some(common::condition::SortKey {
+            condition,
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Match the sort key by equality.
+    pub fn sort_key_equals(self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.sort_key_condition(
+            name,
+            common::condition::SortKeyCondition::Equals(value.into()),
+        )
+    }
+
+    /// Match the sort keys starting with the prefix.
+    pub fn sort_key_begins_with(self, name: impl Into<String>, prefix: impl Into<String>) -> Self {
+        self.sort_key_condition(
+            name,
+            common::condition::SortKeyCondition::BeginsWith(prefix.into()),
+        )
+    }
+
+    /// Match the sort keys between two values, inclusive.
+    pub fn sort_key_between(
+        self,
+        name: impl Into<String>,
+        low: impl Into<T>,
+        high: impl Into<T>,
+    ) -> Self {
+        self.sort_key_condition(
+            name,
+            common::condition::SortKeyCondition::Between(low.into(), high.into()),
+        )
+    }
+
+    /// Query a secondary index instead of the table.
+    pub fn index(mut self, index_name: impl Into<String>) -> Self {
+        self.index_name = Some(index_name.into());
+        self
+    }
+
+    /// Evaluate at most the given number of items per page.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Collect at most the given number of matching items across pages.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Return the items in descending sort key order.
+    pub fn descending(mut self) -> Self {
+        self.scan_index_forward = Some(false);
+        self
+    }
+
+    /// Use a strongly consistent read.
+    pub fn consistent_read(mut self) -> Self {
+        self.consistent_read = Some(true);
+        self
+    }
+
+    /// Filter the matching items, combined with any previous filter.
+    pub fn filter(mut self, condition: impl Into<common::condition::ConditionMap<T>>) -> Self {
+        let condition = match self.condition {
+            Some(previous) => previous.and(condition.into()),
+            None => condition.into(),
+        };
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Build the query operation.
+    pub fn build(self) -> Query<T> {
+        Query {
+            key_schema: None,
+            multiple_read_args: read::common::MultipleReadArgs {
+                condition: self.condition,
+                consistent_read: self.consistent_read,
+                cursor: None,
+                deadline: None,
+                exclusive_start_key: None,
+                index_name: self.index_name,
+                limit: self.limit,
+                max_items: self.max_items,
+                max_pages: None,
+                placeholder_naming: None,
+                prune_empty_maps: false,
+                retry_policy: None,
+                select: None,
+                selection: None,
+                table_name: self.table_name,
+            },
+            partition_key: self.partition_key,
+            return_consumed_capacity: None,
+            scan_index_forward: self.scan_index_forward,
+            sort_key_condition: self.sort_key_condition,
+        }
+    }
+}
+
+/// Extract the keys of an item, the first key name being the partition key.
+fn get_keys<D: DeserializeOwned>(
+    mut item: collections::HashMap<String, types::AttributeValue>,
+    key_names: &[String],
+) -> Result<common::key::Keys<D>> {
+    let mut keys = key_names.iter().map(|name| {
+        let value = item
+            .remove(name)
+            .ok_or_else(|| Error::custom(format!("missing key attribute {name}")))?;
+        let key = common::key::Key {
+            name: name.clone(),
+            value: serde_dynamo::from_attribute_value(value)?,
+        };
+        Ok(key)
+    });
+    let partition_key = keys
+        .next()
+        .unwrap_or_else(|| Err(Error::custom("missing partition key name")))?;
+    let sort_key = keys.next().transpose()?;
+    let keys = common::key::Keys {
+        partition_key,
+        sort_key,
+    };
+    Ok(keys)
+}
+
+impl QueryInput {
+    fn new<T: Serialize>(
+        query: Query<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let placeholders = &mut common::PlaceholderAllocator::new(
+            query.multiple_read_args.placeholder_naming.clone(),
+        );
+        let mut multiple_read_operation = read::common::MultipleReadInput::new(
+            query.multiple_read_args,
+            placeholders,
+            serializer,
+        )?;
+        let key_condition_operation = Query::get_key_condition_expression(
+            query.partition_key,
+            query.sort_key_condition,
+            placeholders,
+            serializer,
+        )?;
         let key_condition_expression = key_condition_operation.merge_into(
             &mut multiple_read_operation.expression_attribute_names,
             &mut multiple_read_operation.expression_attribute_values,
@@ -87,29 +425,990 @@ impl<T: Serialize> TryFrom<Query<T>> for QueryInput {
         };
         Ok(operation)
     }
+
+    fn into_builder(self, client: &Client) -> operation::query::builders::QueryFluentBuilder {
+        let builder = client
+            .query()
+            .key_condition_expression(self.key_condition_expression)
+            .set_return_consumed_capacity(self.return_consumed_capacity)
+            .set_scan_index_forward(self.scan_index_forward);
+        crate::apply_multiple_read_operation!(builder, self.multiple_read_operation)
+    }
+
+    fn into_pages(
+        self,
+        client: &Client,
+    ) -> read::common::Pages<operation::query::builders::QueryFluentBuilder> {
+        let max_items = self.multiple_read_operation.max_items;
+        let retry_policy = self.multiple_read_operation.retry_policy.clone();
+        read::common::Pages::new(self.into_builder(client), client, max_items, retry_policy)
+    }
+}
+
+impl<T: Serialize> TryFrom<Query<T>> for QueryInput {
+    type Error = Error;
+
+    fn try_from(query: Query<T>) -> Result<Self> {
+        Self::new(query, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T: Serialize> Query<T> {
+    /// Validate the key conditions, then convert the query with the given serializer.
+    fn into_input(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> std::result::Result<QueryInput, crate::Error> {
+        self.validate_partition_key()?;
+        self.validate_key_schema()?;
+        QueryInput::new(self, serializer).map_err(crate::Error::Serialization)
+    }
 }
 
 impl<T: Serialize> Query<T> {
     /// Execute the query operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::query::QueryOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the query operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.query", err)
+        tracing::instrument(name = "dynamodb_crud.query", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<operation::query::QueryOutput, error::SdkError<operation::query::QueryError>> {
-        let query: QueryInput = self.try_into().map_err(error::BuildError::other)?;
-        let builder = client
-            .query()
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::query::QueryOutput, crate::Error> {
+        let query = self.into_input(serializer)?;
+        let deadline = query.multiple_read_operation.deadline;
+        let max_items = query.multiple_read_operation.max_items;
+        let max_pages = query.multiple_read_operation.max_pages;
+        let prune_empty_maps = query.multiple_read_operation.prune_empty_maps;
+        let mut paginator = query.into_pages(client);
+        crate::get_paginated_output!(
+            paginator,
+            operation::query::QueryOutput,
+            max_pages,
+            deadline,
+            max_items
+        )
+        .map(|mut output: operation::query::QueryOutput| {
+            read::common::prune_items(output.items.as_mut(), prune_empty_maps);
+            output
+        })
+    }
+
+    /// Build the request of the first page of the query without sending it, such as to log
+    /// or snapshot-test the generated expressions.
+    pub fn dry_run(self) -> Result<operation::query::QueryInput, crate::Error> {
+        self.dry_run_with(&common::serializer::SerdeDynamo)
+    }
+
+    /// Build the request of the first page of the query without sending it, converting the
+    /// values with the given serializer.
+    pub fn dry_run_with(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::query::QueryInput, crate::Error> {
+        let query = self.into_input(serializer)?;
+        let builder = operation::query::QueryInput::builder()
             .key_condition_expression(query.key_condition_expression)
             .set_return_consumed_capacity(query.return_consumed_capacity)
             .set_scan_index_forward(query.scan_index_forward);
-        let mut paginator =
-            crate::apply_multiple_read_operation!(builder, query.multiple_read_operation)
-                .into_paginator()
-                .send();
-        crate::get_paginated_output!(paginator, operation::query::QueryOutput)
+        let input = crate::apply_multiple_read_operation!(builder, query.multiple_read_operation)
+            .build()
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(input)
+    }
+
+    /// Execute the query operation lazily, returning a stream of the items.
+    ///
+    /// Pages are fetched only as the stream is polled, so consumers can process huge
+    /// results with backpressure and stop early by dropping the stream.
+    ///
+    /// The stream owns a handle to the client and is `Send`, so it can outlive the borrow of
+    /// the client and be returned as is from a web handler as a streaming body.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use futures_util::StreamExt;
+    /// use std::pin::pin;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = read::query::Query {
+    ///     partition_key: common::key::Key {
+    ///         name: "id".to_string(),
+    ///         value: "1".to_string(),
+    ///     },
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let mut items = pin!(query.stream(client));
+    /// while let Some(item) = items.next().await {
+    ///     println!("{:?}", item?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(
+        self,
+        client: &Client,
+    ) -> impl Stream<
+        Item = Result<collections::HashMap<String, types::AttributeValue>, crate::Error>,
+    >
+    + Send
+    + 'static
+    + use<T> {
+        let deadline = self.multiple_read_args.deadline;
+        let max_items = self.multiple_read_args.max_items;
+        let max_pages = self.multiple_read_args.max_pages;
+        let prune_empty_maps = self.multiple_read_args.prune_empty_maps;
+        let paginator = self
+            .into_input(&common::serializer::SerdeDynamo)
+            .map(|query| query.into_pages(client));
+        crate::stream_paginated_items!(paginator, max_pages, deadline, max_items).map(move |item| {
+            item.map(|mut item| {
+                if prune_empty_maps {
+                    common::selection::prune_empty_maps(&mut item);
+                }
+                item
+            })
+        })
+    }
+
+    /// Execute the query operation, deserializing every returned item.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = read::query::Query {
+    ///     partition_key: common::key::Key {
+    ///         name: "id".to_string(),
+    ///         value: "1".to_string(),
+    ///     },
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let output = query.send_items::<User>(client).await?;
+    /// for user in output.items {
+    ///     println!("{}", user.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.send_items", skip_all, err)
+    )]
+    pub async fn send_items<O: DeserializeOwned>(
+        self,
+        client: &Client,
+    ) -> Result<read::common::ItemsOutput<O>, crate::Error> {
+        let output = self.send(client).await?;
+        read::common::ItemsOutput::new(
+            output.items,
+            output.count,
+            output.scanned_count,
+            output.consumed_capacity,
+        )
+        .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the query operation, stripping the attributes hidden by the policy from every
+    /// returned item before deserializing it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.send_items_with_policy", skip_all, err)
+    )]
+    pub async fn send_items_with_policy<O: DeserializeOwned>(
+        self,
+        client: &Client,
+        policy: &impl common::visibility::AttributePolicy,
+    ) -> Result<read::common::ItemsOutput<O>, crate::Error> {
+        let output = self.send(client).await?;
+        read::common::ItemsOutput::new(
+            common::visibility::apply_policy(output.items, policy),
+            output.count,
+            output.scanned_count,
+            output.consumed_capacity,
+        )
+        .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the query operation, decoding every returned item into the entity of its
+    /// type.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Customer {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Order {
+    ///     total: f64,
+    /// }
+    ///
+    /// enum Entity {
+    ///     Customer(Customer),
+    ///     Order(Order),
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let decoder = read::common::TypeDecoder::new("entityType")
+    ///     .register("customer", Entity::Customer)
+    ///     .register("order", Entity::Order);
+    /// let query = read::query::Query {
+    ///     partition_key: common::key::Key {
+    ///         name: "pk".to_string(),
+    ///         value: "customer#1".to_string(),
+    ///     },
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "app".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// for entity in query.decode_by_type(client, &decoder).await? {
+    ///     match entity {
+    ///         Entity::Customer(customer) => println!("{}", customer.name),
+    ///         Entity::Order(order) => println!("{}", order.total),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.decode_by_type", skip_all, err)
+    )]
+    pub async fn decode_by_type<E>(
+        self,
+        client: &Client,
+        decoder: &read::common::TypeDecoder<E>,
+    ) -> Result<Vec<E>, crate::Error> {
+        let output = self.send(client).await?;
+        decoder
+            .decode_items(output.items)
+            .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute a single page of the query operation, deserializing the returned items and the
+    /// key to continue from.
+    ///
+    /// The pagination key is split by the key schemas of the table and of the secondary index
+    /// read, if any, and can be passed as is as the `exclusive_start_key` of the next page.
+    /// The page also carries an opaque `cursor`, serializable as a string, to hand to the
+    /// client of a web API and pass back as the `cursor` of the next page.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read, table};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Order {
+    ///     total: f64,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let key_schema = table::common::KeySchema {
+    ///     partition_key: table::common::KeyAttribute {
+    ///         name: "id".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     sort_key: Some(table::common::KeyAttribute {
+    ///         name: "orderId".to_string(),
+    ///         ..Default::default()
+    ///     }),
+    /// };
+    /// let mut exclusive_start_key = None;
+    /// loop {
+    ///      let query = read::query::Query {
+    ///          partition_key: common::key::Key {
+    ///              name: "id".to_string(),
+    ///              value: "1".to_string(),
+    ///          },
+    ///          multiple_read_args: read::common::MultipleReadArgs {
+    ///              exclusive_start_key,
+    ///              limit: Some(10),
+    ///              table_name: "orders".to_string(),
+    ///              ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///      };
+    ///     let page = query
+    ///         .send_page::<Order, String>(client, &key_schema, None)
+    ///         .await?;
+    ///     for order in page.items {
+    ///         println!("{}", order.total);
+    ///     }
+    ///     exclusive_start_key = page.last_evaluated_key;
+    ///     if exclusive_start_key.is_none() {
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.send_page", skip_all, err)
+    )]
+    pub async fn send_page<O: DeserializeOwned, K: DeserializeOwned>(
+        self,
+        client: &Client,
+        key_schema: &table::common::KeySchema,
+        index_key_schema: Option<&table::common::KeySchema>,
+    ) -> Result<read::common::ItemsPage<O, K>, crate::Error> {
+        let retry_policy = self.multiple_read_args.retry_policy.clone();
+        let query = self.into_input(&common::serializer::SerdeDynamo)?;
+        let prune_empty_maps = query.multiple_read_operation.prune_empty_maps;
+        let mut output = query
+            .into_builder(client)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await?;
+        read::common::prune_items(output.items.as_mut(), prune_empty_maps);
+        let items = read::common::ItemsOutput::new(
+            output.items,
+            output.count,
+            output.scanned_count,
+            output.consumed_capacity,
+        )
+        .map_err(crate::Error::Deserialization)?;
+        read::common::ItemsPage::new(
+            items,
+            output.last_evaluated_key,
+            key_schema,
+            index_key_schema,
+        )
+        .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the query operation, grouping the deserialized items by the value of an
+    /// attribute, in order of first appearance.
+    ///
+    /// Items lacking the attribute are grouped under `None` if the key is an `Option`, and
+    /// fail the deserialization otherwise.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde_json::Value;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = read::query::Query {
+    ///     partition_key: common::key::Key {
+    ///         name: "pk".to_string(),
+    ///         value: "customer#1".to_string(),
+    ///     },
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "app".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let groups = query.group_by::<String, Value>(client, "type").await?;
+    /// for (entity_type, items) in groups {
+    ///     println!("{entity_type}: {} items", items.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.group_by", skip_all, err)
+    )]
+    pub async fn group_by<K, O>(
+        self,
+        client: &Client,
+        attribute_name: &str,
+    ) -> Result<IndexMap<K, Vec<O>>, crate::Error>
+    where
+        K: DeserializeOwned + Eq + hash::Hash,
+        O: DeserializeOwned,
+    {
+        let output = self.send(client).await?;
+        read::common::group_items(output.items, attribute_name)
+            .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the query operation lazily, returning a stream of the groups of consecutive
+    /// items sharing the value of an attribute.
+    ///
+    /// Unlike [`Self::group_by`], items are not buffered until the end of the query, so a
+    /// key appears once per run of consecutive items holding it. The stream ends after the
+    /// first error.
+    pub fn stream_group_by<K, O>(
+        self,
+        client: &Client,
+        attribute_name: &str,
+    ) -> impl Stream<Item = Result<(K, Vec<O>), crate::Error>> + Send + 'static + use<T, K, O>
+    where
+        K: DeserializeOwned + PartialEq + Send + 'static,
+        O: DeserializeOwned + Send + 'static,
+    {
+        read::common::group_consecutive_items(self.stream(client), attribute_name.to_string())
+    }
+
+    /// Execute the query operation, sending the deserialized items to a bounded channel as
+    /// pages arrive.
+    ///
+    /// Waits for the receiver whenever the channel is full, and stops fetching pages once
+    /// the receiver is dropped.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde_json::Value;
+    /// use tokio::sync::mpsc;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = read::query::Query {
+    ///     partition_key: common::key::Key {
+    ///         name: "id".to_string(),
+    ///         value: "1".to_string(),
+    ///     },
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let (tx, mut rx) = mpsc::channel::<Value>(100);
+    /// let consumer = tokio::spawn(async move {
+    ///     while let Some(item) = rx.recv().await {
+    ///         println!("{item}");
+    ///     }
+    /// });
+    /// query.send_to_channel(client, tx).await?;
+    /// consumer.await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.send_to_channel", skip_all, err)
+    )]
+    pub async fn send_to_channel<D: DeserializeOwned>(
+        self,
+        client: &Client,
+        tx: mpsc::Sender<D>,
+    ) -> Result<(), crate::Error> {
+        let query = self.into_input(&common::serializer::SerdeDynamo)?;
+        let mut counter = read::common::PageCounter::new(
+            query.multiple_read_operation.max_pages,
+            query.multiple_read_operation.deadline,
+            query.multiple_read_operation.max_items,
+        );
+        let prune_empty_maps = query.multiple_read_operation.prune_empty_maps;
+        let mut paginator = query.into_pages(client);
+        while let Some(page) = paginator.next().await {
+            let page = page.map_err(crate::Error::from)?;
+            let mut items = counter.take_items(page.items.unwrap_or_default());
+            read::common::prune_items(Some(&mut items), prune_empty_maps);
+            let has_next_page = counter.count_page(page.last_evaluated_key.as_ref())?;
+            if !read::common::send_items_to_channel(items, &tx).await? || !has_next_page {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute the query projecting only the key attributes, returning the keys of the
+    /// matching items.
+    ///
+    /// The key names are the partition key name, followed by the sort key name if any. If
+    /// empty, they are taken from the `key_schema` of the query, which must be set and must
+    /// describe the base table: the primary key can't be derived from the key conditions,
+    /// which may leave out the sort key, nor from the key schema of an index.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde_json::Value;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = read::query::Query {
+    ///     partition_key: common::key::Key {
+    ///         name: "status".to_string(),
+    ///         value: "active".to_string(),
+    ///     },
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         index_name: Some("status-index".to_string()),
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let keys = query
+    ///     .keys_only::<Value>(client, vec!["id".to_string()])
+    ///     .await?;
+    /// for keys in keys {
+    ///     println!("{}", keys.partition_key.value);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.keys_only", skip_all, err)
+    )]
+    pub async fn keys_only<D: DeserializeOwned>(
+        self,
+        client: &Client,
+        key_names: Vec<String>,
+    ) -> Result<Vec<common::key::Keys<D>>, crate::Error> {
+        let (query, key_names) = self.into_keys_query(key_names)?;
+        let output = query.send(client).await?;
+        output
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| get_keys(item, &key_names).map_err(crate::Error::Deserialization))
+            .collect()
+    }
+
+    /// Count the items matching the query without fetching them, along with the items
+    /// evaluated before applying the filter.
+    ///
+    /// Forces `Select::Count`, ignores any selection and sums the counts of every page.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::read;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let count = read::query::Query::<String>::builder("orders", "userId", "1")
+    ///     .build()
+    ///     .count(client)
+    ///     .await?;
+    /// println!("{} orders", count.count);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query.count", skip_all, err)
+    )]
+    pub async fn count(self, client: &Client) -> Result<read::common::ItemsCount, crate::Error> {
+        let query = self
+            .into_count_query()
+            .into_input(&common::serializer::SerdeDynamo)?;
+        let deadline = query.multiple_read_operation.deadline;
+        let max_items = query.multiple_read_operation.max_items;
+        let max_pages = query.multiple_read_operation.max_pages;
+        let mut paginator = query.into_pages(client);
+        crate::count_paginated_items!(paginator, max_pages, deadline, max_items)
+    }
+}
+
+/// serde name of the newtype wrapping the name of a template parameter
+const PARAMETER_NAME: &str = "TemplateParameter";
+
+/// Value of a [`QueryTemplate`], either fixed or bound for every request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplateValue<T> {
+    /// Named parameter, bound with [`QueryTemplate::bind`].
+    Parameter(String),
+    /// Fixed value, shared by every request.
+    Value(T),
+}
+
+impl<T> TemplateValue<T> {
+    /// Named parameter, bound with [`QueryTemplate::bind`].
+    pub fn parameter(name: impl Into<String>) -> Self {
+        Self::Parameter(name.into())
+    }
+}
+
+impl<T> From<T> for TemplateValue<T> {
+    fn from(value: T) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl<T: Serialize> Serialize for TemplateValue<T> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Parameter(name) => serializer.serialize_newtype_struct(PARAMETER_NAME, name),
+            Self::Value(value) => value.serialize(serializer),
+        }
+    }
+}
+
+/// Serializer extracting the name of a template parameter, failing on any other value.
+struct ParameterName {
+    /// Whether the serializer is within the newtype of a parameter.
+    nested: bool,
+}
+
+impl ParameterName {
+    fn reject<O>(&self) -> Result<O> {
+        Err(Error::custom("not a template parameter"))
+    }
+}
+
+/// reject the values serialized by the given methods
+macro_rules! reject_values {
+    ($($method:ident($($argument:ty),*)),* $(,)?) => {
+        $(
+            fn $method(self, $(_: $argument),*) -> Result<String> {
+                self.reject()
+            }
+        )*
+    };
+}
+
+impl serde::Serializer for ParameterName {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = serde::ser::Impossible<String, Error>;
+    type SerializeTuple = serde::ser::Impossible<String, Error>;
+    type SerializeTupleStruct = serde::ser::Impossible<String, Error>;
+    type SerializeTupleVariant = serde::ser::Impossible<String, Error>;
+    type SerializeMap = serde::ser::Impossible<String, Error>;
+    type SerializeStruct = serde::ser::Impossible<String, Error>;
+    type SerializeStructVariant = serde::ser::Impossible<String, Error>;
+
+    reject_values!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    );
+
+    fn serialize_str(self, value: &str) -> Result<String> {
+        if self.nested {
+            Ok(value.to_string())
+        } else {
+            self.reject()
+        }
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<String> {
+        self.reject()
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<String> {
+        if !self.nested && name == PARAMETER_NAME {
+            value.serialize(Self { nested: true })
+        } else {
+            self.reject()
+        }
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String> {
+        self.reject()
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq> {
+        self.reject()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple> {
+        self.reject()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.reject()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.reject()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        self.reject()
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct> {
+        self.reject()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.reject()
+    }
+}
+
+/// Serializer of the values of a template, replacing every parameter with its index in the
+/// names of the parameters, shifted by the offset.
+///
+/// Serializing the query twice with different offsets tells apart the placeholders of the
+/// parameters, the only values differing between the two.
+struct TemplateSerializer {
+    names: cell::RefCell<Vec<String>>,
+    offset: usize,
+}
+
+impl TemplateSerializer {
+    fn new(offset: usize) -> Self {
+        Self {
+            names: cell::RefCell::default(),
+            offset,
+        }
+    }
+}
+
+impl common::serializer::ValueSerializer for TemplateSerializer {
+    fn to_attribute_value<T: Serialize>(&self, value: T) -> Result<types::AttributeValue> {
+        match value.serialize(ParameterName { nested: false }) {
+            Ok(name) => {
+                let mut names = self.names.borrow_mut();
+                let index = names.len() + self.offset;
+                names.push(name);
+                Ok(types::AttributeValue::N(index.to_string()))
+            }
+            Err(_) => serde_dynamo::to_attribute_value(value),
+        }
+    }
+}
+
+/// Query built once, with named parameters bound for every request.
+///
+/// The expressions and placeholders of the query are computed when the template is created,
+/// so web services can prepare their queries at startup and only bind the values of each
+/// request.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use read::query::{Query, QueryTemplate, TemplateValue};
+///
+/// let user_id = TemplateValue::parameter("user_id");
+/// let query = Query::<TemplateValue<String>>::builder("orders", "userId", user_id)
+///     .filter(common::condition::ConditionMap::attribute(
+///         "status",
+///         common::condition::Condition::Equals("open".to_string().into()),
+///     ))
+///     .build();
+/// let template = QueryTemplate::new(query)?;
+/// let output = template.bind("user_id", "user-1").send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryTemplate {
+    parameters: IndexMap<String, Vec<String>>,
+    query: QueryInput,
+}
+
+impl QueryTemplate {
+    /// Build the template of a query, whose parameters are bound for every request.
+    pub fn new<T: Clone + Serialize>(
+        query: Query<TemplateValue<T>>,
+    ) -> std::result::Result<Self, crate::Error> {
+        let serializer = TemplateSerializer::new(0);
+        let mut template_query = query.clone().into_input(&serializer)?;
+        let shifted_query = query.into_input(&TemplateSerializer::new(1))?;
+        let names = serializer.names.into_inner();
+        let mut parameters: IndexMap<String, Vec<String>> = IndexMap::new();
+        if let Some(values) = &mut template_query
+            .multiple_read_operation
+            .expression_attribute_values
+        {
+            let shifted_values = shifted_query
+                .multiple_read_operation
+                .expression_attribute_values
+                .unwrap_or_default();
+            values.retain(|placeholder, value| {
+                if shifted_values.get(placeholder) == Some(value) {
+                    return true;
+                }
+                let name = value
+                    .as_n()
+                    .ok()
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .and_then(|index| names.get(index));
+                if let Some(name) = name {
+                    parameters
+                        .entry(name.clone())
+                        .or_default()
+                        .push(placeholder.clone());
+                }
+                false
+            });
+        }
+        parameters.sort_keys();
+        let template = Self {
+            parameters,
+            query: template_query,
+        };
+        Ok(template)
+    }
+
+    /// The names of the parameters of the template, in alphabetical order.
+    pub fn parameters(&self) -> impl Iterator<Item = &str> {
+        self.parameters.keys().map(String::as_str)
+    }
+
+    /// Bind a parameter, starting a new query from the template.
+    pub fn bind(&self, name: &str, value: impl Serialize) -> BoundQuery<'_> {
+        BoundQuery {
+            error: None,
+            query: self.query.clone(),
+            template: self,
+        }
+        .bind(name, value)
+    }
+}
+
+/// Query instantiated from a [`QueryTemplate`], see [`QueryTemplate::bind`].
+#[derive(Debug)]
+pub struct BoundQuery<'a> {
+    error: Option<crate::Error>,
+    query: QueryInput,
+    template: &'a QueryTemplate,
+}
+
+impl BoundQuery<'_> {
+    /// Bind another parameter.
+    ///
+    /// Binding a parameter unknown to the template fails the query with
+    /// [`crate::Error::UnknownParameter`].
+    pub fn bind(mut self, name: &str, value: impl Serialize) -> Self {
+        let Some(placeholders) = self.template.parameters.get(name) else {
+            self.error
+                .get_or_insert(crate::Error::UnknownParameter(name.to_string()));
+            return self;
+        };
+        match serde_dynamo::to_attribute_value::<_, types::AttributeValue>(value) {
+            Ok(value) => {
+                let values = self
+                    .query
+                    .multiple_read_operation
+                    .expression_attribute_values
+                    .get_or_insert_default();
+                for placeholder in placeholders {
+                    values.insert(placeholder.clone(), value.clone());
+                }
+            }
+            Err(error) => {
+                self.error.get_or_insert(crate::Error::Serialization(error));
+            }
+        }
+        self
+    }
+
+    /// Check that every parameter of the template is bound.
+    fn validate(&mut self) -> std::result::Result<(), crate::Error> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let values = self
+            .query
+            .multiple_read_operation
+            .expression_attribute_values
+            .as_ref();
+        let unbound = self.template.parameters.iter().find(|(_, placeholders)| {
+            placeholders
+                .iter()
+                .any(|placeholder| !values.is_some_and(|values| values.contains_key(placeholder)))
+        });
+        match unbound {
+            Some((name, _)) => Err(crate::Error::UnboundParameter(name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Execute the query operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.query_template", skip_all, err)
+    )]
+    pub async fn send(
+        mut self,
+        client: &Client,
+    ) -> Result<operation::query::QueryOutput, crate::Error> {
+        self.validate()?;
+        let deadline = self.query.multiple_read_operation.deadline;
+        let max_items = self.query.multiple_read_operation.max_items;
+        let max_pages = self.query.multiple_read_operation.max_pages;
+        let prune_empty_maps = self.query.multiple_read_operation.prune_empty_maps;
+        let mut paginator = self.query.into_pages(client);
+        crate::get_paginated_output!(
+            paginator,
+            operation::query::QueryOutput,
+            max_pages,
+            deadline,
+            max_items
+        )
+        .map(|mut output: operation::query::QueryOutput| {
+            read::common::prune_items(output.items.as_mut(), prune_empty_maps);
+            output
+        })
     }
 }
 
@@ -119,7 +1418,6 @@ mod tests {
 
     use rstest::rstest;
     use serde_json::Value;
-    use std::collections;
 
     #[rstest]
     #[case::empty(
@@ -166,6 +1464,7 @@ mod tests {
     )]
     #[case::full(
         Query {
+            key_schema: None,
             multiple_read_args: read::common::MultipleReadArgs {
                 condition: Some(
                     common::condition::ConditionMap::Leaves(
@@ -183,20 +1482,29 @@ mod tests {
                     )
                 ),
                 consistent_read: Some(false),
+                cursor: None,
+                deadline: None,
                 exclusive_start_key: Some(
-                    collections::HashMap::from(
-                        [
-                            (
-                                "c".to_string(),
-                                Value::String(
+                    common::key::LastEvaluatedKey {
+                        index_keys: None,
+                        keys: common::key::Keys {
+                            partition_key: common::key::Key {
+                                name: "c".to_string(),
+                                value: Value::String(
                                     "d".to_string()
-                                )
-                            ),
-                        ]
-                    )
+                                ),
+                            },
+                            sort_key: None,
+                        },
+                    }
                 ),
                 index_name: Some("e".to_string()),
                 limit: Some(10),
+                max_items: Some(5),
+                max_pages: Some(read::common::MaxPages::Truncate(2)),
+                placeholder_naming: None,
+                prune_empty_maps: false,
+                retry_policy: None,
                 select: Some(
                     types::Select::Count
                 ),
@@ -221,9 +1529,9 @@ mod tests {
             ),
             scan_index_forward: Some(true),
             sort_key_condition: Some(
-                common::condition::KeyCondition {
+                common::condition::SortKey {
                     name: "k".to_string(),
-                    condition: common::condition::Condition::Equals(
+                    condition: common::condition::SortKeyCondition::Equals(
                         Value::String(
                             "l".to_string()
                         )
@@ -232,9 +1540,10 @@ mod tests {
             ),
         },
         QueryInput {
-            key_condition_expression: "#i = :i_eq0 AND #k = :k_eq1".to_string(),
+            key_condition_expression: "#i = :i_eq1 AND #k = :k_eq2".to_string(),
             multiple_read_operation: read::common::MultipleReadInput {
                 consistent_read: Some(false),
+                deadline: None,
                 exclusive_start_key: Some(
                     collections::HashMap::from(
                         [
@@ -268,13 +1577,13 @@ mod tests {
                                 )
                             ),
                             (
-                                ":i_eq0".to_string(),
+                                ":i_eq1".to_string(),
                                 types::AttributeValue::S(
                                     "j".to_string()
                                 )
                             ),
                             (
-                                ":k_eq1".to_string(),
+                                ":k_eq2".to_string(),
                                 types::AttributeValue::S(
                                     "l".to_string()
                                 )
@@ -287,9 +1596,13 @@ mod tests {
                 ),
                 index_name: Some("e".to_string()),
                 limit: Some(10),
+                max_items: Some(5),
+                max_pages: Some(read::common::MaxPages::Truncate(2)),
                 projection_expression: Some(
                     "#f, #g".to_string()
                 ),
+                prune_empty_maps: false,
+                retry_policy: None,
                 select: Some(
                     types::Select::Count
                 ),
@@ -305,4 +1618,324 @@ mod tests {
         let actual: QueryInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case::partition_key(
+        vec!["a".to_string()],
+        common::key::Keys {
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: Value::String(
+                    "b".to_string()
+                ),
+            },
+            sort_key: None,
+        }
+    )]
+    #[case::sort_key(
+        vec![
+            "a".to_string(),
+            "c".to_string(),
+        ],
+        common::key::Keys {
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: Value::String(
+                    "b".to_string()
+                ),
+            },
+            sort_key: Some(
+                common::key::Key {
+                    name: "c".to_string(),
+                    value: Value::from(1),
+                }
+            ),
+        }
+    )]
+    fn test_get_keys(#[case] key_names: Vec<String>, #[case] expected: common::key::Keys<Value>) {
+        let item = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("b".to_string())),
+            ("c".to_string(), types::AttributeValue::N("1".to_string())),
+        ]);
+        let actual = get_keys(item, &key_names).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_keys_missing() {
+        let item = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let actual: Result<common::key::Keys<Value>> = get_keys(item, &["c".to_string()]);
+        assert!(actual.is_err());
+    }
+
+    fn get_key_attribute(name: &str) -> table::common::KeyAttribute {
+        table::common::KeyAttribute {
+            attribute_type: types::ScalarAttributeType::S,
+            name: name.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::key_names(vec!["c"], Some("b"), None, Some(vec!["c"]))]
+    #[case::key_schema(vec![], Some("b"), None, Some(vec!["a", "b"]))]
+    #[case::no_sort_key(vec![], None, None, Some(vec!["a"]))]
+    #[case::index(vec![], Some("b"), Some("d"), None)]
+    fn test_into_keys_query(
+        #[case] key_names: Vec<&str>,
+        #[case] sort_key_name: Option<&str>,
+        #[case] index_name: Option<&str>,
+        #[case] expected: Option<Vec<&str>>,
+    ) {
+        let query = Query {
+            key_schema: Some(table::common::KeySchema {
+                partition_key: get_key_attribute("a"),
+                sort_key: sort_key_name.map(get_key_attribute),
+            }),
+            multiple_read_args: read::common::MultipleReadArgs {
+                index_name: index_name.map(str::to_string),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: Value::from(1),
+            },
+            ..Default::default()
+        };
+        let key_names = key_names.into_iter().map(str::to_string).collect();
+        let actual = query
+            .into_keys_query(key_names)
+            .ok()
+            .map(|(_, key_names)| key_names);
+        let expected =
+            expected.map(|key_names| key_names.into_iter().map(str::to_string).collect());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_into_keys_query_missing_key_schema() {
+        let query: Query<Value> = Query::default();
+        let actual = query.into_keys_query(vec![]);
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+
+    #[rstest]
+    #[case::valid("a", Some("b"), Some("b"), true)]
+    #[case::partition_key_mismatch("c", None, None, false)]
+    #[case::missing_sort_key("a", Some("b"), None, false)]
+    #[case::sort_key_mismatch("a", Some("c"), Some("b"), false)]
+    #[case::no_sort_key_condition("a", None, Some("b"), true)]
+    fn test_validate_key_schema(
+        #[case] partition_key_name: &str,
+        #[case] sort_key_condition_name: Option<&str>,
+        #[case] sort_key_name: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        let query = Query {
+            key_schema: Some(table::common::KeySchema {
+                partition_key: get_key_attribute("a"),
+                sort_key: sort_key_name.map(get_key_attribute),
+            }),
+            partition_key: common::key::Key {
+                name: partition_key_name.to_string(),
+                value: Value::from(1),
+            },
+            sort_key_condition: sort_key_condition_name.map(|name| common::condition::SortKey {
+                condition: common::condition::SortKeyCondition::Equals(Value::from(2)),
+                name: name.to_string(),
+            }),
+            ..Default::default()
+        };
+        let actual = query.validate_key_schema();
+        assert_eq!(actual.is_ok(), expected);
+        if let Err(error) = actual {
+            assert!(matches!(error, crate::Error::InvalidKeyCondition(_)));
+        }
+    }
+
+    #[rstest]
+    #[case::valid(Some("b"), Some("c"), true)]
+    #[case::sort_key_condition(Some("a"), None, false)]
+    #[case::filter(None, Some("a"), false)]
+    fn test_validate_partition_key(
+        #[case] sort_key_condition_name: Option<&str>,
+        #[case] filter_name: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        let query = Query {
+            multiple_read_args: read::common::MultipleReadArgs {
+                condition: filter_name
+                    .map(|name| common::condition::ConditionMap::begins_with(name, "d")),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: Value::from(1),
+            },
+            sort_key_condition: sort_key_condition_name.map(|name| common::condition::SortKey {
+                condition: common::condition::SortKeyCondition::BeginsWith("d".to_string()),
+                name: name.to_string(),
+            }),
+            ..Default::default()
+        };
+        let actual = query.validate_partition_key();
+        assert_eq!(actual.is_ok(), expected);
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let actual: Query<Value> = Query::builder("a", "b", "c")
+            .sort_key_between("d", 1, 2)
+            .index("e")
+            .limit(3)
+            .max_items(4)
+            .descending()
+            .build();
+        let expected = Query {
+            multiple_read_args: read::common::MultipleReadArgs {
+                index_name: Some("e".to_string()),
+                limit: Some(3),
+                max_items: Some(4),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "b".to_string(),
+                value: Value::from("c"),
+            },
+            scan_index_forward: Some(false),
+            sort_key_condition: Some(common::condition::SortKey {
+                condition: common::condition::SortKeyCondition::Between(
+                    Value::from(1),
+                    Value::from(2),
+                ),
+                name: "d".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_query_count() {
+        let mut query: Query<Value> = Query::builder("a", "b", "c").build();
+        let mut expected = query.clone();
+        query.multiple_read_args.select = Some(types::Select::SpecificAttributes);
+        query.multiple_read_args.selection = Some(common::selection::SelectionMap::Leaves(vec![
+            "d".to_string(),
+        ]));
+        expected.multiple_read_args.select = Some(types::Select::Count);
+        assert_eq!(query.into_count_query(), expected);
+    }
+
+    #[test]
+    fn test_query_template() {
+        let query = Query::<TemplateValue<Value>>::builder("a", "b", TemplateValue::parameter("c"))
+            .sort_key_equals("d", Value::from(1))
+            .filter(common::condition::ConditionMap::attribute(
+                "e",
+                common::condition::Condition::Between(
+                    TemplateValue::parameter("c"),
+                    TemplateValue::parameter("f"),
+                ),
+            ))
+            .build();
+        let template = QueryTemplate::new(query).unwrap();
+        assert_eq!(template.parameters().collect::<Vec<_>>(), vec!["c", "f"]);
+        let mut unbound = template.bind("c", "g");
+        assert!(matches!(
+            unbound.validate(),
+            Err(crate::Error::UnboundParameter(name)) if name == "f"
+        ));
+        let mut unknown = template.bind("c", "g").bind("f", 2).bind("h", 3);
+        assert!(matches!(
+            unknown.validate(),
+            Err(crate::Error::UnknownParameter(name)) if name == "h"
+        ));
+        let mut bound = template.bind("c", "g").bind("f", 2);
+        assert!(bound.validate().is_ok());
+        let actual = bound
+            .query
+            .multiple_read_operation
+            .expression_attribute_values;
+        let expected = collections::HashMap::from([
+            (
+                ":b_eq2".to_string(),
+                types::AttributeValue::S("g".to_string()),
+            ),
+            (
+                ":d_eq3".to_string(),
+                types::AttributeValue::N("1".to_string()),
+            ),
+            (
+                ":e_between0".to_string(),
+                types::AttributeValue::S("g".to_string()),
+            ),
+            (
+                ":e_between1".to_string(),
+                types::AttributeValue::N("2".to_string()),
+            ),
+        ]);
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[test]
+    fn test_query_dry_run() {
+        let query: Query<Value> = Query::builder("a", "b", "c").index("d").build();
+        let input = query.dry_run().unwrap();
+        assert_eq!(input.table_name(), Some("a"));
+        assert_eq!(input.index_name(), Some("d"));
+        assert_eq!(input.key_condition_expression(), Some("#b = :b_eq0"));
+        assert_eq!(
+            input.expression_attribute_values().unwrap()[":b_eq0"],
+            types::AttributeValue::S("c".to_string())
+        );
+    }
+
+    fn get_owned_stream(query: Query<Value>, client: &Client) -> impl Stream + Send + 'static {
+        query.stream(client)
+    }
+
+    #[test]
+    fn test_query_stream_owned() {
+        let _ = get_owned_stream;
+    }
+
+    #[rstest]
+    #[case::index(Some("c"), true)]
+    #[case::base_table(None, false)]
+    fn test_query_projected_only(#[case] index_name: Option<&str>, #[case] expected: bool) {
+        let query: Query<Value> = Query {
+            multiple_read_args: read::common::MultipleReadArgs {
+                index_name: index_name.map(str::to_string),
+                select: Some(types::Select::SpecificAttributes),
+                selection: Some(common::selection::SelectionMap::Leaves(vec![
+                    "d".to_string(),
+                ])),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "b".to_string(),
+                value: Value::Null,
+            },
+            ..Default::default()
+        };
+        match query.projected_only() {
+            Ok(query) => {
+                assert!(expected);
+                assert_eq!(
+                    query.multiple_read_args.select,
+                    Some(types::Select::AllProjectedAttributes)
+                );
+                assert!(query.multiple_read_args.selection.is_none());
+            }
+            Err(error) => {
+                assert!(!expected);
+                assert!(matches!(error, crate::Error::InvalidSelect(_)));
+            }
+        }
+    }
 }
@@ -1,8 +1,157 @@
-use crate::read;
+use crate::{common, common::retry::WithRetryPolicy as _, read, table};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
-use serde::Serialize;
+use aws_sdk_dynamodb::{Client, operation, types};
+use futures_util::{Stream, StreamExt, future};
+use indexmap::IndexMap;
+use serde::{Serialize, de::DeserializeOwned};
 use serde_dynamo::{Error, Result};
+use std::{collections, hash, sync, time};
+use tokio::sync::{Notify, mpsc};
+
+/// Backoff of the retries of throttled pages of an adaptive parallel scan, unless overridden
+/// by a retry policy.
+const ADAPTIVE_SCAN_BACKOFF: common::retry::Backoff = common::retry::Backoff {
+    initial_backoff: time::Duration::from_millis(100),
+    max_attempts: 10,
+    max_backoff: time::Duration::from_secs(10),
+};
+
+/// Period over which the consumed capacity of an adaptive parallel scan is measured.
+const CAPACITY_WINDOW: time::Duration = time::Duration::from_secs(1);
+
+/// Adaptive concurrency of a parallel scan.
+///
+/// Instead of scanning every segment at once, segments share a concurrency limit: it grows
+/// by one after every second of pages without throttling, is halved whenever a page is
+/// throttled, and is scaled down whenever the consumed capacity exceeds its maximum.
+/// Throttled pages are retried with the backoff of the retry policy of the scan.
+///
+/// ```rust
+/// use dynamodb_crud::read;
+///
+/// let adaptive_concurrency = read::scan::AdaptiveConcurrency {
+///     initial_concurrency: Some(4),
+///     max_read_capacity_units: Some(500.0),
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AdaptiveConcurrency {
+    /// The number of segments scanned concurrently at first (defaults to 1).
+    pub initial_concurrency: Option<usize>,
+    /// The read capacity units per second the scan should not exceed.
+    ///
+    /// If `None`, the concurrency only backs off when pages are throttled.
+    pub max_read_capacity_units: Option<f64>,
+}
+
+/// concurrency limit and capacity measurement of an adaptive parallel scan
+#[derive(Debug)]
+struct ConcurrencyState {
+    concurrency: usize,
+    consumed_capacity_units: f64,
+    running: usize,
+    window_start: time::Instant,
+}
+
+impl ConcurrencyState {
+    /// Adjust the concurrency on the outcome of a page.
+    fn adjust(
+        &mut self,
+        throttled: bool,
+        consumed_capacity_units: f64,
+        max_concurrency: usize,
+        max_read_capacity_units: Option<f64>,
+        now: time::Instant,
+    ) {
+        if throttled {
+            self.concurrency = (self.concurrency / 2).max(1);
+            self.consumed_capacity_units = 0.0;
+            self.window_start = now;
+            return;
+        }
+        self.consumed_capacity_units += consumed_capacity_units;
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < CAPACITY_WINDOW {
+            return;
+        }
+        let read_capacity_units = self.consumed_capacity_units / elapsed.as_secs_f64();
+        self.concurrency = match max_read_capacity_units {
+            Some(max_read_capacity_units) if read_capacity_units > max_read_capacity_units => {
+                (self.concurrency as f64 * max_read_capacity_units / read_capacity_units) as usize
+            }
+            _ => self.concurrency + 1,
+        }
+        .clamp(1, max_concurrency);
+        self.consumed_capacity_units = 0.0;
+        self.window_start = now;
+    }
+}
+
+/// concurrency limit shared by the segments of an adaptive parallel scan
+#[derive(Debug)]
+struct ConcurrencyController {
+    max_concurrency: usize,
+    max_read_capacity_units: Option<f64>,
+    notify: Notify,
+    state: sync::Mutex<ConcurrencyState>,
+}
+
+impl ConcurrencyController {
+    fn new(adaptive_concurrency: &AdaptiveConcurrency, max_concurrency: usize) -> Self {
+        let state = ConcurrencyState {
+            concurrency: adaptive_concurrency
+                .initial_concurrency
+                .unwrap_or(1)
+                .clamp(1, max_concurrency),
+            consumed_capacity_units: 0.0,
+            running: 0,
+            window_start: time::Instant::now(),
+        };
+        Self {
+            max_concurrency,
+            max_read_capacity_units: adaptive_concurrency.max_read_capacity_units,
+            notify: Notify::new(),
+            state: sync::Mutex::new(state),
+        }
+    }
+
+    fn lock(&self) -> sync::MutexGuard<'_, ConcurrencyState> {
+        self.state
+            .lock()
+            .unwrap_or_else(sync::PoisonError::into_inner)
+    }
+
+    /// Wait until a page can be fetched within the concurrency limit.
+    async fn acquire(&self) {
+        loop {
+            let notified = self.notify.notified();
+            {
+                let mut state = self.lock();
+                if state.running < state.concurrency {
+                    state.running += 1;
+                    return;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Release the slot of a fetched page, adjusting the concurrency on its outcome.
+    fn release(&self, throttled: bool, consumed_capacity_units: f64) {
+        {
+            let mut state = self.lock();
+            state.running -= 1;
+            state.adjust(
+                throttled,
+                consumed_capacity_units,
+                self.max_concurrency,
+                self.max_read_capacity_units,
+                time::Instant::now(),
+            );
+        }
+        self.notify.notify_waiters();
+    }
+}
 
 /// scan operation
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -13,6 +162,30 @@ struct ScanInput {
     total_segments: Option<i32>,
 }
 
+/// Filter of a scan operation, built with the predicate constructors of the condition map.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::read;
+/// use serde_json::{Value, json};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let filter: read::scan::ScanFilter<Value> =
+///     read::scan::ScanFilter::one_of("status", [json!("failed"), json!("pending")]);
+/// let scan = read::scan::Scan {
+///     multiple_read_args: read::common::MultipleReadArgs {
+///         condition: Some(filter),
+///         table_name: "jobs".to_string(),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// scan.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub type ScanFilter<T> = common::condition::ConditionMap<T>;
+
 /// Scan operation.
 ///
 /// ```rust,no_run
@@ -48,8 +221,23 @@ impl<T: Serialize> TryFrom<Scan<T>> for ScanInput {
     type Error = Error;
 
     fn try_from(scan: Scan<T>) -> Result<Self> {
-        let multiple_read_operation: read::common::MultipleReadInput =
-            scan.multiple_read_args.try_into()?;
+        Self::new(scan, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl ScanInput {
+    fn new<T: Serialize>(
+        scan: Scan<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let placeholders = &mut common::PlaceholderAllocator::new(
+            scan.multiple_read_args.placeholder_naming.clone(),
+        );
+        let multiple_read_operation = read::common::MultipleReadInput::new(
+            scan.multiple_read_args,
+            placeholders,
+            serializer,
+        )?;
         let operation = Self {
             multiple_read_operation,
             return_consumed_capacity: scan.return_consumed_capacity,
@@ -58,40 +246,591 @@ impl<T: Serialize> TryFrom<Scan<T>> for ScanInput {
         };
         Ok(operation)
     }
+
+    fn into_builder(self, client: &Client) -> operation::scan::builders::ScanFluentBuilder {
+        let builder = client
+            .scan()
+            .set_return_consumed_capacity(self.return_consumed_capacity)
+            .set_segment(self.segment)
+            .set_total_segments(self.total_segments);
+        crate::apply_multiple_read_operation!(builder, self.multiple_read_operation)
+    }
+
+    fn into_pages(
+        self,
+        client: &Client,
+    ) -> read::common::Pages<operation::scan::builders::ScanFluentBuilder> {
+        let max_items = self.multiple_read_operation.max_items;
+        let retry_policy = self.multiple_read_operation.retry_policy.clone();
+        read::common::Pages::new(self.into_builder(client), client, max_items, retry_policy)
+    }
+
+    /// Send the page of the scan starting after the given key within the concurrency limit,
+    /// retrying it while it is throttled.
+    async fn send_adaptive_page(
+        &self,
+        client: &Client,
+        controller: &ConcurrencyController,
+        backoff: common::retry::Backoff,
+        exclusive_start_key: Option<collections::HashMap<String, types::AttributeValue>>,
+    ) -> Result<operation::scan::ScanOutput, crate::Error> {
+        let mut attempt = 1;
+        loop {
+            controller.acquire().await;
+            let output = self
+                .clone()
+                .into_builder(client)
+                .set_exclusive_start_key(exclusive_start_key.clone())
+                .customize()
+                .with_retry_policy(client, self.multiple_read_operation.retry_policy.as_ref())
+                .send()
+                .await
+                .map_err(crate::Error::from);
+            let consumed_capacity_units = output
+                .as_ref()
+                .ok()
+                .and_then(|output| output.consumed_capacity())
+                .and_then(|consumed_capacity| consumed_capacity.capacity_units())
+                .unwrap_or_default();
+            controller.release(
+                output.as_ref().is_err_and(crate::Error::is_throttling),
+                consumed_capacity_units,
+            );
+            match output {
+                Err(error) if error.is_throttling() && attempt < backoff.max_attempts => {
+                    backoff.sleep("Scan", attempt).await;
+                    attempt += 1;
+                }
+                output => return output,
+            }
+        }
+    }
+}
+
+impl<T> Scan<T> {
+    fn into_count_scan(mut self) -> Self {
+        self.multiple_read_args.select = Some(types::Select::Count);
+        self.multiple_read_args.selection = None;
+        self
+    }
 }
 
 impl<T: Serialize> Scan<T> {
     /// Execute the scan operation.
+    pub async fn send(self, client: &Client) -> Result<operation::scan::ScanOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the scan operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.scan", err)
+        tracing::instrument(name = "dynamodb_crud.scan", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<operation::scan::ScanOutput, error::SdkError<operation::scan::ScanError>> {
-        let scan: ScanInput = self.try_into().map_err(error::BuildError::other)?;
-        let builder = client
-            .scan()
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::scan::ScanOutput, crate::Error> {
+        let scan = ScanInput::new(self, serializer).map_err(crate::Error::Serialization)?;
+        let deadline = scan.multiple_read_operation.deadline;
+        let max_items = scan.multiple_read_operation.max_items;
+        let max_pages = scan.multiple_read_operation.max_pages;
+        let prune_empty_maps = scan.multiple_read_operation.prune_empty_maps;
+        let mut paginator = scan.into_pages(client);
+        crate::get_paginated_output!(
+            paginator,
+            operation::scan::ScanOutput,
+            max_pages,
+            deadline,
+            max_items
+        )
+        .map(|mut output: operation::scan::ScanOutput| {
+            read::common::prune_items(output.items.as_mut(), prune_empty_maps);
+            output
+        })
+    }
+
+    /// Build the request of the first page of the scan without sending it, such as to log
+    /// or snapshot-test the generated expressions.
+    pub fn dry_run(self) -> Result<operation::scan::ScanInput, crate::Error> {
+        self.dry_run_with(&common::serializer::SerdeDynamo)
+    }
+
+    /// Build the request of the first page of the scan without sending it, converting the
+    /// values with the given serializer.
+    pub fn dry_run_with(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::scan::ScanInput, crate::Error> {
+        let scan = ScanInput::new(self, serializer).map_err(crate::Error::Serialization)?;
+        let builder = operation::scan::ScanInput::builder()
             .set_return_consumed_capacity(scan.return_consumed_capacity)
             .set_segment(scan.segment)
             .set_total_segments(scan.total_segments);
-        let mut paginator =
-            crate::apply_multiple_read_operation!(builder, scan.multiple_read_operation)
-                .into_paginator()
-                .send();
-        crate::get_paginated_output!(paginator, operation::scan::ScanOutput)
+        let input = crate::apply_multiple_read_operation!(builder, scan.multiple_read_operation)
+            .build()
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(input)
+    }
+
+    /// Execute the scan operation lazily, returning a stream of the items.
+    ///
+    /// Pages are fetched only as the stream is polled, so consumers can process huge
+    /// results with backpressure and stop early by dropping the stream.
+    ///
+    /// The stream owns a handle to the client and is `Send`, so it can outlive the borrow of
+    /// the client and be returned as is from a web handler as a streaming body.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::read;
+    /// use futures_util::StreamExt;
+    /// use std::pin::pin;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let scan: read::scan::Scan<String> = read::scan::Scan {
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let mut items = pin!(scan.stream(client));
+    /// while let Some(item) = items.next().await {
+    ///     println!("{:?}", item?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(
+        self,
+        client: &Client,
+    ) -> impl Stream<
+        Item = Result<collections::HashMap<String, types::AttributeValue>, crate::Error>,
+    >
+    + Send
+    + 'static
+    + use<T> {
+        let deadline = self.multiple_read_args.deadline;
+        let max_items = self.multiple_read_args.max_items;
+        let max_pages = self.multiple_read_args.max_pages;
+        let prune_empty_maps = self.multiple_read_args.prune_empty_maps;
+        let paginator = ScanInput::try_from(self)
+            .map(|scan| scan.into_pages(client))
+            .map_err(crate::Error::Serialization);
+        crate::stream_paginated_items!(paginator, max_pages, deadline, max_items).map(move |item| {
+            item.map(|mut item| {
+                if prune_empty_maps {
+                    common::selection::prune_empty_maps(&mut item);
+                }
+                item
+            })
+        })
+    }
+
+    /// Execute the scan operation, deserializing every returned item.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::read;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let scan: read::scan::Scan<String> = read::scan::Scan {
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let output = scan.send_items::<User>(client).await?;
+    /// for user in output.items {
+    ///     println!("{}", user.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.scan.send_items", skip_all, err)
+    )]
+    pub async fn send_items<O: DeserializeOwned>(
+        self,
+        client: &Client,
+    ) -> Result<read::common::ItemsOutput<O>, crate::Error> {
+        let output = self.send(client).await?;
+        read::common::ItemsOutput::new(
+            output.items,
+            output.count,
+            output.scanned_count,
+            output.consumed_capacity,
+        )
+        .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the scan operation, stripping the attributes hidden by the policy from every
+    /// returned item before deserializing it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.scan.send_items_with_policy", skip_all, err)
+    )]
+    pub async fn send_items_with_policy<O: DeserializeOwned>(
+        self,
+        client: &Client,
+        policy: &impl common::visibility::AttributePolicy,
+    ) -> Result<read::common::ItemsOutput<O>, crate::Error> {
+        let output = self.send(client).await?;
+        read::common::ItemsOutput::new(
+            common::visibility::apply_policy(output.items, policy),
+            output.count,
+            output.scanned_count,
+            output.consumed_capacity,
+        )
+        .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute a single page of the scan operation, deserializing the returned items and the
+    /// key to continue from.
+    ///
+    /// The pagination key is split by the key schemas of the table and of the secondary index
+    /// read, if any, and can be passed as is as the `exclusive_start_key` of the next page.
+    /// The page also carries an opaque `cursor`, serializable as a string, to hand to the
+    /// client of a web API and pass back as the `cursor` of the next page.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{read, table};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Order {
+    ///     total: f64,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let key_schema = table::common::KeySchema {
+    ///     partition_key: table::common::KeyAttribute {
+    ///         name: "id".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     sort_key: Some(table::common::KeyAttribute {
+    ///         name: "orderId".to_string(),
+    ///         ..Default::default()
+    ///     }),
+    /// };
+    /// let mut exclusive_start_key = None;
+    /// loop {
+    ///      let scan = read::scan::Scan {
+    ///          multiple_read_args: read::common::MultipleReadArgs {
+    ///              exclusive_start_key,
+    ///              limit: Some(10),
+    ///              table_name: "orders".to_string(),
+    ///              ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///      };
+    ///     let page = scan
+    ///         .send_page::<Order, String>(client, &key_schema, None)
+    ///         .await?;
+    ///     for order in page.items {
+    ///         println!("{}", order.total);
+    ///     }
+    ///     exclusive_start_key = page.last_evaluated_key;
+    ///     if exclusive_start_key.is_none() {
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.scan.send_page", skip_all, err)
+    )]
+    pub async fn send_page<O: DeserializeOwned, K: DeserializeOwned>(
+        self,
+        client: &Client,
+        key_schema: &table::common::KeySchema,
+        index_key_schema: Option<&table::common::KeySchema>,
+    ) -> Result<read::common::ItemsPage<O, K>, crate::Error> {
+        let retry_policy = self.multiple_read_args.retry_policy.clone();
+        let scan = ScanInput::try_from(self).map_err(crate::Error::Serialization)?;
+        let prune_empty_maps = scan.multiple_read_operation.prune_empty_maps;
+        let mut output = scan
+            .into_builder(client)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await?;
+        read::common::prune_items(output.items.as_mut(), prune_empty_maps);
+        let items = read::common::ItemsOutput::new(
+            output.items,
+            output.count,
+            output.scanned_count,
+            output.consumed_capacity,
+        )
+        .map_err(crate::Error::Deserialization)?;
+        read::common::ItemsPage::new(
+            items,
+            output.last_evaluated_key,
+            key_schema,
+            index_key_schema,
+        )
+        .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the scan operation, grouping the deserialized items by the value of an
+    /// attribute, in order of first appearance.
+    ///
+    /// Items lacking the attribute are grouped under `None` if the key is an `Option`, and
+    /// fail the deserialization otherwise.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, read};
+    /// use serde_json::Value;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let scan: read::scan::Scan<String> = read::scan::Scan {
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "app".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let groups = scan.group_by::<String, Value>(client, "type").await?;
+    /// for (entity_type, items) in groups {
+    ///     println!("{entity_type}: {} items", items.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.scan.group_by", skip_all, err)
+    )]
+    pub async fn group_by<K, O>(
+        self,
+        client: &Client,
+        attribute_name: &str,
+    ) -> Result<IndexMap<K, Vec<O>>, crate::Error>
+    where
+        K: DeserializeOwned + Eq + hash::Hash,
+        O: DeserializeOwned,
+    {
+        let output = self.send(client).await?;
+        read::common::group_items(output.items, attribute_name)
+            .map_err(crate::Error::Deserialization)
+    }
+
+    /// Execute the scan operation lazily, returning a stream of the groups of consecutive
+    /// items sharing the value of an attribute.
+    ///
+    /// Unlike [`Self::group_by`], items are not buffered until the end of the scan, so a
+    /// key appears once per run of consecutive items holding it. The stream ends after the
+    /// first error.
+    pub fn stream_group_by<K, O>(
+        self,
+        client: &Client,
+        attribute_name: &str,
+    ) -> impl Stream<Item = Result<(K, Vec<O>), crate::Error>> + Send + 'static + use<T, K, O>
+    where
+        K: DeserializeOwned + PartialEq + Send + 'static,
+        O: DeserializeOwned + Send + 'static,
+    {
+        read::common::group_consecutive_items(self.stream(client), attribute_name.to_string())
+    }
+
+    /// Execute the scan operation, sending the deserialized items to a bounded channel as
+    /// pages arrive.
+    ///
+    /// Waits for the receiver whenever the channel is full, and stops fetching pages once
+    /// the receiver is dropped.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::read;
+    /// use serde_json::Value;
+    /// use tokio::sync::mpsc;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let scan: read::scan::Scan<Value> = read::scan::Scan {
+    ///     multiple_read_args: read::common::MultipleReadArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let (tx, mut rx) = mpsc::channel::<Value>(100);
+    /// let consumer = tokio::spawn(async move {
+    ///     while let Some(item) = rx.recv().await {
+    ///         println!("{item}");
+    ///     }
+    /// });
+    /// scan.send_to_channel(client, tx).await?;
+    /// consumer.await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.scan.send_to_channel", skip_all, err)
+    )]
+    pub async fn send_to_channel<D: DeserializeOwned>(
+        self,
+        client: &Client,
+        tx: mpsc::Sender<D>,
+    ) -> Result<(), crate::Error> {
+        let scan: ScanInput = self.try_into().map_err(crate::Error::Serialization)?;
+        let mut counter = read::common::PageCounter::new(
+            scan.multiple_read_operation.max_pages,
+            scan.multiple_read_operation.deadline,
+            scan.multiple_read_operation.max_items,
+        );
+        let prune_empty_maps = scan.multiple_read_operation.prune_empty_maps;
+        let mut paginator = scan.into_pages(client);
+        while let Some(page) = paginator.next().await {
+            let page = page.map_err(crate::Error::from)?;
+            let mut items = counter.take_items(page.items.unwrap_or_default());
+            read::common::prune_items(Some(&mut items), prune_empty_maps);
+            let has_next_page = counter.count_page(page.last_evaluated_key.as_ref())?;
+            if !read::common::send_items_to_channel(items, &tx).await? || !has_next_page {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fold the scanned items of each segment into an accumulator, scanning up to
+    /// `segments` segments in parallel and stopping after roughly `max_items` items.
+    ///
+    /// With an adaptive concurrency, the segments share a concurrency limit tuned on the
+    /// throttling and the consumed capacity of their pages.
+    ///
+    /// Returns one accumulator per segment.
+    pub(crate) async fn fold_segments<A, F>(
+        self,
+        client: &Client,
+        segments: i32,
+        adaptive_concurrency: Option<&AdaptiveConcurrency>,
+        max_items: Option<usize>,
+        fold: F,
+    ) -> Result<Vec<A>, crate::Error>
+    where
+        T: Clone,
+        A: Default,
+        F: Fn(&mut A, collections::HashMap<String, types::AttributeValue>),
+    {
+        let backoff = common::retry::Backoff::new(
+            self.multiple_read_args.retry_policy.as_ref(),
+            ADAPTIVE_SCAN_BACKOFF,
+        );
+        let segments = segments.max(1);
+        let max_segment_items = max_items.map(|max_items| max_items.div_ceil(segments as usize));
+        let controller = adaptive_concurrency.map(|adaptive_concurrency| {
+            ConcurrencyController::new(adaptive_concurrency, segments as usize)
+        });
+        let controller = controller.as_ref();
+        let fold = &fold;
+        let segment_folds = (0..segments).map(|segment| {
+            let mut scan = self.clone();
+            if segments > 1 {
+                scan.segment = Some(segment);
+                scan.total_segments = Some(segments);
+            }
+            if controller.is_some_and(|controller| controller.max_read_capacity_units.is_some()) {
+                scan.return_consumed_capacity = Some(types::ReturnConsumedCapacity::Total);
+            }
+            async move {
+                let scan: ScanInput = scan.try_into().map_err(crate::Error::Serialization)?;
+                let mut counter = read::common::PageCounter::new(
+                    scan.multiple_read_operation.max_pages,
+                    scan.multiple_read_operation.deadline,
+                    None,
+                );
+                let prune_empty_maps = scan.multiple_read_operation.prune_empty_maps;
+                let mut accumulator = A::default();
+                let mut count = 0;
+                let mut fold_page = |mut page: operation::scan::ScanOutput| {
+                    let has_next_page = counter.count_page(page.last_evaluated_key.as_ref())?;
+                    read::common::prune_items(page.items.as_mut(), prune_empty_maps);
+                    for item in page.items.unwrap_or_default() {
+                        if max_segment_items.is_some_and(|max_items| count >= max_items) {
+                            return Ok(false);
+                        }
+                        fold(&mut accumulator, item);
+                        count += 1;
+                    }
+                    Ok::<_, crate::Error>(has_next_page)
+                };
+                match controller {
+                    Some(controller) => {
+                        let mut exclusive_start_key =
+                            scan.multiple_read_operation.exclusive_start_key.clone();
+                        loop {
+                            let page = scan
+                                .send_adaptive_page(
+                                    client,
+                                    controller,
+                                    backoff,
+                                    exclusive_start_key,
+                                )
+                                .await?;
+                            exclusive_start_key = page.last_evaluated_key.clone();
+                            if !fold_page(page)? {
+                                break;
+                            }
+                        }
+                    }
+                    None => {
+                        let mut paginator = scan.into_pages(client);
+                        while let Some(page) = paginator.next().await {
+                            if !fold_page(page?)? {
+                                break;
+                            }
+                        }
+                    }
+                }
+                Ok(accumulator)
+            }
+        });
+        future::try_join_all(segment_folds).await
+    }
+
+    /// Count the items matching the scan filter without fetching them, along with the items
+    /// scanned.
+    ///
+    /// Forces `Select::Count`, ignores any selection and sums the counts of every page.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.scan.count", skip_all, err)
+    )]
+    pub async fn count(self, client: &Client) -> Result<read::common::ItemsCount, crate::Error> {
+        let scan: ScanInput = self
+            .into_count_scan()
+            .try_into()
+            .map_err(crate::Error::Serialization)?;
+        let deadline = scan.multiple_read_operation.deadline;
+        let max_items = scan.multiple_read_operation.max_items;
+        let max_pages = scan.multiple_read_operation.max_pages;
+        let mut paginator = scan.into_pages(client);
+        crate::count_paginated_items!(paginator, max_pages, deadline, max_items)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common;
 
     use rstest::rstest;
     use serde_json::Value;
-    use std::collections;
 
     #[rstest]
     #[case::empty(
@@ -129,20 +868,29 @@ mod tests {
                     )
                 ),
                 consistent_read: Some(false),
+                cursor: None,
+                deadline: None,
                 exclusive_start_key: Some(
-                    collections::HashMap::from(
-                        [
-                            (
-                                "c".to_string(),
-                                Value::String(
+                    common::key::LastEvaluatedKey {
+                        index_keys: None,
+                        keys: common::key::Keys {
+                            partition_key: common::key::Key {
+                                name: "c".to_string(),
+                                value: Value::String(
                                     "d".to_string()
-                                )
-                            ),
-                        ]
-                    )
+                                ),
+                            },
+                            sort_key: None,
+                        },
+                    }
                 ),
                 index_name: Some("e".to_string()),
                 limit: Some(10),
+                max_items: Some(5),
+                max_pages: Some(read::common::MaxPages::Truncate(2)),
+                placeholder_naming: None,
+                prune_empty_maps: false,
+                retry_policy: None,
                 select: Some(
                     types::Select::Count
                 ),
@@ -165,6 +913,7 @@ mod tests {
         ScanInput {
             multiple_read_operation: read::common::MultipleReadInput {
                 consistent_read: Some(false),
+                deadline: None,
                 exclusive_start_key: Some(
                     collections::HashMap::from(
                         [
@@ -203,9 +952,13 @@ mod tests {
                 ),
                 index_name: Some("e".to_string()),
                 limit: Some(10),
+                max_items: Some(5),
+                max_pages: Some(read::common::MaxPages::Truncate(2)),
                 projection_expression: Some(
                     "#f, #g".to_string()
                 ),
+                prune_empty_maps: false,
+                retry_policy: None,
                 select: Some(
                     types::Select::Count
                 ),
@@ -222,4 +975,69 @@ mod tests {
         let actual: ScanInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case::selection(
+        Scan {
+            multiple_read_args: read::common::MultipleReadArgs {
+                select: Some(
+                    types::Select::SpecificAttributes
+                ),
+                selection: Some(
+                    common::selection::SelectionMap::Leaves(
+                        vec![
+                            "a".to_string(),
+                        ]
+                    )
+                ),
+                table_name: "b".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        Scan {
+            multiple_read_args: read::common::MultipleReadArgs {
+                select: Some(
+                    types::Select::Count
+                ),
+                table_name: "b".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    )]
+    fn test_scan_count(#[case] args: Scan<Value>, #[case] expected: Scan<Value>) {
+        let actual = args.into_count_scan();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::within_window(false, 100.0, 500, Some(50.0), 4)]
+    #[case::throttled(true, 0.0, 0, None, 2)]
+    #[case::under_capacity(false, 10.0, 1000, Some(50.0), 5)]
+    #[case::over_capacity(false, 100.0, 1000, Some(50.0), 2)]
+    #[case::unlimited(false, 100.0, 1000, None, 5)]
+    fn test_concurrency_state(
+        #[case] throttled: bool,
+        #[case] consumed_capacity_units: f64,
+        #[case] elapsed_millis: u64,
+        #[case] max_read_capacity_units: Option<f64>,
+        #[case] expected: usize,
+    ) {
+        let window_start = time::Instant::now();
+        let mut state = ConcurrencyState {
+            concurrency: 4,
+            consumed_capacity_units: 0.0,
+            running: 0,
+            window_start,
+        };
+        state.adjust(
+            throttled,
+            consumed_capacity_units,
+            5,
+            max_read_capacity_units,
+            window_start + time::Duration::from_millis(elapsed_millis),
+        );
+        assert_eq!(state.concurrency, expected);
+    }
 }
@@ -0,0 +1,225 @@
+use crate::{common, common::retry::WithRetryPolicy as _, read};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+use serde::Serialize;
+use serde_dynamo::{Error, Result};
+
+/// Transact get items operation.
+///
+/// Built from the regular get item operations. Only the keys, the table name and the
+/// selection of each get item apply within a transaction, and the responses are returned
+/// in the order of the get items.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let get_user = read::get_item::GetItem {
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: "1".to_string(),
+///         },
+///         ..Default::default()
+///     },
+///     single_read_args: read::common::SingleReadArgs {
+///         selection: Some(common::selection::SelectionMap::Leaves(vec![
+///             "balance".to_string(),
+///         ])),
+///         table_name: "accounts".to_string(),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let get_order = read::get_item::GetItem {
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: "2".to_string(),
+///         },
+///         ..Default::default()
+///     },
+///     single_read_args: read::common::SingleReadArgs {
+///         table_name: "orders".to_string(),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let transact_get_items = read::transact_get_items::TransactGetItems {
+///     transact_items: vec![get_user, get_order],
+///     ..Default::default()
+/// };
+/// let output = transact_get_items.send(client).await?;
+/// for response in output.responses() {
+///     println!("{:?}", response.item());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransactGetItems<T> {
+    /// Whether to return the consumed capacity information.
+    pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The items to read atomically (up to 100).
+    pub transact_items: Vec<read::get_item::GetItem<T>>,
+}
+
+fn get_transact_get_item<T: Serialize>(
+    get_item: read::get_item::GetItem<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> Result<types::TransactGetItem> {
+    let get_item = read::get_item::GetItemInput::new(get_item, serializer)?;
+    let single_read_operation = get_item.single_read_operation;
+    let get = types::Get::builder()
+        .set_key(Some(get_item.keys))
+        .set_expression_attribute_names(single_read_operation.expression_attribute_names)
+        .set_projection_expression(single_read_operation.projection_expression)
+        .table_name(single_read_operation.table_name)
+        .build()
+        .unwrap();
+    Ok(types::TransactGetItem::builder().get(get).build())
+}
+
+fn get_transact_get_items_input<T: Serialize>(
+    transact_get_items: TransactGetItems<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> Result<operation::transact_get_items::TransactGetItemsInput> {
+    let mut transact_items = Vec::with_capacity(transact_get_items.transact_items.len());
+    for get_item in transact_get_items.transact_items {
+        let transact_item = get_transact_get_item(get_item, serializer)?;
+        transact_items.push(transact_item);
+    }
+    let operation = operation::transact_get_items::TransactGetItemsInput::builder()
+        .set_return_consumed_capacity(transact_get_items.return_consumed_capacity)
+        .set_transact_items(Some(transact_items))
+        .build()
+        .unwrap();
+    Ok(operation)
+}
+
+impl<T: Serialize> TryFrom<TransactGetItems<T>>
+    for operation::transact_get_items::TransactGetItemsInput
+{
+    type Error = Error;
+
+    fn try_from(transact_get_items: TransactGetItems<T>) -> Result<Self> {
+        get_transact_get_items_input(transact_get_items, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T: Serialize> TransactGetItems<T> {
+    /// Execute the transact get items operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::transact_get_items::TransactGetItemsOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the transact get items operation, converting the keys with the given
+    /// serializer.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.transact_get_items", skip_all, err)
+    )]
+    pub async fn send_with(
+        self,
+        client: &Client,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::transact_get_items::TransactGetItemsOutput, crate::Error> {
+        let retry_policy = self.retry_policy.clone();
+        let transact_get_items =
+            get_transact_get_items_input(self, serializer).map_err(crate::Error::Serialization)?;
+        client
+            .transact_get_items()
+            .set_return_consumed_capacity(transact_get_items.return_consumed_capacity)
+            .set_transact_items(transact_get_items.transact_items)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::Value;
+
+    #[test]
+    fn test_transact_get_items() {
+        let args = TransactGetItems {
+            return_consumed_capacity: Some(types::ReturnConsumedCapacity::Total),
+            transact_items: vec![
+                read::get_item::GetItem {
+                    keys: common::key::Keys {
+                        partition_key: common::key::Key {
+                            name: "a".to_string(),
+                            value: Value::String("b".to_string()),
+                        },
+                        ..Default::default()
+                    },
+                    single_read_args: read::common::SingleReadArgs {
+                        selection: Some(common::selection::SelectionMap::Leaves(vec![
+                            "c".to_string(),
+                        ])),
+                        table_name: "d".to_string(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                read::get_item::GetItem {
+                    keys: common::key::Keys {
+                        partition_key: common::key::Key {
+                            name: "e".to_string(),
+                            value: Value::String("f".to_string()),
+                        },
+                        ..Default::default()
+                    },
+                    single_read_args: read::common::SingleReadArgs {
+                        table_name: "g".to_string(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let actual: operation::transact_get_items::TransactGetItemsInput = args.try_into().unwrap();
+        let expected = operation::transact_get_items::TransactGetItemsInput::builder()
+            .return_consumed_capacity(types::ReturnConsumedCapacity::Total)
+            .transact_items(
+                types::TransactGetItem::builder()
+                    .get(
+                        types::Get::builder()
+                            .key("a", types::AttributeValue::S("b".to_string()))
+                            .expression_attribute_names("#c", "c")
+                            .projection_expression("#c")
+                            .table_name("d")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .transact_items(
+                types::TransactGetItem::builder()
+                    .get(
+                        types::Get::builder()
+                            .key("e", types::AttributeValue::S("f".to_string()))
+                            .table_name("g")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,379 @@
+//! Typed repositories of the items of a table.
+//!
+//! A repository covers the common reads and writes of an item type bound to its table by
+//! [`DynamoItem`](crate::common::item::DynamoItem): getting, putting, deleting, patching and
+//! querying items by plain key values.
+
+use crate::{common, raw, read, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use indexmap::IndexMap;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{collections, fmt::Write, marker};
+
+/// Typed repository of the items of a table, for the common reads and writes.
+///
+/// The table and its key attributes come from the [`DynamoItem`](common::item::DynamoItem)
+/// implementation of the item type, and keys are given as plain values of type `V`, so callers
+/// never build keys, attribute values or expressions. The sort key is required if the table has
+/// one, and rejected otherwise: operations given a mismatched sort key fail with
+/// [`crate::Error::Validation`].
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, repository};
+/// use serde::{Deserialize, Serialize};
+/// use serde_json::Value;
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct User {
+///     id: String,
+///     name: String,
+/// }
+///
+/// impl common::item::DynamoItem<Value> for User {
+///     const TABLE_NAME: &'static str = "users";
+///     const PARTITION_KEY: &'static str = "id";
+///     const SORT_KEY: Option<&'static str> = None;
+///
+///     fn keys(&self) -> common::key::Keys<Value> {
+///         common::key::Keys {
+///             partition_key: common::key::Key {
+///                 name: Self::PARTITION_KEY.to_string(),
+///                 value: Value::from(self.id.clone()),
+///             },
+///             sort_key: None,
+///         }
+///     }
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Rename<'a> {
+///     name: &'a str,
+/// }
+///
+/// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let users: repository::Repository<User, Value> = repository::Repository::new(client);
+/// let user = User {
+///     id: "1".to_string(),
+///     name: "Jane".to_string(),
+/// };
+/// users.put(&user).await?;
+/// let user = users.update("1", None, Rename { name: "John" }).await?;
+/// if let Some(user) = users.get("1", None).await? {
+///     println!("{}", user.name);
+/// }
+/// let same_partition = users.query_pk("1").await?;
+/// users.delete("1", None).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Repository<T, V> {
+    client: Client,
+    item: marker::PhantomData<fn() -> (T, V)>,
+}
+
+impl<T, V> Clone for Repository<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            item: marker::PhantomData,
+        }
+    }
+}
+
+impl<T, V> Repository<T, V> {
+    /// Create a repository sending its requests with the given client.
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            item: marker::PhantomData,
+        }
+    }
+}
+
+/// Build the update setting every attribute of a patch, except the key attributes, on an
+/// existing item.
+fn get_patch_update(
+    keys: collections::HashMap<String, types::AttributeValue>,
+    patch: collections::HashMap<String, types::AttributeValue>,
+    table_name: &str,
+    partition_key_name: &str,
+) -> Result<raw::RawUpdateItem, crate::Error> {
+    let mut attributes: Vec<_> = patch
+        .into_iter()
+        .filter(|(name, _)| !keys.contains_key(name))
+        .collect();
+    if attributes.is_empty() {
+        return Err(crate::Error::validation(
+            "the patch sets no attribute besides the keys",
+        ));
+    }
+    attributes.sort_by(|(name, _), (other_name, _)| name.cmp(other_name));
+    let mut placeholders = common::PlaceholderAllocator::default();
+    let partition_key_placeholder = placeholders.allocate_name(partition_key_name);
+    let mut write_args = raw::RawWriteArgs {
+        condition_expression: Some(format!("attribute_exists({partition_key_placeholder})")),
+        expression_attribute_names: IndexMap::from([(
+            partition_key_placeholder,
+            partition_key_name.to_string(),
+        )]),
+        return_values: Some(types::ReturnValue::AllNew),
+        table_name: table_name.to_string(),
+        ..Default::default()
+    };
+    let mut update_expression = String::from("SET ");
+    for (index, (name, value)) in attributes.into_iter().enumerate() {
+        let name_placeholder = placeholders.allocate_name(&name);
+        let value_placeholder = placeholders.allocate("set");
+        if index > 0 {
+            update_expression.push_str(", ");
+        }
+        let _ = write!(
+            update_expression,
+            "{name_placeholder} = {value_placeholder}"
+        );
+        write_args
+            .expression_attribute_names
+            .insert(name_placeholder, name);
+        write_args
+            .expression_attribute_values
+            .insert(value_placeholder, value);
+    }
+    let update_item = raw::RawUpdateItem {
+        keys,
+        update_expression,
+        write_args,
+    };
+    Ok(update_item)
+}
+
+impl<T, V> Repository<T, V>
+where
+    T: common::item::DynamoItem<V> + DeserializeOwned + Serialize,
+    V: Clone + Serialize,
+{
+    /// Build the primary key, failing if the sort key is missing for a table with a sort key,
+    /// or given for a table without one.
+    fn get_keys(
+        partition_key: impl Into<V>,
+        sort_key: Option<V>,
+    ) -> Result<common::key::Keys<V>, crate::Error> {
+        let sort_key = match (T::SORT_KEY, sort_key) {
+            (Some(name), Some(value)) => Some(common::key::Key {
+                name: name.to_string(),
+                value,
+            }),
+            (Some(name), None) => {
+                return Err(crate::Error::validation(format!(
+                    "the sort key {name} of table {} is required",
+                    T::TABLE_NAME
+                )));
+            }
+            (None, Some(_)) => {
+                return Err(crate::Error::validation(format!(
+                    "table {} has no sort key",
+                    T::TABLE_NAME
+                )));
+            }
+            (None, None) => None,
+        };
+        let keys = common::key::Keys {
+            partition_key: common::key::Key {
+                name: T::PARTITION_KEY.to_string(),
+                value: partition_key.into(),
+            },
+            sort_key,
+        };
+        Ok(keys)
+    }
+
+    /// Get the item with the given key, or `None` if it does not exist.
+    pub async fn get(
+        &self,
+        partition_key: impl Into<V>,
+        sort_key: Option<V>,
+    ) -> Result<Option<T>, crate::Error> {
+        T::get(Self::get_keys(partition_key, sort_key)?)
+            .send_as(&self.client)
+            .await
+    }
+
+    /// Put the item, replacing any item with the same key.
+    pub async fn put(&self, item: &T) -> Result<(), crate::Error> {
+        let put_item = write::put_item::PutItem {
+            item,
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                table_name: T::TABLE_NAME.to_string(),
+                ..Default::default()
+            },
+        };
+        put_item.send(&self.client).await?;
+        Ok(())
+    }
+
+    /// Delete the item with the given key, if any.
+    pub async fn delete(
+        &self,
+        partition_key: impl Into<V>,
+        sort_key: Option<V>,
+    ) -> Result<(), crate::Error> {
+        T::delete(Self::get_keys(partition_key, sort_key)?)
+            .send(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    /// Get every item with the given partition key, in sort key order.
+    pub async fn query_pk(&self, partition_key: impl Into<V>) -> Result<Vec<T>, crate::Error> {
+        let output = read::query::Query::builder(T::TABLE_NAME, T::PARTITION_KEY, partition_key)
+            .build()
+            .send_items(&self.client)
+            .await?;
+        Ok(output.items)
+    }
+
+    /// Set the attributes of the patch on the item with the given key, returning the updated
+    /// item.
+    ///
+    /// The patch is any serializable struct or map: every attribute it serializes becomes an
+    /// assignment, so fields skipped when serializing are left untouched. Key attributes are
+    /// ignored, and the update fails with [`crate::Error::ConditionalCheckFailed`] if the item
+    /// does not exist, rather than creating a partial item.
+    pub async fn update(
+        &self,
+        partition_key: impl Into<V>,
+        sort_key: Option<V>,
+        patch: impl Serialize,
+    ) -> Result<T, crate::Error> {
+        let serializer = &common::serializer::SerdeDynamo;
+        let keys = Self::get_keys(partition_key, sort_key)?
+            .serialize(serializer)
+            .map_err(crate::Error::Serialization)?;
+        let patch = common::serializer::ValueSerializer::to_item(serializer, patch)
+            .map_err(crate::Error::Serialization)?;
+        let update_item = get_patch_update(keys, patch, T::TABLE_NAME, T::PARTITION_KEY)?;
+        let output = update_item.send(&self.client).await?;
+        serde_dynamo::from_item(output.attributes.unwrap_or_default())
+            .map_err(crate::Error::Deserialization)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    #[derive(Deserialize, Serialize)]
+    struct Item {
+        a: String,
+        b: u64,
+    }
+
+    impl common::item::DynamoItem<Value> for Item {
+        const TABLE_NAME: &'static str = "c";
+        const PARTITION_KEY: &'static str = "a";
+        const SORT_KEY: Option<&'static str> = Some("b");
+
+        fn keys(&self) -> common::key::Keys<Value> {
+            Repository::<Self, Value>::get_keys(self.a.clone(), Some(Value::from(self.b))).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_get_keys() {
+        let actual = Repository::<Item, Value>::get_keys("d", Some(Value::from(1))).unwrap();
+        let expected = common::key::Keys {
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: Value::from("d"),
+            },
+            sort_key: Some(common::key::Key {
+                name: "b".to_string(),
+                value: Value::from(1),
+            }),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_keys_missing_sort_key() {
+        let actual = Repository::<Item, Value>::get_keys("d", None);
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct PartitionItem {
+        a: String,
+    }
+
+    impl common::item::DynamoItem<Value> for PartitionItem {
+        const TABLE_NAME: &'static str = "c";
+        const PARTITION_KEY: &'static str = "a";
+        const SORT_KEY: Option<&'static str> = None;
+
+        fn keys(&self) -> common::key::Keys<Value> {
+            Repository::<Self, Value>::get_keys(self.a.clone(), None).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_get_keys_unexpected_sort_key() {
+        let actual = Repository::<PartitionItem, Value>::get_keys("d", Some(Value::from(1)));
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_get_patch_update() {
+        let keys = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let patch = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::S("c".to_string())),
+            ("e".to_string(), types::AttributeValue::N("1".to_string())),
+            ("d".to_string(), types::AttributeValue::S("f".to_string())),
+        ]);
+        let actual = get_patch_update(keys.clone(), patch, "g", "a").unwrap();
+        let expected = raw::RawUpdateItem {
+            keys,
+            update_expression: "SET #d = :set0, #e = :set1".to_string(),
+            write_args: raw::RawWriteArgs {
+                condition_expression: Some("attribute_exists(#a)".to_string()),
+                expression_attribute_names: IndexMap::from([
+                    ("#a".to_string(), "a".to_string()),
+                    ("#d".to_string(), "d".to_string()),
+                    ("#e".to_string(), "e".to_string()),
+                ]),
+                expression_attribute_values: IndexMap::from([
+                    (
+                        ":set0".to_string(),
+                        types::AttributeValue::S("f".to_string()),
+                    ),
+                    (
+                        ":set1".to_string(),
+                        types::AttributeValue::N("1".to_string()),
+                    ),
+                ]),
+                return_values: Some(types::ReturnValue::AllNew),
+                table_name: "g".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_patch_update_keys_only() {
+        let keys = collections::HashMap::from([(
+            "a".to_string(),
+            types::AttributeValue::S("b".to_string()),
+        )]);
+        let actual = get_patch_update(keys.clone(), keys, "c", "a");
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+}
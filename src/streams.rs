@@ -0,0 +1,243 @@
+//! Typed change records of DynamoDB stream events delivered to Lambda functions.
+//!
+//! This module provides:
+//! - The stream event payload of the Lambda trigger, with its images in DynamoDB JSON
+//! - Change records deserializing the old and new images into the item type
+//! - Deserializing a single image, for consumers of other stream event types
+
+use aws_sdk_dynamodb::types;
+use serde::{Deserialize, de::DeserializeOwned, de::Error as _};
+use std::collections;
+
+/// DynamoDB stream event delivered to a Lambda function.
+///
+/// Mirrors the payload of the Lambda trigger, with the images kept in DynamoDB JSON. Its
+/// images are the `serde_dynamo::Item` images of the `aws_lambda_events` DynamoDB event, so
+/// consumers already deserializing that event can pass its images to [`from_image`] instead.
+///
+/// ```rust
+/// use dynamodb_crud::streams;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     id: String,
+///     name: String,
+/// }
+///
+/// # fn example(payload: serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+/// let event: streams::StreamEvent = serde_json::from_value(payload)?;
+/// for record in event.into_change_records::<User>()? {
+///     if let Some(user) = record.new_image {
+///         println!("{} is now {}", user.id, user.name);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct StreamEvent {
+    /// The records of the event, in stream order.
+    #[serde(default, rename = "Records")]
+    pub records: Vec<StreamEventRecord>,
+}
+
+impl StreamEvent {
+    /// Convert every record of the event into a typed change record, in stream order.
+    pub fn into_change_records<T: DeserializeOwned>(
+        self,
+    ) -> Result<Vec<ChangeRecord<T>>, crate::Error> {
+        self.records
+            .into_iter()
+            .map(ChangeRecord::try_from)
+            .collect()
+    }
+}
+
+/// Record of a DynamoDB stream event.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEventRecord {
+    /// The change made to the item.
+    #[serde(rename = "dynamodb")]
+    pub change: StreamRecordChange,
+    /// The unique identifier of the record.
+    #[serde(default, rename = "eventID")]
+    pub event_id: String,
+    /// The kind of change: `INSERT`, `MODIFY` or `REMOVE`.
+    pub event_name: String,
+    /// The ARN of the stream the record was read from.
+    #[serde(default, rename = "eventSourceARN")]
+    pub event_source_arn: Option<String>,
+}
+
+/// Change made to an item, with its images in DynamoDB JSON.
+///
+/// The images are empty when excluded by the stream view type of the table.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct StreamRecordChange {
+    /// The key attributes of the item.
+    #[serde(default)]
+    pub keys: serde_dynamo::Item,
+    /// The item after the change.
+    #[serde(default)]
+    pub new_image: serde_dynamo::Item,
+    /// The item before the change.
+    #[serde(default)]
+    pub old_image: serde_dynamo::Item,
+    /// The sequence number of the record within its shard.
+    #[serde(default)]
+    pub sequence_number: Option<String>,
+}
+
+/// Kind of change of a stream record.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChangeKind {
+    /// A new item was added.
+    Insert,
+    /// An existing item was updated.
+    Modify,
+    /// An item was deleted.
+    Remove,
+}
+
+impl std::str::FromStr for ChangeKind {
+    type Err = crate::Error;
+
+    fn from_str(event_name: &str) -> Result<Self, Self::Err> {
+        match event_name {
+            "INSERT" => Ok(Self::Insert),
+            "MODIFY" => Ok(Self::Modify),
+            "REMOVE" => Ok(Self::Remove),
+            _ => Err(crate::Error::Deserialization(serde_dynamo::Error::custom(
+                format!("unknown stream event name {event_name}"),
+            ))),
+        }
+    }
+}
+
+/// Typed change of a stream record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeRecord<T> {
+    /// The unique identifier of the record.
+    pub event_id: String,
+    /// The key attributes of the item.
+    pub keys: collections::HashMap<String, types::AttributeValue>,
+    /// The kind of change.
+    pub kind: ChangeKind,
+    /// The item after the change, if included by the stream view type and not removed.
+    pub new_image: Option<T>,
+    /// The item before the change, if included by the stream view type and not inserted.
+    pub old_image: Option<T>,
+    /// The sequence number of the record within its shard.
+    pub sequence_number: Option<String>,
+}
+
+impl<T: DeserializeOwned> TryFrom<StreamEventRecord> for ChangeRecord<T> {
+    type Error = crate::Error;
+
+    fn try_from(record: StreamEventRecord) -> Result<Self, Self::Error> {
+        let change_record = Self {
+            event_id: record.event_id,
+            keys: record.change.keys.into(),
+            kind: record.event_name.parse()?,
+            new_image: from_image(record.change.new_image)?,
+            old_image: from_image(record.change.old_image)?,
+            sequence_number: record.change.sequence_number,
+        };
+        Ok(change_record)
+    }
+}
+
+/// Deserialize the image of a stream record, the way typed reads deserialize items.
+///
+/// Returns `None` if the image is empty, such as when excluded by the stream view type.
+pub fn from_image<T: DeserializeOwned>(
+    image: serde_dynamo::Item,
+) -> Result<Option<T>, crate::Error> {
+    if image.is_empty() {
+        return Ok(None);
+    }
+    serde_dynamo::from_item(image)
+        .map(Some)
+        .map_err(crate::Error::Deserialization)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    fn get_event() -> StreamEvent {
+        let payload = json!({
+            "Records": [
+                {
+                    "eventID": "a",
+                    "eventName": "MODIFY",
+                    "eventSource": "aws:dynamodb",
+                    "eventSourceARN": "b",
+                    "dynamodb": {
+                        "Keys": {"c": {"S": "d"}},
+                        "NewImage": {"c": {"S": "d"}, "e": {"N": "2"}},
+                        "OldImage": {"c": {"S": "d"}, "e": {"N": "1"}},
+                        "SequenceNumber": "f",
+                        "SizeBytes": 10,
+                        "StreamViewType": "NEW_AND_OLD_IMAGES"
+                    }
+                },
+                {
+                    "eventID": "g",
+                    "eventName": "REMOVE",
+                    "dynamodb": {
+                        "Keys": {"c": {"S": "h"}},
+                        "StreamViewType": "NEW_IMAGE"
+                    }
+                }
+            ]
+        });
+        serde_json::from_value(payload).unwrap()
+    }
+
+    #[test]
+    fn test_into_change_records() {
+        let actual = get_event().into_change_records::<Value>().unwrap();
+        let expected = vec![
+            ChangeRecord {
+                event_id: "a".to_string(),
+                keys: collections::HashMap::from([(
+                    "c".to_string(),
+                    types::AttributeValue::S("d".to_string()),
+                )]),
+                kind: ChangeKind::Modify,
+                new_image: Some(json!({"c": "d", "e": 2})),
+                old_image: Some(json!({"c": "d", "e": 1})),
+                sequence_number: Some("f".to_string()),
+            },
+            ChangeRecord {
+                event_id: "g".to_string(),
+                keys: collections::HashMap::from([(
+                    "c".to_string(),
+                    types::AttributeValue::S("h".to_string()),
+                )]),
+                kind: ChangeKind::Remove,
+                new_image: None,
+                old_image: None,
+                sequence_number: None,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::insert("INSERT", Some(ChangeKind::Insert))]
+    #[case::modify("MODIFY", Some(ChangeKind::Modify))]
+    #[case::remove("REMOVE", Some(ChangeKind::Remove))]
+    #[case::unknown("a", None)]
+    fn test_change_kind(#[case] event_name: &str, #[case] expected: Option<ChangeKind>) {
+        let actual = event_name.parse::<ChangeKind>().ok();
+        assert_eq!(actual, expected);
+    }
+}
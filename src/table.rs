@@ -0,0 +1,27 @@
+//! Table management operations for provisioning DynamoDB tables.
+//!
+//! This module provides operations for:
+//! - Creating tables with their key schema, secondary indexes, billing mode, encryption and
+//!   streams
+//! - Deleting tables
+//! - Describing tables
+//! - Updating the throughput, billing mode, streams and global secondary indexes of tables
+//! - Waiting until tables are active or no longer exist
+
+/// Common types for table management operations.
+pub mod common;
+
+/// Create table operation for provisioning new tables.
+pub mod create_table;
+
+/// Delete table operation for removing tables.
+pub mod delete_table;
+
+/// Describe table operation for inspecting tables.
+pub mod describe_table;
+
+/// Update table operation for changing the settings and indexes of tables.
+pub mod update_table;
+
+/// Waiters blocking until tables reach the expected state.
+pub mod waiter;
@@ -0,0 +1,248 @@
+use aws_sdk_dynamodb::types;
+
+/// A key attribute of a table or index, along with its type.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct KeyAttribute {
+    /// The type of the attribute.
+    pub attribute_type: types::ScalarAttributeType,
+    /// The name of the attribute.
+    pub name: String,
+}
+
+impl Default for KeyAttribute {
+    fn default() -> Self {
+        Self {
+            attribute_type: types::ScalarAttributeType::S,
+            name: String::new(),
+        }
+    }
+}
+
+impl KeyAttribute {
+    fn get_key_schema_element(&self, key_type: types::KeyType) -> types::KeySchemaElement {
+        types::KeySchemaElement::builder()
+            .attribute_name(&self.name)
+            .key_type(key_type)
+            .build()
+            .unwrap()
+    }
+
+    fn get_attribute_definition(&self) -> types::AttributeDefinition {
+        types::AttributeDefinition::builder()
+            .attribute_name(&self.name)
+            .attribute_type(self.attribute_type.clone())
+            .build()
+            .unwrap()
+    }
+}
+
+/// The primary key of a table or a global secondary index.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct KeySchema {
+    /// The partition key attribute.
+    pub partition_key: KeyAttribute,
+    /// The sort key attribute, if any.
+    pub sort_key: Option<KeyAttribute>,
+}
+
+impl KeySchema {
+    pub(crate) fn get_key_schema_elements(&self) -> Vec<types::KeySchemaElement> {
+        let mut elements = vec![
+            self.partition_key
+                .get_key_schema_element(types::KeyType::Hash),
+        ];
+        if let Some(sort_key) = &self.sort_key {
+            elements.push(sort_key.get_key_schema_element(types::KeyType::Range));
+        }
+        elements
+    }
+
+    fn get_key_attributes(&self) -> impl Iterator<Item = &KeyAttribute> {
+        std::iter::once(&self.partition_key).chain(&self.sort_key)
+    }
+}
+
+/// Provisioned read and write capacity units.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Throughput {
+    /// The read capacity units.
+    pub read_capacity_units: i64,
+    /// The write capacity units.
+    pub write_capacity_units: i64,
+}
+
+impl From<Throughput> for types::ProvisionedThroughput {
+    fn from(throughput: Throughput) -> Self {
+        Self::builder()
+            .read_capacity_units(throughput.read_capacity_units)
+            .write_capacity_units(throughput.write_capacity_units)
+            .build()
+            .unwrap()
+    }
+}
+
+/// How read and write capacity is billed.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum BillingMode {
+    /// Pay per request, without capacity planning.
+    #[default]
+    PayPerRequest,
+    /// Provisioned capacity for the table and its global secondary indexes.
+    Provisioned(Throughput),
+}
+
+impl BillingMode {
+    /// Get the billing mode along with the provisioned throughput, if any.
+    pub(crate) fn get_billing_mode(
+        &self,
+    ) -> (types::BillingMode, Option<types::ProvisionedThroughput>) {
+        match self {
+            Self::PayPerRequest => (types::BillingMode::PayPerRequest, None),
+            Self::Provisioned(throughput) => {
+                (types::BillingMode::Provisioned, Some((*throughput).into()))
+            }
+        }
+    }
+}
+
+/// The attributes copied into a secondary index.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Projection {
+    /// Every attribute.
+    #[default]
+    All,
+    /// Only the key attributes of the table and the index.
+    KeysOnly,
+    /// The key attributes along with the listed ones.
+    Include(Vec<String>),
+}
+
+impl From<Projection> for types::Projection {
+    fn from(projection: Projection) -> Self {
+        let builder = Self::builder();
+        let builder = match projection {
+            Projection::All => builder.projection_type(types::ProjectionType::All),
+            Projection::KeysOnly => builder.projection_type(types::ProjectionType::KeysOnly),
+            Projection::Include(attribute_names) => builder
+                .projection_type(types::ProjectionType::Include)
+                .set_non_key_attributes(Some(attribute_names)),
+        };
+        builder.build()
+    }
+}
+
+/// A global secondary index.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct GlobalSecondaryIndex {
+    /// The name of the index.
+    pub index_name: String,
+    /// The primary key of the index.
+    pub key_schema: KeySchema,
+    /// The attributes copied into the index.
+    pub projection: Projection,
+    /// The provisioned throughput of the index, required for provisioned tables.
+    pub throughput: Option<Throughput>,
+}
+
+impl From<GlobalSecondaryIndex> for types::GlobalSecondaryIndex {
+    fn from(index: GlobalSecondaryIndex) -> Self {
+        Self::builder()
+            .index_name(index.index_name)
+            .set_key_schema(Some(index.key_schema.get_key_schema_elements()))
+            .projection(index.projection.into())
+            .set_provisioned_throughput(index.throughput.map(Into::into))
+            .build()
+            .unwrap()
+    }
+}
+
+/// A local secondary index, sharing the partition key of the table.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LocalSecondaryIndex {
+    /// The name of the index.
+    pub index_name: String,
+    /// The attributes copied into the index.
+    pub projection: Projection,
+    /// The sort key of the index.
+    pub sort_key: KeyAttribute,
+}
+
+impl LocalSecondaryIndex {
+    pub(crate) fn into_local_secondary_index(
+        self,
+        partition_key: &KeyAttribute,
+    ) -> types::LocalSecondaryIndex {
+        types::LocalSecondaryIndex::builder()
+            .index_name(self.index_name)
+            .key_schema(partition_key.get_key_schema_element(types::KeyType::Hash))
+            .key_schema(self.sort_key.get_key_schema_element(types::KeyType::Range))
+            .projection(self.projection.into())
+            .build()
+            .unwrap()
+    }
+}
+
+/// Get the attribute definitions of the key attributes, without duplicates.
+pub(crate) fn get_attribute_definitions<'a>(
+    key_schemas: impl IntoIterator<Item = &'a KeySchema>,
+    sort_keys: impl IntoIterator<Item = &'a KeyAttribute>,
+) -> Vec<types::AttributeDefinition> {
+    let key_attributes = key_schemas
+        .into_iter()
+        .flat_map(KeySchema::get_key_attributes)
+        .chain(sort_keys);
+    let mut attribute_definitions: Vec<types::AttributeDefinition> = Vec::new();
+    for key_attribute in key_attributes {
+        let defined = attribute_definitions
+            .iter()
+            .any(|definition| definition.attribute_name() == key_attribute.name);
+        if !defined {
+            attribute_definitions.push(key_attribute.get_attribute_definition());
+        }
+    }
+    attribute_definitions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_key_attribute(name: &str) -> KeyAttribute {
+        KeyAttribute {
+            attribute_type: types::ScalarAttributeType::S,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_get_attribute_definitions() {
+        let table_key_schema = KeySchema {
+            partition_key: get_key_attribute("a"),
+            sort_key: Some(get_key_attribute("b")),
+        };
+        let index_key_schema = KeySchema {
+            partition_key: get_key_attribute("b"),
+            sort_key: None,
+        };
+        let actual = get_attribute_definitions(
+            [&table_key_schema, &index_key_schema],
+            [&get_key_attribute("c")],
+        );
+        let expected = vec![
+            get_key_attribute("a").get_attribute_definition(),
+            get_key_attribute("b").get_attribute_definition(),
+            get_key_attribute("c").get_attribute_definition(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_projection() {
+        let actual: types::Projection = Projection::Include(vec!["a".to_string()]).into();
+        let expected = types::Projection::builder()
+            .projection_type(types::ProjectionType::Include)
+            .non_key_attributes("a")
+            .build();
+        assert_eq!(actual, expected);
+    }
+}
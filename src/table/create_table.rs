@@ -0,0 +1,283 @@
+use crate::{common, common::retry::WithRetryPolicy as _, table};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+
+/// Create table operation.
+///
+/// The attribute definitions are derived from the key attributes of the table and its
+/// indexes.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::{Client, types};
+/// use dynamodb_crud::table;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let create_table = table::create_table::CreateTable {
+///     global_secondary_indexes: vec![table::common::GlobalSecondaryIndex {
+///         index_name: "byEmail".to_string(),
+///         key_schema: table::common::KeySchema {
+///             partition_key: table::common::KeyAttribute {
+///                 attribute_type: types::ScalarAttributeType::S,
+///                 name: "email".to_string(),
+///             },
+///             sort_key: None,
+///         },
+///         ..Default::default()
+///     }],
+///     key_schema: table::common::KeySchema {
+///         partition_key: table::common::KeyAttribute {
+///             attribute_type: types::ScalarAttributeType::S,
+///             name: "id".to_string(),
+///         },
+///         sort_key: None,
+///     },
+///     stream_view_type: Some(types::StreamViewType::NewAndOldImages),
+///     table_name: "users".to_string(),
+///     ..Default::default()
+/// };
+/// create_table.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CreateTable {
+    /// How read and write capacity is billed.
+    pub billing_mode: table::common::BillingMode,
+    /// Whether the table is protected from deletion.
+    pub deletion_protection: Option<bool>,
+    /// The global secondary indexes of the table.
+    pub global_secondary_indexes: Vec<table::common::GlobalSecondaryIndex>,
+    /// The primary key of the table.
+    pub key_schema: table::common::KeySchema,
+    /// The local secondary indexes of the table.
+    pub local_secondary_indexes: Vec<table::common::LocalSecondaryIndex>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The server-side encryption settings of the table.
+    ///
+    /// If `None`, the table is encrypted with a key owned by DynamoDB.
+    pub sse_specification: Option<types::SseSpecification>,
+    /// What the stream of the table captures about modified items.
+    ///
+    /// If `None`, the stream is disabled.
+    pub stream_view_type: Option<types::StreamViewType>,
+    /// The name of the table to create.
+    pub table_name: String,
+}
+
+impl From<CreateTable> for operation::create_table::CreateTableInput {
+    fn from(create_table: CreateTable) -> Self {
+        let attribute_definitions = table::common::get_attribute_definitions(
+            std::iter::once(&create_table.key_schema).chain(
+                create_table
+                    .global_secondary_indexes
+                    .iter()
+                    .map(|index| &index.key_schema),
+            ),
+            create_table
+                .local_secondary_indexes
+                .iter()
+                .map(|index| &index.sort_key),
+        );
+        let (billing_mode, provisioned_throughput) = create_table.billing_mode.get_billing_mode();
+        let global_secondary_indexes: Vec<_> = create_table
+            .global_secondary_indexes
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let local_secondary_indexes: Vec<_> = create_table
+            .local_secondary_indexes
+            .into_iter()
+            .map(|index| index.into_local_secondary_index(&create_table.key_schema.partition_key))
+            .collect();
+        let stream_specification = create_table.stream_view_type.map(|stream_view_type| {
+            types::StreamSpecification::builder()
+                .stream_enabled(true)
+                .stream_view_type(stream_view_type)
+                .build()
+                .unwrap()
+        });
+        Self::builder()
+            .set_attribute_definitions(Some(attribute_definitions))
+            .billing_mode(billing_mode)
+            .set_deletion_protection_enabled(create_table.deletion_protection)
+            .set_global_secondary_indexes(
+                (!global_secondary_indexes.is_empty()).then_some(global_secondary_indexes),
+            )
+            .set_key_schema(Some(create_table.key_schema.get_key_schema_elements()))
+            .set_local_secondary_indexes(
+                (!local_secondary_indexes.is_empty()).then_some(local_secondary_indexes),
+            )
+            .set_provisioned_throughput(provisioned_throughput)
+            .set_sse_specification(create_table.sse_specification)
+            .set_stream_specification(stream_specification)
+            .table_name(create_table.table_name)
+            .build()
+            .unwrap()
+    }
+}
+
+impl CreateTable {
+    /// Execute the create table operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.create_table", err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::create_table::CreateTableOutput, crate::Error> {
+        let retry_policy = self.retry_policy.clone();
+        let create_table = operation::create_table::CreateTableInput::from(self);
+        client
+            .create_table()
+            .set_attribute_definitions(create_table.attribute_definitions)
+            .set_billing_mode(create_table.billing_mode)
+            .set_deletion_protection_enabled(create_table.deletion_protection_enabled)
+            .set_global_secondary_indexes(create_table.global_secondary_indexes)
+            .set_key_schema(create_table.key_schema)
+            .set_local_secondary_indexes(create_table.local_secondary_indexes)
+            .set_provisioned_throughput(create_table.provisioned_throughput)
+            .set_sse_specification(create_table.sse_specification)
+            .set_stream_specification(create_table.stream_specification)
+            .set_table_name(create_table.table_name)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_key_attribute(name: &str) -> table::common::KeyAttribute {
+        table::common::KeyAttribute {
+            attribute_type: types::ScalarAttributeType::S,
+            name: name.to_string(),
+        }
+    }
+
+    fn get_key_schema_element(name: &str, key_type: types::KeyType) -> types::KeySchemaElement {
+        types::KeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+            .unwrap()
+    }
+
+    fn get_attribute_definition(name: &str) -> types::AttributeDefinition {
+        types::AttributeDefinition::builder()
+            .attribute_name(name)
+            .attribute_type(types::ScalarAttributeType::S)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_table_empty() {
+        let args = CreateTable {
+            key_schema: table::common::KeySchema {
+                partition_key: get_key_attribute("a"),
+                sort_key: None,
+            },
+            table_name: "b".to_string(),
+            ..Default::default()
+        };
+        let actual = operation::create_table::CreateTableInput::from(args);
+        let expected = operation::create_table::CreateTableInput::builder()
+            .attribute_definitions(get_attribute_definition("a"))
+            .billing_mode(types::BillingMode::PayPerRequest)
+            .key_schema(get_key_schema_element("a", types::KeyType::Hash))
+            .table_name("b")
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_create_table_full() {
+        let throughput = table::common::Throughput {
+            read_capacity_units: 5,
+            write_capacity_units: 10,
+        };
+        let args = CreateTable {
+            billing_mode: table::common::BillingMode::Provisioned(throughput),
+            deletion_protection: Some(true),
+            global_secondary_indexes: vec![table::common::GlobalSecondaryIndex {
+                index_name: "c".to_string(),
+                key_schema: table::common::KeySchema {
+                    partition_key: get_key_attribute("d"),
+                    sort_key: Some(get_key_attribute("b")),
+                },
+                projection: table::common::Projection::KeysOnly,
+                throughput: Some(throughput),
+            }],
+            key_schema: table::common::KeySchema {
+                partition_key: get_key_attribute("a"),
+                sort_key: Some(get_key_attribute("b")),
+            },
+            local_secondary_indexes: vec![table::common::LocalSecondaryIndex {
+                index_name: "e".to_string(),
+                projection: table::common::Projection::All,
+                sort_key: get_key_attribute("f"),
+            }],
+            retry_policy: None,
+            sse_specification: Some(types::SseSpecification::builder().enabled(true).build()),
+            stream_view_type: Some(types::StreamViewType::KeysOnly),
+            table_name: "g".to_string(),
+        };
+        let actual = operation::create_table::CreateTableInput::from(args);
+        let expected = operation::create_table::CreateTableInput::builder()
+            .attribute_definitions(get_attribute_definition("a"))
+            .attribute_definitions(get_attribute_definition("b"))
+            .attribute_definitions(get_attribute_definition("d"))
+            .attribute_definitions(get_attribute_definition("f"))
+            .billing_mode(types::BillingMode::Provisioned)
+            .deletion_protection_enabled(true)
+            .global_secondary_indexes(
+                types::GlobalSecondaryIndex::builder()
+                    .index_name("c")
+                    .key_schema(get_key_schema_element("d", types::KeyType::Hash))
+                    .key_schema(get_key_schema_element("b", types::KeyType::Range))
+                    .projection(
+                        types::Projection::builder()
+                            .projection_type(types::ProjectionType::KeysOnly)
+                            .build(),
+                    )
+                    .provisioned_throughput(throughput.into())
+                    .build()
+                    .unwrap(),
+            )
+            .key_schema(get_key_schema_element("a", types::KeyType::Hash))
+            .key_schema(get_key_schema_element("b", types::KeyType::Range))
+            .local_secondary_indexes(
+                types::LocalSecondaryIndex::builder()
+                    .index_name("e")
+                    .key_schema(get_key_schema_element("a", types::KeyType::Hash))
+                    .key_schema(get_key_schema_element("f", types::KeyType::Range))
+                    .projection(
+                        types::Projection::builder()
+                            .projection_type(types::ProjectionType::All)
+                            .build(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .provisioned_throughput(throughput.into())
+            .sse_specification(types::SseSpecification::builder().enabled(true).build())
+            .stream_specification(
+                types::StreamSpecification::builder()
+                    .stream_enabled(true)
+                    .stream_view_type(types::StreamViewType::KeysOnly)
+                    .build()
+                    .unwrap(),
+            )
+            .table_name("g")
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{common, common::retry::WithRetryPolicy as _};
+
+use aws_sdk_dynamodb::{Client, operation};
+
+/// Delete table operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::table;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let delete_table = table::delete_table::DeleteTable {
+///     table_name: "users".to_string(),
+///     ..Default::default()
+/// };
+/// delete_table.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DeleteTable {
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The name of the table to delete.
+    pub table_name: String,
+}
+
+impl DeleteTable {
+    /// Execute the delete table operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.delete_table", err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::delete_table::DeleteTableOutput, crate::Error> {
+        client
+            .delete_table()
+            .table_name(self.table_name)
+            .customize()
+            .with_retry_policy(client, self.retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{common, common::retry::WithRetryPolicy as _};
+
+use aws_sdk_dynamodb::{Client, operation};
+
+/// Describe table operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::table;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let describe_table = table::describe_table::DescribeTable {
+///     table_name: "users".to_string(),
+///     ..Default::default()
+/// };
+/// let output = describe_table.send(client).await?;
+/// println!("{:?}", output.table().and_then(|table| table.table_status()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DescribeTable {
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The name of the table to describe.
+    pub table_name: String,
+}
+
+impl DescribeTable {
+    /// Execute the describe table operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.describe_table", err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::describe_table::DescribeTableOutput, crate::Error> {
+        client
+            .describe_table()
+            .table_name(self.table_name)
+            .customize()
+            .with_retry_policy(client, self.retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
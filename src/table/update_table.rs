@@ -0,0 +1,259 @@
+use crate::{common, common::retry::WithRetryPolicy as _, table};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+
+/// Update table operation.
+///
+/// Unset fields keep their current value. DynamoDB applies a single global secondary index
+/// creation or deletion per update.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::table;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let update_table = table::update_table::UpdateTable {
+///     billing_mode: Some(table::common::BillingMode::Provisioned(
+///         table::common::Throughput {
+///             read_capacity_units: 10,
+///             write_capacity_units: 5,
+///         },
+///     )),
+///     table_name: "users".to_string(),
+///     ..Default::default()
+/// };
+/// update_table.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UpdateTable {
+    /// The new billing mode, along with the throughput of provisioned tables.
+    pub billing_mode: Option<table::common::BillingMode>,
+    /// The global secondary indexes to create.
+    pub create_global_secondary_indexes: Vec<table::common::GlobalSecondaryIndex>,
+    /// The names of the global secondary indexes to delete.
+    pub delete_global_secondary_indexes: Vec<String>,
+    /// Whether the table is protected from deletion.
+    pub deletion_protection: Option<bool>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The new server-side encryption settings of the table.
+    pub sse_specification: Option<types::SseSpecification>,
+    /// The new stream settings of the table.
+    pub stream_specification: Option<types::StreamSpecification>,
+    /// The name of the table to update.
+    pub table_name: String,
+    /// The new provisioned throughput of global secondary indexes, by index name.
+    pub update_global_secondary_indexes: Vec<(String, table::common::Throughput)>,
+}
+
+impl From<UpdateTable> for operation::update_table::UpdateTableInput {
+    fn from(update_table: UpdateTable) -> Self {
+        let attribute_definitions = table::common::get_attribute_definitions(
+            update_table
+                .create_global_secondary_indexes
+                .iter()
+                .map(|index| &index.key_schema),
+            [],
+        );
+        let (billing_mode, provisioned_throughput) = match update_table.billing_mode {
+            Some(billing_mode) => {
+                let (billing_mode, provisioned_throughput) = billing_mode.get_billing_mode();
+                (Some(billing_mode), provisioned_throughput)
+            }
+            None => (None, None),
+        };
+        let creates = update_table
+            .create_global_secondary_indexes
+            .into_iter()
+            .map(|index| {
+                let create = types::CreateGlobalSecondaryIndexAction::builder()
+                    .index_name(index.index_name)
+                    .set_key_schema(Some(index.key_schema.get_key_schema_elements()))
+                    .projection(index.projection.into())
+                    .set_provisioned_throughput(index.throughput.map(Into::into))
+                    .build()
+                    .unwrap();
+                types::GlobalSecondaryIndexUpdate::builder()
+                    .create(create)
+                    .build()
+            });
+        let deletes = update_table
+            .delete_global_secondary_indexes
+            .into_iter()
+            .map(|index_name| {
+                let delete = types::DeleteGlobalSecondaryIndexAction::builder()
+                    .index_name(index_name)
+                    .build()
+                    .unwrap();
+                types::GlobalSecondaryIndexUpdate::builder()
+                    .delete(delete)
+                    .build()
+            });
+        let updates = update_table
+            .update_global_secondary_indexes
+            .into_iter()
+            .map(|(index_name, throughput)| {
+                let update = types::UpdateGlobalSecondaryIndexAction::builder()
+                    .index_name(index_name)
+                    .provisioned_throughput(throughput.into())
+                    .build()
+                    .unwrap();
+                types::GlobalSecondaryIndexUpdate::builder()
+                    .update(update)
+                    .build()
+            });
+        let global_secondary_index_updates: Vec<_> =
+            creates.chain(deletes).chain(updates).collect();
+        Self::builder()
+            .set_attribute_definitions(
+                (!attribute_definitions.is_empty()).then_some(attribute_definitions),
+            )
+            .set_billing_mode(billing_mode)
+            .set_deletion_protection_enabled(update_table.deletion_protection)
+            .set_global_secondary_index_updates(
+                (!global_secondary_index_updates.is_empty())
+                    .then_some(global_secondary_index_updates),
+            )
+            .set_provisioned_throughput(provisioned_throughput)
+            .set_sse_specification(update_table.sse_specification)
+            .set_stream_specification(update_table.stream_specification)
+            .table_name(update_table.table_name)
+            .build()
+            .unwrap()
+    }
+}
+
+impl UpdateTable {
+    /// Execute the update table operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.update_table", err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::update_table::UpdateTableOutput, crate::Error> {
+        let retry_policy = self.retry_policy.clone();
+        let update_table = operation::update_table::UpdateTableInput::from(self);
+        client
+            .update_table()
+            .set_attribute_definitions(update_table.attribute_definitions)
+            .set_billing_mode(update_table.billing_mode)
+            .set_deletion_protection_enabled(update_table.deletion_protection_enabled)
+            .set_global_secondary_index_updates(update_table.global_secondary_index_updates)
+            .set_provisioned_throughput(update_table.provisioned_throughput)
+            .set_sse_specification(update_table.sse_specification)
+            .set_stream_specification(update_table.stream_specification)
+            .set_table_name(update_table.table_name)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_table_empty() {
+        let args = UpdateTable {
+            table_name: "a".to_string(),
+            ..Default::default()
+        };
+        let actual = operation::update_table::UpdateTableInput::from(args);
+        let expected = operation::update_table::UpdateTableInput::builder()
+            .table_name("a")
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_update_table_indexes() {
+        let throughput = table::common::Throughput {
+            read_capacity_units: 1,
+            write_capacity_units: 2,
+        };
+        let args = UpdateTable {
+            billing_mode: Some(table::common::BillingMode::PayPerRequest),
+            create_global_secondary_indexes: vec![table::common::GlobalSecondaryIndex {
+                index_name: "b".to_string(),
+                key_schema: table::common::KeySchema {
+                    partition_key: table::common::KeyAttribute {
+                        attribute_type: types::ScalarAttributeType::N,
+                        name: "c".to_string(),
+                    },
+                    sort_key: None,
+                },
+                projection: table::common::Projection::All,
+                throughput: None,
+            }],
+            delete_global_secondary_indexes: vec!["d".to_string()],
+            update_global_secondary_indexes: vec![("e".to_string(), throughput)],
+            table_name: "a".to_string(),
+            ..Default::default()
+        };
+        let actual = operation::update_table::UpdateTableInput::from(args);
+        let expected = operation::update_table::UpdateTableInput::builder()
+            .attribute_definitions(
+                types::AttributeDefinition::builder()
+                    .attribute_name("c")
+                    .attribute_type(types::ScalarAttributeType::N)
+                    .build()
+                    .unwrap(),
+            )
+            .billing_mode(types::BillingMode::PayPerRequest)
+            .global_secondary_index_updates(
+                types::GlobalSecondaryIndexUpdate::builder()
+                    .create(
+                        types::CreateGlobalSecondaryIndexAction::builder()
+                            .index_name("b")
+                            .key_schema(
+                                types::KeySchemaElement::builder()
+                                    .attribute_name("c")
+                                    .key_type(types::KeyType::Hash)
+                                    .build()
+                                    .unwrap(),
+                            )
+                            .projection(
+                                types::Projection::builder()
+                                    .projection_type(types::ProjectionType::All)
+                                    .build(),
+                            )
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .global_secondary_index_updates(
+                types::GlobalSecondaryIndexUpdate::builder()
+                    .delete(
+                        types::DeleteGlobalSecondaryIndexAction::builder()
+                            .index_name("d")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .global_secondary_index_updates(
+                types::GlobalSecondaryIndexUpdate::builder()
+                    .update(
+                        types::UpdateGlobalSecondaryIndexAction::builder()
+                            .index_name("e")
+                            .provisioned_throughput(throughput.into())
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .table_name("a")
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
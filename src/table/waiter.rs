@@ -0,0 +1,196 @@
+use crate::{common, common::retry::WithRetryPolicy as _};
+
+use aws_sdk_dynamodb::{Client, types};
+use std::{fmt, time};
+
+/// Error returned while waiting for a table.
+#[derive(Debug)]
+pub enum WaitError {
+    /// A describe table call failed.
+    DescribeTable(crate::Error),
+    /// The table did not reach the expected state within the timeout.
+    Timeout(time::Duration),
+}
+
+impl fmt::Display for WaitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DescribeTable(error) => write!(formatter, "describe table failed: {error}"),
+            Self::Timeout(timeout) => {
+                write!(formatter, "wait for table timed out after {timeout:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WaitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::DescribeTable(error) => Some(error),
+            Self::Timeout(_) => None,
+        }
+    }
+}
+
+/// Waiter polling a table until it reaches the expected state.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::table;
+/// use std::time::Duration;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let waiter = table::waiter::TableWaiter {
+///     poll_interval: Duration::from_millis(200),
+///     table_name: "users".to_string(),
+///     timeout: Duration::from_secs(30),
+///     ..Default::default()
+/// };
+/// let table = waiter.wait_until_table_active(client).await?;
+/// println!("{:?}", table.table_arn());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TableWaiter {
+    /// The delay between two describe table calls.
+    pub poll_interval: time::Duration,
+    /// Retry policy overriding the retry configuration of the client for the describe calls.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The name of the table to wait for.
+    pub table_name: String,
+    /// The maximum time to wait for.
+    pub timeout: time::Duration,
+}
+
+impl Default for TableWaiter {
+    fn default() -> Self {
+        Self {
+            poll_interval: time::Duration::from_secs(1),
+            retry_policy: None,
+            table_name: String::new(),
+            timeout: time::Duration::from_secs(300),
+        }
+    }
+}
+
+/// Whether the table and all of its global secondary indexes are active.
+fn is_table_active(table: &types::TableDescription) -> bool {
+    table.table_status() == Some(&types::TableStatus::Active)
+        && table
+            .global_secondary_indexes()
+            .iter()
+            .all(|index| index.index_status() == Some(&types::IndexStatus::Active))
+}
+
+impl TableWaiter {
+    /// Wait until the table and all of its global secondary indexes are active, returning the
+    /// description of the table.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.wait_until_table_active", err)
+    )]
+    pub async fn wait_until_table_active(
+        &self,
+        client: &Client,
+    ) -> Result<types::TableDescription, WaitError> {
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        loop {
+            let output = client
+                .describe_table()
+                .table_name(&self.table_name)
+                .customize()
+                .with_retry_policy(client, self.retry_policy.as_ref())
+                .send()
+                .await
+                .map_err(crate::Error::from);
+            match output {
+                Ok(output) => {
+                    if let Some(table) = output.table.filter(is_table_active) {
+                        return Ok(table);
+                    }
+                }
+                Err(crate::Error::ResourceNotFound(_)) => {}
+                Err(error) => return Err(WaitError::DescribeTable(error)),
+            }
+            self.sleep_until_next_poll(deadline).await?;
+        }
+    }
+
+    /// Wait until the table no longer exists.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.wait_until_table_not_exists", err)
+    )]
+    pub async fn wait_until_table_not_exists(&self, client: &Client) -> Result<(), WaitError> {
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        loop {
+            let output = client
+                .describe_table()
+                .table_name(&self.table_name)
+                .customize()
+                .with_retry_policy(client, self.retry_policy.as_ref())
+                .send()
+                .await
+                .map_err(crate::Error::from);
+            match output {
+                Ok(_) => {}
+                Err(crate::Error::ResourceNotFound(_)) => return Ok(()),
+                Err(error) => return Err(WaitError::DescribeTable(error)),
+            }
+            self.sleep_until_next_poll(deadline).await?;
+        }
+    }
+
+    /// Sleep for the poll interval, failing if the deadline has passed.
+    async fn sleep_until_next_poll(&self, deadline: tokio::time::Instant) -> Result<(), WaitError> {
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Err(WaitError::Timeout(self.timeout));
+        }
+        tokio::time::sleep_until(deadline.min(now + self.poll_interval)).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case::active(types::TableStatus::Active, types::IndexStatus::Active, true)]
+    #[case::creating_table(types::TableStatus::Creating, types::IndexStatus::Active, false)]
+    #[case::creating_index(types::TableStatus::Active, types::IndexStatus::Creating, false)]
+    fn test_is_table_active(
+        #[case] table_status: types::TableStatus,
+        #[case] index_status: types::IndexStatus,
+        #[case] expected: bool,
+    ) {
+        let table = types::TableDescription::builder()
+            .table_status(table_status)
+            .global_secondary_indexes(
+                types::GlobalSecondaryIndexDescription::builder()
+                    .index_status(index_status)
+                    .build(),
+            )
+            .build();
+        assert_eq!(is_table_active(&table), expected);
+    }
+
+    #[test]
+    fn test_sleep_until_next_poll_timeout() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let waiter = TableWaiter {
+            timeout: time::Duration::ZERO,
+            ..Default::default()
+        };
+        let actual = runtime.block_on(waiter.sleep_until_next_poll(tokio::time::Instant::now()));
+        assert!(matches!(
+            actual,
+            Err(WaitError::Timeout(time::Duration::ZERO))
+        ));
+    }
+}
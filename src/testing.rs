@@ -0,0 +1,446 @@
+//! Test fixture utilities for tables backed by DynamoDB or DynamoDB Local.
+//!
+//! This module provides:
+//! - Seeding tables with typed or JSON items, returning a handle for cleanup
+//! - Seeding tables with random entities generated with the [`fake`] crate
+
+use crate::write;
+
+use aws_sdk_dynamodb::{Client, types};
+use fake::{Dummy, Fake, Faker, rand};
+use serde::{Serialize, ser::Error as _};
+use serde_dynamo::{Error, Result, to_item};
+use std::{collections, fmt, sync};
+
+/// Maximum number of generated entities per requested random item, bounding the retries on
+/// duplicated keys.
+const MAX_RANDOM_ITEM_ATTEMPTS: usize = 10;
+
+/// Error returned while seeding or cleaning up test data.
+#[derive(Debug)]
+pub enum SeedError {
+    /// A batch write of a chunk of items failed.
+    BatchWriteItem(sync::Arc<crate::Error>),
+    /// The table key schema could not be described.
+    DescribeTable(crate::Error),
+    /// An item could not be serialized into a DynamoDB item.
+    Serialization(serde_dynamo::Error),
+    /// Some items were still unprocessed after retrying.
+    UnprocessedItems(usize),
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BatchWriteItem(error) => write!(formatter, "batch write item failed: {error}"),
+            Self::DescribeTable(error) => write!(formatter, "describe table failed: {error}"),
+            Self::Serialization(error) => write!(formatter, "serialization failed: {error}"),
+            Self::UnprocessedItems(count) => write!(formatter, "{count} items were unprocessed"),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::BatchWriteItem(error) => Some(error.as_ref()),
+            Self::DescribeTable(error) => Some(error),
+            Self::Serialization(error) => Some(error),
+            Self::UnprocessedItems(_) => None,
+        }
+    }
+}
+
+/// Handle to the items written by [`seed`], used to delete them once the test is done.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SeededItems {
+    /// The primary key attribute names of the table.
+    pub key_names: Vec<String>,
+    /// The keys of the seeded items, in seeding order.
+    pub keys: Vec<collections::HashMap<String, types::AttributeValue>>,
+    /// The name of the seeded table.
+    pub table_name: String,
+}
+
+impl SeededItems {
+    /// Delete the seeded items with chunked batch writes.
+    pub async fn cleanup(self, client: &Client) -> std::result::Result<(), SeedError> {
+        let requests = write::common::get_delete_requests(self.keys, &self.key_names);
+        write_requests(client, &self.table_name, requests).await
+    }
+}
+
+/// Serialize items into put requests.
+fn get_put_requests<T: Serialize>(
+    items: impl IntoIterator<Item = T>,
+) -> Result<Vec<types::WriteRequest>> {
+    items
+        .into_iter()
+        .map(|item| {
+            let put_request = types::PutRequest::builder()
+                .set_item(Some(to_item(item)?))
+                .build()
+                .unwrap();
+            let request = types::WriteRequest::builder()
+                .put_request(put_request)
+                .build();
+            Ok(request)
+        })
+        .collect()
+}
+
+/// Extract the keys of put requests.
+fn get_put_request_keys(
+    requests: &[types::WriteRequest],
+    key_names: &[String],
+) -> Vec<collections::HashMap<String, types::AttributeValue>> {
+    requests
+        .iter()
+        .filter_map(|request| request.put_request.as_ref())
+        .map(|put_request| {
+            put_request
+                .item
+                .iter()
+                .filter(|(name, _)| key_names.contains(name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Get the primary key attribute names of a table.
+pub(crate) async fn get_key_names(
+    client: &Client,
+    table_name: &str,
+) -> std::result::Result<Vec<String>, crate::Error> {
+    let output = client
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await?;
+    let key_names = output
+        .table
+        .map(|table| {
+            table
+                .key_schema()
+                .iter()
+                .map(|key| key.attribute_name().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(key_names)
+}
+
+async fn write_requests(
+    client: &Client,
+    table_name: &str,
+    requests: Vec<types::WriteRequest>,
+) -> std::result::Result<(), SeedError> {
+    let result = write::common::write_requests_in_chunks(client, table_name, requests).await;
+    let batch_write_item_error = result.failed.iter().find_map(|(_, error)| match error {
+        write::common::BatchWriteError::BatchWriteItem(error) => Some(error.clone()),
+        write::common::BatchWriteError::Unprocessed => None,
+    });
+    match (batch_write_item_error, result.failed.len()) {
+        (Some(error), _) => Err(SeedError::BatchWriteItem(error)),
+        (None, 0) => Ok(()),
+        (None, count) => Err(SeedError::UnprocessedItems(count)),
+    }
+}
+
+async fn seed_requests(
+    client: &Client,
+    table_name: &str,
+    key_names: Vec<String>,
+    requests: Vec<types::WriteRequest>,
+) -> std::result::Result<SeededItems, SeedError> {
+    let keys = get_put_request_keys(&requests, &key_names);
+    write_requests(client, table_name, requests).await?;
+    let seeded_items = SeededItems {
+        key_names,
+        keys,
+        table_name: table_name.to_string(),
+    };
+    Ok(seeded_items)
+}
+
+/// Generate random put requests with distinct keys.
+///
+/// Stops early if too many generated entities have duplicated keys, and fails if an entity
+/// is missing a key attribute.
+fn get_random_put_requests<T, R>(
+    rng: &mut R,
+    count: usize,
+    key_names: &[String],
+) -> Result<Vec<types::WriteRequest>>
+where
+    T: Dummy<Faker> + Serialize,
+    R: rand::Rng + ?Sized,
+{
+    let mut requests = Vec::with_capacity(count);
+    let mut seen_keys = collections::HashSet::new();
+    for _ in 0..count * MAX_RANDOM_ITEM_ATTEMPTS {
+        if requests.len() == count {
+            break;
+        }
+        let item: T = Faker.fake_with_rng(rng);
+        let [request] = get_put_requests([item])?.try_into().unwrap();
+        let [keys] = get_put_request_keys(std::slice::from_ref(&request), key_names)
+            .try_into()
+            .unwrap();
+        let key_values = key_names
+            .iter()
+            .map(|name| {
+                keys.get(name)
+                    .map(|value| format!("{value:?}"))
+                    .ok_or_else(|| Error::custom(format!("missing key attribute {name}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if seen_keys.insert(key_values) {
+            requests.push(request);
+        }
+    }
+    Ok(requests)
+}
+
+/// Seed a table with items, using chunked batch writes.
+///
+/// Items may be any serializable type, including `serde_json::Value`. The table key schema is
+/// described to record the keys of the written items in the returned handle.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::testing;
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let seeded_items = testing::seed(
+///     client,
+///     "users",
+///     vec![
+///         json!({"id": "1", "name": "Jane"}),
+///         json!({"id": "2", "name": "John"}),
+///     ],
+/// )
+/// .await?;
+/// // ... run the test ...
+/// seeded_items.cleanup(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "dynamodb_crud.seed", skip_all, err)
+)]
+pub async fn seed<T: Serialize>(
+    client: &Client,
+    table_name: &str,
+    items: impl IntoIterator<Item = T>,
+) -> std::result::Result<SeededItems, SeedError> {
+    let key_names = get_key_names(client, table_name)
+        .await
+        .map_err(SeedError::DescribeTable)?;
+    let requests = get_put_requests(items).map_err(SeedError::Serialization)?;
+    seed_requests(client, table_name, key_names, requests).await
+}
+
+/// A table used by tests, typically backed by DynamoDB Local.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::testing;
+/// use fake::{Dummy, Fake, Faker, faker::name::en::Name, rand::Rng};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// impl Dummy<Faker> for User {
+///     fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+///         Self {
+///             id: (1..1_000_000).fake_with_rng(rng),
+///             name: Name().fake_with_rng(rng),
+///         }
+///     }
+/// }
+///
+/// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let local_table = testing::LocalTable {
+///     client,
+///     table_name: "users".to_string(),
+/// };
+/// let seeded_items = local_table.seed_random::<User>(100).await?;
+/// // ... run the test ...
+/// seeded_items.cleanup(&local_table.client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LocalTable {
+    /// The client connected to the table.
+    pub client: Client,
+    /// The name of the table.
+    pub table_name: String,
+}
+
+impl LocalTable {
+    /// Seed the table with items, see [`seed`].
+    pub async fn seed<T: Serialize>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+    ) -> std::result::Result<SeededItems, SeedError> {
+        seed(&self.client, &self.table_name, items).await
+    }
+
+    /// Seed the table with random entities generated by their [`Dummy`] implementation.
+    ///
+    /// Every entity must contain the table key attributes; entities whose keys were already
+    /// generated are discarded, so fewer items may be seeded if the key space is too small.
+    pub async fn seed_random<T: Dummy<Faker> + Serialize>(
+        &self,
+        count: usize,
+    ) -> std::result::Result<SeededItems, SeedError> {
+        let key_names = get_key_names(&self.client, &self.table_name)
+            .await
+            .map_err(SeedError::DescribeTable)?;
+        let requests = get_random_put_requests::<T, _>(&mut rand::rng(), count, &key_names)
+            .map_err(SeedError::Serialization)?;
+        seed_requests(&self.client, &self.table_name, key_names, requests).await
+    }
+
+    /// Seed the table with random entities, using the given random number generator.
+    ///
+    /// Useful to seed reproducible data from a seeded generator, see [`Self::seed_random`].
+    pub async fn seed_random_with_rng<T, R>(
+        &self,
+        rng: &mut R,
+        count: usize,
+    ) -> std::result::Result<SeededItems, SeedError>
+    where
+        T: Dummy<Faker> + Serialize,
+        R: rand::Rng + ?Sized,
+    {
+        let key_names = get_key_names(&self.client, &self.table_name)
+            .await
+            .map_err(SeedError::DescribeTable)?;
+        let requests = get_random_put_requests::<T, R>(rng, count, &key_names)
+            .map_err(SeedError::Serialization)?;
+        seed_requests(&self.client, &self.table_name, key_names, requests).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use fake::rand::{SeedableRng, rngs::StdRng};
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    #[derive(Serialize)]
+    struct Entity {
+        id: u8,
+    }
+
+    impl Dummy<Faker> for Entity {
+        fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+            Self {
+                id: (0..3).fake_with_rng(rng),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::partition_key(
+        vec![
+            json!({"a": "b", "c": 1}),
+        ],
+        vec![
+            "a".to_string(),
+        ],
+        vec![
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        )
+                    ),
+                ]
+            ),
+        ]
+    )]
+    #[case::composite_key(
+        vec![
+            json!({"a": "b", "c": 1, "d": true}),
+            json!({"a": "e", "c": 2}),
+        ],
+        vec![
+            "a".to_string(),
+            "c".to_string(),
+        ],
+        vec![
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        )
+                    ),
+                    (
+                        "c".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                ]
+            ),
+            collections::HashMap::from(
+                [
+                    (
+                        "a".to_string(),
+                        types::AttributeValue::S(
+                            "e".to_string()
+                        )
+                    ),
+                    (
+                        "c".to_string(),
+                        types::AttributeValue::N(
+                            "2".to_string()
+                        )
+                    ),
+                ]
+            ),
+        ]
+    )]
+    fn test_get_put_request_keys(
+        #[case] items: Vec<Value>,
+        #[case] key_names: Vec<String>,
+        #[case] expected: Vec<collections::HashMap<String, types::AttributeValue>>,
+    ) {
+        let requests = get_put_requests(items).unwrap();
+        let actual = get_put_request_keys(&requests, &key_names);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::distinct_keys(2, "id", Some(2))]
+    #[case::exhausted_keys(5, "id", Some(3))]
+    #[case::missing_key(1, "other", None)]
+    fn test_get_random_put_requests(
+        #[case] count: usize,
+        #[case] key_name: &str,
+        #[case] expected: Option<usize>,
+    ) {
+        let mut rng = StdRng::seed_from_u64(0);
+        let actual = get_random_put_requests::<Entity, _>(&mut rng, count, &[key_name.to_string()])
+            .ok()
+            .map(|requests| requests.len());
+        assert_eq!(actual, expected);
+    }
+}
@@ -5,18 +5,65 @@
 //! - Updating items with various operations
 //! - Deleting items by primary key
 //! - Batch writing multiple items
+//! - Bulk putting items, routing conditional items through individual put item calls
+//! - Deleting every item matched by a query or a scan filter
+//! - Updating every item matched by a query
+//! - Writing multiple items atomically in a transaction, guarded by condition checks
+//! - Enforcing unique attribute values with marker items written in the same transaction
+//! - Maintaining sparse indexes by setting and removing a flag attribute
+//! - Acquiring and releasing lease locks that expire after a time to live
+//! - Recording idempotency keys and caching the responses of processed requests
+//! - Enforcing invariant conditions on every write to a table
+//! - Using a table as a key-value store with expiring entries
+//! - Refreshing the time to live of existing items, optionally only ever extending it
 
 /// Batch write item operation for efficiently writing multiple items.
 pub mod batch_write_item;
 
+/// Bulk put item operation for writing many items, conditional ones included.
+pub mod bulk_put_item;
+
 /// Common utilities and types for write operations.
 pub mod common;
 
+/// Delete by query operation for removing every item matched by a query.
+pub mod delete_by_query;
+
+/// Delete by scan operation for removing every item matched by a scan filter.
+pub mod delete_by_scan;
+
 /// Delete item operation for removing items from tables.
 pub mod delete_item;
 
+/// Idempotency keys recorded with conditional writes, along with cached responses.
+pub mod idempotency;
+
+/// Invariant conditions ANDed onto the condition of every write to a table.
+pub mod invariants;
+
+/// Key-value store facade for caches and session stores backed by a table.
+pub mod kv_store;
+
+/// Lease locks coordinating exclusive work through conditional writes.
+pub mod lock;
+
 /// Put item operation for creating or replacing items.
 pub mod put_item;
 
+/// Sparse global secondary indexes maintained through a flag attribute.
+pub mod sparse_index;
+
+/// Transact write items operation for writing multiple items atomically.
+pub mod transact_write_items;
+
+/// Time to live refresh of existing items, such as sessions.
+pub mod ttl;
+
+/// Unique constraints enforced with marker items written alongside the entity.
+pub mod unique_constraint;
+
+/// Update by query operation for applying one update to every item matched by a query.
+pub mod update_by_query;
+
 /// Update item operation for modifying existing items.
 pub mod update_item;
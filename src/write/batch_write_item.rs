@@ -1,8 +1,9 @@
-use crate::common;
+use crate::{common, write};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
+use aws_sdk_dynamodb::{Client, operation, types};
+use indexmap::IndexMap;
 use serde::Serialize;
-use serde_dynamo::{Error, Result, to_item};
+use serde_dynamo::{Error, Result};
 use std::collections;
 
 /// A put item request within a batch write operation.
@@ -30,26 +31,28 @@ pub enum BatchWriteItemRequest<T> {
     DeleteItem(BatchWriteItemRequestDeleteItem<T>),
 }
 
-impl<T: Serialize> TryFrom<BatchWriteItemRequest<T>> for types::WriteRequest {
-    type Error = Error;
-
-    fn try_from(write_request: BatchWriteItemRequest<T>) -> Result<Self> {
-        let builder = match write_request {
-            BatchWriteItemRequest::PutItem(put_item) => {
-                let item = to_item(put_item.item)?;
+impl<T: Serialize> BatchWriteItemRequest<T> {
+    /// Convert the request, converting the item or keys with the given serializer.
+    pub(crate) fn serialize(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<types::WriteRequest> {
+        let builder = match self {
+            Self::PutItem(put_item) => {
+                let item = serializer.to_item(put_item.item)?;
                 let put_request = types::PutRequest::builder()
                     .set_item(Some(item))
                     .build()
                     .unwrap();
-                Self::builder().set_put_request(Some(put_request))
+                types::WriteRequest::builder().set_put_request(Some(put_request))
             }
-            BatchWriteItemRequest::DeleteItem(delete_item) => {
-                let keys = delete_item.keys.try_into()?;
+            Self::DeleteItem(delete_item) => {
+                let keys = delete_item.keys.serialize(serializer)?;
                 let delete_request = types::DeleteRequest::builder()
                     .set_key(Some(keys))
                     .build()
                     .unwrap();
-                Self::builder().set_delete_request(Some(delete_request))
+                types::WriteRequest::builder().set_delete_request(Some(delete_request))
             }
         };
         let request = builder.build();
@@ -57,16 +60,98 @@ impl<T: Serialize> TryFrom<BatchWriteItemRequest<T>> for types::WriteRequest {
     }
 }
 
+/// How requests targeting the same primary key within a table are handled.
+///
+/// DynamoDB rejects a batch containing two requests for the same key, so duplicates are
+/// detected while converting the batch.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DuplicateKeyPolicy {
+    /// Fail the conversion with an error naming the duplicate key.
+    #[default]
+    Reject,
+    /// Keep only the last request for each key, at the position of the first one.
+    LastWriteWins,
+}
+
+impl<T: Serialize> TryFrom<BatchWriteItemRequest<T>> for types::WriteRequest {
+    type Error = Error;
+
+    fn try_from(write_request: BatchWriteItemRequest<T>) -> Result<Self> {
+        write_request.serialize(&common::serializer::SerdeDynamo)
+    }
+}
+
+/// Outcome of a single chunk of at most 25 requests of the batch write item operation.
+#[derive(Clone, Debug)]
+pub struct BatchWriteItemChunkSummary {
+    /// The error of the failed requests of the chunk, if any.
+    pub error: Option<write::common::BatchWriteError>,
+    /// The number of requests of the chunk that failed.
+    pub failed: usize,
+    /// The position of the chunk within the operation, starting from 0.
+    pub index: usize,
+    /// The number of requests of the chunk that were written.
+    pub succeeded: usize,
+}
+
+/// Output of the batch write item operation, the outcome of every write request by table.
+#[derive(Clone, Debug, Default)]
+pub struct BatchWriteItemSummary {
+    /// The outcome of every chunk, in request order.
+    pub chunks: Vec<BatchWriteItemChunkSummary>,
+    /// The capacity consumed by every batch write item call by table and index, if requested.
+    pub consumed_capacity: common::capacity::CapacityBreakdown,
+    /// The item collection metrics of every batch write item call by table, if requested.
+    pub item_collection_metrics: collections::HashMap<String, Vec<types::ItemCollectionMetrics>>,
+    /// A map of table names to their written and failed write requests.
+    pub results: collections::HashMap<
+        String,
+        common::bulk::BulkResult<types::WriteRequest, write::common::BatchWriteError>,
+    >,
+}
+
+impl BatchWriteItemSummary {
+    /// Whether every write request was processed successfully.
+    pub fn is_success(&self) -> bool {
+        self.results
+            .values()
+            .all(common::bulk::BulkResult::is_success)
+    }
+
+    /// Merge the summary of a following chunk into this summary.
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.chunks.extend(other.chunks);
+        self.consumed_capacity.merge(other.consumed_capacity);
+        for (table_name, metrics) in other.item_collection_metrics {
+            self.item_collection_metrics
+                .entry(table_name)
+                .or_default()
+                .extend(metrics);
+        }
+        for (table_name, result) in other.results {
+            let existing = self.results.entry(table_name).or_default();
+            existing.failed.extend(result.failed);
+            existing.succeeded.extend(result.succeeded);
+        }
+    }
+}
+
 /// Batch write item operation.
 ///
+/// The requests are sent in chunks of at most 25, the limit of DynamoDB, with up to
+/// `concurrency` chunks in flight at once, and the requests left unprocessed are retried with
+/// exponential backoff and jitter. A failed chunk doesn't abort the other ones: requests that
+/// still fail are reported in the summary, along with the outcome of every chunk.
+///
 /// ```rust,no_run
 /// use aws_sdk_dynamodb::Client;
 /// use dynamodb_crud::write;
-/// use std::collections::HashMap;
+/// use indexmap::IndexMap;
 ///
 /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
 /// let batch_write = write::batch_write_item::BatchWriteItem {
-///     request_items: HashMap::from([(
+///     concurrency: Some(4),
+///     request_items: IndexMap::from([(
 ///         "users".to_string(),
 ///         vec![
 ///             write::batch_write_item::BatchWriteItemRequest::PutItem(
@@ -78,66 +163,222 @@ impl<T: Serialize> TryFrom<BatchWriteItemRequest<T>> for types::WriteRequest {
 ///     )]),
 ///     ..Default::default()
 /// };
-/// batch_write.send(client).await?;
+/// let summary = batch_write.send(client).await?;
+/// for chunk in &summary.chunks {
+///     println!("chunk {}: {} written, {} failed", chunk.index, chunk.succeeded, chunk.failed);
+/// }
+/// for (table_name, result) in &summary.results {
+///     for (request, error) in &result.failed {
+///         println!("{table_name}: {request:?} failed: {error}");
+///     }
+/// }
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct BatchWriteItem<T> {
+    /// The maximum number of chunks in flight at once (defaults to 1).
+    pub concurrency: Option<usize>,
+    /// How requests targeting the same key within a table are handled.
+    pub duplicate_key_policy: DuplicateKeyPolicy,
+    /// A map of table names to their primary key attribute names.
+    ///
+    /// Used to find the keys of put requests. Tables without an entry use the attribute
+    /// names of their first delete request, and their put requests are not checked for
+    /// duplicates if they have none.
+    pub key_names: IndexMap<String, Vec<String>>,
     /// A map of table names to lists of write requests.
-    pub request_items: collections::HashMap<String, Vec<BatchWriteItemRequest<T>>>,
+    pub request_items: IndexMap<String, Vec<BatchWriteItemRequest<T>>>,
     /// Whether to return the consumed capacity information.
     pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
     /// Whether to return item collection metrics.
     pub return_item_collection_metrics: Option<types::ReturnItemCollectionMetrics>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    ///
+    /// Its backoff and maximum number of attempts also apply to the retries of
+    /// unprocessed items.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
 }
 
-impl<T: Serialize> TryFrom<BatchWriteItem<T>> for operation::batch_write_item::BatchWriteItemInput {
-    type Error = Error;
+impl<T> Default for BatchWriteItem<T> {
+    fn default() -> Self {
+        Self {
+            concurrency: None,
+            duplicate_key_policy: DuplicateKeyPolicy::default(),
+            key_names: IndexMap::new(),
+            request_items: IndexMap::new(),
+            return_consumed_capacity: None,
+            return_item_collection_metrics: None,
+            retry_policy: None,
+        }
+    }
+}
 
-    fn try_from(batch_write_item: BatchWriteItem<T>) -> Result<Self> {
-        let mut request_items =
-            collections::HashMap::with_capacity(batch_write_item.request_items.len());
-        for (table_name, table_request_items) in batch_write_item.request_items {
-            let mut serialized_table_request_items = Vec::with_capacity(table_request_items.len());
-            for request_item in table_request_items {
-                let request_item = request_item.try_into()?;
-                serialized_table_request_items.push(request_item);
+/// Get the primary key of a write request, sorted by attribute name.
+///
+/// Returns `None` for put requests whose item lacks any of the key attributes.
+fn get_write_request_key(
+    request: &types::WriteRequest,
+    key_names: &[String],
+) -> Option<Vec<(String, types::AttributeValue)>> {
+    let mut key: Vec<_> = match (&request.put_request, &request.delete_request) {
+        (Some(put_request), _) => {
+            let key: Vec<_> = put_request
+                .item
+                .iter()
+                .filter(|(name, _)| key_names.contains(name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+            if key_names.is_empty() || key.len() != key_names.len() {
+                return None;
             }
-            request_items.insert(table_name, serialized_table_request_items);
+            key
         }
-        let operation = Self::builder()
-            .set_request_items(Some(request_items))
-            .set_return_consumed_capacity(batch_write_item.return_consumed_capacity)
-            .set_return_item_collection_metrics(batch_write_item.return_item_collection_metrics)
-            .build()
-            .unwrap();
-        Ok(operation)
+        (None, Some(delete_request)) => delete_request
+            .key
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect(),
+        (None, None) => return None,
+    };
+    key.sort_by(|(name, _), (other_name, _)| name.cmp(other_name));
+    Some(key)
+}
+
+/// Detect the requests of a table targeting the same key, applying the duplicate key policy.
+fn deduplicate_write_requests(
+    table_name: &str,
+    requests: Vec<types::WriteRequest>,
+    key_names: Option<&Vec<String>>,
+    duplicate_key_policy: DuplicateKeyPolicy,
+) -> std::result::Result<Vec<types::WriteRequest>, crate::Error> {
+    let key_names = match key_names {
+        Some(key_names) => key_names.clone(),
+        None => requests
+            .iter()
+            .find_map(|request| request.delete_request.as_ref())
+            .map(|delete_request| delete_request.key.keys().cloned().collect())
+            .unwrap_or_default(),
+    };
+    let mut key_indexes = collections::HashMap::with_capacity(requests.len());
+    let mut deduplicated_requests = Vec::with_capacity(requests.len());
+    for request in requests {
+        let Some(key) = get_write_request_key(&request, &key_names) else {
+            deduplicated_requests.push(request);
+            continue;
+        };
+        let key_bytes = common::key::get_key_bytes(key.iter().map(|(name, value)| (name, value)));
+        match (key_indexes.get(&key_bytes), duplicate_key_policy) {
+            (Some(_), DuplicateKeyPolicy::Reject) => {
+                let key = key
+                    .iter()
+                    .map(|(name, value)| format!("{name} = {value:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(crate::Error::validation(format!(
+                    "duplicate key ({key}) in the batch write requests of table {table_name}"
+                )));
+            }
+            (Some(&index), DuplicateKeyPolicy::LastWriteWins) => {
+                deduplicated_requests[index] = request;
+            }
+            (None, _) => {
+                key_indexes.insert(key_bytes, deduplicated_requests.len());
+                deduplicated_requests.push(request);
+            }
+        }
+    }
+    Ok(deduplicated_requests)
+}
+
+fn get_batch_write_item_input<T: Serialize>(
+    batch_write_item: BatchWriteItem<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> std::result::Result<operation::batch_write_item::BatchWriteItemInput, crate::Error> {
+    let mut request_items =
+        collections::HashMap::with_capacity(batch_write_item.request_items.len());
+    for (table_name, table_request_items) in batch_write_item.request_items {
+        let mut serialized_table_request_items = Vec::with_capacity(table_request_items.len());
+        for request_item in table_request_items {
+            let request_item = request_item
+                .serialize(serializer)
+                .map_err(crate::Error::Serialization)?;
+            serialized_table_request_items.push(request_item);
+        }
+        let serialized_table_request_items = deduplicate_write_requests(
+            &table_name,
+            serialized_table_request_items,
+            batch_write_item.key_names.get(&table_name),
+            batch_write_item.duplicate_key_policy,
+        )?;
+        request_items.insert(table_name, serialized_table_request_items);
+    }
+    let operation = operation::batch_write_item::BatchWriteItemInput::builder()
+        .set_request_items(Some(request_items))
+        .set_return_consumed_capacity(batch_write_item.return_consumed_capacity)
+        .set_return_item_collection_metrics(batch_write_item.return_item_collection_metrics)
+        .build()
+        .unwrap();
+    Ok(operation)
+}
+
+impl<T: Serialize> TryFrom<BatchWriteItem<T>> for operation::batch_write_item::BatchWriteItemInput {
+    type Error = crate::Error;
+
+    fn try_from(batch_write_item: BatchWriteItem<T>) -> std::result::Result<Self, crate::Error> {
+        get_batch_write_item_input(batch_write_item, &common::serializer::SerdeDynamo)
     }
 }
 
 impl<T: Serialize> BatchWriteItem<T> {
     /// Execute the batch write item operation.
+    pub async fn send(self, client: &Client) -> Result<BatchWriteItemSummary, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the batch write item operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.batch_write_item", err)
+        tracing::instrument(name = "dynamodb_crud.batch_write_item", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<
-        operation::batch_write_item::BatchWriteItemOutput,
-        error::SdkError<operation::batch_write_item::BatchWriteItemError>,
-    > {
-        let batch_write_item: operation::batch_write_item::BatchWriteItemInput =
-            self.try_into().map_err(error::BuildError::other)?;
-        client
-            .batch_write_item()
-            .set_request_items(batch_write_item.request_items)
-            .set_return_consumed_capacity(batch_write_item.return_consumed_capacity)
-            .set_return_item_collection_metrics(batch_write_item.return_item_collection_metrics)
-            .send()
-            .await
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<BatchWriteItemSummary, crate::Error> {
+        let backoff = common::retry::Backoff::new(
+            self.retry_policy.as_ref(),
+            write::common::BATCH_WRITE_BACKOFF,
+        );
+        let concurrency = self.concurrency.unwrap_or(1);
+        let retry_policy = self.retry_policy.clone();
+        let batch_write_item = get_batch_write_item_input(self, serializer)?;
+        let summary = write::common::write_request_items_in_chunks(
+            client,
+            batch_write_item,
+            retry_policy.as_ref(),
+            backoff,
+            concurrency,
+        )
+        .await;
+        Ok(summary)
+    }
+
+    /// Estimate the chunks and the write capacity of the operation without sending it,
+    /// such as to schedule large jobs.
+    ///
+    /// Deletes are estimated at one unit each, since the size of the deleted items is unknown.
+    pub fn estimate_cost(self) -> Result<common::bulk::CostEstimate, crate::Error> {
+        let batch_write_item = get_batch_write_item_input(self, &common::serializer::SerdeDynamo)?;
+        let report = write::common::get_write_requests_estimate(
+            batch_write_item
+                .request_items()
+                .into_iter()
+                .flatten()
+                .flat_map(|(_, requests)| requests),
+        );
+        Ok(report)
     }
 }
 
@@ -151,7 +392,7 @@ mod tests {
     #[rstest]
     #[case::empty(
         BatchWriteItem {
-            request_items: collections::HashMap::from(
+            request_items: IndexMap::from(
                 [(
                     "a".to_string(),
                     vec![
@@ -241,7 +482,7 @@ mod tests {
     )]
     #[case::full(
         BatchWriteItem {
-            request_items: collections::HashMap::from(
+            request_items: IndexMap::from(
                 [
                     (
                         "a".to_string(),
@@ -319,6 +560,7 @@ mod tests {
             return_item_collection_metrics: Some(
                 types::ReturnItemCollectionMetrics::None
             ),
+            ..Default::default()
         },
         operation::batch_write_item::BatchWriteItemInput::builder()
             .set_request_items(
@@ -459,4 +701,142 @@ mod tests {
         let actual: operation::batch_write_item::BatchWriteItemInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    fn get_duplicate_batch_write_item(
+        duplicate_key_policy: DuplicateKeyPolicy,
+    ) -> BatchWriteItem<Value> {
+        BatchWriteItem {
+            duplicate_key_policy,
+            key_names: IndexMap::from([("a".to_string(), vec!["b".to_string()])]),
+            request_items: IndexMap::from([(
+                "a".to_string(),
+                vec![
+                    BatchWriteItemRequest::PutItem(BatchWriteItemRequestPutItem {
+                        item: json!(
+                            {
+                                "b": "c",
+                                "d": "e"
+                            }
+                        ),
+                    }),
+                    BatchWriteItemRequest::PutItem(BatchWriteItemRequestPutItem {
+                        item: json!(
+                            {
+                                "b": "f"
+                            }
+                        ),
+                    }),
+                    BatchWriteItemRequest::DeleteItem(BatchWriteItemRequestDeleteItem {
+                        keys: common::key::Keys {
+                            partition_key: common::key::Key {
+                                name: "b".to_string(),
+                                value: Value::String("c".to_string()),
+                            },
+                            ..Default::default()
+                        },
+                    }),
+                ],
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_write_item_duplicate_key_reject() {
+        let args = get_duplicate_batch_write_item(DuplicateKeyPolicy::Reject);
+        let actual = operation::batch_write_item::BatchWriteItemInput::try_from(args).unwrap_err();
+        assert!(matches!(actual, crate::Error::Validation(_)));
+        assert!(format!("{actual:?}").contains("in the batch write requests of table a"));
+    }
+
+    #[test]
+    fn test_batch_write_item_duplicate_key_last_write_wins() {
+        let args = get_duplicate_batch_write_item(DuplicateKeyPolicy::LastWriteWins);
+        let actual: operation::batch_write_item::BatchWriteItemInput = args.try_into().unwrap();
+        let expected = collections::HashMap::from([(
+            "a".to_string(),
+            vec![
+                types::WriteRequest::builder()
+                    .delete_request(
+                        types::DeleteRequest::builder()
+                            .key("b", types::AttributeValue::S("c".to_string()))
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+                types::WriteRequest::builder()
+                    .put_request(
+                        types::PutRequest::builder()
+                            .item("b", types::AttributeValue::S("f".to_string()))
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            ],
+        )]);
+        assert_eq!(actual.request_items, Some(expected));
+    }
+
+    #[test]
+    fn test_batch_write_item_summary_merge() {
+        let get_request = |id: &str| {
+            types::WriteRequest::builder()
+                .delete_request(
+                    types::DeleteRequest::builder()
+                        .key("b", types::AttributeValue::S(id.to_string()))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+        };
+        fn get_summary(
+            index: usize,
+            result: common::bulk::BulkResult<types::WriteRequest, write::common::BatchWriteError>,
+        ) -> BatchWriteItemSummary {
+            BatchWriteItemSummary {
+                chunks: vec![BatchWriteItemChunkSummary {
+                    error: result.failed.first().map(|(_, error)| error.clone()),
+                    failed: result.failed.len(),
+                    index,
+                    succeeded: result.succeeded.len(),
+                }],
+                results: collections::HashMap::from([("a".to_string(), result)]),
+                ..Default::default()
+            }
+        }
+        let mut actual = get_summary(
+            0,
+            common::bulk::BulkResult {
+                failed: Vec::new(),
+                succeeded: vec![get_request("c")],
+            },
+        );
+        actual.merge(get_summary(
+            1,
+            common::bulk::BulkResult {
+                failed: vec![(
+                    get_request("d"),
+                    write::common::BatchWriteError::Unprocessed,
+                )],
+                succeeded: vec![get_request("e")],
+            },
+        ));
+        let chunks: Vec<_> = actual
+            .chunks
+            .iter()
+            .map(|chunk| {
+                (
+                    chunk.index,
+                    chunk.succeeded,
+                    chunk.failed,
+                    chunk.error.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(chunks, vec![(0, 1, 0, false), (1, 1, 1, true)]);
+        let result = &actual.results["a"];
+        assert_eq!(result.succeeded, vec![get_request("c"), get_request("e")]);
+        assert_eq!(result.failed.len(), 1);
+        assert!(!actual.is_success());
+    }
 }
@@ -0,0 +1,433 @@
+use crate::{analysis, common, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use futures_util::{StreamExt, stream};
+use serde::{Serialize, ser::Error as _};
+use serde_dynamo::{Error, Result};
+use std::{collections, fmt};
+
+/// Error returned by the bulk put item operation, or for one of its items.
+#[derive(Debug)]
+pub enum BulkPutItemError {
+    /// The batch write of an unconditional item failed.
+    BatchWrite(write::common::BatchWriteError),
+    /// The put of a conditional item failed.
+    ///
+    /// Items whose condition was not met are reported with
+    /// [`crate::Error::ConditionalCheckFailed`].
+    PutItem(crate::Error),
+}
+
+impl fmt::Display for BulkPutItemError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BatchWrite(error) => write!(formatter, "batch write failed: {error}"),
+            Self::PutItem(error) => write!(formatter, "put item failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BulkPutItemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::BatchWrite(error) => Some(error),
+            Self::PutItem(error) => Some(error),
+        }
+    }
+}
+
+/// Output of the bulk put item operation, the written items and the failed ones.
+pub type BulkPutItemOutput =
+    common::bulk::BulkResult<collections::HashMap<String, types::AttributeValue>, BulkPutItemError>;
+
+/// A single item within a bulk put item operation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkPutItemRequest<T> {
+    /// Condition expression that must be true for the item to be written.
+    ///
+    /// Batch writes can't carry conditions, so items with a condition are written with
+    /// individual put item calls.
+    pub condition: Option<common::condition::ConditionMap<T>>,
+    /// The item to put into the table.
+    pub item: T,
+}
+
+/// bulk put item operation
+#[derive(Clone, Debug, Default, PartialEq)]
+struct BulkPutItemInput {
+    batch_requests: Vec<types::WriteRequest>,
+    concurrency: usize,
+    conditional_puts: Vec<write::put_item::PutItemInput>,
+    table_name: String,
+}
+
+/// Bulk put item operation.
+///
+/// Writes the unconditional items with chunked batch writes, retrying unprocessed items,
+/// and routes the items with a condition through individual put item calls with bounded
+/// concurrency. Failed items are reported individually instead of aborting the whole
+/// operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, write};
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let if_not_exists = common::condition::ConditionMap::Leaves(
+///     common::condition::LogicalOperator::And,
+///     vec![common::condition::KeyCondition {
+///         name: "id".to_string(),
+///         condition: common::condition::Condition::Null,
+///     }],
+/// );
+/// let bulk_put_item = write::bulk_put_item::BulkPutItem {
+///     conditional_concurrency: Some(10),
+///     items: vec![
+///         write::bulk_put_item::BulkPutItemRequest {
+///             condition: None,
+///             item: json!({"id": "1", "name": "Jane"}),
+///         },
+///         write::bulk_put_item::BulkPutItemRequest {
+///             condition: Some(if_not_exists),
+///             item: json!({"id": "2", "name": "John"}),
+///         },
+///     ],
+///     table_name: "users".to_string(),
+/// };
+/// let result = bulk_put_item.send(client).await?;
+/// for (item, error) in &result.failed {
+///     println!("{item:?} failed: {error}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkPutItem<T> {
+    /// The maximum number of conditional put item requests in flight at once.
+    ///
+    /// If `None`, items with a condition are rejected instead of being routed through
+    /// individual put item calls.
+    pub conditional_concurrency: Option<usize>,
+    /// The items to put into the table.
+    pub items: Vec<BulkPutItemRequest<T>>,
+    /// The name of the table to write to.
+    pub table_name: String,
+}
+
+impl<T> Default for BulkPutItem<T> {
+    fn default() -> Self {
+        Self {
+            conditional_concurrency: None,
+            items: Vec::new(),
+            table_name: String::new(),
+        }
+    }
+}
+
+impl<T: Serialize> TryFrom<BulkPutItem<T>> for BulkPutItemInput {
+    type Error = Error;
+
+    fn try_from(bulk_put_item: BulkPutItem<T>) -> Result<Self> {
+        let serializer = &common::serializer::SerdeDynamo;
+        let mut batch_requests = Vec::new();
+        let mut conditional_puts = Vec::new();
+        for request in bulk_put_item.items {
+            match request.condition {
+                Some(condition) => {
+                    if bulk_put_item.conditional_concurrency.is_none() {
+                        return Err(Error::custom(
+                            "items with a condition require a conditional concurrency",
+                        ));
+                    }
+                    let put_item = write::put_item::PutItem {
+                        item: request.item,
+                        return_values: None,
+                        write_args: write::common::WriteArgs {
+                            condition: Some(condition),
+                            placeholder_naming: None,
+                            return_consumed_capacity: None,
+                            return_item_collection_metrics: None,
+                            return_selection: None,
+                            return_values_on_condition_check_failure: None,
+                            retry_policy: None,
+                            table_name: bulk_put_item.table_name.clone(),
+                        },
+                    };
+                    let put_item = write::put_item::PutItemInput::new(put_item, serializer)?;
+                    conditional_puts.push(put_item);
+                }
+                None => {
+                    let put_request = write::batch_write_item::BatchWriteItemRequest::PutItem(
+                        write::batch_write_item::BatchWriteItemRequestPutItem {
+                            item: request.item,
+                        },
+                    );
+                    batch_requests.push(put_request.serialize(serializer)?);
+                }
+            }
+        }
+        let operation = Self {
+            batch_requests,
+            concurrency: bulk_put_item.conditional_concurrency.unwrap_or(1).max(1),
+            conditional_puts,
+            table_name: bulk_put_item.table_name,
+        };
+        Ok(operation)
+    }
+}
+
+impl<T: Serialize> BulkPutItem<T> {
+    /// Execute the bulk put item operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.bulk_put_item", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<BulkPutItemOutput, crate::Error> {
+        let bulk_put_item: BulkPutItemInput =
+            self.try_into().map_err(crate::Error::Serialization)?;
+        let batch_result = write::common::write_requests_in_chunks(
+            client,
+            &bulk_put_item.table_name,
+            bulk_put_item.batch_requests,
+        )
+        .await
+        .map_inputs(write::common::get_put_request_item);
+        let mut output = BulkPutItemOutput {
+            failed: batch_result
+                .failed
+                .into_iter()
+                .map(|(item, error)| (item, BulkPutItemError::BatchWrite(error)))
+                .collect(),
+            succeeded: batch_result.succeeded,
+        };
+        let puts = bulk_put_item.conditional_puts.into_iter().map(|put_item| {
+            let item = put_item.item.clone();
+            let builder = client.put_item().set_item(Some(put_item.item));
+            let builder = crate::apply_write_operation!(builder, put_item.write_operation);
+            async move { (item, builder.send().await) }
+        });
+        let mut puts = stream::iter(puts).buffer_unordered(bulk_put_item.concurrency);
+        while let Some((item, result)) = puts.next().await {
+            match result {
+                Ok(_) => output.succeeded.push(item),
+                Err(error) => output
+                    .failed
+                    .push((item, BulkPutItemError::PutItem(error.into()))),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Estimate the chunks and the write capacity of the operation without sending it,
+    /// such as to schedule large jobs.
+    ///
+    /// Every conditional item is sent alone, in a chunk of its own.
+    pub fn estimate_cost(self) -> Result<common::bulk::CostEstimate, crate::Error> {
+        let bulk_put_item: BulkPutItemInput =
+            self.try_into().map_err(crate::Error::Serialization)?;
+        let mut report = write::common::get_write_requests_estimate(&bulk_put_item.batch_requests);
+        for put_item in &bulk_put_item.conditional_puts {
+            report.estimated_capacity += common::capacity::CapacityUnits::estimate_write(
+                analysis::get_item_size(&put_item.item),
+            );
+        }
+        report.chunks += bulk_put_item.conditional_puts.len();
+        report.requests += bulk_put_item.conditional_puts.len();
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    #[rstest]
+    #[case::unconditional(
+        BulkPutItem {
+            conditional_concurrency: None,
+            items: vec![
+                BulkPutItemRequest {
+                    condition: None,
+                    item: json!(
+                        {
+                            "a": "b"
+                        }
+                    ),
+                },
+            ],
+            table_name: "c".to_string(),
+        },
+        BulkPutItemInput {
+            batch_requests: vec![
+                types::WriteRequest::builder()
+                    .put_request(
+                        types::PutRequest::builder()
+                            .item(
+                                "a",
+                                types::AttributeValue::S(
+                                    "b".to_string()
+                                ),
+                            )
+                            .build()
+                            .unwrap()
+                    )
+                    .build(),
+            ],
+            concurrency: 1,
+            table_name: "c".to_string(),
+            ..Default::default()
+        }
+    )]
+    #[case::conditional(
+        BulkPutItem {
+            conditional_concurrency: Some(4),
+            items: vec![
+                BulkPutItemRequest {
+                    condition: None,
+                    item: json!(
+                        {
+                            "a": "b"
+                        }
+                    ),
+                },
+                BulkPutItemRequest {
+                    condition: Some(
+                        common::condition::ConditionMap::Leaves(
+                            common::condition::LogicalOperator::And,
+                            vec![
+                                common::condition::KeyCondition {
+                                    name: "a".to_string(),
+                                    condition: common::condition::Condition::Null,
+                                },
+                            ]
+                        )
+                    ),
+                    item: json!(
+                        {
+                            "a": "c"
+                        }
+                    ),
+                },
+            ],
+            table_name: "d".to_string(),
+        },
+        BulkPutItemInput {
+            batch_requests: vec![
+                types::WriteRequest::builder()
+                    .put_request(
+                        types::PutRequest::builder()
+                            .item(
+                                "a",
+                                types::AttributeValue::S(
+                                    "b".to_string()
+                                ),
+                            )
+                            .build()
+                            .unwrap()
+                    )
+                    .build(),
+            ],
+            concurrency: 4,
+            conditional_puts: vec![
+                write::put_item::PutItemInput {
+                    item: collections::HashMap::from(
+                        [
+                            (
+                                "a".to_string(),
+                                types::AttributeValue::S(
+                                    "c".to_string()
+                                )
+                            ),
+                        ]
+                    ),
+                    write_operation: write::common::WriteInput {
+                        condition_expression: Some(
+                            "attribute_not_exists(#a)".to_string()
+                        ),
+                        expression_attribute_names: Some(
+                            collections::HashMap::from(
+                                [
+                                    ("#a".to_string(), "a".to_string()),
+                                ]
+                            )
+                        ),
+                        expression_attribute_values: Some(
+                            collections::HashMap::new()
+                        ),
+                        table_name: "d".to_string(),
+                        ..Default::default()
+                    },
+                },
+            ],
+            table_name: "d".to_string(),
+        }
+    )]
+    fn test_bulk_put_item(#[case] args: BulkPutItem<Value>, #[case] expected: BulkPutItemInput) {
+        let actual: BulkPutItemInput = args.try_into().unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bulk_put_item_conditional_without_concurrency() {
+        let args = BulkPutItem {
+            conditional_concurrency: None,
+            items: vec![BulkPutItemRequest {
+                condition: Some(common::condition::ConditionMap::Leaves(
+                    common::condition::LogicalOperator::And,
+                    vec![common::condition::KeyCondition {
+                        name: "a".to_string(),
+                        condition: common::condition::Condition::Null,
+                    }],
+                )),
+                item: json!({"a": "b"}),
+            }],
+            table_name: "c".to_string(),
+        };
+        let actual: Result<BulkPutItemInput> = args.try_into();
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_bulk_put_item_borrowed() {
+        let items = [json!({"a": "b"}), json!({"a": "c"})];
+        let args = BulkPutItem {
+            items: items
+                .iter()
+                .map(|item| BulkPutItemRequest {
+                    condition: None,
+                    item,
+                })
+                .collect(),
+            table_name: "d".to_string(),
+            ..Default::default()
+        };
+        let actual: BulkPutItemInput = args.try_into().unwrap();
+        assert_eq!(actual.batch_requests.len(), items.len());
+    }
+
+    #[test]
+    fn test_bulk_put_item_estimate_cost() {
+        let args = BulkPutItem {
+            conditional_concurrency: Some(2),
+            items: vec![
+                BulkPutItemRequest {
+                    condition: None,
+                    item: json!({"a": "b"}),
+                },
+                BulkPutItemRequest {
+                    condition: Some(common::condition::ConditionMap::attribute_missing("a")),
+                    item: json!({"a": "c".repeat(1024)}),
+                },
+            ],
+            table_name: "d".to_string(),
+        };
+        let actual = args.estimate_cost().unwrap();
+        assert_eq!(actual.requests, 2);
+        assert_eq!(actual.chunks, 2);
+        assert_eq!(actual.estimated_capacity.write_capacity_units, 3.0);
+    }
+}
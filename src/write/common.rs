@@ -1,9 +1,47 @@
-use crate::common;
+use crate::{analysis, common, common::retry::WithRetryPolicy as _, write};
 
-use aws_sdk_dynamodb::types;
+use aws_sdk_dynamodb::{Client, operation, types};
+use futures_util::{StreamExt, stream};
 use serde::Serialize;
 use serde_dynamo::{Error, Result};
-use std::collections;
+use std::{collections, fmt, sync, time};
+
+/// Maximum number of requests DynamoDB accepts in a single BatchWriteItem call.
+pub(crate) const MAX_BATCH_WRITE_REQUESTS: usize = 25;
+
+/// Backoff of the retries of unprocessed items, unless overridden by a retry policy.
+pub(crate) const BATCH_WRITE_BACKOFF: common::retry::Backoff = common::retry::Backoff {
+    initial_backoff: time::Duration::from_millis(50),
+    max_attempts: 5,
+    max_backoff: time::Duration::from_secs(5),
+};
+
+/// Error of a single request written with chunked batch writes.
+#[derive(Clone, Debug)]
+pub enum BatchWriteError {
+    /// The batch write of the chunk containing the request failed.
+    BatchWriteItem(sync::Arc<crate::Error>),
+    /// The request was still unprocessed after retrying.
+    Unprocessed,
+}
+
+impl fmt::Display for BatchWriteError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BatchWriteItem(error) => write!(formatter, "batch write item failed: {error}"),
+            Self::Unprocessed => write!(formatter, "request was unprocessed after retrying"),
+        }
+    }
+}
+
+impl std::error::Error for BatchWriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::BatchWriteItem(error) => Some(error.as_ref()),
+            Self::Unprocessed => None,
+        }
+    }
+}
 
 /// Internal representation of write operation parameters.
 ///
@@ -25,6 +63,49 @@ pub(crate) struct WriteInput {
 }
 
 impl WriteInput {
+    /// Convert the write arguments, converting the condition values with the given serializer.
+    pub(crate) fn new<T: Serialize>(
+        write_args: WriteArgs<T>,
+        placeholders: &mut common::PlaceholderAllocator,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let (condition_expression, expression_attribute_names, expression_attribute_values) =
+            match write_args.condition {
+                Some(condition) => {
+                    let condition_operation =
+                        condition.get_expression_operation(placeholders, serializer)?;
+                    (
+                        Some(condition_operation.expression),
+                        Some(
+                            condition_operation
+                                .expression_attribute_names
+                                .into_iter()
+                                .collect(),
+                        ),
+                        Some(
+                            condition_operation
+                                .expression_attribute_values
+                                .into_iter()
+                                .collect(),
+                        ),
+                    )
+                }
+                None => (None, None, None),
+            };
+        let operation = Self {
+            condition_expression,
+            expression_attribute_names,
+            expression_attribute_values,
+            return_consumed_capacity: write_args.return_consumed_capacity,
+            return_item_collection_metrics: write_args.return_item_collection_metrics,
+            return_values: None,
+            return_values_on_condition_check_failure: write_args
+                .return_values_on_condition_check_failure,
+            table_name: write_args.table_name,
+        };
+        Ok(operation)
+    }
+
     /// Merge an expression operation into this write operation.
     pub(crate) fn merge_expression(&mut self, operation: common::ExpressionInput) -> String {
         operation.merge_into(
@@ -37,13 +118,20 @@ impl WriteInput {
 /// Arguments common to all write operations (Put, Update, Delete).
 ///
 /// These arguments apply to operations that modify data in DynamoDB tables.
-#[derive(Clone, Debug, Default, PartialEq)]
+///
+/// The default arguments don't require a default value, so operations on borrowed items
+/// (`T = &Item`) can use them.
+#[derive(Clone, Debug, PartialEq)]
 pub struct WriteArgs<T> {
     /// Condition expression that must be true for the operation to succeed.
     ///
     /// If specified, the operation will only proceed if the condition evaluates to true.
     /// If the condition is false, the operation will fail with a conditional check error.
     pub condition: Option<common::condition::ConditionMap<T>>,
+    /// Naming strategy of the placeholders of the condition and update expressions.
+    ///
+    /// If `None`, placeholders are derived from the attribute names.
+    pub placeholder_naming: Option<common::PlaceholderNaming>,
     /// Whether to return the consumed capacity information.
     ///
     /// Useful for monitoring and capacity planning.
@@ -53,47 +141,88 @@ pub struct WriteArgs<T> {
     /// Item collection metrics provide information about collections (local secondary indexes)
     /// affected by the operation.
     pub return_item_collection_metrics: Option<types::ReturnItemCollectionMetrics>,
-    /// Which item attributes to return in the response.
+    /// Which of the returned item attributes to keep.
     ///
-    /// Options: `AllOld`, `AllNew`, `UpdatedOld`, `UpdatedNew`, or `None`.
-    pub return_values: Option<types::ReturnValue>,
+    /// Applied client-side to the item returned by the operation's `return_values` or
+    /// `return_values_on_condition_check_failure`, so large items don't have to be
+    /// deserialized in full. Used by the put, update and delete item operations.
+    pub return_selection: Option<common::selection::SelectionMap>,
     /// Which item attributes to return if a condition check fails.
     ///
     /// Allows you to see the item that caused the condition check to fail.
     pub return_values_on_condition_check_failure:
         Option<types::ReturnValuesOnConditionCheckFailure>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    ///
+    /// Used by the put, update and delete item operations.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
     /// The name of the table to write to.
     pub table_name: String,
 }
 
+impl<T> Default for WriteArgs<T> {
+    fn default() -> Self {
+        Self {
+            condition: None,
+            placeholder_naming: None,
+            return_consumed_capacity: None,
+            return_item_collection_metrics: None,
+            return_selection: None,
+            return_values_on_condition_check_failure: None,
+            retry_policy: None,
+            table_name: String::new(),
+        }
+    }
+}
+
 impl<T: Serialize> TryFrom<WriteArgs<T>> for WriteInput {
     type Error = Error;
 
     fn try_from(write_args: WriteArgs<T>) -> Result<Self> {
-        let (condition_expression, expression_attribute_names, expression_attribute_values) =
-            match write_args.condition {
-                Some(condition) => {
-                    let condition_operation: common::ExpressionInput = condition.try_into()?;
-                    (
-                        Some(condition_operation.expression),
-                        Some(condition_operation.expression_attribute_names),
-                        Some(condition_operation.expression_attribute_values),
-                    )
-                }
-                None => (None, None, None),
-            };
-        let operation = Self {
-            condition_expression,
-            expression_attribute_names,
-            expression_attribute_values,
-            return_consumed_capacity: write_args.return_consumed_capacity,
-            return_item_collection_metrics: write_args.return_item_collection_metrics,
-            return_values: write_args.return_values,
-            return_values_on_condition_check_failure: write_args
-                .return_values_on_condition_check_failure,
-            table_name: write_args.table_name,
-        };
-        Ok(operation)
+        let placeholder_naming = write_args.placeholder_naming.clone();
+        Self::new(
+            write_args,
+            &mut common::PlaceholderAllocator::new(placeholder_naming),
+            &common::serializer::SerdeDynamo,
+        )
+    }
+}
+
+/// Combine a condition with `AND` with the condition already set, if any.
+pub(crate) fn and_condition<T>(
+    existing: Option<common::condition::ConditionMap<T>>,
+    condition: common::condition::ConditionMap<T>,
+) -> common::condition::ConditionMap<T> {
+    match existing {
+        Some(existing) => existing.and(condition),
+        None => condition,
+    }
+}
+
+/// Trim the attributes returned by a write operation to the return selection, if any.
+pub(crate) fn select_returned_attributes(
+    attributes: Option<collections::HashMap<String, types::AttributeValue>>,
+    return_selection: Option<&common::selection::SelectionMap>,
+) -> Option<collections::HashMap<String, types::AttributeValue>> {
+    match return_selection {
+        Some(return_selection) => {
+            attributes.map(|attributes| return_selection.project_item(attributes))
+        }
+        None => attributes,
+    }
+}
+
+/// Get the error of a write operation, trimming the item returned by a failed condition check
+/// to the return selection, if any.
+pub(crate) fn get_write_error(
+    error: crate::Error,
+    return_selection: Option<&common::selection::SelectionMap>,
+) -> crate::Error {
+    match error {
+        crate::Error::ConditionalCheckFailed(item) => {
+            crate::Error::ConditionalCheckFailed(select_returned_attributes(item, return_selection))
+        }
+        error => error,
     }
 }
 
@@ -114,3 +243,303 @@ macro_rules! apply_write_operation {
             .table_name($write_operation.table_name)
     };
 }
+
+/// Build delete requests for items, keeping only their key attributes.
+pub(crate) fn get_delete_requests(
+    items: Vec<collections::HashMap<String, types::AttributeValue>>,
+    key_names: &[String],
+) -> Vec<types::WriteRequest> {
+    items
+        .into_iter()
+        .map(|item| {
+            let keys = item
+                .into_iter()
+                .filter(|(name, _)| key_names.contains(name))
+                .collect();
+            let delete_request = types::DeleteRequest::builder()
+                .set_key(Some(keys))
+                .build()
+                .unwrap();
+            types::WriteRequest::builder()
+                .delete_request(delete_request)
+                .build()
+        })
+        .collect()
+}
+
+/// Extract the key of a delete request.
+pub(crate) fn get_delete_request_key(
+    request: types::WriteRequest,
+) -> collections::HashMap<String, types::AttributeValue> {
+    request
+        .delete_request
+        .map(|delete_request| delete_request.key)
+        .unwrap_or_default()
+}
+
+/// Extract the item of a put request.
+pub(crate) fn get_put_request_item(
+    request: types::WriteRequest,
+) -> collections::HashMap<String, types::AttributeValue> {
+    request
+        .put_request
+        .map(|put_request| put_request.item)
+        .unwrap_or_default()
+}
+
+/// Estimate the chunks and the capacity of write requests, without sending them.
+///
+/// Deletes are estimated at one unit each, since the size of the deleted items is unknown.
+pub(crate) fn get_write_requests_estimate<'a>(
+    requests: impl IntoIterator<Item = &'a types::WriteRequest>,
+) -> common::bulk::CostEstimate {
+    let mut report = common::bulk::CostEstimate::default();
+    for request in requests {
+        let item_size = request
+            .put_request
+            .as_ref()
+            .map_or(0, |put_request| analysis::get_item_size(&put_request.item));
+        report.estimated_capacity += common::capacity::CapacityUnits::estimate_write(item_size);
+        report.requests += 1;
+    }
+    report.chunks = report.requests.div_ceil(MAX_BATCH_WRITE_REQUESTS);
+    report
+}
+
+/// Group the requests of a chunk by table.
+fn get_chunk_request_items(
+    chunk: &[(String, types::WriteRequest)],
+) -> collections::HashMap<String, Vec<types::WriteRequest>> {
+    let mut request_items: collections::HashMap<_, Vec<_>> = collections::HashMap::new();
+    for (table_name, request) in chunk {
+        request_items
+            .entry(table_name.clone())
+            .or_default()
+            .push(request.clone());
+    }
+    request_items
+}
+
+/// Record the outcome of the requests of a chunk, failing the ones still pending.
+///
+/// Returns the number of failed requests.
+fn add_chunk_results(
+    results: &mut collections::HashMap<
+        String,
+        common::bulk::BulkResult<types::WriteRequest, BatchWriteError>,
+    >,
+    chunk: Vec<(String, types::WriteRequest)>,
+    pending_requests: &collections::HashMap<String, Vec<types::WriteRequest>>,
+    error: &BatchWriteError,
+) -> usize {
+    let mut failed = 0;
+    for (table_name, request) in chunk {
+        let pending = pending_requests
+            .get(&table_name)
+            .is_some_and(|pending_requests| pending_requests.contains(&request));
+        let result = results.entry(table_name).or_default();
+        if pending {
+            result.failed.push((request, error.clone()));
+            failed += 1;
+        } else {
+            result.succeeded.push(request);
+        }
+    }
+    failed
+}
+
+/// Write a chunk of at most 25 requests, retrying unprocessed items with exponential backoff
+/// and jitter.
+///
+/// Returns the summary of the chunk alone, to be merged into the summary of the operation.
+async fn write_chunk(
+    client: &Client,
+    batch_write_item: &operation::batch_write_item::BatchWriteItemInput,
+    retry_policy: Option<&common::retry::RetryPolicy>,
+    backoff: common::retry::Backoff,
+    index: usize,
+    chunk: Vec<(String, types::WriteRequest)>,
+) -> write::batch_write_item::BatchWriteItemSummary {
+    let mut summary = write::batch_write_item::BatchWriteItemSummary::default();
+    let mut pending_requests = get_chunk_request_items(&chunk);
+    let mut attempt = 0;
+    let error = loop {
+        let output = client
+            .batch_write_item()
+            .set_request_items(Some(pending_requests.clone()))
+            .set_return_consumed_capacity(batch_write_item.return_consumed_capacity.clone())
+            .set_return_item_collection_metrics(
+                batch_write_item.return_item_collection_metrics.clone(),
+            )
+            .customize()
+            .with_retry_policy(client, retry_policy)
+            .send()
+            .await;
+        let output = match output {
+            Ok(output) => output,
+            Err(error) => break BatchWriteError::BatchWriteItem(sync::Arc::new(error.into())),
+        };
+        summary
+            .consumed_capacity
+            .extend(output.consumed_capacity.unwrap_or_default());
+        for (table_name, metrics) in output.item_collection_metrics.unwrap_or_default() {
+            summary
+                .item_collection_metrics
+                .entry(table_name)
+                .or_default()
+                .extend(metrics);
+        }
+        pending_requests = output.unprocessed_items.unwrap_or_default();
+        pending_requests.retain(|_, requests| !requests.is_empty());
+        attempt += 1;
+        if pending_requests.is_empty() || attempt >= backoff.max_attempts {
+            break BatchWriteError::Unprocessed;
+        }
+        backoff.sleep("BatchWriteItem", attempt).await;
+    };
+    let requests = chunk.len();
+    let failed = add_chunk_results(&mut summary.results, chunk, &pending_requests, &error);
+    summary
+        .chunks
+        .push(write::batch_write_item::BatchWriteItemChunkSummary {
+            error: (failed > 0).then_some(error),
+            failed,
+            index,
+            succeeded: requests - failed,
+        });
+    summary
+}
+
+/// Write the requests of a batch write item operation in chunks of at most 25, with at most
+/// `concurrency` chunks in flight at once, retrying unprocessed items with exponential backoff
+/// and jitter.
+///
+/// A failed chunk doesn't stop the other ones: its pending requests are reported as failed,
+/// as are the requests still unprocessed after the last attempt.
+pub(crate) async fn write_request_items_in_chunks(
+    client: &Client,
+    mut batch_write_item: operation::batch_write_item::BatchWriteItemInput,
+    retry_policy: Option<&common::retry::RetryPolicy>,
+    backoff: common::retry::Backoff,
+    concurrency: usize,
+) -> write::batch_write_item::BatchWriteItemSummary {
+    let mut requests = batch_write_item
+        .request_items
+        .take()
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(table_name, requests)| {
+            requests
+                .into_iter()
+                .map(move |request| (table_name.clone(), request))
+        })
+        .peekable();
+    let mut chunks = Vec::new();
+    while requests.peek().is_some() {
+        let chunk: Vec<_> = requests.by_ref().take(MAX_BATCH_WRITE_REQUESTS).collect();
+        chunks.push(chunk);
+    }
+    let chunks = chunks.into_iter().enumerate().map(|(index, chunk)| {
+        write_chunk(
+            client,
+            &batch_write_item,
+            retry_policy,
+            backoff,
+            index,
+            chunk,
+        )
+    });
+    // buffered rather than buffer_unordered, so the chunks are reported in request order
+    let mut chunks = stream::iter(chunks).buffered(concurrency.max(1));
+    let mut summary = write::batch_write_item::BatchWriteItemSummary::default();
+    while let Some(chunk_summary) = chunks.next().await {
+        summary.merge(chunk_summary);
+    }
+    summary
+}
+
+/// Write requests to a table in chunks of at most 25, retrying unprocessed items with
+/// exponential backoff and jitter.
+pub(crate) async fn write_requests_in_chunks(
+    client: &Client,
+    table_name: &str,
+    requests: Vec<types::WriteRequest>,
+) -> common::bulk::BulkResult<types::WriteRequest, BatchWriteError> {
+    let batch_write_item = operation::batch_write_item::BatchWriteItemInput::builder()
+        .request_items(table_name, requests)
+        .build()
+        .unwrap();
+    write_request_items_in_chunks(client, batch_write_item, None, BATCH_WRITE_BACKOFF, 1)
+        .await
+        .results
+        .remove(table_name)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_delete_request(id: &str) -> types::WriteRequest {
+        let delete_request = types::DeleteRequest::builder()
+            .key("a", types::AttributeValue::S(id.to_string()))
+            .build()
+            .unwrap();
+        types::WriteRequest::builder()
+            .delete_request(delete_request)
+            .build()
+    }
+
+    #[test]
+    fn test_add_chunk_results() {
+        let chunk = vec![
+            ("b".to_string(), get_delete_request("c")),
+            ("b".to_string(), get_delete_request("d")),
+            ("e".to_string(), get_delete_request("c")),
+        ];
+        let pending_requests = get_chunk_request_items(&chunk[1..]);
+        let mut results = collections::HashMap::new();
+        let failed = add_chunk_results(
+            &mut results,
+            chunk,
+            &pending_requests,
+            &BatchWriteError::Unprocessed,
+        );
+        assert_eq!(failed, 2);
+        let actual: Vec<_> = ["b", "e"]
+            .iter()
+            .map(|table_name| {
+                let result = &results[*table_name];
+                let failed: Vec<_> = result
+                    .failed
+                    .iter()
+                    .map(|(request, _)| request.clone())
+                    .collect();
+                (result.succeeded.clone(), failed)
+            })
+            .collect();
+        let expected = vec![
+            (vec![get_delete_request("c")], vec![get_delete_request("d")]),
+            (Vec::new(), vec![get_delete_request("c")]),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_write_requests_estimate() {
+        let put_request = types::PutRequest::builder()
+            .item("a", types::AttributeValue::S("b".repeat(1024)))
+            .build()
+            .unwrap();
+        let put_request = types::WriteRequest::builder()
+            .put_request(put_request)
+            .build();
+        let delete_requests: Vec<_> = (0..25)
+            .map(|id| get_delete_request(&id.to_string()))
+            .collect();
+        let actual = get_write_requests_estimate(delete_requests.iter().chain([&put_request]));
+        assert_eq!(actual.requests, 26);
+        assert_eq!(actual.chunks, 2);
+        assert_eq!(actual.estimated_capacity.write_capacity_units, 27.0);
+    }
+}
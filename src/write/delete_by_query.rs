@@ -0,0 +1,299 @@
+use crate::{common, read, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::Serialize;
+use std::collections;
+
+/// Output of the delete by query operation.
+#[derive(Clone, Debug, Default)]
+pub struct DeleteByQueryOutput {
+    /// The number of items matched by the query.
+    pub count: usize,
+    /// The keys of the deleted items and of the items whose deletion failed.
+    ///
+    /// Always empty for dry runs.
+    pub result: common::bulk::BulkResult<
+        collections::HashMap<String, types::AttributeValue>,
+        write::common::BatchWriteError,
+    >,
+}
+
+/// Delete by query operation.
+///
+/// Pages through the query fetching only the key attributes, then deletes the matching
+/// items with chunked batch writes. With `dry_run` set, the matching items are only counted.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read, write};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let delete_by_query = write::delete_by_query::DeleteByQuery {
+///     dry_run: true,
+///     query: read::query::Query {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: "1".to_string(),
+///         },
+///         multiple_read_args: read::common::MultipleReadArgs {
+///             table_name: "users".to_string(),
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let output = delete_by_query.send(client).await?;
+/// println!("{} items would be deleted", output.count);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeleteByQuery<T> {
+    /// Whether to only count the matching items without deleting them.
+    pub dry_run: bool,
+    /// The primary key attribute names of the table.
+    ///
+    /// If empty, they are taken from the `key_schema` of the query, which must be set and must
+    /// describe the base table. Otherwise the operation fails with
+    /// [`crate::Error::Validation`] before reading anything.
+    pub key_names: Vec<String>,
+    /// The query selecting the items to delete.
+    pub query: read::query::Query<T>,
+}
+
+impl<T> DeleteByQuery<T> {
+    fn get_count_query(self) -> read::query::Query<T> {
+        let mut query = self.query;
+        query.multiple_read_args.select = Some(types::Select::Count);
+        query.multiple_read_args.selection = None;
+        query
+    }
+
+    fn get_keys_query(self) -> Result<(read::query::Query<T>, Vec<String>), crate::Error> {
+        self.query.into_keys_query(self.key_names)
+    }
+}
+
+impl<T: Serialize> DeleteByQuery<T> {
+    /// Execute the delete by query operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.delete_by_query", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<DeleteByQueryOutput, crate::Error> {
+        if self.dry_run {
+            let output = self.get_count_query().send(client).await?;
+            let output = DeleteByQueryOutput {
+                count: output.count.max(0) as usize,
+                ..Default::default()
+            };
+            return Ok(output);
+        }
+        let table_name = self.query.multiple_read_args.table_name.clone();
+        let (query, key_names) = self.get_keys_query()?;
+        let output = query.send(client).await?;
+        let requests =
+            write::common::get_delete_requests(output.items.unwrap_or_default(), &key_names);
+        let count = requests.len();
+        let result = write::common::write_requests_in_chunks(client, &table_name, requests)
+            .await
+            .map_inputs(write::common::get_delete_request_key);
+        let output = DeleteByQueryOutput { count, result };
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table;
+
+    use rstest::rstest;
+    use serde_json::Value;
+
+    #[rstest]
+    #[case::key_schema(
+        DeleteByQuery {
+            query: read::query::Query {
+                key_schema: Some(
+                    table::common::KeySchema {
+                        partition_key: table::common::KeyAttribute {
+                            name: "b".to_string(),
+                            ..Default::default()
+                        },
+                        sort_key: Some(
+                            table::common::KeyAttribute {
+                                name: "d".to_string(),
+                                ..Default::default()
+                            }
+                        ),
+                    }
+                ),
+                multiple_read_args: read::common::MultipleReadArgs {
+                    table_name: "a".to_string(),
+                    ..Default::default()
+                },
+                partition_key: common::key::Key {
+                    name: "b".to_string(),
+                    value: Value::String(
+                        "c".to_string()
+                    ),
+                },
+                sort_key_condition: Some(
+                    common::condition::SortKey {
+                        name: "d".to_string(),
+                        condition: common::condition::SortKeyCondition::BeginsWith(
+                            "e".to_string()
+                        ),
+                    }
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        read::query::Query {
+            key_schema: Some(
+                table::common::KeySchema {
+                    partition_key: table::common::KeyAttribute {
+                        name: "b".to_string(),
+                        ..Default::default()
+                    },
+                    sort_key: Some(
+                        table::common::KeyAttribute {
+                            name: "d".to_string(),
+                            ..Default::default()
+                        }
+                    ),
+                }
+            ),
+            multiple_read_args: read::common::MultipleReadArgs {
+                select: Some(
+                    types::Select::SpecificAttributes
+                ),
+                selection: Some(
+                    common::selection::SelectionMap::Leaves(
+                        vec![
+                            "b".to_string(),
+                            "d".to_string(),
+                        ]
+                    )
+                ),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "b".to_string(),
+                value: Value::String(
+                    "c".to_string()
+                ),
+            },
+            sort_key_condition: Some(
+                common::condition::SortKey {
+                    name: "d".to_string(),
+                    condition: common::condition::SortKeyCondition::BeginsWith(
+                        "e".to_string()
+                    ),
+                }
+            ),
+            ..Default::default()
+        }
+    )]
+    #[case::explicit_key_names(
+        DeleteByQuery {
+            key_names: vec![
+                "a".to_string(),
+                "b".to_string(),
+            ],
+            query: read::query::Query {
+                multiple_read_args: read::common::MultipleReadArgs {
+                    index_name: Some("c".to_string()),
+                    table_name: "d".to_string(),
+                    ..Default::default()
+                },
+                partition_key: common::key::Key {
+                    name: "e".to_string(),
+                    value: Value::String(
+                        "f".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        read::query::Query {
+            multiple_read_args: read::common::MultipleReadArgs {
+                index_name: Some("c".to_string()),
+                select: Some(
+                    types::Select::SpecificAttributes
+                ),
+                selection: Some(
+                    common::selection::SelectionMap::Leaves(
+                        vec![
+                            "a".to_string(),
+                            "b".to_string(),
+                        ]
+                    )
+                ),
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "e".to_string(),
+                value: Value::String(
+                    "f".to_string()
+                ),
+            },
+            ..Default::default()
+        }
+    )]
+    fn test_delete_by_query_keys_query(
+        #[case] args: DeleteByQuery<Value>,
+        #[case] expected: read::query::Query<Value>,
+    ) {
+        let (actual, _) = args.get_keys_query().unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_delete_by_query_partition_key_only() {
+        let delete_by_query = DeleteByQuery {
+            query: read::query::Query {
+                key_schema: Some(table::common::KeySchema {
+                    partition_key: table::common::KeyAttribute {
+                        name: "a".to_string(),
+                        ..Default::default()
+                    },
+                    sort_key: Some(table::common::KeyAttribute {
+                        name: "b".to_string(),
+                        ..Default::default()
+                    }),
+                }),
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::from("c"),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (_, actual) = delete_by_query.get_keys_query().unwrap();
+        assert_eq!(actual, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_delete_by_query_missing_key_names() {
+        let delete_by_query = DeleteByQuery {
+            query: read::query::Query {
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::from("b"),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let actual = delete_by_query.get_keys_query();
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{common, read, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::Serialize;
+use std::collections;
+
+/// Output of the delete by scan operation.
+#[derive(Clone, Debug, Default)]
+pub struct DeleteByScanOutput {
+    /// The number of items matched by the scan.
+    pub count: usize,
+    /// The keys of the deleted items and of the items whose deletion failed.
+    ///
+    /// Always empty for dry runs.
+    pub result: common::bulk::BulkResult<
+        collections::HashMap<String, types::AttributeValue>,
+        write::common::BatchWriteError,
+    >,
+}
+
+/// Delete by scan operation.
+///
+/// Scans the table with the given filter fetching only the key attributes, then deletes
+/// the matching items with chunked batch writes. Since a bad filter can wipe a table, the
+/// operation is a dry run unless `confirm` is set, and it fails with
+/// [`crate::Error::Validation`] without deleting anything when more than `max_items` items
+/// match.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read, write};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let delete_by_scan = write::delete_by_scan::DeleteByScan {
+///     confirm: true,
+///     key_names: vec!["id".to_string()],
+///     max_items: 1000,
+///     scan: read::scan::Scan {
+///         multiple_read_args: read::common::MultipleReadArgs {
+///             condition: Some(common::condition::ConditionMap::Leaves(
+///                 common::condition::LogicalOperator::And,
+///                 vec![common::condition::KeyCondition {
+///                     name: "status".to_string(),
+///                     condition: common::condition::Condition::Equals("expired".to_string()),
+///                 }],
+///             )),
+///             table_name: "sessions".to_string(),
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     },
+/// };
+/// delete_by_scan.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeleteByScan<T> {
+    /// Whether to actually delete the matching items.
+    ///
+    /// Defaults to `false`, which only counts them (dry run).
+    pub confirm: bool,
+    /// The primary key attribute names of the table (required).
+    pub key_names: Vec<String>,
+    /// The maximum number of items allowed to be deleted.
+    ///
+    /// If more items match, the operation fails before deleting anything, reading at most one
+    /// key past the maximum. Defaults to `0`, which refuses to delete any item.
+    pub max_items: usize,
+    /// The scan selecting the items to delete.
+    pub scan: read::scan::Scan<T>,
+}
+
+impl<T> DeleteByScan<T> {
+    fn get_keys_scan(self) -> (read::scan::Scan<T>, Vec<String>) {
+        let mut scan = self.scan;
+        let max_items = self.max_items.saturating_add(1);
+        scan.multiple_read_args.max_items = Some(
+            scan.multiple_read_args
+                .max_items
+                .map_or(max_items, |scan_max_items| scan_max_items.min(max_items)),
+        );
+        scan.multiple_read_args.select = Some(types::Select::SpecificAttributes);
+        scan.multiple_read_args.selection = Some(common::selection::SelectionMap::Leaves(
+            self.key_names.clone(),
+        ));
+        (scan, self.key_names)
+    }
+}
+
+impl<T: Serialize> DeleteByScan<T> {
+    /// Execute the delete by scan operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.delete_by_scan", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<DeleteByScanOutput, crate::Error> {
+        if !self.confirm {
+            let count = self.scan.count(client).await?;
+            let output = DeleteByScanOutput {
+                count: count.count as usize,
+                ..Default::default()
+            };
+            return Ok(output);
+        }
+        if self.key_names.is_empty() {
+            return Err(crate::Error::validation("key attribute names are required"));
+        }
+        let max_items = self.max_items;
+        let table_name = self.scan.multiple_read_args.table_name.clone();
+        let (scan, key_names) = self.get_keys_scan();
+        let output = scan.send(client).await?;
+        let requests =
+            write::common::get_delete_requests(output.items.unwrap_or_default(), &key_names);
+        let count = requests.len();
+        if count > max_items {
+            return Err(crate::Error::validation(format!(
+                "more than {max_items} items matched, nothing was deleted"
+            )));
+        }
+        let result = write::common::write_requests_in_chunks(client, &table_name, requests)
+            .await
+            .map_inputs(write::common::get_delete_request_key);
+        let output = DeleteByScanOutput { count, result };
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::Value;
+
+    #[rstest]
+    #[case::keys(
+        DeleteByScan {
+            confirm: true,
+            key_names: vec![
+                "a".to_string(),
+                "b".to_string(),
+            ],
+            max_items: 10,
+            scan: read::scan::Scan {
+                multiple_read_args: read::common::MultipleReadArgs {
+                    condition: Some(
+                        common::condition::ConditionMap::Leaves(
+                            common::condition::LogicalOperator::And,
+                            vec![
+                                common::condition::KeyCondition {
+                                    name: "c".to_string(),
+                                    condition: common::condition::Condition::Null,
+                                },
+                            ]
+                        )
+                    ),
+                    table_name: "d".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        read::scan::Scan {
+            multiple_read_args: read::common::MultipleReadArgs {
+                condition: Some(
+                    common::condition::ConditionMap::Leaves(
+                        common::condition::LogicalOperator::And,
+                        vec![
+                            common::condition::KeyCondition {
+                                name: "c".to_string(),
+                                condition: common::condition::Condition::Null,
+                            },
+                        ]
+                    )
+                ),
+                max_items: Some(11),
+                select: Some(
+                    types::Select::SpecificAttributes
+                ),
+                selection: Some(
+                    common::selection::SelectionMap::Leaves(
+                        vec![
+                            "a".to_string(),
+                            "b".to_string(),
+                        ]
+                    )
+                ),
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    )]
+    fn test_delete_by_scan_keys_scan(
+        #[case] args: DeleteByScan<Value>,
+        #[case] expected: read::scan::Scan<Value>,
+    ) {
+        let (actual, _) = args.get_keys_scan();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_delete_by_scan_missing_key_names() {
+        let client = common::get_test_client(aws_sdk_dynamodb::config::Builder::new());
+        let delete_by_scan: DeleteByScan<Value> = DeleteByScan {
+            confirm: true,
+            ..Default::default()
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let actual = runtime.block_on(delete_by_scan.send(&client));
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+}
@@ -1,15 +1,33 @@
-use crate::{common, write};
+use crate::{common, common::retry::WithRetryPolicy as _, write};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
+use aws_sdk_dynamodb::{Client, operation, types};
 use serde::Serialize;
 use serde_dynamo::{Error, Result};
 use std::collections;
 
 /// delete item operation
 #[derive(Clone, Debug, Default, PartialEq)]
-struct DeleteItemInput {
-    keys: collections::HashMap<String, types::AttributeValue>,
-    write_operation: write::common::WriteInput,
+pub(crate) struct DeleteItemInput {
+    pub(crate) keys: collections::HashMap<String, types::AttributeValue>,
+    pub(crate) write_operation: write::common::WriteInput,
+}
+
+/// Item attributes returned by a deletion, restricted to the values DynamoDB accepts for it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DeleteReturnValues {
+    /// The whole item as it was before the deletion.
+    AllOld,
+    /// Nothing.
+    None,
+}
+
+impl From<DeleteReturnValues> for types::ReturnValue {
+    fn from(return_values: DeleteReturnValues) -> Self {
+        match return_values {
+            DeleteReturnValues::AllOld => Self::AllOld,
+            DeleteReturnValues::None => Self::None,
+        }
+    }
 }
 
 /// Delete item operation.
@@ -27,6 +45,7 @@ struct DeleteItemInput {
 ///         },
 ///         ..Default::default()
 ///     },
+///     return_values: None,
 ///     write_args: write::common::WriteArgs {
 ///         table_name: "users".to_string(),
 ///         ..Default::default()
@@ -40,16 +59,24 @@ struct DeleteItemInput {
 pub struct DeleteItem<T> {
     /// The primary key of the item to delete.
     pub keys: common::key::Keys<T>,
-    /// Additional write operation arguments (table name, condition, return values, etc.).
+    /// Which item attributes to return in the response.
+    pub return_values: Option<DeleteReturnValues>,
+    /// Additional write operation arguments (table name, condition, etc.).
     pub write_args: write::common::WriteArgs<T>,
 }
 
-impl<T: Serialize> TryFrom<DeleteItem<T>> for DeleteItemInput {
-    type Error = Error;
-
-    fn try_from(delete_item: DeleteItem<T>) -> Result<Self> {
-        let keys = delete_item.keys.try_into()?;
-        let write_operation: write::common::WriteInput = delete_item.write_args.try_into()?;
+impl DeleteItemInput {
+    pub(crate) fn new<T: Serialize>(
+        delete_item: DeleteItem<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let keys = delete_item.keys.serialize(serializer)?;
+        let placeholders = &mut common::PlaceholderAllocator::new(
+            delete_item.write_args.placeholder_naming.clone(),
+        );
+        let mut write_operation =
+            write::common::WriteInput::new(delete_item.write_args, placeholders, serializer)?;
+        write_operation.return_values = delete_item.return_values.map(Into::into);
         let operation = Self {
             keys,
             write_operation,
@@ -58,24 +85,80 @@ impl<T: Serialize> TryFrom<DeleteItem<T>> for DeleteItemInput {
     }
 }
 
+impl<T: Serialize> TryFrom<DeleteItem<T>> for DeleteItemInput {
+    type Error = Error;
+
+    fn try_from(delete_item: DeleteItem<T>) -> Result<Self> {
+        Self::new(delete_item, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T> DeleteItem<T> {
+    /// Which item attributes to return in the response.
+    pub fn return_values(mut self, return_values: DeleteReturnValues) -> Self {
+        self.return_values = Some(return_values);
+        self
+    }
+}
+
 impl<T: Serialize> DeleteItem<T> {
     /// Execute the delete item operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::delete_item::DeleteItemOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the delete item operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.delete_item", err)
+        tracing::instrument(name = "dynamodb_crud.delete_item", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<
-        operation::delete_item::DeleteItemOutput,
-        error::SdkError<operation::delete_item::DeleteItemError>,
-    > {
-        let delete_item: DeleteItemInput = self.try_into().map_err(error::BuildError::other)?;
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::delete_item::DeleteItemOutput, crate::Error> {
+        let return_selection = self.write_args.return_selection.clone();
+        let retry_policy = self.write_args.retry_policy.clone();
+        let delete_item =
+            DeleteItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
         let builder = client.delete_item().set_key(Some(delete_item.keys));
-        crate::apply_write_operation!(builder, delete_item.write_operation)
+        let mut output = crate::apply_write_operation!(builder, delete_item.write_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
             .send()
             .await
+            .map_err(|error| {
+                write::common::get_write_error(error.into(), return_selection.as_ref())
+            })?;
+        output.attributes =
+            write::common::select_returned_attributes(output.attributes, return_selection.as_ref());
+        Ok(output)
+    }
+
+    /// Build the delete item request without sending it, such as to log or snapshot-test the
+    /// generated expressions.
+    pub fn dry_run(self) -> Result<operation::delete_item::DeleteItemInput, crate::Error> {
+        self.dry_run_with(&common::serializer::SerdeDynamo)
+    }
+
+    /// Build the delete item request without sending it, converting the values with the given
+    /// serializer.
+    pub fn dry_run_with(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::delete_item::DeleteItemInput, crate::Error> {
+        let delete_item =
+            DeleteItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
+        let builder =
+            operation::delete_item::DeleteItemInput::builder().set_key(Some(delete_item.keys));
+        let input = crate::apply_write_operation!(builder, delete_item.write_operation)
+            .build()
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(input)
     }
 }
 
@@ -98,6 +181,7 @@ mod tests {
                 },
                 ..Default::default()
             },
+            return_values: None,
             write_args: write::common::WriteArgs {
                 table_name: "c".to_string(),
                 ..Default::default()
@@ -138,6 +222,9 @@ mod tests {
                     }
                 ),
             },
+            return_values: Some(
+                DeleteReturnValues::AllOld
+            ),
             write_args: write::common::WriteArgs {
                 condition: Some(
                     common::condition::ConditionMap::Leaves(
@@ -154,18 +241,18 @@ mod tests {
                         ]
                     )
                 ),
+                placeholder_naming: None,
                 return_consumed_capacity: Some(
                     types::ReturnConsumedCapacity::Total
                 ),
                 return_item_collection_metrics: Some(
                     types::ReturnItemCollectionMetrics::Size
                 ),
-                return_values: Some(
-                    types::ReturnValue::AllOld
-                ),
+                return_selection: None,
                 return_values_on_condition_check_failure: Some(
                     types::ReturnValuesOnConditionCheckFailure::AllOld
                 ),
+                retry_policy: None,
                 table_name: "g".to_string(),
             },
         },
@@ -229,4 +316,26 @@ mod tests {
         let actual: DeleteItemInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case::all_old(DeleteReturnValues::AllOld, types::ReturnValue::AllOld)]
+    #[case::none(DeleteReturnValues::None, types::ReturnValue::None)]
+    fn test_delete_item_return_values(
+        #[case] return_values: DeleteReturnValues,
+        #[case] expected: types::ReturnValue,
+    ) {
+        let delete_item = DeleteItem {
+            keys: common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::from("b"),
+                },
+                ..Default::default()
+            },
+            return_values: None,
+            write_args: write::common::WriteArgs::default(),
+        };
+        let actual: DeleteItemInput = delete_item.return_values(return_values).try_into().unwrap();
+        assert_eq!(actual.write_operation.return_values, Some(expected));
+    }
 }
@@ -0,0 +1,362 @@
+use crate::{common, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{collections, time};
+
+/// Outcome of recording an idempotency key.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum IdempotencyOutcome<D> {
+    /// The key was recorded, the request must be processed and its response stored.
+    Started,
+    /// Another request with the same key is being processed.
+    InProgress,
+    /// A request with the same key was already processed, with the given response.
+    AlreadyProcessed(D),
+}
+
+/// Store of idempotency keys and the responses of the requests they identify.
+///
+/// A key is recorded with a conditional put before processing its request, so only one of
+/// the concurrent requests sharing the key is processed, and the response is stored once
+/// the request completes, so duplicates get the cached response back. Records expire after
+/// the time to live, in progress ones included, so a request whose processing crashed can
+/// be retried. Enable the time to live of the table on the expiry attribute, so expired
+/// records are eventually deleted.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::write;
+/// use serde_json::{Value, json};
+/// use std::time::Duration;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let store = write::idempotency::IdempotencyStore {
+///     expires_at_name: "expiresAt".to_string(),
+///     key_name: "id".to_string(),
+///     response_name: "response".to_string(),
+///     table_name: "idempotency".to_string(),
+///     ttl: Duration::from_secs(24 * 60 * 60),
+/// };
+/// let response = match store.start::<Value>(client, "request-1").await? {
+///     write::idempotency::IdempotencyOutcome::Started => {
+///         let response = json!({"orderId": "1"});
+///         store.complete(client, "request-1", &response).await?;
+///         response
+///     }
+///     write::idempotency::IdempotencyOutcome::InProgress => json!({"status": "pending"}),
+///     write::idempotency::IdempotencyOutcome::AlreadyProcessed(response) => response,
+/// };
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IdempotencyStore {
+    /// The name of the attribute storing the record expiry, in epoch seconds.
+    pub expires_at_name: String,
+    /// The partition key name of the idempotency table.
+    pub key_name: String,
+    /// The name of the attribute storing the cached response.
+    pub response_name: String,
+    /// The name of the idempotency table.
+    pub table_name: String,
+    /// The duration after which a record expires and its key can be reused.
+    pub ttl: time::Duration,
+}
+
+fn get_now() -> u64 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl IdempotencyStore {
+    fn get_key(
+        &self,
+        idempotency_key: &str,
+    ) -> collections::HashMap<String, types::AttributeValue> {
+        collections::HashMap::from([(
+            self.key_name.clone(),
+            types::AttributeValue::S(idempotency_key.to_string()),
+        )])
+    }
+
+    fn get_item(
+        &self,
+        idempotency_key: &str,
+        now: u64,
+    ) -> collections::HashMap<String, types::AttributeValue> {
+        let expires_at = now + self.ttl.as_secs();
+        let mut item = self.get_key(idempotency_key);
+        item.insert(
+            self.expires_at_name.clone(),
+            types::AttributeValue::N(expires_at.to_string()),
+        );
+        item
+    }
+
+    fn get_start_input(&self, idempotency_key: &str, now: u64) -> write::put_item::PutItemInput {
+        let write_operation = write::common::WriteInput {
+            condition_expression: Some(
+                "attribute_not_exists(#key) OR #expires_at < :now".to_string(),
+            ),
+            expression_attribute_names: Some(collections::HashMap::from([
+                ("#expires_at".to_string(), self.expires_at_name.clone()),
+                ("#key".to_string(), self.key_name.clone()),
+            ])),
+            expression_attribute_values: Some(collections::HashMap::from([(
+                ":now".to_string(),
+                types::AttributeValue::N(now.to_string()),
+            )])),
+            return_values_on_condition_check_failure: Some(
+                types::ReturnValuesOnConditionCheckFailure::AllOld,
+            ),
+            table_name: self.table_name.clone(),
+            ..Default::default()
+        };
+        write::put_item::PutItemInput {
+            item: self.get_item(idempotency_key, now),
+            write_operation,
+        }
+    }
+
+    fn get_complete_input(
+        &self,
+        idempotency_key: &str,
+        response: types::AttributeValue,
+        now: u64,
+    ) -> write::put_item::PutItemInput {
+        let mut item = self.get_item(idempotency_key, now);
+        item.insert(self.response_name.clone(), response);
+        write::put_item::PutItemInput {
+            item,
+            write_operation: write::common::WriteInput {
+                table_name: self.table_name.clone(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn get_abandon_input(&self, idempotency_key: &str) -> write::delete_item::DeleteItemInput {
+        let write_operation = write::common::WriteInput {
+            condition_expression: Some("attribute_not_exists(#response)".to_string()),
+            expression_attribute_names: Some(collections::HashMap::from([(
+                "#response".to_string(),
+                self.response_name.clone(),
+            )])),
+            table_name: self.table_name.clone(),
+            ..Default::default()
+        };
+        write::delete_item::DeleteItemInput {
+            keys: self.get_key(idempotency_key),
+            write_operation,
+        }
+    }
+
+    /// Get the outcome of a key already recorded, from the record holding it.
+    fn get_outcome<D: DeserializeOwned>(
+        &self,
+        item: Option<&collections::HashMap<String, types::AttributeValue>>,
+    ) -> Result<IdempotencyOutcome<D>, crate::Error> {
+        let response = item.and_then(|item| item.get(&self.response_name));
+        match response {
+            Some(response) => serde_dynamo::from_attribute_value(response.clone())
+                .map(IdempotencyOutcome::AlreadyProcessed)
+                .map_err(crate::Error::Deserialization),
+            None => Ok(IdempotencyOutcome::InProgress),
+        }
+    }
+
+    /// Record the idempotency key before processing its request.
+    ///
+    /// Returns the cached response if a request with the same key was already processed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.idempotency.start", skip(client), err)
+    )]
+    pub async fn start<D: DeserializeOwned>(
+        &self,
+        client: &Client,
+        idempotency_key: &str,
+    ) -> Result<IdempotencyOutcome<D>, crate::Error> {
+        let put_item = self.get_start_input(idempotency_key, get_now());
+        let builder = client.put_item().set_item(Some(put_item.item));
+        match crate::apply_write_operation!(builder, put_item.write_operation)
+            .send()
+            .await
+            .map_err(crate::Error::from)
+        {
+            Ok(_) => Ok(IdempotencyOutcome::Started),
+            Err(crate::Error::ConditionalCheckFailed(item)) => self.get_outcome(item.as_ref()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Store the response of a processed request, returned to the following duplicates.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "dynamodb_crud.idempotency.complete",
+            skip(client, response),
+            err
+        )
+    )]
+    pub async fn complete<T: Serialize>(
+        &self,
+        client: &Client,
+        idempotency_key: &str,
+        response: T,
+    ) -> Result<(), crate::Error> {
+        let response = common::serializer::ValueSerializer::to_attribute_value(
+            &common::serializer::SerdeDynamo,
+            response,
+        )
+        .map_err(crate::Error::Serialization)?;
+        let put_item = self.get_complete_input(idempotency_key, response, get_now());
+        let builder = client.put_item().set_item(Some(put_item.item));
+        crate::apply_write_operation!(builder, put_item.write_operation)
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Forget a key whose request failed, so it can be retried before the record expires.
+    ///
+    /// Returns whether the key was forgotten, keys with a stored response are kept.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.idempotency.abandon", skip(client), err)
+    )]
+    pub async fn abandon(
+        &self,
+        client: &Client,
+        idempotency_key: &str,
+    ) -> Result<bool, crate::Error> {
+        let delete_item = self.get_abandon_input(idempotency_key);
+        let builder = client.delete_item().set_key(Some(delete_item.keys));
+        match crate::apply_write_operation!(builder, delete_item.write_operation)
+            .send()
+            .await
+            .map_err(crate::Error::from)
+        {
+            Ok(_) => Ok(true),
+            Err(crate::Error::ConditionalCheckFailed(_)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    fn get_store() -> IdempotencyStore {
+        IdempotencyStore {
+            expires_at_name: "a".to_string(),
+            key_name: "b".to_string(),
+            response_name: "c".to_string(),
+            table_name: "d".to_string(),
+            ttl: time::Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn test_get_start_input() {
+        let actual = get_store().get_start_input("e", 100);
+        let expected = write::put_item::PutItemInput {
+            item: collections::HashMap::from([
+                ("a".to_string(), types::AttributeValue::N("110".to_string())),
+                ("b".to_string(), types::AttributeValue::S("e".to_string())),
+            ]),
+            write_operation: write::common::WriteInput {
+                condition_expression: Some(
+                    "attribute_not_exists(#key) OR #expires_at < :now".to_string(),
+                ),
+                expression_attribute_names: Some(collections::HashMap::from([
+                    ("#expires_at".to_string(), "a".to_string()),
+                    ("#key".to_string(), "b".to_string()),
+                ])),
+                expression_attribute_values: Some(collections::HashMap::from([(
+                    ":now".to_string(),
+                    types::AttributeValue::N("100".to_string()),
+                )])),
+                return_values_on_condition_check_failure: Some(
+                    types::ReturnValuesOnConditionCheckFailure::AllOld,
+                ),
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_complete_input() {
+        let actual =
+            get_store().get_complete_input("e", types::AttributeValue::S("f".to_string()), 100);
+        let expected = write::put_item::PutItemInput {
+            item: collections::HashMap::from([
+                ("a".to_string(), types::AttributeValue::N("110".to_string())),
+                ("b".to_string(), types::AttributeValue::S("e".to_string())),
+                ("c".to_string(), types::AttributeValue::S("f".to_string())),
+            ]),
+            write_operation: write::common::WriteInput {
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::in_progress(
+        Some(
+            collections::HashMap::from(
+                [
+                    (
+                        "b".to_string(),
+                        types::AttributeValue::S(
+                            "e".to_string()
+                        )
+                    ),
+                ]
+            )
+        ),
+        IdempotencyOutcome::InProgress
+    )]
+    #[case::already_processed(
+        Some(
+            collections::HashMap::from(
+                [
+                    (
+                        "b".to_string(),
+                        types::AttributeValue::S(
+                            "e".to_string()
+                        )
+                    ),
+                    (
+                        "c".to_string(),
+                        types::AttributeValue::S(
+                            "f".to_string()
+                        )
+                    ),
+                ]
+            )
+        ),
+        IdempotencyOutcome::AlreadyProcessed(
+            json!("f")
+        )
+    )]
+    #[case::missing(None, IdempotencyOutcome::InProgress)]
+    fn test_get_outcome(
+        #[case] item: Option<collections::HashMap<String, types::AttributeValue>>,
+        #[case] expected: IdempotencyOutcome<Value>,
+    ) {
+        let actual = get_store().get_outcome(item.as_ref()).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,445 @@
+use crate::{common, write};
+
+use aws_sdk_dynamodb::{Client, operation};
+use serde::Serialize;
+use std::sync;
+
+/// Kind of write an invariant applies to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WriteKind {
+    /// Delete item requests.
+    Delete,
+    /// Put item requests.
+    Put,
+    /// Update item requests.
+    Update,
+}
+
+/// Condition every write of the given kinds to a table must satisfy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invariant<T> {
+    /// The condition, combined with `AND` with the condition of the write.
+    pub condition: common::condition::ConditionMap<T>,
+    /// The kinds of writes the condition applies to.
+    pub kinds: Vec<WriteKind>,
+    /// The name of the table the condition applies to.
+    pub table_name: String,
+}
+
+/// Registry of the invariant conditions of the tables, centralizing data-integrity rules.
+///
+/// Guarding a write combines the invariants of its table and kind with `AND` with its
+/// condition, so call sites don't have to repeat them. To make sure no call site skips them,
+/// send the writes through a [`GuardedClient`] instead of guarding them one by one.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, write};
+/// use serde_json::{Value, json};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let invariants = write::invariants::WriteInvariants::<Value>::default()
+///     .register(
+///         "users",
+///         [write::invariants::WriteKind::Update],
+///         common::condition::ConditionMap::attribute_exists("id"),
+///     )
+///     .register(
+///         "users",
+///         [
+///             write::invariants::WriteKind::Delete,
+///             write::invariants::WriteKind::Update,
+///         ],
+///         !common::condition::ConditionMap::equals("status", json!("locked")),
+///     );
+/// let update_item = write::update_item::UpdateItem::builder("users", "id", "1")
+///     .set("name", "John")
+///     .build()?;
+/// invariants.guard_update_item(update_item).send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WriteInvariants<T> {
+    /// The registered invariants, in registration order.
+    pub invariants: Vec<Invariant<T>>,
+}
+
+impl<T> Default for WriteInvariants<T> {
+    fn default() -> Self {
+        Self {
+            invariants: Vec::new(),
+        }
+    }
+}
+
+impl<T> WriteInvariants<T> {
+    /// Register a condition every write of the given kinds to the table must satisfy.
+    pub fn register(
+        mut self,
+        table_name: impl Into<String>,
+        kinds: impl IntoIterator<Item = WriteKind>,
+        condition: common::condition::ConditionMap<T>,
+    ) -> Self {
+        self.invariants.push(Invariant {
+            condition,
+            kinds: kinds.into_iter().collect(),
+            table_name: table_name.into(),
+        });
+        self
+    }
+}
+
+impl<T: Clone> WriteInvariants<T> {
+    /// Get the invariants of the writes of the given kind to the table, combined with `AND`.
+    pub fn get_condition(
+        &self,
+        table_name: &str,
+        kind: WriteKind,
+    ) -> Option<common::condition::ConditionMap<T>> {
+        self.invariants
+            .iter()
+            .filter(|invariant| {
+                invariant.table_name == table_name && invariant.kinds.contains(&kind)
+            })
+            .map(|invariant| invariant.condition.clone())
+            .reduce(common::condition::ConditionMap::and)
+    }
+
+    /// Combine the invariants of the write with its condition.
+    fn guard_write_args(
+        &self,
+        mut write_args: write::common::WriteArgs<T>,
+        kind: WriteKind,
+    ) -> write::common::WriteArgs<T> {
+        if let Some(condition) = self.get_condition(&write_args.table_name, kind) {
+            write_args.condition = Some(write::common::and_condition(
+                write_args.condition.take(),
+                condition,
+            ));
+        }
+        write_args
+    }
+
+    /// Combine the invariants of the delete item request with its condition.
+    pub fn guard_delete_item(
+        &self,
+        delete_item: write::delete_item::DeleteItem<T>,
+    ) -> write::delete_item::DeleteItem<T> {
+        write::delete_item::DeleteItem {
+            write_args: self.guard_write_args(delete_item.write_args, WriteKind::Delete),
+            ..delete_item
+        }
+    }
+
+    /// Combine the invariants of the put item request with its condition.
+    pub fn guard_put_item(
+        &self,
+        put_item: write::put_item::PutItem<T>,
+    ) -> write::put_item::PutItem<T> {
+        write::put_item::PutItem {
+            write_args: self.guard_write_args(put_item.write_args, WriteKind::Put),
+            ..put_item
+        }
+    }
+
+    /// Combine the invariants of the update item request with its condition.
+    pub fn guard_update_item(
+        &self,
+        update_item: write::update_item::UpdateItem<T>,
+    ) -> write::update_item::UpdateItem<T> {
+        write::update_item::UpdateItem {
+            write_args: self.guard_write_args(update_item.write_args, WriteKind::Update),
+            ..update_item
+        }
+    }
+
+    /// Combine the invariants of every write of the transaction with its condition.
+    ///
+    /// Condition checks are left unchanged.
+    pub fn guard_transact_write_items(
+        &self,
+        transact_write_items: write::transact_write_items::TransactWriteItems<T>,
+    ) -> write::transact_write_items::TransactWriteItems<T> {
+        let transact_items = transact_write_items
+            .transact_items
+            .into_iter()
+            .map(|transact_item| match transact_item {
+                write::transact_write_items::TransactWriteItem::Delete(delete_item) => {
+                    self.guard_delete_item(delete_item).into()
+                }
+                write::transact_write_items::TransactWriteItem::Put(put_item) => {
+                    self.guard_put_item(put_item).into()
+                }
+                write::transact_write_items::TransactWriteItem::Update(update_item) => {
+                    self.guard_update_item(update_item).into()
+                }
+                transact_item => transact_item,
+            })
+            .collect();
+        write::transact_write_items::TransactWriteItems {
+            transact_items,
+            ..transact_write_items
+        }
+    }
+}
+
+/// Client sending writes with the invariants always combined with their condition.
+///
+/// Every write goes through the registered invariants, and the wrapped client is not
+/// exposed, so a write path holding only the guarded client can't bypass them.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, write};
+/// use serde_json::{Value, json};
+///
+/// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let invariants = write::invariants::WriteInvariants::<Value>::default().register(
+///     "users",
+///     [write::invariants::WriteKind::Delete],
+///     !common::condition::ConditionMap::equals("status", json!("locked")),
+/// );
+/// let guarded_client = write::invariants::GuardedClient::new(client, invariants);
+/// let delete_item = write::delete_item::DeleteItem {
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: json!("1"),
+///         },
+///         ..Default::default()
+///     },
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// guarded_client.delete_item(delete_item).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GuardedClient<T> {
+    client: Client,
+    invariants: sync::Arc<WriteInvariants<T>>,
+}
+
+impl<T> Clone for GuardedClient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            invariants: self.invariants.clone(),
+        }
+    }
+}
+
+impl<T> GuardedClient<T> {
+    /// Create a guarded client sending its writes with the given client.
+    pub fn new(client: Client, invariants: WriteInvariants<T>) -> Self {
+        Self {
+            client,
+            invariants: sync::Arc::new(invariants),
+        }
+    }
+
+    /// The invariants combined with every write.
+    pub fn invariants(&self) -> &WriteInvariants<T> {
+        &self.invariants
+    }
+}
+
+impl<T: Clone + Serialize> GuardedClient<T> {
+    /// Execute the delete item operation, guarded by the invariants.
+    pub async fn delete_item(
+        &self,
+        delete_item: write::delete_item::DeleteItem<T>,
+    ) -> Result<operation::delete_item::DeleteItemOutput, crate::Error> {
+        self.invariants
+            .guard_delete_item(delete_item)
+            .send(&self.client)
+            .await
+    }
+
+    /// Execute the put item operation, guarded by the invariants.
+    pub async fn put_item(
+        &self,
+        put_item: write::put_item::PutItem<T>,
+    ) -> Result<operation::put_item::PutItemOutput, crate::Error> {
+        self.invariants
+            .guard_put_item(put_item)
+            .send(&self.client)
+            .await
+    }
+
+    /// Execute the update item operation, guarded by the invariants.
+    pub async fn update_item(
+        &self,
+        update_item: write::update_item::UpdateItem<T>,
+    ) -> Result<operation::update_item::UpdateItemOutput, crate::Error> {
+        self.invariants
+            .guard_update_item(update_item)
+            .send(&self.client)
+            .await
+    }
+
+    /// Execute the transact write items operation, every write guarded by the invariants.
+    pub async fn transact_write_items(
+        &self,
+        transact_write_items: write::transact_write_items::TransactWriteItems<T>,
+    ) -> Result<operation::transact_write_items::TransactWriteItemsOutput, crate::Error> {
+        self.invariants
+            .guard_transact_write_items(transact_write_items)
+            .send(&self.client)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aws_sdk_dynamodb::config;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    /// Interceptor capturing the condition expressions of the writes instead of sending them.
+    #[derive(Debug, Default)]
+    struct ConditionCapture(sync::Arc<sync::Mutex<Vec<Option<String>>>>);
+
+    impl config::Intercept for ConditionCapture {
+        fn name(&self) -> &'static str {
+            "condition capture"
+        }
+
+        fn read_before_execution(
+            &self,
+            context: &config::interceptors::BeforeSerializationInterceptorContextRef<'_>,
+            _: &mut config::ConfigBag,
+        ) -> Result<(), BoxError> {
+            let input = context.input();
+            let mut conditions = self.0.lock().unwrap();
+            if let Some(input) = input.downcast_ref::<operation::put_item::PutItemInput>() {
+                conditions.push(input.condition_expression.clone());
+            }
+            if let Some(input) = input.downcast_ref::<operation::update_item::UpdateItemInput>() {
+                conditions.push(input.condition_expression.clone());
+            }
+            if let Some(input) =
+                input.downcast_ref::<operation::transact_write_items::TransactWriteItemsInput>()
+            {
+                for transact_item in input.transact_items() {
+                    let condition = match transact_item {
+                        aws_sdk_dynamodb::types::TransactWriteItem { put: Some(put), .. } => {
+                            put.condition_expression.clone()
+                        }
+                        aws_sdk_dynamodb::types::TransactWriteItem {
+                            update: Some(update),
+                            ..
+                        } => update.condition_expression.clone(),
+                        _ => None,
+                    };
+                    conditions.push(condition);
+                }
+            }
+            Err("captured".into())
+        }
+    }
+
+    fn get_invariants() -> WriteInvariants<Value> {
+        WriteInvariants::default()
+            .register(
+                "a",
+                [WriteKind::Update],
+                common::condition::ConditionMap::attribute_exists("b"),
+            )
+            .register(
+                "a",
+                [WriteKind::Put, WriteKind::Update],
+                common::condition::ConditionMap::equals("c", json!("d")),
+            )
+    }
+
+    #[rstest]
+    #[case::update(
+        "a",
+        WriteKind::Update,
+        Some(
+            common::condition::ConditionMap::attribute_exists("b")
+                .and(common::condition::ConditionMap::equals("c", json!("d")))
+        )
+    )]
+    #[case::put("a", WriteKind::Put, Some(common::condition::ConditionMap::equals("c", json!("d"))))]
+    #[case::delete("a", WriteKind::Delete, None)]
+    #[case::other_table("e", WriteKind::Update, None)]
+    fn test_get_condition(
+        #[case] table_name: &str,
+        #[case] kind: WriteKind,
+        #[case] expected: Option<common::condition::ConditionMap<Value>>,
+    ) {
+        assert_eq!(get_invariants().get_condition(table_name, kind), expected);
+    }
+
+    #[test]
+    fn test_guard_put_item() {
+        let put_item = write::put_item::PutItem {
+            item: json!({"b": "f"}),
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                condition: Some(common::condition::ConditionMap::attribute_missing("b")),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+        };
+        let actual = get_invariants().guard_put_item(put_item);
+        let expected = common::condition::ConditionMap::attribute_missing("b")
+            .and(common::condition::ConditionMap::equals("c", json!("d")));
+        assert_eq!(actual.write_args.condition.as_ref(), Some(&expected));
+    }
+
+    #[test]
+    fn test_guarded_client() {
+        let conditions = sync::Arc::default();
+        let client = common::get_test_client(
+            config::Builder::new().interceptor(ConditionCapture(sync::Arc::clone(&conditions))),
+        );
+        let guarded_client = GuardedClient::new(client, get_invariants());
+        let put_item = write::put_item::PutItem {
+            item: json!({"b": "f"}),
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+        };
+        let update_item = write::update_item::UpdateItem::builder("a", "b", "f")
+            .set("g", "h")
+            .build()
+            .unwrap();
+        let transact_write_items = write::transact_write_items::TransactWriteItems {
+            transact_items: vec![put_item.clone().into(), update_item.clone().into()],
+            ..Default::default()
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            assert!(guarded_client.put_item(put_item).await.is_err());
+            assert!(guarded_client.update_item(update_item).await.is_err());
+            let transacted = guarded_client.transact_write_items(transact_write_items);
+            assert!(transacted.await.is_err());
+        });
+        let actual = conditions.lock().unwrap().clone();
+        let put_condition = Some("#c = :c_eq0".to_string());
+        let update_condition = Some("attribute_exists(#b) AND #c = :c_eq0".to_string());
+        let expected = vec![
+            put_condition.clone(),
+            update_condition.clone(),
+            put_condition,
+            update_condition,
+        ];
+        assert_eq!(actual, expected);
+    }
+}
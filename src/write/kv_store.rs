@@ -0,0 +1,212 @@
+use crate::common;
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{collections, time};
+
+/// Key-value store backed by a table, such as a cache or a session store.
+///
+/// Every entry is an item holding its key, its value and, if the store has a time to live,
+/// its expiry. Expired entries are never returned, even before DynamoDB deletes them: enable
+/// the time to live of the table on the expiry attribute, so they are eventually deleted.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::write;
+/// use serde_json::{Value, json};
+/// use std::time::Duration;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let sessions = write::kv_store::KvStore {
+///     expires_at_name: "expiresAt".to_string(),
+///     key_name: "id".to_string(),
+///     namespace: Some("session".to_string()),
+///     table_name: "cache".to_string(),
+///     ttl: Some(Duration::from_secs(30 * 60)),
+///     value_name: "value".to_string(),
+/// };
+/// sessions.set(client, "token-1", json!({"userId": "1"})).await?;
+/// let session: Option<Value> = sessions.get(client, "token-1").await?;
+/// sessions.delete(client, "token-1").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct KvStore {
+    /// The name of the attribute storing the entry expiry, in epoch seconds.
+    pub expires_at_name: String,
+    /// The partition key name of the table.
+    pub key_name: String,
+    /// Prefix of the keys, separated by `#`, so several stores can share a table.
+    pub namespace: Option<String>,
+    /// The name of the table.
+    pub table_name: String,
+    /// The duration after which an entry expires.
+    ///
+    /// If `None`, entries never expire.
+    pub ttl: Option<time::Duration>,
+    /// The name of the attribute storing the value.
+    pub value_name: String,
+}
+
+fn get_now() -> u64 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl KvStore {
+    fn get_key(&self, key: &str) -> collections::HashMap<String, types::AttributeValue> {
+        let key = match &self.namespace {
+            Some(namespace) => format!("{namespace}#{key}"),
+            None => key.to_string(),
+        };
+        collections::HashMap::from([(self.key_name.clone(), types::AttributeValue::S(key))])
+    }
+
+    fn get_item(
+        &self,
+        key: &str,
+        value: types::AttributeValue,
+        now: u64,
+    ) -> collections::HashMap<String, types::AttributeValue> {
+        let mut item = self.get_key(key);
+        item.insert(self.value_name.clone(), value);
+        if let Some(ttl) = self.ttl {
+            let expires_at = now + ttl.as_secs();
+            item.insert(
+                self.expires_at_name.clone(),
+                types::AttributeValue::N(expires_at.to_string()),
+            );
+        }
+        item
+    }
+
+    /// Get the value of an item, unless the entry expired.
+    fn get_value(
+        &self,
+        item: collections::HashMap<String, types::AttributeValue>,
+        now: u64,
+    ) -> Option<types::AttributeValue> {
+        let expired = item
+            .get(&self.expires_at_name)
+            .and_then(|expires_at| expires_at.as_n().ok())
+            .and_then(|expires_at| expires_at.parse::<u64>().ok())
+            .is_some_and(|expires_at| expires_at < now);
+        if expired {
+            return None;
+        }
+        let mut item = item;
+        item.remove(&self.value_name)
+    }
+
+    /// Get the value of an entry, or `None` if it is missing or expired.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.kv_store.get", skip(client), err)
+    )]
+    pub async fn get<D: DeserializeOwned>(
+        &self,
+        client: &Client,
+        key: &str,
+    ) -> Result<Option<D>, crate::Error> {
+        let output = client
+            .get_item()
+            .table_name(&self.table_name)
+            .set_key(Some(self.get_key(key)))
+            .send()
+            .await?;
+        output
+            .item
+            .and_then(|item| self.get_value(item, get_now()))
+            .map(serde_dynamo::from_attribute_value)
+            .transpose()
+            .map_err(crate::Error::Deserialization)
+    }
+
+    /// Set the value of an entry, renewing its expiry.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.kv_store.set", skip(client, value), err)
+    )]
+    pub async fn set<T: Serialize>(
+        &self,
+        client: &Client,
+        key: &str,
+        value: T,
+    ) -> Result<(), crate::Error> {
+        let value = common::serializer::ValueSerializer::to_attribute_value(
+            &common::serializer::SerdeDynamo,
+            value,
+        )
+        .map_err(crate::Error::Serialization)?;
+        client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(self.get_item(key, value, get_now())))
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Delete an entry, if any.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.kv_store.delete", skip(client), err)
+    )]
+    pub async fn delete(&self, client: &Client, key: &str) -> Result<(), crate::Error> {
+        client
+            .delete_item()
+            .table_name(&self.table_name)
+            .set_key(Some(self.get_key(key)))
+            .send()
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn get_store(namespace: Option<&str>) -> KvStore {
+        KvStore {
+            expires_at_name: "a".to_string(),
+            key_name: "b".to_string(),
+            namespace: namespace.map(str::to_string),
+            table_name: "c".to_string(),
+            ttl: Some(time::Duration::from_secs(10)),
+            value_name: "d".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::plain(None, "e")]
+    #[case::namespaced(Some("f"), "f#e")]
+    fn test_get_item(#[case] namespace: Option<&str>, #[case] expected_key: &str) {
+        let actual =
+            get_store(namespace).get_item("e", types::AttributeValue::S("g".to_string()), 100);
+        let expected = collections::HashMap::from([
+            ("a".to_string(), types::AttributeValue::N("110".to_string())),
+            (
+                "b".to_string(),
+                types::AttributeValue::S(expected_key.to_string()),
+            ),
+            ("d".to_string(), types::AttributeValue::S("g".to_string())),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::live(100, Some(types::AttributeValue::S("g".to_string())))]
+    #[case::expired(111, None)]
+    fn test_get_value(#[case] now: u64, #[case] expected: Option<types::AttributeValue>) {
+        let store = get_store(None);
+        let item = store.get_item("e", types::AttributeValue::S("g".to_string()), 100);
+        let actual = store.get_value(item, now);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::write;
+
+use aws_sdk_dynamodb::{Client, types};
+use std::{collections, time};
+
+/// Lease lock on a resource, stored as an item of a lock table.
+///
+/// The lock is acquired if no one holds it, if its lease expired, or if the owner already
+/// holds it, in which case the lease is renewed. Enable the time to live of the table on the
+/// expiry attribute, so the items of abandoned locks are eventually deleted.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::write;
+/// use std::time::Duration;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let lock = write::lock::Lock {
+///     expires_at_name: "expiresAt".to_string(),
+///     key_name: "id".to_string(),
+///     owner: "worker-1".to_string(),
+///     owner_name: "owner".to_string(),
+///     resource_id: "nightly-report".to_string(),
+///     table_name: "locks".to_string(),
+///     ttl: Duration::from_secs(60),
+/// };
+/// if lock.acquire(client).await? {
+///     // run the singleton job
+///     lock.release(client).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Lock {
+    /// The name of the attribute storing the lease expiry, in epoch seconds.
+    pub expires_at_name: String,
+    /// The partition key name of the lock table.
+    pub key_name: String,
+    /// The identifier of the lock holder, so only it can renew or release the lock.
+    pub owner: String,
+    /// The name of the attribute storing the lock holder.
+    pub owner_name: String,
+    /// The identifier of the locked resource, the partition key value of the lock item.
+    pub resource_id: String,
+    /// The name of the lock table.
+    pub table_name: String,
+    /// The duration of the lease, after which the lock can be acquired by someone else.
+    pub ttl: time::Duration,
+}
+
+impl Lock {
+    fn get_key(&self) -> collections::HashMap<String, types::AttributeValue> {
+        collections::HashMap::from([(
+            self.key_name.clone(),
+            types::AttributeValue::S(self.resource_id.clone()),
+        )])
+    }
+
+    fn get_acquire_input(&self, now: u64) -> write::put_item::PutItemInput {
+        let expires_at = now + self.ttl.as_secs();
+        let mut item = self.get_key();
+        item.insert(
+            self.expires_at_name.clone(),
+            types::AttributeValue::N(expires_at.to_string()),
+        );
+        item.insert(
+            self.owner_name.clone(),
+            types::AttributeValue::S(self.owner.clone()),
+        );
+        let write_operation = write::common::WriteInput {
+            condition_expression: Some(
+                "attribute_not_exists(#key) OR #expires_at < :now OR #owner = :owner".to_string(),
+            ),
+            expression_attribute_names: Some(collections::HashMap::from([
+                ("#expires_at".to_string(), self.expires_at_name.clone()),
+                ("#key".to_string(), self.key_name.clone()),
+                ("#owner".to_string(), self.owner_name.clone()),
+            ])),
+            expression_attribute_values: Some(collections::HashMap::from([
+                (
+                    ":now".to_string(),
+                    types::AttributeValue::N(now.to_string()),
+                ),
+                (
+                    ":owner".to_string(),
+                    types::AttributeValue::S(self.owner.clone()),
+                ),
+            ])),
+            table_name: self.table_name.clone(),
+            ..Default::default()
+        };
+        write::put_item::PutItemInput {
+            item,
+            write_operation,
+        }
+    }
+
+    fn get_release_input(&self) -> write::delete_item::DeleteItemInput {
+        let write_operation = write::common::WriteInput {
+            condition_expression: Some("#owner = :owner".to_string()),
+            expression_attribute_names: Some(collections::HashMap::from([(
+                "#owner".to_string(),
+                self.owner_name.clone(),
+            )])),
+            expression_attribute_values: Some(collections::HashMap::from([(
+                ":owner".to_string(),
+                types::AttributeValue::S(self.owner.clone()),
+            )])),
+            table_name: self.table_name.clone(),
+            ..Default::default()
+        };
+        write::delete_item::DeleteItemInput {
+            keys: self.get_key(),
+            write_operation,
+        }
+    }
+
+    /// Acquire or renew the lock, returning whether it is now held by the owner.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.lock.acquire", skip(client), err)
+    )]
+    pub async fn acquire(&self, client: &Client) -> Result<bool, crate::Error> {
+        let now = time::SystemTime::now()
+            .duration_since(time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let put_item = self.get_acquire_input(now);
+        let builder = client.put_item().set_item(Some(put_item.item));
+        match crate::apply_write_operation!(builder, put_item.write_operation)
+            .send()
+            .await
+            .map_err(crate::Error::from)
+        {
+            Ok(_) => Ok(true),
+            Err(crate::Error::ConditionalCheckFailed(_)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Release the lock, returning whether it was held by the owner.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.lock.release", skip(client), err)
+    )]
+    pub async fn release(&self, client: &Client) -> Result<bool, crate::Error> {
+        let delete_item = self.get_release_input();
+        let builder = client.delete_item().set_key(Some(delete_item.keys));
+        match crate::apply_write_operation!(builder, delete_item.write_operation)
+            .send()
+            .await
+            .map_err(crate::Error::from)
+        {
+            Ok(_) => Ok(true),
+            Err(crate::Error::ConditionalCheckFailed(_)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_lock() -> Lock {
+        Lock {
+            expires_at_name: "a".to_string(),
+            key_name: "b".to_string(),
+            owner: "c".to_string(),
+            owner_name: "d".to_string(),
+            resource_id: "e".to_string(),
+            table_name: "f".to_string(),
+            ttl: time::Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn test_get_acquire_input() {
+        let actual = get_lock().get_acquire_input(100);
+        let expected = write::put_item::PutItemInput {
+            item: collections::HashMap::from([
+                ("a".to_string(), types::AttributeValue::N("110".to_string())),
+                ("b".to_string(), types::AttributeValue::S("e".to_string())),
+                ("d".to_string(), types::AttributeValue::S("c".to_string())),
+            ]),
+            write_operation: write::common::WriteInput {
+                condition_expression: Some(
+                    "attribute_not_exists(#key) OR #expires_at < :now OR #owner = :owner"
+                        .to_string(),
+                ),
+                expression_attribute_names: Some(collections::HashMap::from([
+                    ("#expires_at".to_string(), "a".to_string()),
+                    ("#key".to_string(), "b".to_string()),
+                    ("#owner".to_string(), "d".to_string()),
+                ])),
+                expression_attribute_values: Some(collections::HashMap::from([
+                    (
+                        ":now".to_string(),
+                        types::AttributeValue::N("100".to_string()),
+                    ),
+                    (
+                        ":owner".to_string(),
+                        types::AttributeValue::S("c".to_string()),
+                    ),
+                ])),
+                table_name: "f".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_release_input() {
+        let actual = get_lock().get_release_input();
+        let expected = write::delete_item::DeleteItemInput {
+            keys: collections::HashMap::from([(
+                "b".to_string(),
+                types::AttributeValue::S("e".to_string()),
+            )]),
+            write_operation: write::common::WriteInput {
+                condition_expression: Some("#owner = :owner".to_string()),
+                expression_attribute_names: Some(collections::HashMap::from([(
+                    "#owner".to_string(),
+                    "d".to_string(),
+                )])),
+                expression_attribute_values: Some(collections::HashMap::from([(
+                    ":owner".to_string(),
+                    types::AttributeValue::S("c".to_string()),
+                )])),
+                table_name: "f".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,15 +1,33 @@
-use crate::write;
+use crate::{common, common::retry::WithRetryPolicy as _, write};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
+use aws_sdk_dynamodb::{Client, operation, types};
 use serde::Serialize;
-use serde_dynamo::{Error, Result, to_item};
+use serde_dynamo::{Error, Result};
 use std::collections;
 
 /// put item operation
 #[derive(Clone, Debug, Default, PartialEq)]
-struct PutItemInput {
-    item: collections::HashMap<String, types::AttributeValue>,
-    write_operation: write::common::WriteInput,
+pub(crate) struct PutItemInput {
+    pub(crate) item: collections::HashMap<String, types::AttributeValue>,
+    pub(crate) write_operation: write::common::WriteInput,
+}
+
+/// Item attributes returned by a put, restricted to the values DynamoDB accepts for it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PutReturnValues {
+    /// The whole item as it was before the put.
+    AllOld,
+    /// Nothing.
+    None,
+}
+
+impl From<PutReturnValues> for types::ReturnValue {
+    fn from(return_values: PutReturnValues) -> Self {
+        match return_values {
+            PutReturnValues::AllOld => Self::AllOld,
+            PutReturnValues::None => Self::None,
+        }
+    }
 }
 
 /// Put item operation.
@@ -22,12 +40,41 @@ struct PutItemInput {
 /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
 /// let put_item = write::put_item::PutItem {
 ///     item: json!({"id": "1", "name": "John"}),
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// put_item.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Items are only serialized, so a borrowed item can be put without cloning it:
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::write;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: String,
+///     name: String,
+/// }
+///
+/// # async fn example(client: &Client, user: &User) -> Result<(), Box<dyn std::error::Error>> {
+/// let put_item = write::put_item::PutItem {
+///     item: user,
+///     return_values: None,
 ///     write_args: write::common::WriteArgs {
 ///         table_name: "users".to_string(),
 ///         ..Default::default()
 ///     },
 /// };
 /// put_item.send(client).await?;
+/// println!("{}", user.name);
 /// # Ok(())
 /// # }
 /// ```
@@ -35,16 +82,23 @@ struct PutItemInput {
 pub struct PutItem<T> {
     /// The item to put into the table.
     pub item: T,
-    /// Additional write operation arguments (table name, condition, return values, etc.).
+    /// Which item attributes to return in the response.
+    pub return_values: Option<PutReturnValues>,
+    /// Additional write operation arguments (table name, condition, etc.).
     pub write_args: write::common::WriteArgs<T>,
 }
 
-impl<T: Serialize> TryFrom<PutItem<T>> for PutItemInput {
-    type Error = Error;
-
-    fn try_from(put_item: PutItem<T>) -> Result<Self> {
-        let item = to_item(put_item.item)?;
-        let write_operation: write::common::WriteInput = put_item.write_args.try_into()?;
+impl PutItemInput {
+    pub(crate) fn new<T: Serialize>(
+        put_item: PutItem<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let item = serializer.to_item(put_item.item)?;
+        let placeholders =
+            &mut common::PlaceholderAllocator::new(put_item.write_args.placeholder_naming.clone());
+        let mut write_operation =
+            write::common::WriteInput::new(put_item.write_args, placeholders, serializer)?;
+        write_operation.return_values = put_item.return_values.map(Into::into);
         let operation = Self {
             item,
             write_operation,
@@ -53,31 +107,124 @@ impl<T: Serialize> TryFrom<PutItem<T>> for PutItemInput {
     }
 }
 
+impl<T: Serialize> TryFrom<PutItem<T>> for PutItemInput {
+    type Error = Error;
+
+    fn try_from(put_item: PutItem<T>) -> Result<Self> {
+        Self::new(put_item, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T> PutItem<T> {
+    /// Which item attributes to return in the response.
+    pub fn return_values(mut self, return_values: PutReturnValues) -> Self {
+        self.return_values = Some(return_values);
+        self
+    }
+}
+
+impl<T: Clone> PutItem<T> {
+    /// Only create the item, failing with a conditional check error instead of overwriting
+    /// an existing item with the same key.
+    ///
+    /// Attaches an `attribute_not_exists` condition on the partition key, combined with `AND`
+    /// with the condition already set, if any.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{Error, write};
+    /// use serde_json::json;
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let put_item = write::put_item::PutItem {
+    ///     item: json!({"id": "1", "name": "John"}),
+    ///     return_values: None,
+    ///     write_args: write::common::WriteArgs {
+    ///         table_name: "users".to_string(),
+    ///         ..Default::default()
+    ///     },
+    /// }
+    /// .if_not_exists("id");
+    /// match put_item.send(client).await {
+    ///     Err(Error::ConditionalCheckFailed(_)) => println!("user already exists"),
+    ///     output => {
+    ///         output?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn if_not_exists(mut self, partition_key_name: impl Into<String>) -> Self {
+        let condition = common::condition::ConditionMap::attribute_missing(partition_key_name);
+        self.write_args.condition = Some(write::common::and_condition(
+            self.write_args.condition.take(),
+            condition,
+        ));
+        self
+    }
+}
+
 impl<T: Serialize> PutItem<T> {
     /// Execute the put item operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::put_item::PutItemOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the put item operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.put_item", err)
+        tracing::instrument(name = "dynamodb_crud.put_item", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<
-        operation::put_item::PutItemOutput,
-        error::SdkError<operation::put_item::PutItemError>,
-    > {
-        let put_item: PutItemInput = self.try_into().map_err(error::BuildError::other)?;
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::put_item::PutItemOutput, crate::Error> {
+        let return_selection = self.write_args.return_selection.clone();
+        let retry_policy = self.write_args.retry_policy.clone();
+        let put_item = PutItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
         let builder = client.put_item().set_item(Some(put_item.item));
-        crate::apply_write_operation!(builder, put_item.write_operation)
+        let mut output = crate::apply_write_operation!(builder, put_item.write_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
             .send()
             .await
+            .map_err(|error| {
+                write::common::get_write_error(error.into(), return_selection.as_ref())
+            })?;
+        output.attributes =
+            write::common::select_returned_attributes(output.attributes, return_selection.as_ref());
+        Ok(output)
+    }
+
+    /// Build the put item request without sending it, such as to log or snapshot-test the
+    /// generated expressions.
+    pub fn dry_run(self) -> Result<operation::put_item::PutItemInput, crate::Error> {
+        self.dry_run_with(&common::serializer::SerdeDynamo)
+    }
+
+    /// Build the put item request without sending it, converting the values with the given
+    /// serializer.
+    pub fn dry_run_with(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::put_item::PutItemInput, crate::Error> {
+        let put_item = PutItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
+        let builder = operation::put_item::PutItemInput::builder().set_item(Some(put_item.item));
+        let input = crate::apply_write_operation!(builder, put_item.write_operation)
+            .build()
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(input)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common;
 
     use rstest::rstest;
     use serde_json::{Value, json};
@@ -90,6 +237,7 @@ mod tests {
                     "a": "b"
                 }
             ),
+            return_values: None,
             write_args: write::common::WriteArgs {
                 table_name: "c".to_string(),
                 ..Default::default()
@@ -117,6 +265,9 @@ mod tests {
                     "a": "b"
                 }
             ),
+            return_values: Some(
+                PutReturnValues::AllOld
+            ),
             write_args: write::common::WriteArgs {
                 condition: Some(
                     common::condition::ConditionMap::Leaves(
@@ -133,18 +284,18 @@ mod tests {
                         ]
                     )
                 ),
+                placeholder_naming: None,
                 return_consumed_capacity: Some(
                     types::ReturnConsumedCapacity::Total
                 ),
                 return_item_collection_metrics: Some(
                     types::ReturnItemCollectionMetrics::Size
                 ),
-                return_values: Some(
-                    types::ReturnValue::AllOld
-                ),
+                return_selection: None,
                 return_values_on_condition_check_failure: Some(
                     types::ReturnValuesOnConditionCheckFailure::AllOld
                 ),
+                retry_policy: None,
                 table_name: "e".to_string(),
             },
         },
@@ -200,4 +351,90 @@ mod tests {
         let actual: PutItemInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_put_item_borrowed() {
+        let item = json!({"a": "b"});
+        let put_item = PutItem {
+            item: &item,
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                table_name: "c".to_string(),
+                ..Default::default()
+            },
+        };
+        let actual: PutItemInput = put_item.try_into().unwrap();
+        let expected: PutItemInput = PutItem {
+            item: item.clone(),
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                table_name: "c".to_string(),
+                ..Default::default()
+            },
+        }
+        .try_into()
+        .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::no_condition(None, "attribute_not_exists(#a)")]
+    #[case::condition(
+        Some(common::condition::ConditionMap::equals("b", json!("c"))),
+        "#b = :b_eq0 AND attribute_not_exists(#a)"
+    )]
+    fn test_put_item_if_not_exists(
+        #[case] condition: Option<common::condition::ConditionMap<Value>>,
+        #[case] expected: &str,
+    ) {
+        let put_item = PutItem {
+            item: json!({"a": "d"}),
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                condition,
+                table_name: "e".to_string(),
+                ..Default::default()
+            },
+        };
+        let actual: PutItemInput = put_item.if_not_exists("a").try_into().unwrap();
+        assert_eq!(
+            actual.write_operation.condition_expression.as_deref(),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_put_item_compact_placeholders() {
+        let put_item = PutItem {
+            item: json!({"a": "b"}),
+            return_values: None,
+            write_args: write::common::WriteArgs {
+                condition: Some(common::condition::ConditionMap::equals("c", json!("d"))),
+                placeholder_naming: Some(common::PlaceholderNaming::Compact),
+                table_name: "e".to_string(),
+                ..Default::default()
+            },
+        };
+        let actual: PutItemInput = put_item.try_into().unwrap();
+        assert_eq!(
+            actual.write_operation.condition_expression.as_deref(),
+            Some("#n0 = :v0")
+        );
+    }
+
+    #[rstest]
+    #[case::all_old(PutReturnValues::AllOld, types::ReturnValue::AllOld)]
+    #[case::none(PutReturnValues::None, types::ReturnValue::None)]
+    fn test_put_item_return_values(
+        #[case] return_values: PutReturnValues,
+        #[case] expected: types::ReturnValue,
+    ) {
+        let put_item = PutItem {
+            item: json!({"a": "b"}),
+            return_values: None,
+            write_args: write::common::WriteArgs::default(),
+        };
+        let actual: PutItemInput = put_item.return_values(return_values).try_into().unwrap();
+        assert_eq!(actual.write_operation.return_values, Some(expected));
+    }
 }
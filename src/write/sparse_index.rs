@@ -0,0 +1,141 @@
+use crate::{common, read, write};
+
+/// Sparse global secondary index, keyed by a flag attribute set only on the flagged items.
+///
+/// Items without the flag attribute are left out of the index, so querying it only reads
+/// the flagged items. Since index keys can't be booleans, boolean flags are usually stored
+/// as a constant string.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, write};
+/// use serde_json::{Value, json};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let sparse_index = write::sparse_index::SparseIndex {
+///     attribute_name: "pendingReview".to_string(),
+///     index_name: "pending-review-index".to_string(),
+///     table_name: "orders".to_string(),
+/// };
+/// let update_item = write::update_item::UpdateItem {
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: json!("1"),
+///         },
+///         ..Default::default()
+///     },
+///     return_values: None,
+///     update_expression: sparse_index.set(json!("true")),
+///     write_args: write::common::WriteArgs {
+///         table_name: sparse_index.table_name.clone(),
+///         ..Default::default()
+///     },
+/// };
+/// update_item.send(client).await?;
+/// let pending = sparse_index.query(json!("true")).send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SparseIndex {
+    /// The name of the flag attribute, the partition key of the index.
+    pub attribute_name: String,
+    /// The name of the index.
+    pub index_name: String,
+    /// The name of the indexed table.
+    pub table_name: String,
+}
+
+impl SparseIndex {
+    /// Update expression setting the flag, adding the item to the index.
+    pub fn set<T>(&self, value: T) -> write::update_item::UpdateExpressionMap<T> {
+        write::update_item::UpdateExpressionMap::Set(write::update_item::SetInputsMap::Leaves(
+            vec![(
+                self.attribute_name.clone(),
+                write::update_item::SetInput::Assign(value),
+            )],
+        ))
+    }
+
+    /// Update expression removing the flag, dropping the item from the index.
+    pub fn clear<T>(&self) -> write::update_item::UpdateExpressionMap<T> {
+        write::update_item::UpdateExpressionMap::Remove(common::selection::SelectionMap::Leaves(
+            vec![self.attribute_name.clone()],
+        ))
+    }
+
+    /// Query over the index for the items flagged with the given value.
+    pub fn query<T>(&self, value: T) -> read::query::Query<T> {
+        read::query::Query {
+            key_schema: None,
+            multiple_read_args: read::common::MultipleReadArgs {
+                condition: None,
+                consistent_read: None,
+                cursor: None,
+                deadline: None,
+                exclusive_start_key: None,
+                index_name: Some(self.index_name.clone()),
+                limit: None,
+                max_items: None,
+                max_pages: None,
+                placeholder_naming: None,
+                prune_empty_maps: false,
+                retry_policy: None,
+                select: None,
+                selection: None,
+                table_name: self.table_name.clone(),
+            },
+            partition_key: common::key::Key {
+                name: self.attribute_name.clone(),
+                value,
+            },
+            return_consumed_capacity: None,
+            scan_index_forward: None,
+            sort_key_condition: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{Value, json};
+
+    fn get_sparse_index() -> SparseIndex {
+        SparseIndex {
+            attribute_name: "a".to_string(),
+            index_name: "b".to_string(),
+            table_name: "c".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sparse_index_update_expressions() {
+        let sparse_index = get_sparse_index();
+        let actual: common::ExpressionInput = (&sparse_index.set(json!("d"))).try_into().unwrap();
+        assert_eq!(actual.expression, "SET #a = :set0");
+        let actual: common::ExpressionInput = (&sparse_index.clear::<Value>()).try_into().unwrap();
+        assert_eq!(actual.expression, "REMOVE #a");
+    }
+
+    #[test]
+    fn test_sparse_index_query() {
+        let actual = get_sparse_index().query(json!("d"));
+        let expected = read::query::Query {
+            key_schema: None,
+            multiple_read_args: read::common::MultipleReadArgs {
+                index_name: Some("b".to_string()),
+                table_name: "c".to_string(),
+                ..Default::default()
+            },
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: json!("d"),
+            },
+            ..Default::default()
+        };
+        assert_eq!(actual, expected);
+    }
+}
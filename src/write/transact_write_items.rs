@@ -0,0 +1,842 @@
+use crate::{common, common::retry::WithRetryPolicy as _, write};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_dynamo::{Error, Result};
+use std::{collections, hash};
+
+/// Maximum number of writes of a single transaction.
+const MAX_TRANSACT_ITEMS: usize = 100;
+
+/// Condition checked on an item within a transaction, without writing it.
+///
+/// ```rust
+/// use dynamodb_crud::{common, write};
+/// use serde_json::{Value, json};
+///
+/// let condition_check = write::transact_write_items::ConditionCheck {
+///     condition: common::condition::ConditionMap::Leaves(
+///         common::condition::LogicalOperator::And,
+///         vec![common::condition::KeyCondition {
+///             name: "status".to_string(),
+///             condition: common::condition::Condition::Equals(json!("active")),
+///         }],
+///     ),
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: json!("1"),
+///         },
+///         ..Default::default()
+///     },
+///     return_values_on_condition_check_failure: None,
+///     table_name: "accounts".to_string(),
+/// };
+/// let transact_item: write::transact_write_items::TransactWriteItem<Value> =
+///     condition_check.into();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionCheck<T> {
+    /// Condition expression that must be true for the transaction to succeed.
+    pub condition: common::condition::ConditionMap<T>,
+    /// The primary key of the item to check.
+    pub keys: common::key::Keys<T>,
+    /// Whether to return the item attributes if the condition check fails.
+    pub return_values_on_condition_check_failure:
+        Option<types::ReturnValuesOnConditionCheckFailure>,
+    /// The name of the table of the item to check.
+    pub table_name: String,
+}
+
+/// A single write within a transaction.
+///
+/// Built from the regular write operations, so an existing code path can be composed
+/// into a transaction as is:
+///
+/// ```rust
+/// use dynamodb_crud::write;
+/// use serde_json::{Value, json};
+///
+/// let put_item = write::put_item::PutItem {
+///     item: json!({"id": "1", "name": "John"}),
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// let transact_item: write::transact_write_items::TransactWriteItem<Value> = put_item.into();
+/// ```
+///
+/// Only the table name, the condition and `return_values_on_condition_check_failure` of the
+/// write arguments apply within a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactWriteItem<T> {
+    /// Condition check request - checks a condition on an item without writing it.
+    ConditionCheck(ConditionCheck<T>),
+    /// Delete item request - removes an item by its primary key.
+    Delete(write::delete_item::DeleteItem<T>),
+    /// Put item request - creates or replaces an item.
+    Put(write::put_item::PutItem<T>),
+    /// Update item request - modifies an existing item.
+    Update(write::update_item::UpdateItem<T>),
+}
+
+impl<T> From<ConditionCheck<T>> for TransactWriteItem<T> {
+    fn from(condition_check: ConditionCheck<T>) -> Self {
+        Self::ConditionCheck(condition_check)
+    }
+}
+
+impl<T> From<write::delete_item::DeleteItem<T>> for TransactWriteItem<T> {
+    fn from(delete_item: write::delete_item::DeleteItem<T>) -> Self {
+        Self::Delete(delete_item)
+    }
+}
+
+impl<T> From<write::put_item::PutItem<T>> for TransactWriteItem<T> {
+    fn from(put_item: write::put_item::PutItem<T>) -> Self {
+        Self::Put(put_item)
+    }
+}
+
+impl<T> From<write::update_item::UpdateItem<T>> for TransactWriteItem<T> {
+    fn from(update_item: write::update_item::UpdateItem<T>) -> Self {
+        Self::Update(update_item)
+    }
+}
+
+impl<T: Serialize> TransactWriteItem<T> {
+    /// Convert the request, converting the values with the given serializer.
+    pub(crate) fn serialize(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<types::TransactWriteItem> {
+        let builder = types::TransactWriteItem::builder();
+        let builder = match self {
+            Self::ConditionCheck(condition_check) => {
+                let keys = condition_check.keys.serialize(serializer)?;
+                let condition_operation = condition_check.condition.get_expression_operation(
+                    &mut common::PlaceholderAllocator::default(),
+                    serializer,
+                )?;
+                let condition_check = types::ConditionCheck::builder()
+                    .set_key(Some(keys))
+                    .condition_expression(condition_operation.expression)
+                    .set_expression_attribute_names(Some(
+                        condition_operation
+                            .expression_attribute_names
+                            .into_iter()
+                            .collect(),
+                    ))
+                    .set_expression_attribute_values(Some(
+                        condition_operation
+                            .expression_attribute_values
+                            .into_iter()
+                            .collect(),
+                    ))
+                    .set_return_values_on_condition_check_failure(
+                        condition_check.return_values_on_condition_check_failure,
+                    )
+                    .table_name(condition_check.table_name)
+                    .build()
+                    .unwrap();
+                builder.condition_check(condition_check)
+            }
+            Self::Delete(delete_item) => {
+                let delete_item =
+                    write::delete_item::DeleteItemInput::new(delete_item, serializer)?;
+                let write_operation = delete_item.write_operation;
+                let delete = types::Delete::builder()
+                    .set_key(Some(delete_item.keys))
+                    .set_condition_expression(write_operation.condition_expression)
+                    .set_expression_attribute_names(write_operation.expression_attribute_names)
+                    .set_expression_attribute_values(write_operation.expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        write_operation.return_values_on_condition_check_failure,
+                    )
+                    .table_name(write_operation.table_name)
+                    .build()
+                    .unwrap();
+                builder.delete(delete)
+            }
+            Self::Put(put_item) => {
+                let put_item = write::put_item::PutItemInput::new(put_item, serializer)?;
+                let write_operation = put_item.write_operation;
+                let put = types::Put::builder()
+                    .set_item(Some(put_item.item))
+                    .set_condition_expression(write_operation.condition_expression)
+                    .set_expression_attribute_names(write_operation.expression_attribute_names)
+                    .set_expression_attribute_values(write_operation.expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        write_operation.return_values_on_condition_check_failure,
+                    )
+                    .table_name(write_operation.table_name)
+                    .build()
+                    .unwrap();
+                builder.put(put)
+            }
+            Self::Update(update_item) => {
+                let update_item =
+                    write::update_item::UpdateItemInput::new(update_item, serializer)?;
+                let write_operation = update_item.write_operation;
+                let update = types::Update::builder()
+                    .set_key(Some(update_item.keys))
+                    .update_expression(update_item.update_expression)
+                    .set_condition_expression(write_operation.condition_expression)
+                    .set_expression_attribute_names(write_operation.expression_attribute_names)
+                    .set_expression_attribute_values(write_operation.expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        write_operation.return_values_on_condition_check_failure,
+                    )
+                    .table_name(write_operation.table_name)
+                    .build()
+                    .unwrap();
+                builder.update(update)
+            }
+        };
+        Ok(builder.build())
+    }
+}
+
+/// Transact write items operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, write};
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let put_item = write::put_item::PutItem {
+///     item: json!({"id": "1", "name": "John"}),
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// let delete_item = write::delete_item::DeleteItem {
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: json!("2"),
+///         },
+///         ..Default::default()
+///     },
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         table_name: "users".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// let transact_write_items = write::transact_write_items::TransactWriteItems {
+///     transact_items: vec![put_item.into(), delete_item.into()],
+///     ..Default::default()
+/// };
+/// transact_write_items.send(client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransactWriteItems<T> {
+    /// The idempotency token of the transaction.
+    pub client_request_token: Option<String>,
+    /// Whether to return the consumed capacity information.
+    pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    /// Whether to return item collection metrics.
+    pub return_item_collection_metrics: Option<types::ReturnItemCollectionMetrics>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The writes applied atomically (up to 100).
+    pub transact_items: Vec<TransactWriteItem<T>>,
+}
+
+/// Output of the chunked transact write items operation.
+#[derive(Debug, Default)]
+pub struct ChunkedTransactWriteItemsOutput {
+    /// The writes of the committed transactions and of the failed ones, one input per
+    /// transaction.
+    pub result: common::bulk::BulkResult<Vec<types::TransactWriteItem>, crate::Error>,
+    /// The writes of the transactions not sent because a previous one failed.
+    ///
+    /// Always empty with `continue_on_error`.
+    pub unsent: Vec<Vec<types::TransactWriteItem>>,
+}
+
+/// Chunked transact write items operation.
+///
+/// Splits a logical batch larger than a transaction into transactions of up to 100 writes,
+/// never splitting a group: the writes of a group are always applied atomically, while
+/// distinct groups may land in distinct transactions. Since a transaction can't write the
+/// same item twice, a group writing an item already written by the current transaction
+/// starts a new one. Transactions are sent in order, and the writes of the failed ones are
+/// reported instead of failing the whole batch.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::write;
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let groups = (0..500)
+///     .map(|index| {
+///         let put_item = |table_name: &str| write::put_item::PutItem {
+///             item: json!({"id": index.to_string()}),
+///             return_values: None,
+///             write_args: write::common::WriteArgs {
+///                 table_name: table_name.to_string(),
+///                 ..Default::default()
+///             },
+///         };
+///         vec![put_item("orders").into(), put_item("invoices").into()]
+///     })
+///     .collect();
+/// let chunked = write::transact_write_items::ChunkedTransactWriteItems {
+///     groups,
+///     ..Default::default()
+/// };
+/// let output = chunked.send(client).await?;
+/// if !output.result.is_success() {
+///     println!("{} transactions failed", output.result.failed.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChunkedTransactWriteItems<T> {
+    /// Whether to keep sending the following transactions after one fails, instead of
+    /// leaving them unsent.
+    pub continue_on_error: bool,
+    /// The groups of writes, each applied atomically within a single transaction (up to 100
+    /// writes each, on distinct items).
+    pub groups: Vec<Vec<TransactWriteItem<T>>>,
+    /// A map of table names to their primary key attribute names.
+    ///
+    /// Used to find the items written by puts. Tables without an entry use the key attribute
+    /// names of their first other write, and their puts are not checked for overlaps if they
+    /// have none.
+    pub key_names: IndexMap<String, Vec<String>>,
+    /// Whether to return the consumed capacity information.
+    pub return_consumed_capacity: Option<types::ReturnConsumedCapacity>,
+    /// Whether to return item collection metrics.
+    pub return_item_collection_metrics: Option<types::ReturnItemCollectionMetrics>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+}
+
+/// Pack consecutive groups into transactions of up to the maximum number of writes, keeping
+/// every group whole and the items of every transaction distinct.
+///
+/// Writes without a key are never considered overlapping.
+fn split_groups<I, K: Eq + hash::Hash>(
+    groups: Vec<Vec<I>>,
+    max_transact_items: usize,
+    get_key: impl Fn(&I) -> Option<K>,
+) -> std::result::Result<Vec<Vec<I>>, crate::Error> {
+    let mut transactions: Vec<Vec<I>> = Vec::new();
+    let mut transaction_keys = collections::HashSet::new();
+    for group in groups {
+        if group.len() > max_transact_items {
+            return Err(crate::Error::TransactionTooLarge(group.len()));
+        }
+        let mut group_keys = collections::HashSet::with_capacity(group.len());
+        for key in group.iter().filter_map(&get_key) {
+            if !group_keys.insert(key) {
+                return Err(crate::Error::validation(
+                    "a group of transaction writes writes the same item twice",
+                ));
+            }
+        }
+        match transactions.last_mut() {
+            Some(transaction)
+                if transaction.len() + group.len() <= max_transact_items
+                    && transaction_keys.is_disjoint(&group_keys) =>
+            {
+                transaction.extend(group);
+                transaction_keys.extend(group_keys);
+            }
+            _ if group.is_empty() => {}
+            _ => {
+                transactions.push(group);
+                transaction_keys = group_keys;
+            }
+        }
+    }
+    Ok(transactions)
+}
+
+/// Get the key attribute names of the tables of the writes, for the tables without an entry
+/// in the given key names.
+fn get_key_names(
+    groups: &[Vec<types::TransactWriteItem>],
+    mut key_names: IndexMap<String, Vec<String>>,
+) -> IndexMap<String, Vec<String>> {
+    for transact_item in groups.iter().flatten() {
+        let target = match transact_item {
+            types::TransactWriteItem {
+                condition_check: Some(condition_check),
+                ..
+            } => Some((&condition_check.table_name, &condition_check.key)),
+            types::TransactWriteItem {
+                delete: Some(delete),
+                ..
+            } => Some((&delete.table_name, &delete.key)),
+            types::TransactWriteItem {
+                update: Some(update),
+                ..
+            } => Some((&update.table_name, &update.key)),
+            _ => None,
+        };
+        if let Some((table_name, key)) = target {
+            key_names
+                .entry(table_name.clone())
+                .or_insert_with(|| key.keys().cloned().collect());
+        }
+    }
+    key_names
+}
+
+/// Get the table name and the encoded key of the item written by a write, or `None` for a
+/// put whose key attribute names are unknown.
+fn get_transact_item_key(
+    transact_item: &types::TransactWriteItem,
+    key_names: &IndexMap<String, Vec<String>>,
+) -> Option<(String, Vec<u8>)> {
+    if let Some(condition_check) = &transact_item.condition_check {
+        let key = common::key::get_key_bytes(&condition_check.key);
+        return Some((condition_check.table_name.clone(), key));
+    }
+    if let Some(delete) = &transact_item.delete {
+        return Some((
+            delete.table_name.clone(),
+            common::key::get_key_bytes(&delete.key),
+        ));
+    }
+    if let Some(update) = &transact_item.update {
+        return Some((
+            update.table_name.clone(),
+            common::key::get_key_bytes(&update.key),
+        ));
+    }
+    let put = transact_item.put.as_ref()?;
+    let key_names = key_names.get(&put.table_name)?;
+    let key: Vec<_> = put
+        .item
+        .iter()
+        .filter(|(name, _)| key_names.contains(name))
+        .collect();
+    if key_names.is_empty() || key.len() != key_names.len() {
+        return None;
+    }
+    Some((put.table_name.clone(), common::key::get_key_bytes(key)))
+}
+
+fn get_transact_write_items_input<T: Serialize>(
+    transact_write_items: TransactWriteItems<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> Result<operation::transact_write_items::TransactWriteItemsInput> {
+    let mut transact_items = Vec::with_capacity(transact_write_items.transact_items.len());
+    for transact_item in transact_write_items.transact_items {
+        let transact_item = transact_item.serialize(serializer)?;
+        transact_items.push(transact_item);
+    }
+    let operation = operation::transact_write_items::TransactWriteItemsInput::builder()
+        .set_client_request_token(transact_write_items.client_request_token)
+        .set_return_consumed_capacity(transact_write_items.return_consumed_capacity)
+        .set_return_item_collection_metrics(transact_write_items.return_item_collection_metrics)
+        .set_transact_items(Some(transact_items))
+        .build()
+        .unwrap();
+    Ok(operation)
+}
+
+impl<T: Serialize> TryFrom<TransactWriteItems<T>>
+    for operation::transact_write_items::TransactWriteItemsInput
+{
+    type Error = Error;
+
+    fn try_from(transact_write_items: TransactWriteItems<T>) -> Result<Self> {
+        get_transact_write_items_input(transact_write_items, &common::serializer::SerdeDynamo)
+    }
+}
+
+impl<T: Serialize> TransactWriteItems<T> {
+    /// Execute the transact write items operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::transact_write_items::TransactWriteItemsOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the transact write items operation, converting the values with the given
+    /// serializer.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.transact_write_items", skip_all, err)
+    )]
+    pub async fn send_with(
+        self,
+        client: &Client,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::transact_write_items::TransactWriteItemsOutput, crate::Error> {
+        let retry_policy = self.retry_policy.clone();
+        let transact_write_items = get_transact_write_items_input(self, serializer)
+            .map_err(crate::Error::Serialization)?;
+        client
+            .transact_write_items()
+            .set_client_request_token(transact_write_items.client_request_token)
+            .set_return_consumed_capacity(transact_write_items.return_consumed_capacity)
+            .set_return_item_collection_metrics(transact_write_items.return_item_collection_metrics)
+            .set_transact_items(transact_write_items.transact_items)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(crate::Error::from)
+    }
+}
+
+impl<T: Serialize> ChunkedTransactWriteItems<T> {
+    /// Execute the chunked transact write items operation.
+    ///
+    /// Fails before sending any transaction if a write can't be serialized, or a group
+    /// exceeds 100 writes or writes the same item twice.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<ChunkedTransactWriteItemsOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the chunked transact write items operation, converting the values with the
+    /// given serializer.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.chunked_transact_write_items", skip_all, err)
+    )]
+    pub async fn send_with(
+        self,
+        client: &Client,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<ChunkedTransactWriteItemsOutput, crate::Error> {
+        let mut groups = Vec::with_capacity(self.groups.len());
+        for group in self.groups {
+            let group = group
+                .into_iter()
+                .map(|transact_item| transact_item.serialize(serializer))
+                .collect::<Result<Vec<_>>>()
+                .map_err(crate::Error::Serialization)?;
+            groups.push(group);
+        }
+        let key_names = get_key_names(&groups, self.key_names);
+        let mut output = ChunkedTransactWriteItemsOutput::default();
+        let mut transactions = split_groups(groups, MAX_TRANSACT_ITEMS, |transact_item| {
+            get_transact_item_key(transact_item, &key_names)
+        })?
+        .into_iter();
+        for transact_items in transactions.by_ref() {
+            let sent = client
+                .transact_write_items()
+                .set_return_consumed_capacity(self.return_consumed_capacity.clone())
+                .set_return_item_collection_metrics(self.return_item_collection_metrics.clone())
+                .set_transact_items(Some(transact_items.clone()))
+                .customize()
+                .with_retry_policy(client, self.retry_policy.as_ref())
+                .send()
+                .await;
+            match sent {
+                Ok(_) => output.result.succeeded.push(transact_items),
+                Err(error) => {
+                    output.result.failed.push((transact_items, error.into()));
+                    if !self.continue_on_error {
+                        break;
+                    }
+                }
+            }
+        }
+        output.unsent = transactions.collect();
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use std::collections;
+
+    fn get_keys() -> common::key::Keys<Value> {
+        common::key::Keys {
+            partition_key: common::key::Key {
+                name: "a".to_string(),
+                value: Value::String("b".to_string()),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn get_write_args() -> write::common::WriteArgs<Value> {
+        write::common::WriteArgs {
+            condition: Some(common::condition::ConditionMap::Leaves(
+                common::condition::LogicalOperator::And,
+                vec![common::condition::KeyCondition {
+                    name: "c".to_string(),
+                    condition: common::condition::Condition::NotNull,
+                }],
+            )),
+            table_name: "d".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case::condition_check(
+        ConditionCheck {
+            condition: common::condition::ConditionMap::Leaves(
+                common::condition::LogicalOperator::And,
+                vec![
+                    common::condition::KeyCondition {
+                        name: "c".to_string(),
+                        condition: common::condition::Condition::Equals(
+                            Value::String(
+                                "e".to_string()
+                            )
+                        ),
+                    },
+                ]
+            ),
+            keys: get_keys(),
+            return_values_on_condition_check_failure: Some(
+                types::ReturnValuesOnConditionCheckFailure::AllOld
+            ),
+            table_name: "d".to_string(),
+        }.into(),
+        types::TransactWriteItem::builder()
+            .condition_check(
+                types::ConditionCheck::builder()
+                    .key(
+                        "a",
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        ),
+                    )
+                    .condition_expression("#c = :c_eq0")
+                    .expression_attribute_names("#c", "c")
+                    .expression_attribute_values(
+                        ":c_eq0",
+                        types::AttributeValue::S(
+                            "e".to_string()
+                        ),
+                    )
+                    .return_values_on_condition_check_failure(
+                        types::ReturnValuesOnConditionCheckFailure::AllOld
+                    )
+                    .table_name("d")
+                    .build()
+                    .unwrap()
+            )
+            .build()
+    )]
+    #[case::delete(
+        write::delete_item::DeleteItem {
+            keys: get_keys(),
+            return_values: None,
+            write_args: get_write_args(),
+        }.into(),
+        types::TransactWriteItem::builder()
+            .delete(
+                types::Delete::builder()
+                    .key(
+                        "a",
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        ),
+                    )
+                    .condition_expression("attribute_exists(#c)")
+                    .expression_attribute_names("#c", "c")
+                    .set_expression_attribute_values(Some(collections::HashMap::new()))
+                    .table_name("d")
+                    .build()
+                    .unwrap()
+            )
+            .build()
+    )]
+    #[case::put(
+        write::put_item::PutItem {
+            item: json!({"a": "b"}),
+            return_values: None,
+            write_args: get_write_args(),
+        }.into(),
+        types::TransactWriteItem::builder()
+            .put(
+                types::Put::builder()
+                    .item(
+                        "a",
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        ),
+                    )
+                    .condition_expression("attribute_exists(#c)")
+                    .expression_attribute_names("#c", "c")
+                    .set_expression_attribute_values(Some(collections::HashMap::new()))
+                    .table_name("d")
+                    .build()
+                    .unwrap()
+            )
+            .build()
+    )]
+    #[case::update(
+        write::update_item::UpdateItem {
+            keys: get_keys(),
+            return_values: None,
+            update_expression: write::update_item::UpdateExpressionMap::Set(
+                write::update_item::SetInputsMap::Leaves(
+                    vec![
+                        (
+                            "e".to_string(),
+                            write::update_item::SetInput::Assign(
+                                Value::String(
+                                    "f".to_string()
+                                )
+                            )
+                        ),
+                    ]
+                )
+            ),
+            write_args: get_write_args(),
+        }.into(),
+        types::TransactWriteItem::builder()
+            .update(
+                types::Update::builder()
+                    .key(
+                        "a",
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        ),
+                    )
+                    .update_expression("SET #e = :set0")
+                    .condition_expression("attribute_exists(#c)")
+                    .expression_attribute_names("#c", "c")
+                    .expression_attribute_names("#e", "e")
+                    .expression_attribute_values(
+                        ":set0",
+                        types::AttributeValue::S(
+                            "f".to_string()
+                        ),
+                    )
+                    .table_name("d")
+                    .build()
+                    .unwrap()
+            )
+            .build()
+    )]
+    fn test_transact_write_item(
+        #[case] transact_item: TransactWriteItem<Value>,
+        #[case] expected: types::TransactWriteItem,
+    ) {
+        let actual = transact_item
+            .serialize(&common::serializer::SerdeDynamo)
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_transact_write_items() {
+        let args = TransactWriteItems {
+            client_request_token: Some("a".to_string()),
+            transact_items: vec![
+                write::put_item::PutItem {
+                    item: json!({"b": "c"}),
+                    return_values: None,
+                    write_args: write::common::WriteArgs {
+                        table_name: "d".to_string(),
+                        ..Default::default()
+                    },
+                }
+                .into(),
+            ],
+            ..Default::default()
+        };
+        let actual: operation::transact_write_items::TransactWriteItemsInput =
+            args.try_into().unwrap();
+        let expected = operation::transact_write_items::TransactWriteItemsInput::builder()
+            .client_request_token("a")
+            .transact_items(
+                types::TransactWriteItem::builder()
+                    .put(
+                        types::Put::builder()
+                            .item("b", types::AttributeValue::S("c".to_string()))
+                            .table_name("d")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::empty(vec![], vec![])]
+    #[case::packed(vec![vec![1, 2], vec![3], vec![4, 5]], vec![vec![1, 2, 3], vec![4, 5]])]
+    #[case::empty_group(vec![vec![], vec![1], vec![]], vec![vec![1]])]
+    #[case::full(vec![vec![1, 2, 3], vec![4]], vec![vec![1, 2, 3], vec![4]])]
+    #[case::overlapping(vec![vec![1, 2], vec![2], vec![3]], vec![vec![1, 2], vec![2, 3]])]
+    fn test_split_groups(#[case] groups: Vec<Vec<u32>>, #[case] expected: Vec<Vec<u32>>) {
+        let actual = split_groups(groups, 3, |item| Some(*item)).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_split_groups_too_large() {
+        let actual = split_groups(vec![vec![1], vec![2, 3, 4, 5]], 3, |item| Some(*item));
+        assert!(matches!(actual, Err(crate::Error::TransactionTooLarge(4))));
+    }
+
+    #[test]
+    fn test_split_groups_duplicate_key() {
+        let actual = split_groups(vec![vec![1], vec![2, 3, 2]], 3, |item| Some(*item));
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_split_groups_shared_key() {
+        let put = |id: &str| {
+            let put = types::Put::builder()
+                .item("a", types::AttributeValue::S(id.to_string()))
+                .item("b", types::AttributeValue::S("c".to_string()))
+                .table_name("d")
+                .build()
+                .unwrap();
+            types::TransactWriteItem::builder().put(put).build()
+        };
+        let delete = |id: &str| {
+            let delete = types::Delete::builder()
+                .key("a", types::AttributeValue::S(id.to_string()))
+                .table_name("d")
+                .build()
+                .unwrap();
+            types::TransactWriteItem::builder().delete(delete).build()
+        };
+        let groups = vec![
+            vec![put("e"), delete("f")],
+            vec![delete("e")],
+            vec![put("g")],
+        ];
+        let key_names = get_key_names(&groups, IndexMap::new());
+        assert_eq!(key_names["d"], vec!["a".to_string()]);
+        let actual = split_groups(groups, MAX_TRANSACT_ITEMS, |transact_item| {
+            get_transact_item_key(transact_item, &key_names)
+        })
+        .unwrap();
+        let expected = vec![vec![put("e"), delete("f")], vec![delete("e"), put("g")]];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{common, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use serde::Serialize;
+use serde_dynamo::Result;
+use std::{collections, time};
+
+/// Refresh of the time to live of an existing item, such as a session.
+///
+/// The item is never created: the refresh fails if it does not exist. With
+/// `only_if_sooner`, the expiry is only ever extended, never shortened.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, write};
+/// use std::time::{Duration, SystemTime};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let touch = write::ttl::Touch {
+///     expires_at: SystemTime::now() + Duration::from_secs(30 * 60),
+///     expires_at_name: "expiresAt".to_string(),
+///     keys: common::key::Keys {
+///         partition_key: common::key::Key {
+///             name: "id".to_string(),
+///             value: "session-1".to_string(),
+///         },
+///         ..Default::default()
+///     },
+///     only_if_sooner: true,
+///     table_name: "sessions".to_string(),
+/// };
+/// if !touch.send(client).await? {
+///     println!("session missing or already expiring later");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Touch<T> {
+    /// The new expiry of the item.
+    pub expires_at: time::SystemTime,
+    /// The name of the attribute storing the item expiry, in epoch seconds.
+    pub expires_at_name: String,
+    /// The primary key of the item to refresh.
+    pub keys: common::key::Keys<T>,
+    /// Whether to refresh the expiry only if the current one is sooner than the new one, or
+    /// missing.
+    pub only_if_sooner: bool,
+    /// The name of the table.
+    pub table_name: String,
+}
+
+impl<T: Serialize> Touch<T> {
+    fn get_update_input(self) -> Result<write::update_item::UpdateItemInput> {
+        let expires_at = self
+            .expires_at
+            .duration_since(time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let key_name = self.keys.partition_key.name.clone();
+        let keys = self.keys.serialize(&common::serializer::SerdeDynamo)?;
+        let condition_expression = if self.only_if_sooner {
+            "attribute_exists(#key) AND (attribute_not_exists(#expires_at) OR #expires_at < :expires_at)"
+        } else {
+            "attribute_exists(#key)"
+        };
+        let write_operation = write::common::WriteInput {
+            condition_expression: Some(condition_expression.to_string()),
+            expression_attribute_names: Some(collections::HashMap::from([
+                ("#expires_at".to_string(), self.expires_at_name),
+                ("#key".to_string(), key_name),
+            ])),
+            expression_attribute_values: Some(collections::HashMap::from([(
+                ":expires_at".to_string(),
+                types::AttributeValue::N(expires_at.to_string()),
+            )])),
+            table_name: self.table_name,
+            ..Default::default()
+        };
+        let update_item = write::update_item::UpdateItemInput {
+            keys,
+            update_expression: "SET #expires_at = :expires_at".to_string(),
+            write_operation,
+        };
+        Ok(update_item)
+    }
+
+    /// Refresh the expiry, returning whether it was updated.
+    ///
+    /// Returns `false` if the item does not exist or, with `only_if_sooner`, if it already
+    /// expires at or after the new expiry.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.ttl.touch", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<bool, crate::Error> {
+        let update_item = self
+            .get_update_input()
+            .map_err(crate::Error::Serialization)?;
+        let builder = client
+            .update_item()
+            .set_key(Some(update_item.keys))
+            .update_expression(update_item.update_expression);
+        match crate::apply_write_operation!(builder, update_item.write_operation)
+            .send()
+            .await
+            .map_err(crate::Error::from)
+        {
+            Ok(_) => Ok(true),
+            Err(crate::Error::ConditionalCheckFailed(_)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::always(false, "attribute_exists(#key)")]
+    #[case::only_if_sooner(
+        true,
+        "attribute_exists(#key) AND (attribute_not_exists(#expires_at) OR #expires_at < :expires_at)"
+    )]
+    fn test_get_update_input(#[case] only_if_sooner: bool, #[case] expected_condition: &str) {
+        let touch = Touch {
+            expires_at: time::UNIX_EPOCH + time::Duration::from_secs(100),
+            expires_at_name: "a".to_string(),
+            keys: common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "b".to_string(),
+                    value: "c".to_string(),
+                },
+                sort_key: None,
+            },
+            only_if_sooner,
+            table_name: "d".to_string(),
+        };
+        let actual = touch.get_update_input().unwrap();
+        let expected = write::update_item::UpdateItemInput {
+            keys: collections::HashMap::from([(
+                "b".to_string(),
+                types::AttributeValue::S("c".to_string()),
+            )]),
+            update_expression: "SET #expires_at = :expires_at".to_string(),
+            write_operation: write::common::WriteInput {
+                condition_expression: Some(expected_condition.to_string()),
+                expression_attribute_names: Some(collections::HashMap::from([
+                    ("#expires_at".to_string(), "a".to_string()),
+                    ("#key".to_string(), "b".to_string()),
+                ])),
+                expression_attribute_values: Some(collections::HashMap::from([(
+                    ":expires_at".to_string(),
+                    types::AttributeValue::N("100".to_string()),
+                )])),
+                table_name: "d".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{common, common::retry::WithRetryPolicy as _, write};
+
+use aws_sdk_dynamodb::{Client, operation, types};
+use serde::Serialize;
+use serde_dynamo::Result;
+use std::{collections, fmt};
+
+/// Error returned by a write enforcing unique constraints.
+#[derive(Debug)]
+pub enum UniqueWriteError {
+    /// The transaction failed for another reason than a unique constraint violation.
+    TransactWriteItems(crate::Error),
+    /// The claimed unique values already belong to another item.
+    UniqueViolation(Vec<UniqueConstraint>),
+}
+
+impl fmt::Display for UniqueWriteError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransactWriteItems(error) => {
+                write!(formatter, "transact write items failed: {error}")
+            }
+            Self::UniqueViolation(unique_constraints) => {
+                formatter.write_str("unique constraint violated:")?;
+                for unique_constraint in unique_constraints {
+                    write!(
+                        formatter,
+                        " {} = {:?}",
+                        unique_constraint.attribute_name, unique_constraint.value
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for UniqueWriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::TransactWriteItems(error) => Some(error),
+            Self::UniqueViolation(_) => None,
+        }
+    }
+}
+
+/// Unique value of an entity attribute, backed by a marker item.
+///
+/// The marker item key is the attribute name and the value joined by `#`, so several
+/// unique attributes can share the same table.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct UniqueConstraint {
+    /// The name of the entity attribute that must be unique.
+    pub attribute_name: String,
+    /// The partition key name of the marker items table.
+    pub key_name: String,
+    /// The name of the marker items table, usually the entity table.
+    pub table_name: String,
+    /// The unique value.
+    pub value: String,
+}
+
+impl UniqueConstraint {
+    fn get_marker_key(&self) -> collections::HashMap<String, types::AttributeValue> {
+        let marker = format!("{}#{}", self.attribute_name, self.value);
+        collections::HashMap::from([(self.key_name.clone(), types::AttributeValue::S(marker))])
+    }
+
+    /// Put the marker item, failing if it already exists.
+    fn get_claim(&self) -> types::TransactWriteItem {
+        let placeholder = common::PlaceholderAllocator::default().allocate_name(&self.key_name);
+        let put = types::Put::builder()
+            .set_item(Some(self.get_marker_key()))
+            .condition_expression(format!("attribute_not_exists({placeholder})"))
+            .expression_attribute_names(placeholder, &self.key_name)
+            .table_name(&self.table_name)
+            .build()
+            .unwrap();
+        types::TransactWriteItem::builder().put(put).build()
+    }
+
+    /// Delete the marker item.
+    fn get_release(&self) -> types::TransactWriteItem {
+        let delete = types::Delete::builder()
+            .set_key(Some(self.get_marker_key()))
+            .table_name(&self.table_name)
+            .build()
+            .unwrap();
+        types::TransactWriteItem::builder().delete(delete).build()
+    }
+}
+
+/// Write of an entity claiming and releasing unique values in the same transaction.
+///
+/// Putting an entity claims its unique values, deleting it releases them, and updating a
+/// unique attribute releases the old value and claims the new one.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::write;
+/// use serde_json::json;
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let put_item = write::put_item::PutItem {
+///     item: json!({"pk": "user#1", "email": "jane@example.com"}),
+///     return_values: None,
+///     write_args: write::common::WriteArgs {
+///         table_name: "app".to_string(),
+///         ..Default::default()
+///     },
+/// };
+/// let unique_write = write::unique_constraint::UniqueWrite {
+///     claimed: vec![write::unique_constraint::UniqueConstraint {
+///         attribute_name: "email".to_string(),
+///         key_name: "pk".to_string(),
+///         table_name: "app".to_string(),
+///         value: "jane@example.com".to_string(),
+///     }],
+///     write: put_item.into(),
+///     ..Default::default()
+/// };
+/// match unique_write.send(client).await {
+///     Err(write::unique_constraint::UniqueWriteError::UniqueViolation(constraints)) => {
+///         println!("already taken: {constraints:?}")
+///     }
+///     result => {
+///         result?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniqueWrite<T> {
+    /// The unique values to claim, failing the write if one is already claimed.
+    pub claimed: Vec<UniqueConstraint>,
+    /// The idempotency token of the transaction.
+    pub client_request_token: Option<String>,
+    /// The unique values to release.
+    pub released: Vec<UniqueConstraint>,
+    /// Retry policy overriding the retry configuration of the client for this call.
+    pub retry_policy: Option<common::retry::RetryPolicy>,
+    /// The write of the entity.
+    pub write: write::transact_write_items::TransactWriteItem<T>,
+}
+
+impl<T: Default> Default for UniqueWrite<T> {
+    fn default() -> Self {
+        Self {
+            claimed: Vec::new(),
+            client_request_token: None,
+            released: Vec::new(),
+            retry_policy: None,
+            write: write::transact_write_items::TransactWriteItem::Put(Default::default()),
+        }
+    }
+}
+
+impl<T: Serialize> UniqueWrite<T> {
+    /// Get the entity write followed by the released and claimed marker items.
+    fn get_transact_items(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Vec<types::TransactWriteItem>> {
+        let mut transact_items = Vec::with_capacity(1 + self.released.len() + self.claimed.len());
+        transact_items.push(self.write.serialize(serializer)?);
+        transact_items.extend(self.released.iter().map(UniqueConstraint::get_release));
+        transact_items.extend(self.claimed.iter().map(UniqueConstraint::get_claim));
+        Ok(transact_items)
+    }
+
+    /// Execute the write of the entity along with its marker items.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.unique_write", skip_all, err)
+    )]
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> std::result::Result<
+        operation::transact_write_items::TransactWriteItemsOutput,
+        UniqueWriteError,
+    > {
+        let claimed = self.claimed.clone();
+        let claims_start = 1 + self.released.len();
+        let client_request_token = self.client_request_token.clone();
+        let retry_policy = self.retry_policy.clone();
+        let transact_items = self
+            .get_transact_items(&common::serializer::SerdeDynamo)
+            .map_err(|error| {
+                UniqueWriteError::TransactWriteItems(crate::Error::Serialization(error))
+            })?;
+        client
+            .transact_write_items()
+            .set_client_request_token(client_request_token)
+            .set_transact_items(Some(transact_items))
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
+            .send()
+            .await
+            .map_err(|error| get_unique_write_error(error.into(), claimed, claims_start))
+    }
+}
+
+/// Report the claimed unique values whose marker item already exists, if any.
+fn get_unique_write_error(
+    error: crate::Error,
+    claimed: Vec<UniqueConstraint>,
+    claims_start: usize,
+) -> UniqueWriteError {
+    if let crate::Error::TransactionCanceled(reasons) = &error {
+        let violated: Vec<UniqueConstraint> = reasons
+            .iter()
+            .skip(claims_start)
+            .zip(claimed)
+            .filter(|(reason, _)| reason.code() == Some("ConditionalCheckFailed"))
+            .map(|(_, unique_constraint)| unique_constraint)
+            .collect();
+        if !violated.is_empty() {
+            return UniqueWriteError::UniqueViolation(violated);
+        }
+    }
+    UniqueWriteError::TransactWriteItems(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{Value, json};
+
+    fn get_unique_constraint(value: &str) -> UniqueConstraint {
+        UniqueConstraint {
+            attribute_name: "a".to_string(),
+            key_name: "b".to_string(),
+            table_name: "c".to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_unique_write() {
+        let args: UniqueWrite<Value> = UniqueWrite {
+            claimed: vec![get_unique_constraint("d")],
+            released: vec![get_unique_constraint("e")],
+            write: write::put_item::PutItem {
+                item: json!({"b": "f"}),
+                return_values: None,
+                write_args: write::common::WriteArgs {
+                    table_name: "c".to_string(),
+                    ..Default::default()
+                },
+            }
+            .into(),
+            ..Default::default()
+        };
+        let actual = args
+            .get_transact_items(&common::serializer::SerdeDynamo)
+            .unwrap();
+        let expected = vec![
+            types::TransactWriteItem::builder()
+                .put(
+                    types::Put::builder()
+                        .item("b", types::AttributeValue::S("f".to_string()))
+                        .table_name("c")
+                        .build()
+                        .unwrap(),
+                )
+                .build(),
+            types::TransactWriteItem::builder()
+                .delete(
+                    types::Delete::builder()
+                        .key("b", types::AttributeValue::S("a#e".to_string()))
+                        .table_name("c")
+                        .build()
+                        .unwrap(),
+                )
+                .build(),
+            types::TransactWriteItem::builder()
+                .put(
+                    types::Put::builder()
+                        .item("b", types::AttributeValue::S("a#d".to_string()))
+                        .condition_expression("attribute_not_exists(#b)")
+                        .expression_attribute_names("#b", "b")
+                        .table_name("c")
+                        .build()
+                        .unwrap(),
+                )
+                .build(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_unique_write_error() {
+        let error = crate::Error::TransactionCanceled(vec![
+            types::CancellationReason::builder().code("None").build(),
+            types::CancellationReason::builder().code("None").build(),
+            types::CancellationReason::builder()
+                .code("ConditionalCheckFailed")
+                .build(),
+        ]);
+        let actual = get_unique_write_error(error, vec![get_unique_constraint("d")], 2);
+        assert!(matches!(
+            actual,
+            UniqueWriteError::UniqueViolation(violated) if violated == vec![get_unique_constraint("d")]
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{common, read, write};
+
+use aws_sdk_dynamodb::{Client, types};
+use futures_util::{StreamExt, stream};
+use serde::Serialize;
+use std::{collections, time};
+
+/// Output of the update by query operation, the keys of the updated items and of the
+/// failed ones.
+pub type UpdateByQueryOutput =
+    common::bulk::BulkResult<collections::HashMap<String, types::AttributeValue>, crate::Error>;
+
+/// update by query operation
+#[derive(Clone, Debug, Default, PartialEq)]
+struct UpdateByQueryInput<T> {
+    concurrency: usize,
+    key_names: Vec<String>,
+    keys_query: read::query::Query<T>,
+    request_interval: Option<time::Duration>,
+    update_expression: String,
+    write_operation: write::common::WriteInput,
+}
+
+/// Update by query operation.
+///
+/// Pages through the query fetching only the key attributes, then applies the same update
+/// expression to every matching item with bounded concurrency and an optional rate limit.
+/// Failed updates are reported per item instead of aborting the whole operation.
+///
+/// ```rust,no_run
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_crud::{common, read, write};
+///
+/// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let update_by_query = write::update_by_query::UpdateByQuery {
+///     concurrency: Some(10),
+///     key_names: vec!["user_id".to_string(), "created_at".to_string()],
+///     query: read::query::Query {
+///         partition_key: common::key::Key {
+///             name: "user_id".to_string(),
+///             value: "1".to_string(),
+///         },
+///         multiple_read_args: read::common::MultipleReadArgs {
+///             table_name: "orders".to_string(),
+///             ..Default::default()
+///         },
+///         sort_key_condition: Some(common::condition::SortKey {
+///             name: "created_at".to_string(),
+///             condition: common::condition::SortKeyCondition::LessThan("2024-01-01".to_string()),
+///         }),
+///         ..Default::default()
+///     },
+///     requests_per_second: Some(50),
+///     update_expression: write::update_item::UpdateExpressionMap::Set(
+///         write::update_item::SetInputsMap::Leaves(vec![(
+///             "status".to_string(),
+///             write::update_item::SetInput::Assign("archived".to_string()),
+///         )]),
+///     ),
+///     write_args: Default::default(),
+/// };
+/// let output = update_by_query.send(client).await?;
+/// println!("{} updated, {} failed", output.succeeded.len(), output.failed.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateByQuery<T> {
+    /// The maximum number of update requests in flight at once (defaults to 1).
+    pub concurrency: Option<usize>,
+    /// The primary key attribute names of the table.
+    ///
+    /// If empty, they are taken from the `key_schema` of the query, which must be set and must
+    /// describe the base table.
+    pub key_names: Vec<String>,
+    /// The query selecting the items to update.
+    pub query: read::query::Query<T>,
+    /// The maximum number of update requests issued per second (unlimited if `None`).
+    pub requests_per_second: Option<u32>,
+    /// The update expression applied to every matching item.
+    pub update_expression: write::update_item::UpdateExpressionMap<T>,
+    /// Additional write operation arguments applied to every update.
+    ///
+    /// The table name is always taken from the query.
+    pub write_args: write::common::WriteArgs<T>,
+}
+
+impl<T: Serialize> TryFrom<UpdateByQuery<T>> for UpdateByQueryInput<T> {
+    type Error = crate::Error;
+
+    fn try_from(update_by_query: UpdateByQuery<T>) -> Result<Self, crate::Error> {
+        let (update_expression, mut write_operation) = write::update_item::get_update_operation(
+            &update_by_query.update_expression,
+            update_by_query.write_args,
+            &common::serializer::SerdeDynamo,
+        )
+        .map_err(crate::Error::Serialization)?;
+        write_operation
+            .table_name
+            .clone_from(&update_by_query.query.multiple_read_args.table_name);
+        let (keys_query, key_names) = update_by_query
+            .query
+            .into_keys_query(update_by_query.key_names)?;
+        let request_interval = update_by_query
+            .requests_per_second
+            .filter(|requests_per_second| *requests_per_second > 0)
+            .map(|requests_per_second| time::Duration::from_secs(1) / requests_per_second);
+        let operation = Self {
+            concurrency: update_by_query.concurrency.unwrap_or(1).max(1),
+            key_names,
+            keys_query,
+            request_interval,
+            update_expression,
+            write_operation,
+        };
+        Ok(operation)
+    }
+}
+
+impl<T: Serialize> UpdateByQuery<T> {
+    /// Execute the update by query operation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.update_by_query", skip_all, err)
+    )]
+    pub async fn send(self, client: &Client) -> Result<UpdateByQueryOutput, crate::Error> {
+        let update_by_query: UpdateByQueryInput<T> = self.try_into()?;
+        let output = update_by_query.keys_query.send(client).await?;
+        let items = output.items.unwrap_or_default();
+        let start = tokio::time::Instant::now();
+        let updates = items.into_iter().enumerate().map(|(index, item)| {
+            let keys: collections::HashMap<_, _> = item
+                .into_iter()
+                .filter(|(name, _)| update_by_query.key_names.contains(name))
+                .collect();
+            let builder = client
+                .update_item()
+                .set_key(Some(keys.clone()))
+                .update_expression(&update_by_query.update_expression);
+            let builder =
+                crate::apply_write_operation!(builder, update_by_query.write_operation.clone());
+            async move {
+                if let Some(request_interval) = update_by_query.request_interval {
+                    tokio::time::sleep_until(start + request_interval * index as u32).await;
+                }
+                (keys, builder.send().await)
+            }
+        });
+        let mut updates = stream::iter(updates).buffer_unordered(update_by_query.concurrency);
+        let mut output = UpdateByQueryOutput::default();
+        while let Some((keys, result)) = updates.next().await {
+            match result {
+                Ok(_) => output.succeeded.push(keys),
+                Err(error) => output.failed.push((keys, error.into())),
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{common, table};
+
+    use rstest::rstest;
+    use serde_json::Value;
+
+    #[rstest]
+    #[case::full(
+        UpdateByQuery {
+            concurrency: Some(4),
+            key_names: vec![],
+            query: read::query::Query {
+                key_schema: Some(
+                    table::common::KeySchema {
+                        partition_key: table::common::KeyAttribute {
+                            name: "b".to_string(),
+                            ..Default::default()
+                        },
+                        sort_key: Some(
+                            table::common::KeyAttribute {
+                                name: "g".to_string(),
+                                ..Default::default()
+                            }
+                        ),
+                    }
+                ),
+                multiple_read_args: read::common::MultipleReadArgs {
+                    table_name: "a".to_string(),
+                    ..Default::default()
+                },
+                partition_key: common::key::Key {
+                    name: "b".to_string(),
+                    value: Value::String(
+                        "c".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            requests_per_second: Some(4),
+            update_expression: write::update_item::UpdateExpressionMap::Set(
+                write::update_item::SetInputsMap::Leaves(
+                    vec![
+                        (
+                            "d".to_string(),
+                            write::update_item::SetInput::Assign(
+                                Value::String(
+                                    "e".to_string()
+                                )
+                            )
+                        ),
+                    ]
+                )
+            ),
+            write_args: write::common::WriteArgs {
+                table_name: "f".to_string(),
+                ..Default::default()
+            },
+        },
+        UpdateByQueryInput {
+            concurrency: 4,
+            key_names: vec![
+                "b".to_string(),
+                "g".to_string(),
+            ],
+            keys_query: read::query::Query {
+                key_schema: Some(
+                    table::common::KeySchema {
+                        partition_key: table::common::KeyAttribute {
+                            name: "b".to_string(),
+                            ..Default::default()
+                        },
+                        sort_key: Some(
+                            table::common::KeyAttribute {
+                                name: "g".to_string(),
+                                ..Default::default()
+                            }
+                        ),
+                    }
+                ),
+                multiple_read_args: read::common::MultipleReadArgs {
+                    select: Some(
+                        types::Select::SpecificAttributes
+                    ),
+                    selection: Some(
+                        common::selection::SelectionMap::Leaves(
+                            vec![
+                                "b".to_string(),
+                                "g".to_string(),
+                            ]
+                        )
+                    ),
+                    table_name: "a".to_string(),
+                    ..Default::default()
+                },
+                partition_key: common::key::Key {
+                    name: "b".to_string(),
+                    value: Value::String(
+                        "c".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            request_interval: Some(
+                time::Duration::from_millis(250)
+            ),
+            update_expression: "SET #d = :set0".to_string(),
+            write_operation: write::common::WriteInput {
+                expression_attribute_names: Some(
+                    collections::HashMap::from(
+                        [
+                            ("#d".to_string(), "d".to_string()),
+                        ]
+                    )
+                ),
+                expression_attribute_values: Some(
+                    collections::HashMap::from(
+                        [
+                            (
+                                ":set0".to_string(),
+                                types::AttributeValue::S(
+                                    "e".to_string()
+                                )
+                            ),
+                        ]
+                    )
+                ),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+        }
+    )]
+    #[case::defaults(
+        UpdateByQuery {
+            concurrency: None,
+            key_names: vec![
+                "a".to_string(),
+            ],
+            query: read::query::Query {
+                multiple_read_args: read::common::MultipleReadArgs {
+                    index_name: Some("b".to_string()),
+                    table_name: "c".to_string(),
+                    ..Default::default()
+                },
+                partition_key: common::key::Key {
+                    name: "d".to_string(),
+                    value: Value::String(
+                        "e".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            requests_per_second: None,
+            update_expression: write::update_item::UpdateExpressionMap::Remove(
+                common::selection::SelectionMap::Leaves(
+                    vec![
+                        "f".to_string(),
+                    ]
+                )
+            ),
+            write_args: Default::default(),
+        },
+        UpdateByQueryInput {
+            concurrency: 1,
+            key_names: vec![
+                "a".to_string(),
+            ],
+            keys_query: read::query::Query {
+                multiple_read_args: read::common::MultipleReadArgs {
+                    index_name: Some("b".to_string()),
+                    select: Some(
+                        types::Select::SpecificAttributes
+                    ),
+                    selection: Some(
+                        common::selection::SelectionMap::Leaves(
+                            vec![
+                                "a".to_string(),
+                            ]
+                        )
+                    ),
+                    table_name: "c".to_string(),
+                    ..Default::default()
+                },
+                partition_key: common::key::Key {
+                    name: "d".to_string(),
+                    value: Value::String(
+                        "e".to_string()
+                    ),
+                },
+                ..Default::default()
+            },
+            request_interval: None,
+            update_expression: "REMOVE #f".to_string(),
+            write_operation: write::common::WriteInput {
+                expression_attribute_names: Some(
+                    collections::HashMap::from(
+                        [
+                            ("#f".to_string(), "f".to_string()),
+                        ]
+                    )
+                ),
+                expression_attribute_values: Some(
+                    collections::HashMap::new()
+                ),
+                table_name: "c".to_string(),
+                ..Default::default()
+            },
+        }
+    )]
+    fn test_update_by_query(
+        #[case] args: UpdateByQuery<Value>,
+        #[case] expected: UpdateByQueryInput<Value>,
+    ) {
+        let actual: UpdateByQueryInput<Value> = args.try_into().unwrap();
+        assert_eq!(actual, expected);
+    }
+}
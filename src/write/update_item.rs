@@ -1,65 +1,237 @@
-use crate::{common, write};
+use crate::{common, common::retry::WithRetryPolicy as _, write};
 
-use aws_sdk_dynamodb::{Client, error, operation, types};
+use aws_sdk_dynamodb::{Client, operation, primitives, types};
 use indexmap::IndexMap;
-use serde::Serialize;
-use serde_dynamo::{Error, Result, to_attribute_value};
-use std::collections;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_dynamo::{Error, Result};
+use std::{collections, fmt::Write};
 
-/// Separator for attribute path components.
-const PATH_SEPARATOR: &str = ".";
+/// Typed DynamoDB set, for ADD and DELETE operations on set attributes.
+///
+/// Sequences are serialized as lists, which DynamoDB rejects when adding to or deleting from
+/// a set, so set values are built explicitly.
+///
+/// ```rust
+/// use dynamodb_crud::write::update_item::SetValue;
+///
+/// let tags = SetValue::StringSet(vec!["a".to_string(), "b".to_string()]);
+/// let scores = SetValue::numbers([1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SetValue {
+    /// Binary set (`BS`).
+    BinarySet(Vec<Vec<u8>>),
+    /// Number set (`NS`), every number in its string representation.
+    NumberSet(Vec<String>),
+    /// String set (`SS`).
+    StringSet(Vec<String>),
+}
+
+impl SetValue {
+    /// Number set of the given numbers.
+    pub fn numbers<N: ToString>(numbers: impl IntoIterator<Item = N>) -> Self {
+        Self::NumberSet(
+            numbers
+                .into_iter()
+                .map(|number| number.to_string())
+                .collect(),
+        )
+    }
+}
+
+impl From<SetValue> for types::AttributeValue {
+    fn from(set_value: SetValue) -> Self {
+        match set_value {
+            SetValue::BinarySet(values) => {
+                Self::Bs(values.into_iter().map(primitives::Blob::new).collect())
+            }
+            SetValue::NumberSet(values) => Self::Ns(values),
+            SetValue::StringSet(values) => Self::Ss(values),
+        }
+    }
+}
 
 /// Map for ADD and DELETE operations.
+///
+/// ```rust
+/// use dynamodb_crud::write::update_item::{AddOrDeleteInputsMap, SetValue};
+///
+/// // ADD #tags :add_or_delete0, with a string set value
+/// let add: AddOrDeleteInputsMap<String> = AddOrDeleteInputsMap::SetLeaves(vec![(
+///     "tags".to_string(),
+///     SetValue::StringSet(vec!["new".to_string()]),
+/// )]);
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum AddOrDeleteInputsMap<T> {
+    /// Index leaf operations - flat list of (list_element_position, value) pairs.
+    IndexLeaves(Vec<(usize, T)>),
+    /// Index node operations - nested operations within list elements, by position.
+    IndexNode(IndexMap<usize, AddOrDeleteInputsMap<T>>),
     /// Leaf operations - flat list of (attribute_name, value) pairs.
     Leaves(Vec<(String, T)>),
     /// Node operations - nested operations for hierarchical attribute paths.
     Node(IndexMap<String, AddOrDeleteInputsMap<T>>),
+    /// Set leaf operations - flat list of (attribute_name, set) pairs, written as `SS`, `NS`
+    /// or `BS` values.
+    SetLeaves(Vec<(String, SetValue)>),
 }
 
 impl<T: Serialize> AddOrDeleteInputsMap<T> {
-    fn get_add_or_delete_expression_recursive(
-        self,
-        keys: &[String],
-        index: &mut usize,
-    ) -> Result<common::ExpressionInput> {
-        let mut operations = Vec::new();
+    fn write_add_or_delete_expression_recursive(
+        &self,
+        path: &mut String,
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<()> {
+        let group_start = operation.expression.len();
         match self {
+            Self::IndexLeaves(leaves) => {
+                for (index, value) in leaves {
+                    let path_length = common::push_index(path, *index);
+                    let value = serializer.to_attribute_value(value)?;
+                    let value_placeholder = placeholders.allocate("add_or_delete");
+                    operation.push_expression(group_start, " ", |operation| {
+                        let _ = write!(operation.expression, "{path} {value_placeholder}");
+                    });
+                    operation
+                        .expression_attribute_values
+                        .insert(value_placeholder, value);
+                    path.truncate(path_length);
+                }
+            }
+            Self::IndexNode(map) => {
+                for (index, value) in map {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, " ", |operation| {
+                        value.write_add_or_delete_expression_recursive(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
+                }
+            }
             Self::Leaves(leaves) => {
                 for (key, value) in leaves {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &key);
-                    let path = new_keys.join(PATH_SEPARATOR);
-                    let value = to_attribute_value(value)?;
-                    let value_placeholder = format!(":add_or_delete{index}");
-                    *index += 1;
-                    let expression = format!("{path} {value_placeholder}");
-                    let expression_attribute_names =
-                        collections::HashMap::from([(placeholder, key)]);
-                    let expression_attribute_values =
-                        collections::HashMap::from([(value_placeholder, value)]);
-                    let operation = common::ExpressionInput {
-                        expression,
-                        expression_attribute_names,
-                        expression_attribute_values,
-                    };
-                    operations.push(operation);
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
+                    let value = serializer.to_attribute_value(value)?;
+                    let value_placeholder = placeholders.allocate("add_or_delete");
+                    operation.push_expression(group_start, " ", |operation| {
+                        let _ = write!(operation.expression, "{path} {value_placeholder}");
+                    });
+                    operation
+                        .expression_attribute_names
+                        .insert(placeholder, key.clone());
+                    operation
+                        .expression_attribute_values
+                        .insert(value_placeholder, value);
+                    path.truncate(path_length);
                 }
             }
             Self::Node(map) => {
                 for (key, value) in map {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &key);
-                    let mut operation =
-                        value.get_add_or_delete_expression_recursive(&new_keys, index)?;
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
+                    operation
+                        .expression_attribute_names
+                        .insert(placeholder, key.clone());
+                    operation.push_expression(group_start, " ", |operation| {
+                        value.write_add_or_delete_expression_recursive(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
+                }
+            }
+            Self::SetLeaves(leaves) => {
+                for (key, value) in leaves {
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
+                    let value_placeholder = placeholders.allocate("add_or_delete");
+                    operation.push_expression(group_start, " ", |operation| {
+                        let _ = write!(operation.expression, "{path} {value_placeholder}");
+                    });
                     operation
                         .expression_attribute_names
-                        .insert(placeholder, key);
-                    operations.push(operation);
+                        .insert(placeholder, key.clone());
+                    operation
+                        .expression_attribute_values
+                        .insert(value_placeholder, value.clone().into());
+                    path.truncate(path_length);
                 }
             }
         }
-        let operation = common::ExpressionInput::merge(" ", operations);
-        Ok(operation)
+        Ok(())
+    }
+}
+
+/// Segment of an attribute path.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PathSegment {
+    /// Position of a list element.
+    Index(usize),
+    /// Name of an attribute or map entry.
+    Name(String),
+}
+
+/// Path of an attribute, used as an operand of SET operations.
+///
+/// ```rust
+/// use dynamodb_crud::write::update_item::AttributePath;
+///
+/// // #items[0].#price
+/// let price = AttributePath::new("items").index(0).name("price");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct AttributePath(pub Vec<PathSegment>);
+
+impl AttributePath {
+    /// Create a path to a top-level attribute.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(vec![PathSegment::Name(name.into())])
+    }
+
+    /// Append a map entry to the path.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.0.push(PathSegment::Name(name.into()));
+        self
+    }
+
+    /// Append a list element to the path.
+    pub fn index(mut self, index: usize) -> Self {
+        self.0.push(PathSegment::Index(index));
+        self
+    }
+
+    /// Get the expression of the path, registering the placeholders of its names.
+    fn get_expression(
+        &self,
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+    ) -> String {
+        let mut path = String::new();
+        for segment in &self.0 {
+            match segment {
+                PathSegment::Index(index) => {
+                    common::push_index(&mut path, *index);
+                }
+                PathSegment::Name(name) => {
+                    let (placeholder, _) = common::push_placeholder(&mut path, placeholders, name);
+                    operation
+                        .expression_attribute_names
+                        .insert(placeholder, name.clone());
+                }
+            }
+        }
+        path
     }
 }
 
@@ -70,6 +242,16 @@ impl<T: Serialize> AddOrDeleteInputsMap<T> {
 ///
 /// let assign = update_item::SetInput::Assign("value".to_string());
 /// let increment = update_item::SetInput::Increment(10);
+/// // tags = list_append(if_not_exists(tags, :set0), :set1)
+/// let tags = update_item::SetInput::ListAppendIfNotExists {
+///     default: vec![],
+///     values: vec!["new".to_string()],
+/// };
+/// // total = price + tax
+/// let total: update_item::SetInput<u32> = update_item::SetInput::AddPaths(
+///     update_item::AttributePath::new("price"),
+///     update_item::AttributePath::new("tax"),
+/// );
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum SetInput<T> {
@@ -85,42 +267,142 @@ pub enum SetInput<T> {
     ListPrepend(T),
     /// Assign a value only if the attribute doesn't exist.
     IfNotExists(T),
+    /// Append values to the end of a list attribute, starting from the default list if the
+    /// attribute doesn't exist.
+    ListAppendIfNotExists {
+        /// The list to start from, typically empty.
+        default: T,
+        /// The values to append.
+        values: T,
+    },
+    /// Prepend values to the beginning of a list attribute, starting from the default list if
+    /// the attribute doesn't exist.
+    ListPrependIfNotExists {
+        /// The list to start from, typically empty.
+        default: T,
+        /// The values to prepend.
+        values: T,
+    },
+    /// Assign the value of another attribute.
+    CopyFrom(AttributePath),
+    /// Assign the sum of two numeric attributes.
+    AddPaths(AttributePath, AttributePath),
+    /// Assign the difference of two numeric attributes.
+    SubtractPaths(AttributePath, AttributePath),
 }
 
-impl<T> SetInput<T> {
-    fn get_set_expression(self, path: &str, value_placeholder: &str) -> (T, String) {
+impl<T: Serialize> SetInput<T> {
+    fn write_set_expression(
+        &self,
+        path: &str,
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<()> {
+        let mut set_values = Vec::new();
+        let mut push_value = |placeholders: &mut common::PlaceholderAllocator, value| {
+            let value_placeholder = placeholders.allocate("set");
+            set_values.push((value_placeholder.clone(), value));
+            value_placeholder
+        };
         match self {
             SetInput::Assign(value) => {
-                let expression = format!("{path} = {value_placeholder}");
-                (value, expression)
+                let value = push_value(placeholders, value);
+                let _ = write!(operation.expression, "{path} = {value}");
             }
             SetInput::Increment(value) => {
-                let expression = format!("{path} = {path} + {value_placeholder}");
-                (value, expression)
+                let value = push_value(placeholders, value);
+                let _ = write!(operation.expression, "{path} = {path} + {value}");
             }
             SetInput::Decrement(value) => {
-                let expression = format!("{path} = {path} - {value_placeholder}");
-                (value, expression)
+                let value = push_value(placeholders, value);
+                let _ = write!(operation.expression, "{path} = {path} - {value}");
             }
             SetInput::ListAppend(value) => {
-                let expression = format!("{path} = list_append({path}, {value_placeholder})");
-                (value, expression)
+                let value = push_value(placeholders, value);
+                let _ = write!(
+                    operation.expression,
+                    "{path} = list_append({path}, {value})"
+                );
             }
             SetInput::ListPrepend(value) => {
-                let expression = format!("{path} = list_append({value_placeholder}, {path})");
-                (value, expression)
+                let value = push_value(placeholders, value);
+                let _ = write!(
+                    operation.expression,
+                    "{path} = list_append({value}, {path})"
+                );
             }
             SetInput::IfNotExists(value) => {
-                let expression = format!("{path} = if_not_exists({path}, {value_placeholder})");
-                (value, expression)
+                let value = push_value(placeholders, value);
+                let _ = write!(
+                    operation.expression,
+                    "{path} = if_not_exists({path}, {value})"
+                );
+            }
+            SetInput::ListAppendIfNotExists { default, values } => {
+                let default = push_value(placeholders, default);
+                let values = push_value(placeholders, values);
+                let _ = write!(
+                    operation.expression,
+                    "{path} = list_append(if_not_exists({path}, {default}), {values})"
+                );
+            }
+            SetInput::ListPrependIfNotExists { default, values } => {
+                let default = push_value(placeholders, default);
+                let values = push_value(placeholders, values);
+                let _ = write!(
+                    operation.expression,
+                    "{path} = list_append({values}, if_not_exists({path}, {default}))"
+                );
             }
+            SetInput::CopyFrom(source) => {
+                let source = source.get_expression(placeholders, operation);
+                let _ = write!(operation.expression, "{path} = {source}");
+            }
+            SetInput::AddPaths(left, right) => {
+                let left = left.get_expression(placeholders, operation);
+                let right = right.get_expression(placeholders, operation);
+                let _ = write!(operation.expression, "{path} = {left} + {right}");
+            }
+            SetInput::SubtractPaths(left, right) => {
+                let left = left.get_expression(placeholders, operation);
+                let right = right.get_expression(placeholders, operation);
+                let _ = write!(operation.expression, "{path} = {left} - {right}");
+            }
+        }
+        for (value_placeholder, value) in set_values {
+            let value = serializer.to_attribute_value(value)?;
+            operation
+                .expression_attribute_values
+                .insert(value_placeholder, value);
         }
+        Ok(())
     }
 }
 
 /// Map for SET operations.
+///
+/// List elements are targeted by position with the index variants:
+///
+/// ```rust
+/// use dynamodb_crud::write::update_item::{SetInput, SetInputsMap};
+/// use indexmap::IndexMap;
+///
+/// // SET #items[2].#qty = :set0
+/// let set: SetInputsMap<u32> = SetInputsMap::Node(IndexMap::from([(
+///     "items".to_string(),
+///     SetInputsMap::IndexNode(IndexMap::from([(
+///         2,
+///         SetInputsMap::Leaves(vec![("qty".to_string(), SetInput::Assign(3))]),
+///     )])),
+/// )]));
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum SetInputsMap<T> {
+    /// Index leaf operations - flat list of (list_element_position, set_operation) pairs.
+    IndexLeaves(Vec<(usize, SetInput<T>)>),
+    /// Index node operations - nested operations within list elements, by position.
+    IndexNode(IndexMap<usize, SetInputsMap<T>>),
     /// Leaf operations - flat list of (attribute_name, set_operation) pairs.
     Leaves(Vec<(String, SetInput<T>)>),
     /// Node operations - nested operations for hierarchical attribute paths.
@@ -128,47 +410,81 @@ pub enum SetInputsMap<T> {
 }
 
 impl<T: Serialize> SetInputsMap<T> {
-    fn get_set_expression_recursive(
-        self,
-        keys: &[String],
-        index: &mut usize,
-    ) -> Result<common::ExpressionInput> {
-        let mut operations = Vec::new();
+    fn write_set_expression_recursive(
+        &self,
+        path: &mut String,
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<()> {
+        let group_start = operation.expression.len();
         match self {
+            Self::IndexLeaves(leaves) => {
+                for (index, set_operation) in leaves {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, ", ", |operation| {
+                        set_operation.write_set_expression(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
+                }
+            }
+            Self::IndexNode(map) => {
+                for (index, value) in map {
+                    let path_length = common::push_index(path, *index);
+                    operation.push_expression(group_start, ", ", |operation| {
+                        value.write_set_expression_recursive(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
+                }
+            }
             Self::Leaves(leaves) => {
                 for (key, set_operation) in leaves {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &key);
-                    let path = new_keys.join(PATH_SEPARATOR);
-                    let value_placeholder = format!(":set{index}");
-                    let (value, expression) =
-                        set_operation.get_set_expression(&path, &value_placeholder);
-                    let value = to_attribute_value(value)?;
-                    let expression_attribute_names =
-                        collections::HashMap::from([(placeholder, key)]);
-                    let expression_attribute_values =
-                        collections::HashMap::from([(value_placeholder, value)]);
-                    *index += 1;
-                    let operation = common::ExpressionInput {
-                        expression,
-                        expression_attribute_names,
-                        expression_attribute_values,
-                    };
-                    operations.push(operation);
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
+                    operation
+                        .expression_attribute_names
+                        .insert(placeholder, key.clone());
+                    operation.push_expression(group_start, ", ", |operation| {
+                        set_operation.write_set_expression(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
                 }
             }
             Self::Node(map) => {
                 for (key, value) in map {
-                    let (placeholder, new_keys) = common::add_placeholder(keys, &key);
-                    let mut operation = value.get_set_expression_recursive(&new_keys, index)?;
+                    let (placeholder, path_length) =
+                        common::push_placeholder(path, placeholders, key);
                     operation
                         .expression_attribute_names
-                        .insert(placeholder, key);
-                    operations.push(operation);
+                        .insert(placeholder, key.clone());
+                    operation.push_expression(group_start, ", ", |operation| {
+                        value.write_set_expression_recursive(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
+                    path.truncate(path_length);
                 }
             }
         }
-        let operation = common::ExpressionInput::merge(", ", operations);
-        Ok(operation)
+        Ok(())
     }
 }
 
@@ -195,65 +511,146 @@ pub enum UpdateExpressionMap<T> {
     Set(SetInputsMap<T>),
     /// Combined operations - multiple operation types in a single update expression.
     Combined(Vec<UpdateExpressionMap<T>>),
+    /// Raw operations - a hand-written update expression fragment, clause keyword included,
+    /// for operations the other variants cannot express.
+    ///
+    /// Its placeholders are renamed to placeholders allocated for the operation, so they are
+    /// merged with the generated ones without colliding. Placeholders missing from the maps
+    /// are kept as is.
+    Raw {
+        /// The expression fragment, such as `SET #tags = list_append(#tags, :tags)`.
+        expression: String,
+        /// The attribute names of the name placeholders, such as `#tags`, by placeholder.
+        names: IndexMap<String, String>,
+        /// The values of the value placeholders, such as `:tags`, by placeholder.
+        values: IndexMap<String, T>,
+    },
 }
 
 impl<T: Serialize> UpdateExpressionMap<T> {
-    fn get_update_expression_recursive(
-        self,
-        keys: &[String],
-        index: &mut usize,
+    /// Build the update expression, converting the values with the given serializer.
+    pub(crate) fn get_expression_operation(
+        &self,
+        placeholders: &mut common::PlaceholderAllocator,
+        serializer: &impl common::serializer::ValueSerializer,
     ) -> Result<common::ExpressionInput> {
+        let mut operation = common::ExpressionInput::default();
+        self.write_update_expression_recursive(
+            &mut String::new(),
+            placeholders,
+            &mut operation,
+            serializer,
+        )?;
+        Ok(operation)
+    }
+
+    fn write_update_expression_recursive(
+        &self,
+        path: &mut String,
+        placeholders: &mut common::PlaceholderAllocator,
+        operation: &mut common::ExpressionInput,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<()> {
         match self {
             Self::Add(add_operations) => {
-                let mut operation =
-                    add_operations.get_add_or_delete_expression_recursive(keys, index)?;
-                operation.expression = format!("ADD {}", operation.expression);
-                Ok(operation)
+                operation.expression.push_str("ADD ");
+                add_operations.write_add_or_delete_expression_recursive(
+                    path,
+                    placeholders,
+                    operation,
+                    serializer,
+                )
             }
             Self::Delete(delete_operations) => {
-                let mut operation =
-                    delete_operations.get_add_or_delete_expression_recursive(keys, index)?;
-                operation.expression = format!("DELETE {}", operation.expression);
-                Ok(operation)
+                operation.expression.push_str("DELETE ");
+                delete_operations.write_add_or_delete_expression_recursive(
+                    path,
+                    placeholders,
+                    operation,
+                    serializer,
+                )
             }
             Self::Remove(remove_operations) => {
-                let mut operation = remove_operations.get_selection_operation_recursive(keys);
-                operation.expression = format!("REMOVE {}", operation.expression);
-                Ok(operation)
+                operation.expression.push_str("REMOVE ");
+                remove_operations.write_selection_recursive(path, placeholders, operation);
+                Ok(())
             }
             Self::Set(set_operations) => {
-                let mut operation = set_operations.get_set_expression_recursive(keys, index)?;
-                operation.expression = format!("SET {}", operation.expression);
-                Ok(operation)
+                operation.expression.push_str("SET ");
+                set_operations.write_set_expression_recursive(
+                    path,
+                    placeholders,
+                    operation,
+                    serializer,
+                )
             }
             Self::Combined(combined_operations) => {
-                let mut operations = Vec::with_capacity(combined_operations.len());
-                for operation in combined_operations {
-                    let operation = operation.get_update_expression_recursive(keys, index)?;
-                    operations.push(operation);
+                let group_start = operation.expression.len();
+                for combined_operation in combined_operations {
+                    operation.push_expression(group_start, " ", |operation| {
+                        combined_operation.write_update_expression_recursive(
+                            path,
+                            placeholders,
+                            operation,
+                            serializer,
+                        )
+                    })?;
                 }
-                let operation = common::ExpressionInput::merge(" ", operations);
-                Ok(operation)
+                Ok(())
             }
+            Self::Raw {
+                expression,
+                names,
+                values,
+            } => operation.push_raw_expression(expression, names, values, placeholders, serializer),
         }
     }
 }
 
-impl<T: Serialize> TryFrom<UpdateExpressionMap<T>> for common::ExpressionInput {
+impl<T: Serialize> TryFrom<&UpdateExpressionMap<T>> for common::ExpressionInput {
     type Error = Error;
 
-    fn try_from(update_expression_map: UpdateExpressionMap<T>) -> Result<Self> {
-        let mut index = 0;
-        update_expression_map.get_update_expression_recursive(&[], &mut index)
+    fn try_from(update_expression_map: &UpdateExpressionMap<T>) -> Result<Self> {
+        update_expression_map.get_expression_operation(
+            &mut common::PlaceholderAllocator::default(),
+            &common::serializer::SerdeDynamo,
+        )
+    }
+}
+
+/// Item attributes returned by an update, restricted to the values DynamoDB accepts for it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UpdateReturnValues {
+    /// The whole item as it is after the update.
+    AllNew,
+    /// The whole item as it was before the update.
+    AllOld,
+    /// Nothing.
+    None,
+    /// The updated attributes as they are after the update.
+    UpdatedNew,
+    /// The updated attributes as they were before the update.
+    UpdatedOld,
+}
+
+impl From<UpdateReturnValues> for types::ReturnValue {
+    fn from(return_values: UpdateReturnValues) -> Self {
+        match return_values {
+            UpdateReturnValues::AllNew => Self::AllNew,
+            UpdateReturnValues::AllOld => Self::AllOld,
+            UpdateReturnValues::None => Self::None,
+            UpdateReturnValues::UpdatedNew => Self::UpdatedNew,
+            UpdateReturnValues::UpdatedOld => Self::UpdatedOld,
+        }
     }
 }
 
 /// update item operation
 #[derive(Clone, Debug, Default, PartialEq)]
-struct UpdateItemInput {
-    keys: collections::HashMap<String, types::AttributeValue>,
-    update_expression: String,
-    write_operation: write::common::WriteInput,
+pub(crate) struct UpdateItemInput {
+    pub(crate) keys: collections::HashMap<String, types::AttributeValue>,
+    pub(crate) update_expression: String,
+    pub(crate) write_operation: write::common::WriteInput,
 }
 
 /// Update item operation.
@@ -271,6 +668,7 @@ struct UpdateItemInput {
 ///         },
 ///         ..Default::default()
 ///     },
+///     return_values: None,
 ///     update_expression: write::update_item::UpdateExpressionMap::Set(
 ///         write::update_item::SetInputsMap::Leaves(vec![
 ///             ("name".to_string(), write::update_item::SetInput::Assign("New".to_string())),
@@ -289,20 +687,40 @@ struct UpdateItemInput {
 pub struct UpdateItem<T> {
     /// The primary key of the item to update.
     pub keys: common::key::Keys<T>,
+    /// Which item attributes to return in the response.
+    pub return_values: Option<UpdateReturnValues>,
     /// The update expression specifying what changes to make.
     pub update_expression: UpdateExpressionMap<T>,
-    /// Additional write operation arguments (table name, condition, return values, etc.).
+    /// Additional write operation arguments (table name, condition, etc.).
     pub write_args: write::common::WriteArgs<T>,
 }
 
-impl<T: Serialize> TryFrom<UpdateItem<T>> for UpdateItemInput {
-    type Error = Error;
+/// Build the update expression and merge its placeholders into the write operation.
+pub(crate) fn get_update_operation<T: Serialize>(
+    update_expression: &UpdateExpressionMap<T>,
+    write_args: write::common::WriteArgs<T>,
+    serializer: &impl common::serializer::ValueSerializer,
+) -> Result<(String, write::common::WriteInput)> {
+    let placeholders =
+        &mut common::PlaceholderAllocator::new(write_args.placeholder_naming.clone());
+    let mut write_operation = write::common::WriteInput::new(write_args, placeholders, serializer)?;
+    let operation = update_expression.get_expression_operation(placeholders, serializer)?;
+    let update_expression = write_operation.merge_expression(operation);
+    Ok((update_expression, write_operation))
+}
 
-    fn try_from(update_item: UpdateItem<T>) -> Result<Self> {
-        let keys = update_item.keys.try_into()?;
-        let mut write_operation: write::common::WriteInput = update_item.write_args.try_into()?;
-        let operation = update_item.update_expression.try_into()?;
-        let update_expression = write_operation.merge_expression(operation);
+impl UpdateItemInput {
+    pub(crate) fn new<T: Serialize>(
+        update_item: UpdateItem<T>,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<Self> {
+        let keys = update_item.keys.serialize(serializer)?;
+        let (update_expression, mut write_operation) = get_update_operation(
+            &update_item.update_expression,
+            update_item.write_args,
+            serializer,
+        )?;
+        write_operation.return_values = update_item.return_values.map(Into::into);
         let operation = Self {
             keys,
             update_expression,
@@ -312,27 +730,349 @@ impl<T: Serialize> TryFrom<UpdateItem<T>> for UpdateItemInput {
     }
 }
 
+impl<T: Serialize> TryFrom<UpdateItem<T>> for UpdateItemInput {
+    type Error = Error;
+
+    fn try_from(update_item: UpdateItem<T>) -> Result<Self> {
+        Self::new(update_item, &common::serializer::SerdeDynamo)
+    }
+}
+
+/// Outcome of a compare-and-swap update, see [`UpdateItem::send_cas`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum CasOutcome<D> {
+    /// The condition was met, along with the updated item.
+    Updated(D),
+    /// The condition was not met, along with the current item if it exists.
+    Conflict(Option<D>),
+}
+
+/// Turn the result of an update item call into a compare-and-swap outcome.
+///
+/// The updated and current items are expected to be already trimmed to the return selection.
+fn get_cas_outcome<D: DeserializeOwned>(
+    result: Result<operation::update_item::UpdateItemOutput, crate::Error>,
+) -> Result<CasOutcome<D>, crate::Error> {
+    match result {
+        Ok(output) => {
+            let item = serde_dynamo::from_item(output.attributes.unwrap_or_default())
+                .map_err(crate::Error::Deserialization)?;
+            Ok(CasOutcome::Updated(item))
+        }
+        Err(crate::Error::ConditionalCheckFailed(item)) => {
+            let item = item
+                .map(serde_dynamo::from_item)
+                .transpose()
+                .map_err(crate::Error::Deserialization)?;
+            Ok(CasOutcome::Conflict(item))
+        }
+        Err(error) => Err(error),
+    }
+}
+
 impl<T: Serialize> UpdateItem<T> {
     /// Execute the update item operation.
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<operation::update_item::UpdateItemOutput, crate::Error> {
+        self.send_with(client, &common::serializer::SerdeDynamo)
+            .await
+    }
+
+    /// Execute the update item operation, converting the values with the given serializer.
     #[cfg_attr(
         feature = "tracing",
-        tracing::instrument(name = "dynamodb_crud.update_item", err)
+        tracing::instrument(name = "dynamodb_crud.update_item", skip_all, err)
     )]
-    pub async fn send(
+    pub async fn send_with(
         self,
         client: &Client,
-    ) -> Result<
-        operation::update_item::UpdateItemOutput,
-        error::SdkError<operation::update_item::UpdateItemError>,
-    > {
-        let update_item: UpdateItemInput = self.try_into().map_err(error::BuildError::other)?;
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::update_item::UpdateItemOutput, crate::Error> {
+        let return_selection = self.write_args.return_selection.clone();
+        let retry_policy = self.write_args.retry_policy.clone();
+        let update_item =
+            UpdateItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
         let builder = client
             .update_item()
             .set_key(Some(update_item.keys))
             .update_expression(update_item.update_expression);
-        crate::apply_write_operation!(builder, update_item.write_operation)
+        let mut output = crate::apply_write_operation!(builder, update_item.write_operation)
+            .customize()
+            .with_retry_policy(client, retry_policy.as_ref())
             .send()
             .await
+            .map_err(|error| {
+                write::common::get_write_error(error.into(), return_selection.as_ref())
+            })?;
+        output.attributes =
+            write::common::select_returned_attributes(output.attributes, return_selection.as_ref());
+        Ok(output)
+    }
+
+    /// Build the update item request without sending it, such as to log or snapshot-test the
+    /// generated expressions.
+    pub fn dry_run(self) -> Result<operation::update_item::UpdateItemInput, crate::Error> {
+        self.dry_run_with(&common::serializer::SerdeDynamo)
+    }
+
+    /// Build the update item request without sending it, converting the values with the given
+    /// serializer.
+    pub fn dry_run_with(
+        self,
+        serializer: &impl common::serializer::ValueSerializer,
+    ) -> Result<operation::update_item::UpdateItemInput, crate::Error> {
+        let update_item =
+            UpdateItemInput::new(self, serializer).map_err(crate::Error::Serialization)?;
+        let builder = operation::update_item::UpdateItemInput::builder()
+            .set_key(Some(update_item.keys))
+            .update_expression(update_item.update_expression);
+        let input = crate::apply_write_operation!(builder, update_item.write_operation)
+            .build()
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(input)
+    }
+
+    /// Execute the update item operation as a compare-and-swap, returning the typed item.
+    ///
+    /// Returns the updated item if the condition is met, and the current item instead of an
+    /// error if it is not, so the caller can retry with a condition matching it.
+    ///
+    /// ```rust,no_run
+    /// use aws_sdk_dynamodb::Client;
+    /// use dynamodb_crud::{common, write};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Counter {
+    ///     id: String,
+    ///     value: u64,
+    /// }
+    ///
+    /// # async fn example(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let update_item = write::update_item::UpdateItem {
+    ///     keys: common::key::Keys {
+    ///         partition_key: common::key::Key {
+    ///             name: "id".to_string(),
+    ///             value: serde_json::json!("a"),
+    ///         },
+    ///         ..Default::default()
+    ///     },
+    ///     return_values: None,
+    ///     update_expression: write::update_item::UpdateExpressionMap::Set(
+    ///         write::update_item::SetInputsMap::Leaves(vec![(
+    ///             "value".to_string(),
+    ///             write::update_item::SetInput::Assign(serde_json::json!(2)),
+    ///         )]),
+    ///     ),
+    ///     write_args: write::common::WriteArgs {
+    ///         condition: Some(common::condition::ConditionMap::Leaves(
+    ///             common::condition::LogicalOperator::And,
+    ///             vec![common::condition::KeyCondition {
+    ///                 name: "value".to_string(),
+    ///                 condition: common::condition::Condition::Equals(serde_json::json!(1)),
+    ///             }],
+    ///         )),
+    ///         table_name: "counters".to_string(),
+    ///         ..Default::default()
+    ///     },
+    /// };
+    /// match update_item.send_cas::<Counter>(client).await? {
+    ///     write::update_item::CasOutcome::Updated(counter) => println!("{}", counter.value),
+    ///     write::update_item::CasOutcome::Conflict(counter) => {
+    ///         println!("conflict: {:?}", counter.map(|counter| counter.value))
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dynamodb_crud.update_item.send_cas", skip_all, err)
+    )]
+    pub async fn send_cas<D: DeserializeOwned>(
+        mut self,
+        client: &Client,
+    ) -> Result<CasOutcome<D>, crate::Error> {
+        self.return_values = Some(UpdateReturnValues::AllNew);
+        self.write_args.return_values_on_condition_check_failure =
+            Some(types::ReturnValuesOnConditionCheckFailure::AllOld);
+        get_cas_outcome(self.send(client).await)
+    }
+}
+
+impl<T> UpdateItem<T> {
+    /// Start building an update of the item of the given table with the given partition key.
+    ///
+    /// ```rust
+    /// use dynamodb_crud::{common, write};
+    ///
+    /// # fn example() -> Result<(), dynamodb_crud::Error> {
+    /// let update_item: write::update_item::UpdateItem<String> =
+    ///     write::update_item::UpdateItem::builder("users", "id", "1")
+    ///         .sort_key("createdAt", "2024-01-01")
+    ///         .set("name", "Jane")
+    ///         .remove("legacy")
+    ///         .condition(common::condition::ConditionMap::attribute_exists("id"))
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder(
+        table_name: impl Into<String>,
+        partition_key_name: impl Into<String>,
+        partition_key_value: impl Into<T>,
+    ) -> UpdateItemBuilder<T> {
+        UpdateItemBuilder {
+            adds: Vec::new(),
+            condition: None,
+            deletes: Vec::new(),
+            partition_key: common::key::Key {
+                name: partition_key_name.into(),
+                value: partition_key_value.into(),
+            },
+            removes: Vec::new(),
+            return_values: None,
+            sets: Vec::new(),
+            sort_key: None,
+            table_name: table_name.into(),
+        }
+    }
+}
+
+/// Builder of an [`UpdateItem`], see [`UpdateItem::builder`].
+///
+/// Every operation of the same kind is gathered into a single clause, in call order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateItemBuilder<T> {
+    adds: Vec<(String, T)>,
+    condition: Option<common::condition::ConditionMap<T>>,
+    deletes: Vec<(String, T)>,
+    partition_key: common::key::Key<T>,
+    removes: Vec<String>,
+    return_values: Option<UpdateReturnValues>,
+    sets: Vec<(String, SetInput<T>)>,
+    sort_key: Option<common::key::Key<T>>,
+    table_name: String,
+}
+
+impl<T> UpdateItemBuilder<T> {
+    /// Set the sort key of the item, for tables with one.
+    pub fn sort_key(mut self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.sort_key = Some(common::key::Key {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Assign a value to an attribute.
+    pub fn set(self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.set_input(name, SetInput::Assign(value.into()))
+    }
+
+    /// Increment a numeric attribute.
+    pub fn increment(self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.set_input(name, SetInput::Increment(value.into()))
+    }
+
+    /// Decrement a numeric attribute.
+    pub fn decrement(self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.set_input(name, SetInput::Decrement(value.into()))
+    }
+
+    /// Apply any SET operation to an attribute.
+    pub fn set_input(mut self, name: impl Into<String>, set_input: SetInput<T>) -> Self {
+        self.sets.push((name.into(), set_input));
+        self
+    }
+
+    /// Remove an attribute.
+    pub fn remove(mut self, name: impl Into<String>) -> Self {
+        self.removes.push(name.into());
+        self
+    }
+
+    /// Add a value to a number or set attribute.
+    pub fn add(mut self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.adds.push((name.into(), value.into()));
+        self
+    }
+
+    /// Delete values from a set attribute.
+    pub fn delete(mut self, name: impl Into<String>, value: impl Into<T>) -> Self {
+        self.deletes.push((name.into(), value.into()));
+        self
+    }
+
+    /// Which item attributes to return in the response.
+    pub fn return_values(mut self, return_values: UpdateReturnValues) -> Self {
+        self.return_values = Some(return_values);
+        self
+    }
+
+    /// Require a condition for the update to succeed, combined with any previous one.
+    pub fn condition(mut self, condition: impl Into<common::condition::ConditionMap<T>>) -> Self {
+        let condition = match self.condition {
+            Some(previous) => previous.and(condition.into()),
+            None => condition.into(),
+        };
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Build the update item operation.
+    ///
+    /// Fails with [`Validation`](crate::Error::Validation) if no operation was added.
+    pub fn build(self) -> Result<UpdateItem<T>, crate::Error> {
+        let mut expressions = Vec::new();
+        if !self.sets.is_empty() {
+            expressions.push(UpdateExpressionMap::Set(SetInputsMap::Leaves(self.sets)));
+        }
+        if !self.removes.is_empty() {
+            expressions.push(UpdateExpressionMap::Remove(
+                common::selection::SelectionMap::Leaves(self.removes),
+            ));
+        }
+        if !self.adds.is_empty() {
+            expressions.push(UpdateExpressionMap::Add(AddOrDeleteInputsMap::Leaves(
+                self.adds,
+            )));
+        }
+        if !self.deletes.is_empty() {
+            expressions.push(UpdateExpressionMap::Delete(AddOrDeleteInputsMap::Leaves(
+                self.deletes,
+            )));
+        }
+        let update_expression = match expressions.len() {
+            0 => {
+                return Err(crate::Error::validation(
+                    "the update item must set, remove, add or delete at least one attribute",
+                ));
+            }
+            1 => expressions.remove(0),
+            _ => UpdateExpressionMap::Combined(expressions),
+        };
+        let update_item = UpdateItem {
+            keys: common::key::Keys {
+                partition_key: self.partition_key,
+                sort_key: self.sort_key,
+            },
+            return_values: self.return_values,
+            update_expression,
+            write_args: write::common::WriteArgs {
+                condition: self.condition,
+                placeholder_naming: None,
+                return_consumed_capacity: None,
+                return_item_collection_metrics: None,
+                return_selection: None,
+                return_values_on_condition_check_failure: None,
+                retry_policy: None,
+                table_name: self.table_name,
+            },
+        };
+        Ok(update_item)
     }
 }
 
@@ -340,6 +1080,7 @@ impl<T: Serialize> UpdateItem<T> {
 mod tests {
     use super::*;
 
+    use aws_sdk_dynamodb::error;
     use rstest::rstest;
     use serde_json::Value;
 
@@ -361,12 +1102,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #attr = :set0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#attr".to_string(), "attr".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -395,12 +1136,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #count = #count + :set0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#count".to_string(), "count".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -429,12 +1170,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #count = #count - :set0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#count".to_string(), "count".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -467,12 +1208,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #list = list_append(#list, :set0)".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#list".to_string(), "list".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -509,12 +1250,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #list = list_append(:set0, #list)".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#list".to_string(), "list".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -547,12 +1288,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #attr = if_not_exists(#attr, :set0)".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#attr".to_string(), "attr".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -589,13 +1330,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #attr1 = :set0, #attr2 = :set1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#attr1".to_string(), "attr1".to_string()),
                     ("#attr2".to_string(), "attr2".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -613,47 +1354,172 @@ mod tests {
             ),
         }
     )]
-    #[case::remove_single(
-        UpdateExpressionMap::Remove(
-            common::selection::SelectionMap::Leaves(
+    #[case::set_list_append_if_not_exists(
+        UpdateExpressionMap::Set(
+            SetInputsMap::Leaves(
                 vec![
-                    "attr".to_string(),
+                    (
+                        "a".to_string(),
+                        SetInput::ListAppendIfNotExists {
+                            default: Value::Array(vec![]),
+                            values: Value::Array(
+                                vec![
+                                    Value::from(1),
+                                ]
+                            ),
+                        }
+                    ),
+                    (
+                        "b".to_string(),
+                        SetInput::ListPrependIfNotExists {
+                            default: Value::Array(vec![]),
+                            values: Value::Array(
+                                vec![
+                                    Value::from(2),
+                                ]
+                            ),
+                        }
+                    ),
                 ]
             )
         ),
         common::ExpressionInput {
-            expression: "REMOVE #attr".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression: "SET #a = list_append(if_not_exists(#a, :set0), :set1), #b = list_append(:set3, if_not_exists(#b, :set2))".to_string(),
+            expression_attribute_names: IndexMap::from(
                 [
-                    ("#attr".to_string(), "attr".to_string()),
+                    ("#a".to_string(), "a".to_string()),
+                    ("#b".to_string(), "b".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":set0".to_string(),
+                        types::AttributeValue::L(vec![])
+                    ),
+                    (
+                        ":set1".to_string(),
+                        types::AttributeValue::L(
+                            vec![
+                                types::AttributeValue::N("1".to_string()),
+                            ]
+                        )
+                    ),
+                    (
+                        ":set2".to_string(),
+                        types::AttributeValue::L(vec![])
+                    ),
+                    (
+                        ":set3".to_string(),
+                        types::AttributeValue::L(
+                            vec![
+                                types::AttributeValue::N("2".to_string()),
+                            ]
+                        )
+                    ),
                 ]
             ),
-            ..Default::default()
         }
     )]
-    #[case::remove_multiple(
-        UpdateExpressionMap::Remove(
-            common::selection::SelectionMap::Leaves(
+    #[case::set_paths(
+        UpdateExpressionMap::Set(
+            SetInputsMap::Leaves(
                 vec![
-                    "attr1".to_string(),
-                    "attr2".to_string(),
+                    (
+                        "total".to_string(),
+                        SetInput::AddPaths(
+                            AttributePath::new("price"),
+                            AttributePath::new("tax"),
+                        )
+                    ),
+                    (
+                        "net".to_string(),
+                        SetInput::SubtractPaths(
+                            AttributePath::new("total"),
+                            AttributePath::new("items").index(0).name("price"),
+                        )
+                    ),
+                    (
+                        "copy".to_string(),
+                        SetInput::CopyFrom(
+                            AttributePath::new("price")
+                        )
+                    ),
+                    (
+                        "a".to_string(),
+                        SetInput::Assign(
+                            Value::from(1)
+                        )
+                    ),
                 ]
             )
         ),
         common::ExpressionInput {
-            expression: "REMOVE #attr1, #attr2".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression: "SET #total = #price + #tax, #net = #total - #items[0].#price, #copy = #price, #a = :set0".to_string(),
+            expression_attribute_names: IndexMap::from(
                 [
-                    ("#attr1".to_string(), "attr1".to_string()),
-                    ("#attr2".to_string(), "attr2".to_string()),
+                    ("#total".to_string(), "total".to_string()),
+                    ("#price".to_string(), "price".to_string()),
+                    ("#tax".to_string(), "tax".to_string()),
+                    ("#net".to_string(), "net".to_string()),
+                    ("#items".to_string(), "items".to_string()),
+                    ("#copy".to_string(), "copy".to_string()),
+                    ("#a".to_string(), "a".to_string()),
                 ]
             ),
-            ..Default::default()
-        }
-    )]
-    #[case::add_number(
-        UpdateExpressionMap::Add(
-            AddOrDeleteInputsMap::Leaves(
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":set0".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
+    #[case::remove_single(
+        UpdateExpressionMap::Remove(
+            common::selection::SelectionMap::Leaves(
+                vec![
+                    "attr".to_string(),
+                ]
+            )
+        ),
+        common::ExpressionInput {
+            expression: "REMOVE #attr".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#attr".to_string(), "attr".to_string()),
+                ]
+            ),
+            ..Default::default()
+        }
+    )]
+    #[case::remove_multiple(
+        UpdateExpressionMap::Remove(
+            common::selection::SelectionMap::Leaves(
+                vec![
+                    "attr1".to_string(),
+                    "attr2".to_string(),
+                ]
+            )
+        ),
+        common::ExpressionInput {
+            expression: "REMOVE #attr1, #attr2".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#attr1".to_string(), "attr1".to_string()),
+                    ("#attr2".to_string(), "attr2".to_string()),
+                ]
+            ),
+            ..Default::default()
+        }
+    )]
+    #[case::add_number(
+        UpdateExpressionMap::Add(
+            AddOrDeleteInputsMap::Leaves(
                 vec![
                     (
                         "count".to_string(),
@@ -666,12 +1532,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "ADD #count :add_or_delete0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#count".to_string(), "count".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":add_or_delete0".to_string(),
@@ -705,12 +1571,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "ADD #tags :add_or_delete0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#tags".to_string(), "tags".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":add_or_delete0".to_string(),
@@ -729,6 +1595,77 @@ mod tests {
             ),
         }
     )]
+    #[case::add_typed_sets(
+        UpdateExpressionMap::Add(
+            AddOrDeleteInputsMap::SetLeaves(
+                vec![
+                    (
+                        "a".to_string(),
+                        SetValue::StringSet(
+                            vec![
+                                "b".to_string(),
+                            ]
+                        )
+                    ),
+                    (
+                        "c".to_string(),
+                        SetValue::numbers(
+                            [1, 2]
+                        )
+                    ),
+                    (
+                        "d".to_string(),
+                        SetValue::BinarySet(
+                            vec![
+                                vec![0, 1],
+                            ]
+                        )
+                    ),
+                ]
+            )
+        ),
+        common::ExpressionInput {
+            expression: "ADD #a :add_or_delete0 #c :add_or_delete1 #d :add_or_delete2".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                    ("#c".to_string(), "c".to_string()),
+                    ("#d".to_string(), "d".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":add_or_delete0".to_string(),
+                        types::AttributeValue::Ss(
+                            vec![
+                                "b".to_string(),
+                            ]
+                        )
+                    ),
+                    (
+                        ":add_or_delete1".to_string(),
+                        types::AttributeValue::Ns(
+                            vec![
+                                "1".to_string(),
+                                "2".to_string(),
+                            ]
+                        )
+                    ),
+                    (
+                        ":add_or_delete2".to_string(),
+                        types::AttributeValue::Bs(
+                            vec![
+                                primitives::Blob::new(
+                                    vec![0, 1]
+                                ),
+                            ]
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
     #[case::delete_set(
         UpdateExpressionMap::Delete(
             AddOrDeleteInputsMap::Leaves(
@@ -748,12 +1685,12 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "DELETE #tags :add_or_delete0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#tags".to_string(), "tags".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":add_or_delete0".to_string(),
@@ -795,13 +1732,13 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #user.#name = :set0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#user".to_string(), "user".to_string()),
                     ("#name".to_string(), "name".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -848,14 +1785,14 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #user.#profile.#email = :set0".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#user".to_string(), "user".to_string()),
                     ("#profile".to_string(), "profile".to_string()),
                     ("#email".to_string(), "email".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -867,6 +1804,157 @@ mod tests {
             ),
         }
     )]
+    #[case::list_index_set(
+        UpdateExpressionMap::Set(
+            SetInputsMap::Node(
+                IndexMap::from(
+                    [
+                        (
+                            "items".to_string(),
+                            SetInputsMap::IndexNode(
+                                IndexMap::from(
+                                    [
+                                        (
+                                            2,
+                                            SetInputsMap::Leaves(
+                                                vec![
+                                                    (
+                                                        "qty".to_string(),
+                                                        SetInput::Assign(
+                                                            Value::Number(
+                                                                3.into()
+                                                            )
+                                                        )
+                                                    ),
+                                                ]
+                                            )
+                                        ),
+                                    ]
+                                )
+                            )
+                        ),
+                        (
+                            "matrix".to_string(),
+                            SetInputsMap::IndexNode(
+                                IndexMap::from(
+                                    [
+                                        (
+                                            0,
+                                            SetInputsMap::IndexLeaves(
+                                                vec![
+                                                    (
+                                                        2,
+                                                        SetInput::Increment(
+                                                            Value::Number(
+                                                                1.into()
+                                                            )
+                                                        )
+                                                    ),
+                                                ]
+                                            )
+                                        ),
+                                    ]
+                                )
+                            )
+                        ),
+                    ]
+                )
+            )
+        ),
+        common::ExpressionInput {
+            expression: "SET #items[2].#qty = :set0, #matrix[0][2] = #matrix[0][2] + :set1"
+                .to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#items".to_string(), "items".to_string()),
+                    ("#qty".to_string(), "qty".to_string()),
+                    ("#matrix".to_string(), "matrix".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":set0".to_string(),
+                        types::AttributeValue::N(
+                            "3".to_string()
+                        )
+                    ),
+                    (
+                        ":set1".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
+    #[case::list_index_remove(
+        UpdateExpressionMap::Remove(
+            common::selection::SelectionMap::Node(
+                IndexMap::from(
+                    [
+                        (
+                            "items".to_string(),
+                            common::selection::SelectionMap::IndexLeaves(
+                                vec![0]
+                            )
+                        ),
+                    ]
+                )
+            )
+        ),
+        common::ExpressionInput {
+            expression: "REMOVE #items[0]".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#items".to_string(), "items".to_string()),
+                ]
+            ),
+            ..Default::default()
+        }
+    )]
+    #[case::list_index_add(
+        UpdateExpressionMap::Add(
+            AddOrDeleteInputsMap::Node(
+                IndexMap::from(
+                    [
+                        (
+                            "counts".to_string(),
+                            AddOrDeleteInputsMap::IndexLeaves(
+                                vec![
+                                    (
+                                        1,
+                                        Value::Number(
+                                            5.into()
+                                        )
+                                    ),
+                                ]
+                            )
+                        ),
+                    ]
+                )
+            )
+        ),
+        common::ExpressionInput {
+            expression: "ADD #counts[1] :add_or_delete0".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#counts".to_string(), "counts".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":add_or_delete0".to_string(),
+                        types::AttributeValue::N(
+                            "5".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
     #[case::combined_operations(
         UpdateExpressionMap::Combined(
             vec![
@@ -907,14 +1995,14 @@ mod tests {
         ),
         common::ExpressionInput {
             expression: "SET #attr1 = :set0 REMOVE #oldAttr ADD #count :add_or_delete1".to_string(),
-            expression_attribute_names: collections::HashMap::from(
+            expression_attribute_names: IndexMap::from(
                 [
                     ("#attr1".to_string(), "attr1".to_string()),
                     ("#oldAttr".to_string(), "oldAttr".to_string()),
                     ("#count".to_string(), "count".to_string()),
                 ]
             ),
-            expression_attribute_values: collections::HashMap::from(
+            expression_attribute_values: IndexMap::from(
                 [
                     (
                         ":set0".to_string(),
@@ -932,11 +2020,60 @@ mod tests {
             ),
         }
     )]
+    #[case::raw(
+        UpdateExpressionMap::Combined(
+            vec![
+                UpdateExpressionMap::Set(
+                    SetInputsMap::Leaves(
+                        vec![
+                            (
+                                "a".to_string(),
+                                SetInput::Assign(
+                                    Value::String(
+                                        "b".to_string()
+                                    )
+                                )
+                            ),
+                        ]
+                    )
+                ),
+                UpdateExpressionMap::Raw {
+                    expression: "ADD #x :y".to_string(),
+                    names: IndexMap::from([("#x".to_string(), "a".to_string())]),
+                    values: IndexMap::from([(":y".to_string(), Value::from(1))]),
+                },
+            ]
+        ),
+        common::ExpressionInput {
+            expression: "SET #a = :set0 ADD #a :raw1".to_string(),
+            expression_attribute_names: IndexMap::from(
+                [
+                    ("#a".to_string(), "a".to_string()),
+                ]
+            ),
+            expression_attribute_values: IndexMap::from(
+                [
+                    (
+                        ":set0".to_string(),
+                        types::AttributeValue::S(
+                            "b".to_string()
+                        )
+                    ),
+                    (
+                        ":raw1".to_string(),
+                        types::AttributeValue::N(
+                            "1".to_string()
+                        )
+                    ),
+                ]
+            ),
+        }
+    )]
     fn test_update_expression_map(
         #[case] update_expression_map: UpdateExpressionMap<Value>,
         #[case] expected: common::ExpressionInput,
     ) {
-        let actual: common::ExpressionInput = update_expression_map.try_into().unwrap();
+        let actual: common::ExpressionInput = (&update_expression_map).try_into().unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -952,6 +2089,7 @@ mod tests {
                 },
                 ..Default::default()
             },
+            return_values: None,
             update_expression: UpdateExpressionMap::Set(
                 SetInputsMap::Leaves(
                     vec![
@@ -1024,6 +2162,9 @@ mod tests {
                     }
                 ),
             },
+            return_values: Some(
+                UpdateReturnValues::AllOld
+            ),
             update_expression: UpdateExpressionMap::Set(
                 SetInputsMap::Leaves(
                     vec![
@@ -1054,18 +2195,18 @@ mod tests {
                         ]
                     )
                 ),
+                placeholder_naming: None,
                 return_consumed_capacity: Some(
                     types::ReturnConsumedCapacity::Total
                 ),
                 return_item_collection_metrics: Some(
                     types::ReturnItemCollectionMetrics::Size
                 ),
-                return_values: Some(
-                    types::ReturnValue::AllOld
-                ),
+                return_selection: None,
                 return_values_on_condition_check_failure: Some(
                     types::ReturnValuesOnConditionCheckFailure::AllOld
                 ),
+                retry_policy: None,
                 table_name: "g".to_string(),
             },
         },
@@ -1086,7 +2227,7 @@ mod tests {
                     ),
                 ]
             ),
-            update_expression: "SET #c = :set0".to_string(),
+            update_expression: "SET #c = :set1".to_string(),
             write_operation: write::common::WriteInput {
                 condition_expression: Some(
                     "#e = :e_eq0".to_string()
@@ -1109,7 +2250,7 @@ mod tests {
                                 )
                             ),
                             (
-                                ":set0".to_string(),
+                                ":set1".to_string(),
                                 types::AttributeValue::S(
                                     "d".to_string()
                                 )
@@ -1137,4 +2278,228 @@ mod tests {
         let actual: UpdateItemInput = args.try_into().unwrap();
         assert_eq!(actual, expected);
     }
+
+    fn get_conflict(
+        item: Option<collections::HashMap<String, types::AttributeValue>>,
+    ) -> crate::Error {
+        let exception = types::error::ConditionalCheckFailedException::builder()
+            .set_item(item)
+            .build();
+        error::SdkError::service_error(
+            operation::update_item::UpdateItemError::ConditionalCheckFailedException(exception),
+            aws_sdk_dynamodb::config::http::HttpResponse::new(
+                400.try_into().unwrap(),
+                aws_smithy_types::body::SdkBody::empty(),
+            ),
+        )
+        .into()
+    }
+
+    #[rstest]
+    #[case::updated(
+        Ok(
+            operation::update_item::UpdateItemOutput::builder()
+                .attributes(
+                    "a",
+                    types::AttributeValue::N(
+                        "2".to_string()
+                    ),
+                )
+                .build()
+        ),
+        CasOutcome::Updated(
+            serde_json::json!(
+                {
+                    "a": 2
+                }
+            )
+        )
+    )]
+    #[case::conflict(
+        Err(
+            get_conflict(
+                Some(
+                    collections::HashMap::from(
+                        [
+                            (
+                                "a".to_string(),
+                                types::AttributeValue::N(
+                                    "1".to_string()
+                                )
+                            ),
+                        ]
+                    )
+                )
+            )
+        ),
+        CasOutcome::Conflict(
+            Some(
+                serde_json::json!(
+                    {
+                        "a": 1
+                    }
+                )
+            )
+        )
+    )]
+    #[case::conflict_missing(Err(get_conflict(None)), CasOutcome::Conflict(None))]
+    fn test_get_cas_outcome(
+        #[case] result: Result<operation::update_item::UpdateItemOutput, crate::Error>,
+        #[case] expected: CasOutcome<Value>,
+    ) {
+        let actual = get_cas_outcome(result).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_cas_outcome_error() {
+        let result = Err(
+            error::SdkError::<operation::update_item::UpdateItemError>::timeout_error("timeout")
+                .into(),
+        );
+        let actual = get_cas_outcome::<Value>(result);
+        assert!(matches!(actual, Err(crate::Error::DynamoDb(_))));
+    }
+
+    struct UppercaseSerializer;
+
+    impl common::serializer::ValueSerializer for UppercaseSerializer {
+        fn to_attribute_value<U: Serialize>(&self, value: U) -> Result<types::AttributeValue> {
+            match serde_dynamo::to_attribute_value(value)? {
+                types::AttributeValue::S(value) => {
+                    Ok(types::AttributeValue::S(value.to_uppercase()))
+                }
+                value => Ok(value),
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_item_serializer() {
+        let args = UpdateItem {
+            keys: common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "a".to_string(),
+                    value: Value::String("b".to_string()),
+                },
+                ..Default::default()
+            },
+            return_values: None,
+            update_expression: UpdateExpressionMap::Set(SetInputsMap::Leaves(vec![(
+                "c".to_string(),
+                SetInput::Assign(Value::String("d".to_string())),
+            )])),
+            write_args: write::common::WriteArgs {
+                condition: Some(common::condition::ConditionMap::Leaves(
+                    common::condition::LogicalOperator::And,
+                    vec![common::condition::KeyCondition {
+                        name: "e".to_string(),
+                        condition: common::condition::Condition::Equals(Value::String(
+                            "f".to_string(),
+                        )),
+                    }],
+                )),
+                table_name: "g".to_string(),
+                ..Default::default()
+            },
+        };
+        let actual = UpdateItemInput::new(args, &UppercaseSerializer).unwrap();
+        let expected = UpdateItemInput {
+            keys: collections::HashMap::from([(
+                "a".to_string(),
+                types::AttributeValue::S("B".to_string()),
+            )]),
+            update_expression: "SET #c = :set1".to_string(),
+            write_operation: write::common::WriteInput {
+                condition_expression: Some("#e = :e_eq0".to_string()),
+                expression_attribute_names: Some(collections::HashMap::from([
+                    ("#c".to_string(), "c".to_string()),
+                    ("#e".to_string(), "e".to_string()),
+                ])),
+                expression_attribute_values: Some(collections::HashMap::from([
+                    (
+                        ":e_eq0".to_string(),
+                        types::AttributeValue::S("F".to_string()),
+                    ),
+                    (
+                        ":set1".to_string(),
+                        types::AttributeValue::S("D".to_string()),
+                    ),
+                ])),
+                table_name: "g".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_update_item_builder() {
+        let actual: UpdateItem<Value> = UpdateItem::builder("a", "b", "c")
+            .sort_key("d", 1)
+            .set("e", "f")
+            .increment("g", 2)
+            .remove("h")
+            .add("i", 3)
+            .condition(common::condition::ConditionMap::attribute_exists("b"))
+            .build()
+            .unwrap();
+        let expected = UpdateItem {
+            keys: common::key::Keys {
+                partition_key: common::key::Key {
+                    name: "b".to_string(),
+                    value: Value::from("c"),
+                },
+                sort_key: Some(common::key::Key {
+                    name: "d".to_string(),
+                    value: Value::from(1),
+                }),
+            },
+            return_values: None,
+            update_expression: UpdateExpressionMap::Combined(vec![
+                UpdateExpressionMap::Set(SetInputsMap::Leaves(vec![
+                    ("e".to_string(), SetInput::Assign(Value::from("f"))),
+                    ("g".to_string(), SetInput::Increment(Value::from(2))),
+                ])),
+                UpdateExpressionMap::Remove(common::selection::SelectionMap::Leaves(vec![
+                    "h".to_string(),
+                ])),
+                UpdateExpressionMap::Add(AddOrDeleteInputsMap::Leaves(vec![(
+                    "i".to_string(),
+                    Value::from(3),
+                )])),
+            ]),
+            write_args: write::common::WriteArgs {
+                condition: Some(common::condition::ConditionMap::attribute_exists("b")),
+                table_name: "a".to_string(),
+                ..Default::default()
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_update_item_builder_empty() {
+        let actual = UpdateItem::<Value>::builder("a", "b", "c")
+            .condition(common::condition::ConditionMap::attribute_exists("b"))
+            .build();
+        assert!(matches!(actual, Err(crate::Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_update_item_dry_run() {
+        let update_item: UpdateItem<Value> = UpdateItem::builder("a", "b", "c")
+            .set("d", "e")
+            .condition(common::condition::ConditionMap::attribute_exists("b"))
+            .build()
+            .unwrap();
+        let input = update_item.dry_run().unwrap();
+        assert_eq!(input.table_name(), Some("a"));
+        assert_eq!(input.update_expression(), Some("SET #d = :set0"));
+        assert_eq!(input.condition_expression(), Some("attribute_exists(#b)"));
+        assert_eq!(
+            input.expression_attribute_values().unwrap()[":set0"],
+            types::AttributeValue::S("e".to_string())
+        );
+    }
 }